@@ -15,13 +15,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // List existing API keys
     println!("Listing existing API keys:");
-    let (keys_response, _) = client.list_api_keys().await?;
+    let (keys_response, _) = ApiKeysApi::list_api_keys(&client).await?;
     for key in &keys_response.data {
-        println!("- {} ({})", key.name, key.id);
-        println!("  Created: {}", key.created_at);
-        println!("  Prefix: {}", key.prefix);
-        println!("  Last used: {}", key.last_used_at.unwrap_or_default());
-        println!("  Active: {}", key.active);
+        println!("- {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
+        println!("  Created: {}", key.created);
+        println!("  Last chars: {}", key.last_chars);
+        println!("  Revoked: {}", key.revoked);
         println!();
     }
     
@@ -31,20 +30,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
         name: "SDK Example Key".to_string(),
     };
     
-    let (create_response, _) = client.create_api_key(request).await?;
+    let (create_response, _) = ApiKeysApi::create_api_key(&client, request).await?;
+    let (key, secret) = create_response.data.into_parts();
     
     println!("New API Key created:");
-    println!("- Name: {}", create_response.key.name);
-    println!("- ID: {}", create_response.key.id);
-    println!("- Created: {}", create_response.key.created_at);
-    println!("- Key: {}", create_response.secret);
+    println!("- Name: {}", key.name.as_deref().unwrap_or("Unnamed"));
+    println!("- ID: {}", key.id);
+    println!("- Created: {}", key.created);
+    println!("- Key: {}", secret.expose_secret());
     println!("  (IMPORTANT: Save this key now as it won't be shown again)");
     
     // Delete the API key (commented out for safety in this example)
     // Uncomment these lines if you want to delete the key
     /*
     println!("\nDeleting the created API key...");
-    let (delete_response, _) = client.delete_api_key(&create_response.key.id).await?;
+    let (delete_response, _) = client.delete_api_key(&key.id).await?;
     
     if delete_response.deleted {
         println!("Successfully deleted API key: {}", delete_response.id);
@@ -37,7 +37,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // IMPORTANT: The full API key is only returned once
     println!("\n⚠️ IMPORTANT: Save this API key, it will not be shown again!");
-    println!("API Key: {}", create_response.secret);
+    println!("API Key: {}", create_response.secret.expose_secret());
     
     // Print rate limit information
     println!("\nRate limit information:");
@@ -0,0 +1,57 @@
+use std::env;
+use std::error::Error;
+use venice_ai_api_sdk_rust::{
+    Client,
+    api_keys::{Chain, GenerateWeb3KeyRequestBuilder},
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    // Load environment variables from .env file
+    if let Err(e) = dotenv::dotenv() {
+        println!("Warning: Could not load .env file: {}", e);
+    }
+    
+    // Get API key from environment variable
+    let api_key = env::var("VENICE_API_KEY").expect("VENICE_API_KEY not set");
+    
+    let client = Client::new(&api_key)?;
+    
+    // Get the wallet address from command line arguments
+    let args: Vec<String> = env::args().collect();
+    let wallet_address = if args.len() > 1 {
+        args[1].clone()
+    } else {
+        println!("No wallet address provided.");
+        println!("Usage: cargo run --example api_keys_web3_key_builder --features api_keys_examples -- <wallet_address>");
+        return Ok(());
+    };
+    
+    // Build the request; the address checksum is validated before it is sent
+    let request = GenerateWeb3KeyRequestBuilder::new(&wallet_address)
+        .with_name("SDK Web3 Example Key")
+        .with_chain(Chain::Base)
+        .with_expires_in(chrono::Duration::days(30))
+        .build();
+    
+    println!("Generating Web3 key for {} on {}...", wallet_address, Chain::Base);
+    let (response, rate_limit) = client.generate_web3_key(request).await?;
+    
+    println!("\nWeb3 key generated successfully!");
+    println!("- ID: {}", response.data.id);
+    println!("- Name: {}", response.data.name);
+    println!("- Wallet: {}", response.data.wallet_address);
+    println!("- Expires at: {}", response.data.expires_at.as_deref().unwrap_or("never"));
+    
+    println!("\n⚠️ IMPORTANT: Save this API key, it will not be shown again!");
+    println!("API Key: {}", response.data.key.expose_secret());
+    
+    // Print rate limit information
+    println!("\nRate limit information:");
+    println!("Requests: {}/{}", 
+        rate_limit.remaining_requests.unwrap_or(0),
+        rate_limit.limit_requests.unwrap_or(0)
+    );
+    
+    Ok(())
+}
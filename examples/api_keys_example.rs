@@ -65,7 +65,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     println!("- Name: {}", create_response.data.name);
     println!("- ID: {}", create_response.data.id);
     println!("- Created: {}", format_timestamp(create_response.data.created));
-    println!("- Key: {}", create_response.data.key);
+    println!("- Key: {}", create_response.data.key.expose_secret());
     println!("  (IMPORTANT: Save this key now as it won't be shown again)");
     
     // Delete the created API key
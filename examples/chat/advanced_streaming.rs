@@ -144,7 +144,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             eprintln!("\nError creating streaming chat completion: {}", e);
             
             // Provide more specific error handling
-            match e {
+            match e.root() {
                 VeniceError::ApiError { status, code, message } => {
                     eprintln!("API Error (Status {}): {} - {}", status, code, message);
                 },
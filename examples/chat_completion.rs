@@ -2,10 +2,7 @@ use std::env;
 use std::error::Error;
 use venice_ai_api_sdk_rust::{
     Client,
-    traits::{
-        models::ModelsApi,
-        chat::{ChatApi, ChatCompletionBuilder},
-    },
+    traits::chat::{ChatApi, ChatCompletionBuilder},
 };
 
 #[tokio::main]
@@ -36,7 +33,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // Send the request
     println!("Sending chat completion request...");
-    let (response, rate_limit) = client.create_chat_completion(request).await?;
+    let (response, rate_limit) = ChatApi::create_chat_completion(&client, request).await?;
     
     // Print the response
     println!("\nResponse:");
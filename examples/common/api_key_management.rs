@@ -52,7 +52,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // IMPORTANT: The full API key is only returned once
     println!("\n⚠️ IMPORTANT: Save this API key, it will not be shown again!");
-    println!("API Key: {}", create_response.secret);
+    println!("API Key: {}", create_response.secret.expose_secret());
     
     // Delete the API key we just created
     println!("\nDeleting the API key we just created...");
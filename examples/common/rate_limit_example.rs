@@ -61,6 +61,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let custom_config = RateLimiterConfig {
         auto_wait: true,
         max_wait_time: 30, // Maximum wait time of 30 seconds
+        ..Default::default()
     };
     
     let client = Client::builder()
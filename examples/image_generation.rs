@@ -38,7 +38,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     
     // Send the request
     println!("Generating image...");
-    let (response, rate_limit) = client.generate_image(request).await?;
+    let (response, rate_limit) = ImageApi::generate_image(&client, request).await?;
     
     // Print the response
     println!("\nImage(s) generated:");
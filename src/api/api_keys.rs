@@ -4,9 +4,10 @@
 
 use async_trait::async_trait;
 
+use crate::endpoints;
 use crate::error::{RateLimitInfo, VeniceResult};
 use crate::http::SharedHttpClient;
-use crate::pagination::{PaginationParams, Paginator};
+use crate::pagination::{BoxPaginator, PaginationParams};
 use crate::api_keys::list::{ListApiKeysRequest, ListApiKeysResponse};
 use crate::traits::api_keys::{
     ApiKeysApi, CreateApiKeyRequest, CreateApiKeyResponse,
@@ -25,22 +26,28 @@ impl ApiKeysApiImpl {
     pub fn new(http_client: SharedHttpClient) -> Self {
         Self { http_client }
     }
+
+    /// Send requests through another HTTP client, keeping cached data and settings
+    pub(crate) fn with_http_client(mut self, http_client: SharedHttpClient) -> Self {
+        self.http_client = http_client;
+        self
+    }
 }
 
 #[async_trait]
 impl ApiKeysApi for ApiKeysApiImpl {
     async fn list_api_keys(&self) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)> {
-        self.http_client.get::<ListApiKeysResponse>("api-keys").await
+        self.http_client.get::<ListApiKeysResponse>(endpoints::API_KEYS).await
     }
     
     async fn list_api_keys_with_params(
         &self,
         request: ListApiKeysRequest,
     ) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)> {
-        self.http_client.get_with_query::<_, ListApiKeysResponse>("api-keys", &request).await
+        self.http_client.get_with_query::<_, ListApiKeysResponse>(endpoints::API_KEYS, &request).await
     }
     
-    fn list_api_keys_paginator(&self, params: PaginationParams) -> impl Paginator<crate::api_keys::list::ApiKey> {
+    fn list_api_keys_paginator(&self, params: PaginationParams) -> BoxPaginator<crate::api_keys::list::ApiKey> {
         let http_client = self.http_client.clone();
         
         // Create an async function that fetches a page
@@ -48,22 +55,22 @@ impl ApiKeysApi for ApiKeysApiImpl {
             let http_client = http_client.clone();
             async move {
                 let request = ListApiKeysRequest { pagination: params };
-                http_client.get_with_query::<_, crate::api_keys::list::ListApiKeysResponse>("api-keys", &request).await
+                http_client.get_with_query::<_, crate::api_keys::list::ListApiKeysResponse>(endpoints::API_KEYS, &request).await
             }
         };
         
-        crate::create_async_paginator(fetch_page, params)
+        Box::new(crate::create_async_paginator(fetch_page, params))
     }
     
     async fn create_api_key(
         &self,
         request: CreateApiKeyRequest,
     ) -> VeniceResult<(CreateApiKeyResponse, RateLimitInfo)> {
-        self.http_client.post("api-keys", &request).await
+        self.http_client.post(endpoints::API_KEYS, &request).await
     }
     
     async fn delete_api_key(&self, key_id: &str) -> VeniceResult<(DeleteApiKeyResponse, RateLimitInfo)> {
-        let url = format!("api-keys/{}", key_id);
+        let url = format!("{}/{}", endpoints::API_KEYS, key_id);
         self.http_client.delete(&url).await
     }
     
@@ -71,7 +78,7 @@ impl ApiKeysApi for ApiKeysApiImpl {
         &self,
         request: GenerateWeb3KeyRequest,
     ) -> VeniceResult<(GenerateWeb3KeyResponse, RateLimitInfo)> {
-        self.http_client.post("api-keys/web3", &request).await
+        self.http_client.post(endpoints::API_KEYS_GENERATE_WEB3_KEY, &request).await
     }
 }
 
@@ -83,12 +90,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_api_keys() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the API keys API implementation
@@ -102,12 +104,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_api_key() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the API keys API implementation
@@ -117,4 +114,52 @@ mod tests {
         // For now, we'll just check that the method exists and has the right signature
         let _: ApiKeysApiImpl = api_keys_api;
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_delete_api_key_by_name() {
+        let mut server = mockito::Server::new_async().await;
+        let key = |id: &str, name: &str| serde_json::json!({"id": id, "name": name, "last_chars": format!("{}-end", id)});
+        let second_page = server
+            .mock("GET", "/api_keys")
+            .match_query(mockito::Matcher::UrlEncoded("cursor".into(), "page2".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({"data": [key("key_3", "prod"), key("key_4", "ci")]}).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+        let first_page = server
+            .mock("GET", "/api_keys")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"data": [key("key_1", "ci"), key("key_2", "dev")], "has_more": true, "next_cursor": "page2"})
+                    .to_string(),
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let delete = server
+            .mock("DELETE", "/api_keys/key_3")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"deleted": true, "id": "key_3"}"#)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let api_keys_api: std::sync::Arc<dyn ApiKeysApi + Send + Sync> =
+            std::sync::Arc::new(ApiKeysApiImpl::new(new_shared_http_client(config).unwrap()));
+        
+        let (response, _) = api_keys_api.delete_api_key_by_name("prod").await.unwrap();
+        assert_eq!(response.id, "key_3");
+        
+        let err = api_keys_api.delete_api_key_by_name("ci").await.unwrap_err();
+        assert!(matches!(&err, crate::error::VeniceError::InvalidInput(msg) if msg.contains("key_1 (ci, ...key_1-end)") && msg.contains("key_4")));
+        
+        first_page.assert_async().await;
+        second_page.assert_async().await;
+        delete.assert_async().await;
+    }
+}
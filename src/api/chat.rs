@@ -3,9 +3,16 @@
 //! This module provides an implementation of the chat API.
 
 use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::chat::{reconnecting_events, ChatEventStream, ReconnectPolicy};
+use crate::content_policy::{ApplyContentPolicy, ContentPolicy};
+use crate::endpoints;
 use crate::error::{RateLimitInfo, VeniceResult};
 use crate::http::SharedHttpClient;
 use crate::models::chat::ChatCompletionRequest;
+use crate::observer::RequestLabels;
+use crate::telemetry::ApiSpan;
 use crate::traits::chat::{ChatApi, ChatCompletionStream};
 
 /// Implementation of the chat API
@@ -13,12 +20,60 @@ use crate::traits::chat::{ChatApi, ChatCompletionStream};
 pub struct ChatApiImpl {
     /// The HTTP client to use for requests
     http_client: SharedHttpClient,
+    /// Content policy applied to every request
+    content_policy: Option<Arc<ContentPolicy>>,
 }
 
 impl ChatApiImpl {
     /// Create a new chat API implementation
     pub fn new(http_client: SharedHttpClient) -> Self {
-        Self { http_client }
+        Self {
+            http_client,
+            content_policy: None,
+        }
+    }
+    
+    /// Get the HTTP client requests are sent through
+    pub(crate) fn http_client(&self) -> &SharedHttpClient {
+        &self.http_client
+    }
+
+    /// Send requests through another HTTP client, keeping cached data and settings
+    pub(crate) fn with_http_client(mut self, http_client: SharedHttpClient) -> Self {
+        self.http_client = http_client;
+        self
+    }
+    
+    /// Set the content policy applied to every request
+    pub fn with_content_policy(mut self, content_policy: Arc<ContentPolicy>) -> Self {
+        self.content_policy = Some(content_policy);
+        self
+    }
+    
+    /// Send a streaming chat completion request, reconnecting the stream if it drops
+    pub async fn stream_events(
+        &self,
+        request: crate::traits::chat::ChatCompletionRequest,
+        policy: ReconnectPolicy,
+    ) -> VeniceResult<(ChatEventStream, RateLimitInfo)> {
+        let mut request = request;
+        if let Some(content_policy) = &self.content_policy {
+            request.apply_content_policy(content_policy);
+        }
+        request.validate()?;
+        
+        let mut models_request: ChatCompletionRequest = request.into();
+        models_request.stream = Some(true);
+        
+        let span = ApiSpan::chat_completions(&models_request.model);
+        let labels = RequestLabels::new(models_request.user.as_deref(), &models_request.request_tags);
+        let result = labels
+            .scope(span.instrument(self.http_client.post_event_stream(endpoints::CHAT_COMPLETIONS, &models_request, None)))
+            .await;
+        let (events, rate_limit_info) = span.finish(result, |_| None)?;
+        
+        let events = reconnecting_events(self.http_client.clone(), models_request, events, policy);
+        Ok((events, rate_limit_info))
     }
 }
 
@@ -28,11 +83,24 @@ impl ChatApi for ChatApiImpl {
         &self,
         request: crate::traits::chat::ChatCompletionRequest,
     ) -> VeniceResult<(crate::traits::chat::ChatCompletionResponse, RateLimitInfo)> {
+        let mut request = request;
+        if let Some(content_policy) = &self.content_policy {
+            request.apply_content_policy(content_policy);
+        }
+        request.validate()?;
+        
         // Convert the request to the models type
         let models_request: ChatCompletionRequest = request.into();
         
         // Send the request
-        let (response, rate_limit_info) = self.http_client.post::<_, crate::models::chat::ChatCompletionResponse>("chat/completions", &models_request).await?;
+        let span = ApiSpan::chat_completions(&models_request.model);
+        let labels = RequestLabels::new(models_request.user.as_deref(), &models_request.request_tags);
+        let result = labels
+            .scope(span.instrument(self.http_client.post::<_, crate::models::chat::ChatCompletionResponse>(endpoints::CHAT_COMPLETIONS, &models_request)))
+            .await;
+        let (response, rate_limit_info) = span.finish(result, |response| {
+            response.usage.as_ref().map(|u| (u.prompt_tokens, u.completion_tokens, u.total_tokens))
+        })?;
         
         // Convert the response to the traits type
         let traits_response = crate::traits::chat::ChatCompletionResponse {
@@ -44,9 +112,11 @@ impl ChatApi for ChatApiImpl {
                 crate::traits::chat::ChatCompletionChoice {
                     index: choice.index,
                     message: crate::traits::chat::ChatMessage {
-                        role: choice.message.role.into(),
+                        role: choice.message.role,
                         content: choice.message.content,
                         name: None,
+                        tool_calls: choice.message.tool_calls,
+                        tool_call_id: choice.message.tool_call_id,
                     },
                     finish_reason: choice.finish_reason,
                 }
@@ -58,6 +128,7 @@ impl ChatApi for ChatApiImpl {
                     total_tokens: usage.total_tokens,
                 }
             }),
+            venice_parameters: response.venice_parameters,
         };
         
         Ok((traits_response, rate_limit_info))
@@ -67,6 +138,12 @@ impl ChatApi for ChatApiImpl {
         &self,
         request: crate::traits::chat::ChatCompletionRequest,
     ) -> VeniceResult<(ChatCompletionStream, RateLimitInfo)> {
+        let mut request = request;
+        if let Some(content_policy) = &self.content_policy {
+            request.apply_content_policy(content_policy);
+        }
+        request.validate()?;
+        
         // Convert the request to the models type
         let mut models_request: ChatCompletionRequest = request.into();
         
@@ -74,7 +151,12 @@ impl ChatApi for ChatApiImpl {
         models_request.stream = Some(true);
         
         // Send the request
-        let (stream, rate_limit_info) = self.http_client.post_streaming::<_, crate::traits::chat::ChatCompletionChunk>("chat/completions", &models_request).await?;
+        let span = ApiSpan::chat_completions(&models_request.model);
+        let labels = RequestLabels::new(models_request.user.as_deref(), &models_request.request_tags);
+        let result = labels
+            .scope(span.instrument(self.http_client.post_streaming::<_, crate::traits::chat::ChatCompletionChunk>(endpoints::CHAT_COMPLETIONS, &models_request)))
+            .await;
+        let (stream, rate_limit_info) = span.finish(result, |_| None)?;
         
         Ok((stream, rate_limit_info))
     }
@@ -89,12 +171,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_chat_completion() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the chat API implementation
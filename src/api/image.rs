@@ -3,13 +3,20 @@
 //! This module provides an implementation of the image API.
 
 use async_trait::async_trait;
+use std::sync::{Arc, RwLock};
 
+use crate::content_policy::{ApplyContentPolicy, ContentPolicy};
+use crate::endpoints;
 use crate::error::{RateLimitInfo, VeniceResult};
 use crate::http::SharedHttpClient;
-use crate::models::list::Model;
+use crate::image::{check_output, ImageConstraints, ImageConstraintsCache, ImageFormat, ImageModelsResponse, ImageRequestFields};
+use crate::models::list::{ListModelsRequest, ListModelsResponse, ModelType};
+use crate::observer::RequestLabels;
+use crate::telemetry::ApiSpan;
 use crate::traits::image::{
-    ImageApi, ImageGenerateRequest, ImageGenerateResponse,
-    ImageUpscaleRequest, ImageUpscaleResponse, ListImageStylesResponse,
+    ImageApi, ImageGenerateRequest, ImageGenerateResponse, ImageStyle,
+    ImageUpscaleRequest, ImageUpscaleResponse, ListImageStylesDetailedResponse,
+    ListImageStylesResponse, StylePreset,
 };
 
 /// Implementation of the image API
@@ -17,12 +24,35 @@ use crate::traits::image::{
 pub struct ImageApiImpl {
     /// The HTTP client to use for requests
     http_client: SharedHttpClient,
+    /// Image model constraints, fetched on first use
+    constraints: ImageConstraintsCache,
+    /// Content policy applied to every generation request
+    content_policy: Option<Arc<ContentPolicy>>,
+    /// Image styles, fetched on first use by [`ImageApiImpl::find_style`]
+    styles: Arc<RwLock<Option<Arc<ListImageStylesDetailedResponse>>>>,
 }
 
 impl ImageApiImpl {
     /// Create a new image API implementation
     pub fn new(http_client: SharedHttpClient) -> Self {
-        Self { http_client }
+        Self {
+            http_client,
+            constraints: ImageConstraintsCache::default(),
+            content_policy: None,
+            styles: Arc::default(),
+        }
+    }
+
+    /// Send requests through another HTTP client, keeping cached data and settings
+    pub(crate) fn with_http_client(mut self, http_client: SharedHttpClient) -> Self {
+        self.http_client = http_client;
+        self
+    }
+    
+    /// Set the content policy applied to every generation request
+    pub fn with_content_policy(mut self, content_policy: Arc<ContentPolicy>) -> Self {
+        self.content_policy = Some(content_policy);
+        self
     }
 }
 
@@ -32,13 +62,23 @@ impl ImageApi for ImageApiImpl {
         &self,
         request: ImageGenerateRequest,
     ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
-        let (mut result, rate_limit_info): (ImageGenerateResponse, RateLimitInfo) = self.http_client.post("image/generations", &request).await?;
+        let mut request = request;
+        if let Some(content_policy) = &self.content_policy {
+            request.apply_content_policy(content_policy);
+        }
+        check_output(endpoints::IMAGE_GENERATE, ImageFormat::GENERATE, request.format, request.quality)?;
+        self.check_request(&request.fields()).await?;
+        
+        let span = ApiSpan::image_generate(&request.model);
+        let labels = RequestLabels::new(request.user.as_deref(), &request.request_tags);
+        let response = labels.scope(span.instrument(self.http_client.post(endpoints::IMAGE_GENERATE, &request))).await;
+        let (mut result, rate_limit_info): (ImageGenerateResponse, RateLimitInfo) = span.finish(response, |_| None)?;
         
         // Populate backward compatibility fields
         result.created = Some(chrono::Utc::now().timestamp() as u64);
         
         // Convert images array to the old data format
-        result.data = result.images.iter().enumerate().map(|(_i, img_data)| {
+        result.data = result.images.iter().map(|img_data| {
             crate::traits::image::ImageData {
                 // We don't have URLs in the new format, just base64 data
                 url: None,
@@ -52,13 +92,13 @@ impl ImageApi for ImageApiImpl {
     }
     
     async fn list_styles(&self) -> VeniceResult<(ListImageStylesResponse, RateLimitInfo)> {
-        self.http_client.get("image/styles").await
+        self.http_client.get(endpoints::IMAGE_STYLES).await
     }
     
     async fn upscale_image(
         &self,
         request: ImageUpscaleRequest,
-    ) -> VeniceResult<ImageUpscaleResponse> {
+    ) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)> {
         // The API requires multipart/form-data for upscaling
         let mut form = reqwest::multipart::Form::new()
             .text("model", request.model.clone());
@@ -72,6 +112,14 @@ impl ImageApi for ImageApiImpl {
         }
         form = form.text("scale", scale.to_string());
         
+        check_output(endpoints::IMAGE_UPSCALE, ImageFormat::UPSCALE, request.format, request.quality)?;
+        if let Some(format) = request.format {
+            form = form.text("format", format.as_str());
+        }
+        if let Some(quality) = request.quality {
+            form = form.text("quality", quality.to_string());
+        }
+        
         // Add the image data - either from URL or base64
         if let Some(image_url) = &request.image_url {
             // If URL provided, add it as text
@@ -84,6 +132,7 @@ impl ImageApi for ImageApiImpl {
                     format!("Invalid base64 data: {}", e)
                 )),
             };
+            self.http_client.check_request_size(binary_data.len())?;
             
             let part = reqwest::multipart::Part::bytes(binary_data)
                 .file_name("image.png")
@@ -98,7 +147,7 @@ impl ImageApi for ImageApiImpl {
         }
         
         // Send the multipart request
-        let (binary_data, mime_type, _) = self.http_client.post_multipart_binary("image/upscale", form).await?;
+        let (binary_data, mime_type, rate_limit_info) = self.http_client.post_multipart_binary(endpoints::IMAGE_UPSCALE, form).await?;
         
         // Create response with binary data
         let mut result = ImageUpscaleResponse {
@@ -115,15 +164,79 @@ impl ImageApi for ImageApiImpl {
             b64_json: Some(b64_data),
         });
         
-        Ok(result)
+        Ok((result, rate_limit_info))
     }
 }
 
 // Additional methods not part of the ImageApi trait
 impl ImageApiImpl {
     /// Get models that are compatible with image generation
-    pub async fn get_compatible_models(&self) -> VeniceResult<(Vec<Model>, RateLimitInfo)> {
-        self.http_client.get("models?supports_image_generation=true").await
+    pub async fn get_compatible_models(&self) -> VeniceResult<(ListModelsResponse, RateLimitInfo)> {
+        let request = ListModelsRequest::new().model_type(ModelType::Image);
+        self.http_client.get_with_query(endpoints::MODELS, &request).await
+    }
+    
+    /// Get the generation constraints of an image model
+    ///
+    /// Constraints for all image models are fetched on first use and cached.
+    /// Returns `None` if the model is not in the list of image models.
+    pub async fn image_constraints(&self, model: &str) -> VeniceResult<Option<ImageConstraints>> {
+        if !self.constraints.is_loaded() {
+            let (models, _): (ImageModelsResponse, RateLimitInfo) = self
+                .http_client
+                .get_with_query(endpoints::MODELS, &[("type", "image")])
+                .await?;
+            self.constraints.store(models.into_constraints());
+        }
+        
+        Ok(self.constraints.get(model))
+    }
+    
+    /// Check an image generation request against the constraints of its model
+    ///
+    /// Validation is skipped if the constraints cannot be fetched or the model is unknown,
+    /// leaving the decision to the API.
+    pub(crate) async fn check_request(&self, fields: &ImageRequestFields<'_>) -> VeniceResult<()> {
+        match self.image_constraints(fields.model).await {
+            Ok(Some(constraints)) => constraints.check(fields),
+            Ok(None) => Ok(()),
+            Err(err) => {
+                log::debug!("Skipping image request validation, could not fetch constraints: {}", err);
+                Ok(())
+            }
+        }
+    }
+    
+    /// List available image styles with their metadata
+    pub async fn list_styles_detailed(&self) -> VeniceResult<(ListImageStylesDetailedResponse, RateLimitInfo)> {
+        self.http_client.get(endpoints::IMAGE_STYLES).await
+    }
+    
+    /// Check that a style preset is offered by the API
+    ///
+    /// Fetches the current style list and returns `VeniceError::InvalidInput`
+    /// if the preset is not in it.
+    pub async fn validate_style_preset(&self, preset: &StylePreset) -> VeniceResult<()> {
+        let (styles, _) = self.list_styles_detailed().await?;
+        styles.validate(preset)
+    }
+    
+    /// Find the style a loosely written name refers to, such as `"cinematic"`
+    ///
+    /// The style list is fetched on first use and kept for the life of the client. See
+    /// [`ListImageStylesDetailedResponse::search`] for how names are matched.
+    pub async fn find_style(&self, query: &str) -> VeniceResult<ImageStyle> {
+        let cached = self.styles.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let styles = match cached {
+            Some(styles) => styles,
+            None => {
+                let (styles, _) = self.list_styles_detailed().await?;
+                let styles = Arc::new(styles);
+                *self.styles.write().unwrap_or_else(|e| e.into_inner()) = Some(styles.clone());
+                styles
+            }
+        };
+        styles.search(query).cloned()
     }
 }
 
@@ -135,12 +248,7 @@ mod tests {
     #[tokio::test]
     async fn test_generate_image() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the image API implementation
@@ -153,20 +261,237 @@ mod tests {
     
     #[tokio::test]
     async fn test_upscale_image() {
-        // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
-        let http_client = new_shared_http_client(config).unwrap();
+        // A 1x1 transparent PNG
+        let png = base64::decode("iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==").unwrap();
+        let mut server = mockito::Server::new_async().await;
+        let upscale = server
+            .mock("POST", "/image/upscale")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_header("x-ratelimit-limit-requests", "20")
+            .with_header("x-ratelimit-remaining-requests", "19")
+            .with_body(&png)
+            .create_async()
+            .await;
         
-        // Create the image API implementation
-        let image_api = ImageApiImpl::new(http_client);
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
         
-        // TODO: Mock the HTTP client to return a response
-        // For now, we'll just check that the method exists and has the right signature
-        let _: ImageApiImpl = image_api;
+        let request = crate::traits::image::ImageUpscaleBuilder::with_url("upscale-xl", "https://example.com/image.png").build();
+        let (response, rate_limit_info) = image_api.upscale_image(request).await.unwrap();
+        upscale.assert_async().await;
+        
+        assert_eq!(response.image_data, png);
+        assert_eq!(response.size(), png.len());
+        assert_eq!(rate_limit_info.limit_requests, Some(20));
+        assert_eq!(rate_limit_info.remaining_requests, Some(19));
+        #[cfg(feature = "image")]
+        assert_eq!(response.dimensions().unwrap(), (1, 1));
+        
+        let directory = std::env::temp_dir().join(format!("venice-upscale-{}", std::process::id()));
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        let path = response.save(directory.join("upscaled.img")).await.unwrap();
+        assert_eq!(path, directory.join("upscaled.png"));
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), png);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_upscale_image_checks_upload_size() {
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let mut server = mockito::Server::new_async().await;
+        let upscale = server.mock("POST", "/image/upscale").expect(0).create_async().await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let http_client = crate::http::HttpClient::new(config).unwrap().with_max_request_size(32);
+        let image_api = ImageApiImpl::new(std::sync::Arc::new(http_client));
+        
+        let request = crate::traits::image::ImageUpscaleBuilder::with_data("upscale-xl", png).build();
+        let err = image_api.upscale_image(request).await.unwrap_err();
+        assert!(matches!(err, crate::error::VeniceError::PayloadTooLarge { size: 70, limit: 32 }));
+        
+        upscale.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_upscale_image_rejects_unsupported_format() {
+        let mut server = mockito::Server::new_async().await;
+        let upscale = server.mock("POST", "/image/upscale").expect(0).create_async().await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let request = crate::traits::image::ImageUpscaleBuilder::with_url("upscale-xl", "https://example.com/image.png")
+            .format(ImageFormat::Webp)
+            .build();
+        let err = image_api.upscale_image(request).await.unwrap_err();
+        assert!(matches!(err, crate::error::VeniceError::InvalidInput(msg) if msg.contains("webp")));
+        
+        upscale.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_get_compatible_models() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models?type=image")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": [{"id": "fluently-xl", "object": "model", "owned_by": "venice"}]}"#)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let (models, _) = image_api.get_compatible_models().await.unwrap();
+        assert_eq!(models.data[0].id, "fluently-xl");
+        mock.assert_async().await;
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_list_styles_detailed() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/image/styles")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": ["3D Model", {"id": "anime", "name": "Anime", "description": "Anime style"}]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let (styles, _) = image_api.list_styles_detailed().await.unwrap();
+        assert_eq!(styles.styles.len(), 2);
+        assert_eq!(styles.styles[0].id, "3D Model");
+        assert_eq!(styles.styles[0].name, "3D Model");
+        assert_eq!(styles.styles[1].description.as_deref(), Some("Anime style"));
+        
+        assert!(styles.validate(&StylePreset::from("anime")).is_ok());
+        assert!(styles.validate(&"3d model".into()).is_ok());
+        
+        let err = image_api.validate_style_preset(&StylePreset::from("Oil Painting")).await.unwrap_err();
+        assert!(matches!(err, crate::error::VeniceError::InvalidInput(msg) if msg.contains("Oil Painting")));
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_find_style() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/image/styles")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": ["3D Model", "Analog Film", "Anime", "Cinematic", "Comic Book", "Film Noir"]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        assert_eq!(image_api.find_style("cinematic").await.unwrap().id, "Cinematic");
+        assert_eq!(image_api.find_style("3d-model").await.unwrap().id, "3D Model");
+        assert_eq!(image_api.find_style("comic").await.unwrap().id, "Comic Book");
+        assert_eq!(image_api.find_style("anme").await.unwrap().id, "Anime");
+        assert_eq!(image_api.find_style("cinematik").await.unwrap().id, "Cinematic");
+        
+        let err = image_api.find_style("film").await.unwrap_err();
+        assert!(matches!(err, crate::error::VeniceError::InvalidInput(msg) if msg.contains("Analog Film, Film Noir")));
+        let err = image_api.find_style("anim3d").await.unwrap_err();
+        assert!(matches!(err, crate::error::VeniceError::InvalidInput(msg) if msg.contains("Did you mean: Anime")));
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_generate_image_checks_constraints() {
+        let mut server = mockito::Server::new_async().await;
+        let models = server
+            .mock("GET", "/models?type=image")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": [{"id": "fluently-xl", "model_spec": {"constraints": {"widthHeightDivisor": 8, "steps": {"max": 30}}}}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let generate = server
+            .mock("POST", "/image/generate")
+            .expect(0)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let request = crate::traits::image::ImageGenerateBuilder::new("fluently-xl", "A lighthouse")
+            .width(1001)
+            .steps(40)
+            .build();
+        let err = image_api.generate_image(request).await.unwrap_err();
+        assert!(err.to_string().contains("width must be a multiple of 8, got 1001"));
+        assert!(err.to_string().contains("steps must be between 1 and 30, got 40"));
+        
+        // Constraints are cached after the first fetch
+        let constraints = image_api.image_constraints("fluently-xl").await.unwrap().unwrap();
+        assert_eq!(constraints.max_steps, 30);
+        
+        models.assert_async().await;
+        generate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_generate_images_batch() {
+        use crate::traits::image::{BatchOptions, ImageGenerateBuilder};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        
+        let mut server = mockito::Server::new_async().await;
+        let _models = server
+            .mock("GET", "/models?type=image")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": [{"id": "fluently-xl"}]}"#)
+            .create_async()
+            .await;
+        let generate = server
+            .mock("POST", "/image/generate")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id": "generate-1", "images": ["aGVsbG8="]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let image_api = ImageApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let requests = vec![
+            ImageGenerateBuilder::new("fluently-xl", "A lighthouse").build(),
+            ImageGenerateBuilder::new("fluently-xl", "A forest").width(1001).build(),
+            ImageGenerateBuilder::new("fluently-xl", "A city").build(),
+        ];
+        let progress_calls = Arc::new(AtomicUsize::new(0));
+        let counter = progress_calls.clone();
+        let options = BatchOptions::new()
+            .concurrency(2)
+            .on_progress(move |progress| {
+                assert_eq!(progress.total, 3);
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        
+        let batch = image_api.generate_images_batch(requests, options).await;
+        
+        assert_eq!(batch.items.len(), 3);
+        assert_eq!(batch.items.iter().map(|item| item.index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(batch.successes().count(), 2);
+        assert_eq!(batch.failures().map(|(index, _)| index).collect::<Vec<_>>(), vec![1]);
+        assert!(!batch.all_succeeded());
+        assert_eq!(progress_calls.load(Ordering::SeqCst), 3);
+        generate.assert_async().await;
+    }
+}
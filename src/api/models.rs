@@ -3,12 +3,16 @@
 //! This module provides an implementation of the models API.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
+use crate::endpoints;
 use crate::error::{RateLimitInfo, VeniceResult};
 use crate::http::SharedHttpClient;
-use crate::models::list::{ListModelsRequest, ListModelsResponse as ModelsListResponse};
+use crate::models::CompatibilityIndex;
+use crate::models::list::{ListModelsRequest, ListModelsResponse as ModelsListResponse, ModelType};
 use crate::models::traits::{ModelTraitsRequest, ModelTraitsResponse as ModelsTraitsResponse};
-use crate::pagination::{PaginationParams, Paginator};
+use crate::pagination::{BoxPaginator, PaginationParams};
 use crate::traits::models::{ListModelsResponse, ModelTraitsResponse, ModelsApi};
 
 /// Implementation of the models API
@@ -16,12 +20,53 @@ use crate::traits::models::{ListModelsResponse, ModelTraitsResponse, ModelsApi};
 pub struct ModelsApiImpl {
     /// The HTTP client to use for requests
     http_client: SharedHttpClient,
+    /// Cached model traits used for compatibility checks
+    compatibility: CompatibilityIndex,
+    /// Context sizes of text models, fetched on first use
+    context_sizes: Arc<RwLock<Option<HashMap<String, u32>>>>,
 }
 
 impl ModelsApiImpl {
     /// Create a new models API implementation
     pub fn new(http_client: SharedHttpClient) -> Self {
-        Self { http_client }
+        Self {
+            compatibility: CompatibilityIndex::new(http_client.clone()),
+            http_client,
+            context_sizes: Arc::default(),
+        }
+    }
+
+    /// Send requests through another HTTP client, keeping cached data and settings
+    pub(crate) fn with_http_client(mut self, http_client: SharedHttpClient) -> Self {
+        self.http_client = http_client;
+        self
+    }
+    
+    /// Get the context size of a text model, `None` if the model is not listed or has none
+    ///
+    /// The text models are listed on first use and kept for the life of the client.
+    /// Feature suffixes such as `:enable_web_search=on` are ignored.
+    pub async fn context_size(&self, model: &str) -> VeniceResult<Option<u32>> {
+        let model = model.split(':').next().unwrap_or(model);
+        if let Some(context_sizes) = self.context_sizes.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return Ok(context_sizes.get(model).copied());
+        }
+        
+        let request = ListModelsRequest::new().model_type(ModelType::Text);
+        let (response, _) = self.list_models_with_params(request).await?;
+        let context_sizes: HashMap<String, u32> = response
+            .data
+            .into_iter()
+            .filter_map(|model| Some((model.id, model.context_size?)))
+            .collect();
+        let context_size = context_sizes.get(model).copied();
+        *self.context_sizes.write().unwrap_or_else(|e| e.into_inner()) = Some(context_sizes);
+        Ok(context_size)
+    }
+
+    /// Get the cached model compatibility index
+    pub fn compatibility(&self) -> &CompatibilityIndex {
+        &self.compatibility
     }
 }
 
@@ -50,22 +95,22 @@ impl ModelsApi for ModelsApiImpl {
         &self,
         request: ListModelsRequest,
     ) -> VeniceResult<(ModelsListResponse, RateLimitInfo)> {
-        self.http_client.get_with_query("models", &request).await
+        self.http_client.get_with_query(endpoints::MODELS, &request).await
     }
     
-    fn list_models_paginator(&self, params: PaginationParams) -> impl Paginator<crate::models::list::Model> {
+    fn list_models_paginator(&self, params: PaginationParams) -> BoxPaginator<crate::models::list::Model> {
         let http_client = self.http_client.clone();
         
         // Create an async function that fetches a page
         let fetch_page = move |params: PaginationParams| {
             let http_client = http_client.clone();
             async move {
-                let request = ListModelsRequest { pagination: params };
-                http_client.get_with_query::<_, ModelsListResponse>("models", &request).await
+                let request = ListModelsRequest { pagination: params, ..Default::default() };
+                http_client.get_with_query::<_, ModelsListResponse>(endpoints::MODELS, &request).await
             }
         };
         
-        crate::create_async_paginator(fetch_page, params)
+        Box::new(crate::create_async_paginator(fetch_page, params))
     }
     
     async fn get_model_traits(&self, model_id: &str) -> VeniceResult<(ModelTraitsResponse, RateLimitInfo)> {
@@ -101,30 +146,15 @@ impl ModelsApi for ModelsApiImpl {
                     let url = format!("models/{}/traits", model);
                     self.http_client.get(&url).await
                 } else {
-                    self.http_client.get("models/traits").await
+                    self.http_client.get(endpoints::MODELS_TRAITS).await
                 }
             },
-            None => self.http_client.get("models/traits").await,
+            None => self.http_client.get(endpoints::MODELS_TRAITS).await,
         }
     }
     
     async fn is_model_compatible(&self, model_id: &str, feature: &str) -> VeniceResult<bool> {
-        // Create a request to get traits for a specific model
-        let request = Some(ModelTraitsRequest {
-            model: Some(model_id.to_string()),
-        });
-        
-        // Get the traits from the API
-        let (response, _) = self.get_model_traits_internal(request).await?;
-        
-        // Check if any of the traits match the feature
-        for trait_info in &response.data {
-            if trait_info.id == feature && trait_info.models.contains(&model_id.to_string()) {
-                return Ok(true);
-            }
-        }
-        
-        Ok(false)
+        self.compatibility.supports(model_id, feature).await
     }
 }
 
@@ -136,12 +166,7 @@ mod tests {
     #[tokio::test]
     async fn test_list_models() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the models API implementation
@@ -155,12 +180,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_model_traits() {
         // Create a mock HTTP client
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         let http_client = new_shared_http_client(config).unwrap();
         
         // Create the models API implementation
@@ -170,4 +190,90 @@ mod tests {
         // For now, we'll just check that the method exists and has the right signature
         let _: ModelsApiImpl = models_api;
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_list_models_with_filters() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models?limit=5&type=text&supports_function_calling=true")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": [{"id": "llama-3.3-70b", "object": "model", "owned_by": "venice", "supports_function_calling": true}]}"#)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let models_api = ModelsApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let request = ListModelsRequest::new()
+            .limit(5)
+            .model_type(crate::models::list::ModelType::Text)
+            .capability(crate::models::list::ModelCapability::FunctionCalling);
+        let (response, _) = models_api.list_models_with_params(request).await.unwrap();
+        assert_eq!(response.data[0].id, "llama-3.3-70b");
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_diff_models() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": [
+                {"id": "llama-3.3-70b", "object": "model", "owned_by": "venice", "pricing": {"prompt": 0.0008, "completion": 0.0028}},
+                {"id": "qwen3-235b", "object": "model", "owned_by": "venice"}
+            ]}"#)
+            .create_async()
+            .await;
+        let previous: ModelsListResponse = serde_json::from_str(r#"{"object": "list", "data": [
+            {"id": "llama-3.3-70b", "object": "model", "owned_by": "venice", "pricing": {"prompt": 0.0007, "completion": 0.0028}},
+            {"id": "mistral-31-24b", "object": "model", "owned_by": "venice"}
+        ]}"#).unwrap();
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let models_api = ModelsApiImpl::new(new_shared_http_client(config).unwrap());
+        
+        let (diff, current) = models_api.diff_models(&previous).await.unwrap();
+        assert_eq!(diff.added[0].id, "qwen3-235b");
+        assert_eq!(diff.removed[0].id, "mistral-31-24b");
+        assert_eq!(diff.changed[0].id(), "llama-3.3-70b");
+        assert!(diff.changed[0].pricing_changed());
+        assert!(current.diff(&current).is_empty());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_paginator_through_trait_object() {
+        let mut server = mockito::Server::new_async().await;
+        // The second page is matched by its cursor, so it is created first
+        let second = server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::UrlEncoded("cursor".into(), "page-2".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": [{"id": "qwen3-235b", "object": "model", "owned_by": "venice"}]}"#)
+            .create_async()
+            .await;
+        let first = server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "has_more": true, "next_cursor": "page-2", "data": [
+                {"id": "llama-3.3-70b", "object": "model", "owned_by": "venice"}
+            ]}"#)
+            .create_async()
+            .await;
+
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let models_api: Arc<dyn ModelsApi + Send + Sync> = Arc::new(ModelsApiImpl::new(new_shared_http_client(config).unwrap()));
+
+        let models = models_api.list_models_paginator(PaginationParams::new().limit(1)).all_pages().await.unwrap();
+        let ids: Vec<&str> = models.iter().map(|model| model.id.as_str()).collect();
+        assert_eq!(ids, ["llama-3.3-70b", "qwen3-235b"]);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+}
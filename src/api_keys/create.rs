@@ -1,27 +1,84 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{
     client::Client,
-    error::{RateLimitInfo, VeniceResult},
+    endpoints,
+    api_keys::ApiKey,
+    error::{RateLimitInfo, VeniceError, VeniceResult},
+    secret::SecretString,
 };
 
-/// The endpoint for creating API keys
-const API_KEYS_ENDPOINT: &str = "api_keys";
+/// Type of API key, which determines what it is allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ApiKeyType {
+    /// Can manage API keys and account settings as well as run inference
+    Admin,
+    /// Can only call inference endpoints
+    Inference,
+}
+
+/// Endpoint group an API key can be restricted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Chat completions
+    Chat,
+    /// Image generation, styles and upscaling
+    Image,
+    /// Model listing
+    Models,
+    /// API key management
+    ApiKeys,
+}
 
 /// Request for creating an API key
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CreateApiKeyRequest {
     /// Name of the API key
     pub name: String,
     /// Rate limit configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rate_limits: Option<CreateApiKeyRateLimits>,
+    /// Type of the API key
+    #[serde(rename = "apiKeyType", skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<ApiKeyType>,
+    /// When the API key should expire (RFC 3339 timestamp)
+    #[serde(rename = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// Endpoint groups the key is restricted to; unrestricted when empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<ApiKeyScope>,
     /// Additional custom parameters
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+impl CreateApiKeyRequest {
+    /// Validate the request before sending it
+    pub fn validate(&self) -> VeniceResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(VeniceError::InvalidInput("name cannot be empty".to_string()));
+        }
+
+        if let Some(expires_at) = &self.expires_at {
+            let expiry = DateTime::parse_from_rfc3339(expires_at).map_err(|e| {
+                VeniceError::InvalidInput(format!("expires_at is not a valid RFC 3339 timestamp: {}", e))
+            })?;
+            if expiry <= Utc::now() {
+                return Err(VeniceError::InvalidInput(format!(
+                    "expires_at must be in the future, got {}",
+                    expires_at
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Rate limit configuration for creating an API key
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateApiKeyRateLimits {
@@ -42,34 +99,71 @@ pub struct CreateApiKeyResponse {
     /// The created API key object
     pub data: CreatedApiKey,
     /// Type of object
-    pub object: String,
+    #[serde(default)]
+    pub object: Option<String>,
 }
 
 /// Information about a created API key
+///
+/// The same fields as [`ApiKey`], plus the full key value, which is only returned once.
+/// Use [`CreatedApiKey::into_parts`] to keep the key value and describe the key like a
+/// listed one.
 #[derive(Debug, Deserialize, Clone)]
 pub struct CreatedApiKey {
     /// The API key identifier
     pub id: String,
     /// The type of object
-    pub object: String,
+    #[serde(default)]
+    pub object: Option<String>,
     /// The name of the API key
-    pub name: String,
+    #[serde(default)]
+    pub name: Option<String>,
     /// When the API key was created
+    #[serde(default)]
     pub created: u64,
-    /// The full API key value (only returned on creation)
-    pub key: String,
+    /// The full API key value (only returned on creation), redacted in `Debug` output
+    pub key: SecretString,
     /// Rate limit information for the key
     #[serde(default)]
     pub rate_limits: Option<CreateApiKeyRateLimits>,
+    /// Type of the API key
+    #[serde(rename = "apiKeyType", default)]
+    pub key_type: Option<ApiKeyType>,
+    /// When the API key expires
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<String>,
+    /// Endpoint groups the key is restricted to
+    #[serde(default)]
+    pub scopes: Vec<ApiKeyScope>,
 }
 
-impl Default for CreateApiKeyRequest {
-    fn default() -> Self {
-        Self {
-            name: String::new(),
-            rate_limits: None,
-            extra: HashMap::new(),
-        }
+impl CreatedApiKey {
+    /// Describe the key like a listed one, without its full value
+    pub fn api_key(&self) -> ApiKey {
+        self.clone().into_parts().0
+    }
+
+    /// Split into the key description and the full key value
+    pub fn into_parts(self) -> (ApiKey, SecretString) {
+        let api_key = ApiKey {
+            id: self.id,
+            object: self.object,
+            name: self.name,
+            created: self.created,
+            last_chars: ApiKey::last_chars_of(self.key.expose_secret()),
+            revoked: false,
+            rate_limits: self.rate_limits.map(Into::into),
+            key_type: self.key_type,
+            expires_at: self.expires_at,
+            scopes: self.scopes,
+        };
+        (api_key, self.key)
+    }
+}
+
+impl From<CreatedApiKey> for ApiKey {
+    fn from(created: CreatedApiKey) -> Self {
+        created.into_parts().0
     }
 }
 
@@ -129,6 +223,36 @@ impl CreateApiKeyRequestBuilder {
         self
     }
 
+    /// Set the type of the API key
+    pub fn with_key_type(mut self, key_type: ApiKeyType) -> Self {
+        self.request.key_type = Some(key_type);
+        self
+    }
+
+    /// Set an absolute expiry time for the API key
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.request.expires_at = Some(expires_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Set the API key to expire after a duration from now
+    pub fn with_expires_in(self, duration: Duration) -> Self {
+        self.with_expires_at(Utc::now() + duration)
+    }
+
+    /// Restrict the API key to an endpoint group
+    pub fn with_scope(mut self, scope: ApiKeyScope) -> Self {
+        if !self.request.scopes.contains(&scope) {
+            self.request.scopes.push(scope);
+        }
+        self
+    }
+
+    /// Restrict the API key to several endpoint groups
+    pub fn with_scopes(self, scopes: impl IntoIterator<Item = ApiKeyScope>) -> Self {
+        scopes.into_iter().fold(self, Self::with_scope)
+    }
+
     /// Add a custom parameter to the request
     pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
         self.request.extra.insert(key.into(), value.into());
@@ -146,10 +270,10 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{
     ///     Client,
-    ///     api_keys::CreateApiKeyRequestBuilder,
+    ///     api_keys::{ApiKeyScope, ApiKeyType, CreateApiKeyRequestBuilder},
     /// };
     ///
     /// #[tokio::main]
@@ -157,13 +281,16 @@ impl Client {
     ///     let client = Client::new("your-api-key")?;
     ///     
     ///     let request = CreateApiKeyRequestBuilder::new("My New API Key")
+    ///         .with_key_type(ApiKeyType::Inference)
+    ///         .with_scopes([ApiKeyScope::Chat, ApiKeyScope::Models])
+    ///         .with_expires_in(chrono::Duration::days(90))
     ///         .with_requests_per_minute(100)
     ///         .with_tokens_per_minute(10000)
     ///         .build();
     ///     
     ///     let (response, _) = client.create_api_key(request).await?;
     ///     
-    ///     println!("Created API Key: {} ({})", response.data.name, response.data.key);
+    ///     println!("Created API Key: {} ({})", response.data.name.as_deref().unwrap_or("Unnamed"), response.data.key.expose_secret());
     ///     
     ///     Ok(())
     /// }
@@ -172,7 +299,8 @@ impl Client {
         &self,
         request: CreateApiKeyRequest,
     ) -> VeniceResult<(CreateApiKeyResponse, RateLimitInfo)> {
-        self.post(API_KEYS_ENDPOINT, &request).await
+        request.validate()?;
+        self.post(endpoints::API_KEYS, &request).await
     }
 }
 
@@ -180,7 +308,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::api_keys::{
 ///     create_api_key,
 ///     CreateApiKeyRequestBuilder,
@@ -195,7 +323,7 @@ impl Client {
 ///     
 ///     let (response, _) = create_api_key("your-api-key", request).await?;
 ///     
-///     println!("Created API Key: {} ({})", response.data.name, response.data.key);
+///     println!("Created API Key: {} ({})", response.data.name.as_deref().unwrap_or("Unnamed"), response.data.key.expose_secret());
 ///     
 ///     Ok(())
 /// }
@@ -206,4 +334,65 @@ pub async fn create_api_key(
 ) -> VeniceResult<(CreateApiKeyResponse, RateLimitInfo)> {
     let client = Client::new(api_key)?;
     client.create_api_key(request).await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_serializes_type_expiry_and_scopes() {
+        let expires_at = DateTime::parse_from_rfc3339("2030-06-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let request = CreateApiKeyRequestBuilder::new("scoped")
+            .with_key_type(ApiKeyType::Inference)
+            .with_expires_at(expires_at)
+            .with_scopes([ApiKeyScope::Chat, ApiKeyScope::ApiKeys, ApiKeyScope::Chat])
+            .build();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["apiKeyType"], "INFERENCE");
+        assert_eq!(json["expiresAt"], "2030-06-01T12:00:00Z");
+        assert_eq!(json["scopes"], serde_json::json!(["chat", "api_keys"]));
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn optional_fields_are_omitted_by_default() {
+        let json = serde_json::to_value(CreateApiKeyRequestBuilder::new("plain").build()).unwrap();
+        assert_eq!(json, serde_json::json!({ "name": "plain" }));
+    }
+
+    #[test]
+    fn validate_rejects_past_expiry_and_blank_name() {
+        let expired = CreateApiKeyRequestBuilder::new("old")
+            .with_expires_in(Duration::minutes(-5))
+            .build();
+        assert!(matches!(expired.validate(), Err(VeniceError::InvalidInput(_))));
+        assert!(CreateApiKeyRequestBuilder::new("  ").build().validate().is_err());
+    }
+
+    #[test]
+    fn created_key_deserializes_type_and_scopes() {
+        let key: CreatedApiKey = serde_json::from_value(serde_json::json!({
+            "id": "key_1",
+            "object": "api_key",
+            "name": "admin",
+            "created": 1700000000,
+            "key": "vk_secret",
+            "apiKeyType": "ADMIN",
+            "scopes": ["models"]
+        }))
+        .unwrap();
+        assert_eq!(key.key_type, Some(ApiKeyType::Admin));
+        assert_eq!(key.scopes, vec![ApiKeyScope::Models]);
+        assert!(key.expires_at.is_none());
+        assert_eq!(key.key, "vk_secret");
+        assert!(!format!("{:?}", key).contains("vk_secret"));
+
+        let (listed, secret) = key.into_parts();
+        assert_eq!(listed.name.as_deref(), Some("admin"));
+        assert_eq!(listed.last_chars, "secret");
+        assert_eq!(listed.key_type, Some(ApiKeyType::Admin));
+        assert_eq!(secret, "vk_secret");
+    }
+}
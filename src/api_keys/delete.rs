@@ -2,21 +2,21 @@ use serde::Deserialize;
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
 };
 
-/// The endpoint for deleting API keys
-const API_KEYS_ENDPOINT: &str = "api_keys";
-
 /// Response from deleting an API key
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DeleteApiKeyResponse {
     /// Whether the deletion was successful
     pub deleted: bool,
     /// The ID of the deleted API key
     pub id: String,
     /// Type of object
-    pub object: String,
+    #[serde(default)]
+    pub object: Option<String>,
 }
 
 impl Client {
@@ -24,7 +24,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::Client;
     /// 
     /// #[tokio::main]
@@ -47,7 +47,7 @@ impl Client {
         &self,
         api_key_id: impl AsRef<str>,
     ) -> VeniceResult<(DeleteApiKeyResponse, RateLimitInfo)> {
-        let endpoint = format!("{}/{}", API_KEYS_ENDPOINT, api_key_id.as_ref());
+        let endpoint = format!("{}/{}", endpoints::API_KEYS, api_key_id.as_ref());
         self.delete(&endpoint).await
     }
 }
@@ -56,7 +56,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::api_keys::delete_api_key;
 /// 
 /// #[tokio::main]
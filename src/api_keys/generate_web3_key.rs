@@ -1,21 +1,136 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 use crate::{
     client::Client,
-    error::{RateLimitInfo, VeniceResult},
+    endpoints,
+    api_keys::ApiKey,
+    error::{RateLimitInfo, VeniceError, VeniceResult},
+    secret::SecretString,
+    utils::validation::validate_wallet_address,
 };
 
-/// The endpoint for generating a Web3 API key
-const GENERATE_WEB3_KEY_ENDPOINT: &str = "api_keys/generate_web3_key";
+/// Blockchain network the wallet address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    /// Ethereum mainnet
+    Ethereum,
+    /// Base mainnet
+    Base,
+    /// Arbitrum One
+    Arbitrum,
+    /// Optimism mainnet
+    Optimism,
+    /// Polygon PoS
+    Polygon,
+}
+
+impl Chain {
+    /// The identifier sent to the API for this chain
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chain::Ethereum => "ethereum",
+            Chain::Base => "base",
+            Chain::Arbitrum => "arbitrum",
+            Chain::Optimism => "optimism",
+            Chain::Polygon => "polygon",
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Request for generating a Web3 API key
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct GenerateWeb3KeyRequest {
     /// The wallet address to associate with the key
     pub wallet_address: String,
     /// Optional name for the API key
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Chain the wallet address belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<Chain>,
+    /// When the API key should expire (RFC 3339 timestamp)
+    #[serde(rename = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+impl GenerateWeb3KeyRequest {
+    /// Validate the request before sending it
+    ///
+    /// Checks that the wallet address is well-formed (including its EIP-55 checksum
+    /// when mixed-case) and that any expiry is a valid timestamp in the future.
+    pub fn validate(&self) -> VeniceResult<()> {
+        validate_wallet_address(&self.wallet_address, "wallet_address")
+            .map_err(VeniceError::InvalidInput)?;
+
+        if let Some(expires_at) = &self.expires_at {
+            let expiry = DateTime::parse_from_rfc3339(expires_at).map_err(|e| {
+                VeniceError::InvalidInput(format!("expires_at is not a valid RFC 3339 timestamp: {}", e))
+            })?;
+            if expiry <= Utc::now() {
+                return Err(VeniceError::InvalidInput(format!(
+                    "expires_at must be in the future, got {}",
+                    expires_at
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for Web3 API key generation requests
+#[derive(Debug, Clone)]
+pub struct GenerateWeb3KeyRequestBuilder {
+    request: GenerateWeb3KeyRequest,
+}
+
+impl GenerateWeb3KeyRequestBuilder {
+    /// Create a new Web3 API key request builder for a wallet address
+    pub fn new(wallet_address: impl Into<String>) -> Self {
+        Self {
+            request: GenerateWeb3KeyRequest {
+                wallet_address: wallet_address.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Set the name of the API key
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.request.name = Some(name.into());
+        self
+    }
+
+    /// Set the chain the wallet address belongs to
+    pub fn with_chain(mut self, chain: Chain) -> Self {
+        self.request.chain = Some(chain);
+        self
+    }
+
+    /// Set an absolute expiry time for the API key
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.request.expires_at = Some(expires_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
+    /// Set the API key to expire after a duration from now
+    pub fn with_expires_in(self, duration: Duration) -> Self {
+        self.with_expires_at(Utc::now() + duration)
+    }
+
+    /// Build the Web3 API key generation request
+    pub fn build(self) -> GenerateWeb3KeyRequest {
+        self.request
+    }
 }
 
 /// Response from generating a Web3 API key
@@ -24,7 +139,8 @@ pub struct GenerateWeb3KeyResponse {
     /// The generated API key data
     pub data: Web3KeyData,
     /// Type of object
-    pub object: String,
+    #[serde(default)]
+    pub object: Option<String>,
 }
 
 /// Data for a generated Web3 API key
@@ -40,8 +156,8 @@ pub struct Web3KeyData {
     /// When the API key was created (parsed from ISO date string)
     #[serde(rename = "createdAt")]
     pub created_at: String,
-    /// The full API key value (only returned on creation)
-    pub key: String,
+    /// The full API key value (only returned on creation), redacted in `Debug` output
+    pub key: SecretString,
     /// The wallet address associated with this key
     pub wallet_address: String,
     /// When the API key expires
@@ -49,28 +165,69 @@ pub struct Web3KeyData {
     pub expires_at: Option<String>,
 }
 
+impl Web3KeyData {
+    /// Describe the key like a listed one, without its full value
+    pub fn api_key(&self) -> ApiKey {
+        self.clone().into_parts().0
+    }
+
+    /// Split into the key description and the full key value
+    ///
+    /// The creation time is converted to a Unix timestamp, or 0 if it is not a valid
+    /// RFC 3339 timestamp.
+    pub fn into_parts(self) -> (ApiKey, SecretString) {
+        let created = DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .and_then(|created| u64::try_from(created.timestamp()).ok())
+            .unwrap_or_default();
+        let api_key = ApiKey {
+            id: self.id,
+            object: Some(self.object),
+            name: Some(self.name),
+            created,
+            last_chars: ApiKey::last_chars_of(self.key.expose_secret()),
+            revoked: false,
+            rate_limits: None,
+            key_type: None,
+            expires_at: self.expires_at,
+            scopes: Vec::new(),
+        };
+        (api_key, self.key)
+    }
+}
+
+impl From<Web3KeyData> for ApiKey {
+    fn from(data: Web3KeyData) -> Self {
+        data.into_parts().0
+    }
+}
+
 impl Client {
     /// Generate a Web3 API key
     ///
     /// # Examples
     ///
-    /// ```
-    /// use venice_ai_api_sdk_rust::{Client, api_keys::GenerateWeb3KeyRequest};
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{
+    ///     Client,
+    ///     api_keys::{Chain, GenerateWeb3KeyRequestBuilder},
+    /// };
     /// 
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::new("your-api-key")?;
     ///     
-    ///     let request = GenerateWeb3KeyRequest {
-    ///         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
-    ///         name: Some("My Web3 API Key".to_string()),
-    ///     };
+    ///     let request = GenerateWeb3KeyRequestBuilder::new("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+    ///         .with_name("My Web3 API Key")
+    ///         .with_chain(Chain::Base)
+    ///         .with_expires_in(chrono::Duration::days(30))
+    ///         .build();
     ///     
     ///     let (response, _) = client.generate_web3_key(request).await?;
     ///     
     ///     println!("Generated Web3 API Key:");
     ///     println!("ID: {}", response.data.id);
-    ///     println!("Key: {}", response.data.key);
+    ///     println!("Key: {}", response.data.key.expose_secret());
     ///     println!("Wallet Address: {}", response.data.wallet_address);
     ///     
     ///     Ok(())
@@ -80,7 +237,8 @@ impl Client {
         &self,
         request: GenerateWeb3KeyRequest,
     ) -> VeniceResult<(GenerateWeb3KeyResponse, RateLimitInfo)> {
-        self.post(GENERATE_WEB3_KEY_ENDPOINT, &request).await
+        request.validate()?;
+        self.post(endpoints::API_KEYS_GENERATE_WEB3_KEY, &request).await
     }
 }
 
@@ -88,19 +246,23 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
-/// use venice_ai_api_sdk_rust::api_keys::{generate_web3_key, GenerateWeb3KeyRequest};
+/// ```no_run
+/// use venice_ai_api_sdk_rust::api_keys::{
+///     generate_web3_key,
+///     Chain,
+///     GenerateWeb3KeyRequestBuilder,
+/// };
 /// 
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let request = GenerateWeb3KeyRequest {
-///         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
-///         name: Some("My Web3 API Key".to_string()),
-///     };
+///     let request = GenerateWeb3KeyRequestBuilder::new("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+///         .with_name("My Web3 API Key")
+///         .with_chain(Chain::Ethereum)
+///         .build();
 ///     
 ///     let (response, _) = generate_web3_key("your-api-key", request).await?;
 ///     
-///     println!("Generated Web3 API Key: {}", response.data.key);
+///     println!("Generated Web3 API Key: {}", response.data.key.expose_secret());
 ///     
 ///     Ok(())
 /// }
@@ -111,4 +273,69 @@ pub async fn generate_web3_key(
 ) -> VeniceResult<(GenerateWeb3KeyResponse, RateLimitInfo)> {
     let client = Client::new(api_key)?;
     client.generate_web3_key(request).await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn validate_accepts_checksummed_and_single_case_addresses() {
+        for address in [
+            CHECKSUMMED,
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED",
+        ] {
+            assert!(GenerateWeb3KeyRequestBuilder::new(address).build().validate().is_ok(), "{}", address);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_addresses() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xzzAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        ] {
+            let err = GenerateWeb3KeyRequestBuilder::new(address).build().validate().unwrap_err();
+            assert!(matches!(err, VeniceError::InvalidInput(_)), "{}", address);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_past_expiry() {
+        let request = GenerateWeb3KeyRequestBuilder::new(CHECKSUMMED)
+            .with_expires_in(Duration::hours(-1))
+            .build();
+        assert!(request.validate().is_err());
+
+        let request = GenerateWeb3KeyRequestBuilder::new(CHECKSUMMED)
+            .with_expires_in(Duration::days(7))
+            .build();
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn builder_serializes_chain_and_expiry() {
+        let expires_at = DateTime::parse_from_rfc3339("2030-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let request = GenerateWeb3KeyRequestBuilder::new(CHECKSUMMED)
+            .with_name("wallet key")
+            .with_chain(Chain::Base)
+            .with_expires_at(expires_at)
+            .build();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["wallet_address"], CHECKSUMMED);
+        assert_eq!(json["name"], "wallet key");
+        assert_eq!(json["chain"], "base");
+        assert_eq!(json["expiresAt"], "2030-01-01T00:00:00Z");
+
+        let minimal = serde_json::to_value(GenerateWeb3KeyRequestBuilder::new(CHECKSUMMED).build()).unwrap();
+        assert!(minimal.get("chain").is_none());
+        assert!(minimal.get("expiresAt").is_none());
+    }
+}
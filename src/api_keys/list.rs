@@ -2,14 +2,13 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
+    api_keys::{ApiKeyScope, ApiKeyType, CreateApiKeyRateLimits},
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
     pagination::{PaginationInfo, PaginationParams, Paginator},
 };
 
-/// The endpoint for listing API keys
-const API_KEYS_ENDPOINT: &str = "api_keys";
-
 /// Request parameters for listing API keys
 #[derive(Debug, Serialize, Default)]
 pub struct ListApiKeysRequest {
@@ -39,6 +38,7 @@ impl ListApiKeysRequest {
 
 /// Response from the API keys list endpoint
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ListApiKeysResponse {
     /// Array of API key information
     pub data: Vec<ApiKey>,
@@ -68,7 +68,13 @@ impl PaginationInfo<ApiKey> for ListApiKeysResponse {
 }
 
 /// Information about an API key
+///
+/// Listing, creating and generating keys all describe a key with this type: the key data
+/// of [`CreateApiKeyResponse`](crate::api_keys::CreateApiKeyResponse) and
+/// [`GenerateWeb3KeyResponse`](crate::api_keys::GenerateWeb3KeyResponse) converts into it,
+/// leaving the full key value aside.
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ApiKey {
     /// The API key identifier
     pub id: String,
@@ -90,10 +96,28 @@ pub struct ApiKey {
     /// Rate limit information for the key
     #[serde(default)]
     pub rate_limits: Option<ApiKeyRateLimits>,
+    /// Type of the API key
+    #[serde(rename = "apiKeyType", default)]
+    pub key_type: Option<ApiKeyType>,
+    /// When the API key expires
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<String>,
+    /// Endpoint groups the key is restricted to; unrestricted when empty
+    #[serde(default)]
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl ApiKey {
+    /// Get the characters of a full key value that identify it in listings
+    pub(crate) fn last_chars_of(key: &str) -> String {
+        let chars: Vec<char> = key.chars().collect();
+        chars[chars.len().saturating_sub(6)..].iter().collect()
+    }
 }
 
 /// Rate limit information for an API key
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ApiKeyRateLimits {
     /// Requests per minute limit
     pub requests_per_minute: Option<u32>,
@@ -103,12 +127,22 @@ pub struct ApiKeyRateLimits {
     pub tokens_per_minute: Option<u32>,
 }
 
+impl From<CreateApiKeyRateLimits> for ApiKeyRateLimits {
+    fn from(limits: CreateApiKeyRateLimits) -> Self {
+        Self {
+            requests_per_minute: limits.requests_per_minute,
+            requests_per_day: limits.requests_per_day,
+            tokens_per_minute: limits.tokens_per_minute,
+        }
+    }
+}
+
 impl Client {
     /// List API keys
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::Client;
     ///
     /// #[tokio::main]
@@ -117,7 +151,7 @@ impl Client {
     ///     let (keys, _) = client.list_api_keys().await?;
     ///
     ///     for key in keys.data {
-    ///         println!("API Key: {} ({})", key.name, key.id);
+    ///         println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
     ///     }
     ///
     ///     Ok(())
@@ -131,7 +165,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{Client, api_keys::ListApiKeysRequest};
     ///
     /// #[tokio::main]
@@ -143,7 +177,7 @@ impl Client {
     ///     let (keys, _) = client.list_api_keys_with_params(request).await?;
     ///
     ///     for key in keys.data {
-    ///         println!("API Key: {} ({})", key.name, key.id);
+    ///         println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
     ///     }
     ///
     ///     // If there are more keys, get the next page
@@ -153,7 +187,7 @@ impl Client {
     ///             let (next_keys, _) = client.list_api_keys_with_params(next_request).await?;
     ///
     ///             for key in next_keys.data {
-    ///                 println!("API Key: {} ({})", key.name, key.id);
+    ///                 println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
     ///             }
     ///         }
     ///     }
@@ -165,15 +199,15 @@ impl Client {
         &self,
         request: ListApiKeysRequest,
     ) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)> {
-        self.get_with_query(API_KEYS_ENDPOINT, &request).await
+        self.get_with_query(endpoints::API_KEYS, &request).await
     }
     
     /// Create a paginator for listing API keys
     ///
     /// # Examples
     ///
-    /// ```
-    /// use venice_ai_api_sdk_rust::{Client, pagination::{PaginationParams, Paginator}};
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, PaginationParams, Paginator};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -181,7 +215,7 @@ impl Client {
     ///
     ///     // Create a paginator with 5 API keys per page
     ///     let params = PaginationParams::new().limit(5);
-    ///     let mut paginator = client.list_api_keys_paginator(params);
+    ///     let mut paginator = client.list_api_keys_paginator(params.clone());
     ///
     ///     // Get all API keys
     ///     let all_keys = paginator.all_pages().await?;
@@ -193,7 +227,7 @@ impl Client {
     ///         println!("Got page with {} API keys", page.data.len());
     ///
     ///         for key in page.data {
-    ///             println!("API Key: {} ({})", key.name, key.id);
+    ///             println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
     ///         }
     ///     }
     ///
@@ -220,7 +254,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::api_keys::list_api_keys;
 ///
 /// #[tokio::main]
@@ -228,7 +262,7 @@ impl Client {
 ///     let (keys, _) = list_api_keys("your-api-key").await?;
 ///
 ///     for key in keys.data {
-///         println!("API Key: {} ({})", key.name, key.id);
+///         println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
 ///     }
 ///
 ///     Ok(())
@@ -245,7 +279,7 @@ pub async fn list_api_keys(
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::api_keys::{list_api_keys_with_params, ListApiKeysRequest};
 ///
 /// #[tokio::main]
@@ -255,7 +289,7 @@ pub async fn list_api_keys(
 ///     let (keys, _) = list_api_keys_with_params("your-api-key", request).await?;
 ///
 ///     for key in keys.data {
-///         println!("API Key: {} ({})", key.name, key.id);
+///         println!("API Key: {} ({})", key.name.as_deref().unwrap_or("Unnamed"), key.id);
 ///     }
 ///
 ///     Ok(())
@@ -273,10 +307,10 @@ pub async fn list_api_keys_with_params(
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::{
 ///     api_keys::list_api_keys_paginator,
-///     pagination::{PaginationParams, Paginator},
+///     PaginationParams, Paginator,
 /// };
 ///
 /// #[tokio::main]
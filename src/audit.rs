@@ -0,0 +1,309 @@
+//! Audit logging
+//!
+//! An [`AuditLog`] is a [`UsageObserver`] that writes one [`AuditRecord`] per request
+//! to an [`AuditSink`], so compliance teams can review the AI usage of an application.
+//! Records never contain prompts or completions; request bodies are identified by
+//! their SHA-256 hash.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::cost::CostCalculator;
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::observer::{RequestInfo, UsageObserver};
+use crate::traits::chat::ChatCompletionUsage;
+
+/// One audited request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the request finished
+    pub timestamp: DateTime<Utc>,
+    /// The HTTP method
+    pub method: String,
+    /// The API endpoint, relative to the base URL
+    pub endpoint: String,
+    /// Hex-encoded SHA-256 of the JSON request body, if it had one
+    pub payload_sha256: Option<String>,
+    /// The HTTP status of the response, when known
+    pub status: Option<u16>,
+    /// Whether the request succeeded
+    pub success: bool,
+    /// The error message of a failed request
+    pub error: Option<String>,
+    /// The model that served the request
+    pub model: Option<String>,
+    /// The end-user ID sent with the request
+    pub user: Option<String>,
+    /// The tags sent with the request
+    pub tags: Vec<String>,
+    /// Prompt tokens used
+    pub prompt_tokens: Option<u32>,
+    /// Completion tokens used
+    pub completion_tokens: Option<u32>,
+    /// Cost in US dollars, when the log has pricing for the model
+    pub cost_usd: Option<f64>,
+    /// Cost in Venice Compute Units, when the log has a VCU exchange rate
+    pub cost_vcu: Option<f64>,
+    /// Time taken by the request, in milliseconds
+    pub latency_ms: u64,
+}
+
+/// Destination of audit records
+///
+/// Implement this to store records in a database or forward them to another system.
+/// Closures taking an [`AuditRecord`] are sinks too.
+pub trait AuditSink: Send + Sync {
+    /// Store a record
+    fn write(&self, record: &AuditRecord) -> VeniceResult<()>;
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) -> VeniceResult<()> + Send + Sync,
+{
+    fn write(&self, record: &AuditRecord) -> VeniceResult<()> {
+        self(record)
+    }
+}
+
+/// Writes audit records as JSON lines
+pub struct JsonLinesSink<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl JsonLinesSink<BufWriter<File>> {
+    /// Append records to a file, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> VeniceResult<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    /// Write records to any writer
+    pub fn new(writer: W) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+
+    /// Get the underlying writer back
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<W: Write + Send> AuditSink for JsonLinesSink<W> {
+    fn write(&self, record: &AuditRecord) -> VeniceResult<()> {
+        let line = serde_json::to_string(record)
+            .map_err(|e| VeniceError::InvalidInput(format!("Failed to serialize audit record: {}", e)))?;
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(writer, "{}", line)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Records every request of a client to an [`AuditSink`]
+///
+/// Sink failures are logged and do not fail the request.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::{AuditLog, Client, JsonLinesSink};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let audit_log = AuditLog::new(JsonLinesSink::open("venice-audit.jsonl")?);
+/// let client = Client::new("your-api-key")?.with_usage_observer(Arc::new(audit_log));
+/// # Ok(())
+/// # }
+/// ```
+pub struct AuditLog {
+    sink: Box<dyn AuditSink>,
+    calculator: Option<CostCalculator>,
+}
+
+impl AuditLog {
+    /// Create an audit log writing to a sink
+    pub fn new(sink: impl AuditSink + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+            calculator: None,
+        }
+    }
+
+    /// Price the usage of each request with a calculator
+    pub fn with_cost_calculator(mut self, calculator: CostCalculator) -> Self {
+        self.calculator = Some(calculator);
+        self
+    }
+
+    /// Build the record of a finished request
+    fn record(
+        &self,
+        request: &RequestInfo,
+        usage: Option<&ChatCompletionUsage>,
+        error: Option<&VeniceError>,
+        latency: Duration,
+    ) -> AuditRecord {
+        let cost = match (&self.calculator, request.model.as_deref(), usage) {
+            (Some(calculator), Some(model), Some(usage)) => calculator.cost(model, usage),
+            _ => None,
+        };
+        AuditRecord {
+            timestamp: Utc::now(),
+            method: request.method.to_string(),
+            endpoint: request.endpoint.clone(),
+            payload_sha256: request.payload_sha256.clone(),
+            status: request.status.map(|status| status.as_u16()),
+            success: error.is_none(),
+            error: error.map(ToString::to_string),
+            model: request.model.clone(),
+            user: request.user.clone(),
+            tags: request.tags.clone(),
+            prompt_tokens: usage.map(|usage| usage.prompt_tokens),
+            completion_tokens: usage.map(|usage| usage.completion_tokens),
+            cost_usd: cost.map(|cost| cost.usd),
+            cost_vcu: cost.and_then(|cost| cost.vcu),
+            latency_ms: latency.as_millis() as u64,
+        }
+    }
+
+    fn write(&self, record: AuditRecord) {
+        if let Err(err) = self.sink.write(&record) {
+            log::warn!("Failed to write audit record for {} {}: {}", record.method, record.endpoint, err);
+        }
+    }
+}
+
+impl std::fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("priced", &self.calculator.is_some())
+            .finish()
+    }
+}
+
+impl UsageObserver for AuditLog {
+    fn on_response(
+        &self,
+        request: &RequestInfo,
+        usage: Option<&ChatCompletionUsage>,
+        _rate_limit: &RateLimitInfo,
+        latency: Duration,
+    ) {
+        self.write(self.record(request, usage, None, latency));
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &VeniceError, latency: Duration) {
+        self.write(self.record(request, None, Some(error), latency));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_audit_log_records_requests() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 1000, "completion_tokens": 500, "total_tokens": 1500}
+            }"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/models")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": {"code": "unauthorized", "message": "Invalid API key"}}"#)
+            .create_async()
+            .await;
+
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let records = records.clone();
+            move |record: &AuditRecord| {
+                records.lock().unwrap().push(record.clone());
+                Ok(())
+            }
+        };
+        let pricing = crate::models::list::ModelPricing {
+            prompt: Some(0.001),
+            completion: Some(0.002),
+        };
+        let audit_log = AuditLog::new(sink)
+            .with_cost_calculator(CostCalculator::new().with_pricing("llama-3.3-70b", pricing));
+        let client = crate::Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap()
+            .with_usage_observer(Arc::new(audit_log));
+
+        let body = serde_json::json!({"model": "llama-3.3-70b", "messages": [{"role": "user", "content": "secret"}]});
+        let _: (serde_json::Value, _) = client.post("chat/completions", &body).await.unwrap();
+        assert!(client.get::<serde_json::Value>("models").await.is_err());
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+
+        let chat = &records[0];
+        assert!(chat.success);
+        assert_eq!(chat.status, Some(200));
+        assert_eq!(chat.model.as_deref(), Some("llama-3.3-70b"));
+        assert_eq!(chat.payload_sha256.as_deref().map(str::len), Some(64));
+        assert_eq!(chat.prompt_tokens, Some(1000));
+        assert!((chat.cost_usd.unwrap() - 0.002).abs() < 1e-9);
+        assert!(!serde_json::to_string(chat).unwrap().contains("secret"));
+
+        let models = &records[1];
+        assert!(!models.success);
+        assert_eq!(models.status, Some(401));
+        assert!(models.payload_sha256.is_none());
+    }
+
+    #[test]
+    fn test_json_lines_sink() {
+        let sink = JsonLinesSink::new(Vec::new());
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            method: "GET".to_string(),
+            endpoint: "models".to_string(),
+            payload_sha256: None,
+            status: Some(200),
+            success: true,
+            error: None,
+            model: None,
+            user: None,
+            tags: Vec::new(),
+            prompt_tokens: None,
+            completion_tokens: None,
+            cost_usd: None,
+            cost_vcu: None,
+            latency_ms: 12,
+        };
+        sink.write(&record).unwrap();
+        sink.write(&record).unwrap();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(serde_json::from_str::<AuditRecord>(lines[0]).unwrap(), record);
+    }
+}
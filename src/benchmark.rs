@@ -0,0 +1,351 @@
+//! Comparing models on the same prompt
+//!
+//! [`Client::benchmark_models`](crate::Client::benchmark_models) streams a prompt to each
+//! model and measures the time to the first token, the total latency, the generation
+//! speed and, when the client has a [`CostTracker`](crate::CostTracker), the cost. The
+//! [`BenchmarkReport`] answers which model was fastest or cheapest, so a program can pick
+//! a model from measurements instead of a hardcoded name.
+//!
+//! Models are benchmarked one at a time, and the runs of a model one after another, so
+//! requests do not compete for the connection or the rate limit.
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+
+use crate::chat::{StreamCollector, StreamStats, TimedStream};
+use crate::client::Client;
+use crate::cost::Cost;
+use crate::error::VeniceResult;
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+
+/// Options for [`Client::benchmark_models`](crate::Client::benchmark_models)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkOptions {
+    /// Number of requests sent to each model
+    pub runs: u32,
+    /// System message sent before the prompt
+    pub system: Option<String>,
+    /// Maximum number of tokens of each answer
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+}
+
+impl Default for BenchmarkOptions {
+    fn default() -> Self {
+        Self {
+            runs: 1,
+            system: None,
+            max_tokens: Some(256),
+            temperature: None,
+        }
+    }
+}
+
+impl BenchmarkOptions {
+    /// Create options with default values: one run of at most 256 tokens per model
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of requests sent to each model
+    pub fn runs(mut self, runs: u32) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Set the system message sent before the prompt
+    pub fn system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    /// Set the maximum number of tokens of each answer
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Let answers run until the model stops or the context window is full
+    pub fn unlimited_tokens(mut self) -> Self {
+        self.max_tokens = None;
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+}
+
+/// Measurements of one request
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRun {
+    /// Time from sending the request to the first content
+    pub first_token_latency: Option<Duration>,
+    /// Time from sending the request to the end of the stream
+    pub latency: Duration,
+    /// Prompt tokens, as reported by the API or else estimated
+    pub prompt_tokens: u32,
+    /// Completion tokens, as reported by the API or else estimated
+    pub completion_tokens: u32,
+    /// Completion tokens per second after the first token
+    pub tokens_per_second: Option<f64>,
+    /// Cost of the request, if the client tracks costs and knows the model's pricing
+    pub cost: Option<Cost>,
+}
+
+/// Measurements of one model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelBenchmark {
+    /// The model
+    pub model: String,
+    /// Runs that succeeded
+    pub runs: Vec<BenchmarkRun>,
+    /// Errors of the runs that failed
+    pub errors: Vec<String>,
+}
+
+impl ModelBenchmark {
+    /// Check whether at least one run succeeded
+    pub fn succeeded(&self) -> bool {
+        !self.runs.is_empty()
+    }
+
+    /// Mean time to the first content
+    pub fn first_token_latency(&self) -> Option<Duration> {
+        mean_duration(self.runs.iter().filter_map(|run| run.first_token_latency))
+    }
+
+    /// Mean time to the end of the stream
+    pub fn latency(&self) -> Option<Duration> {
+        mean_duration(self.runs.iter().map(|run| run.latency))
+    }
+
+    /// Mean generation speed in completion tokens per second
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        mean(self.runs.iter().filter_map(|run| run.tokens_per_second))
+    }
+
+    /// Mean cost of a run, if every run has a cost
+    pub fn cost(&self) -> Option<Cost> {
+        let costs = self.runs.iter().map(|run| run.cost).collect::<Option<Vec<_>>>()?;
+        let count = costs.len() as f64;
+        let total = costs.into_iter().reduce(|a, b| a + b)?;
+        Some(Cost {
+            usd: total.usd / count,
+            vcu: total.vcu.map(|vcu| vcu / count),
+        })
+    }
+}
+
+/// Results of [`Client::benchmark_models`](crate::Client::benchmark_models)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    /// The prompt sent to every model
+    pub prompt: String,
+    /// Measurements of each model, in the order they were given
+    pub models: Vec<ModelBenchmark>,
+}
+
+impl BenchmarkReport {
+    /// Get the measurements of a model
+    pub fn model(&self, model: &str) -> Option<&ModelBenchmark> {
+        self.models.iter().find(|benchmark| benchmark.model == model)
+    }
+
+    /// The model with the lowest mean time to the first content
+    pub fn fastest_first_token(&self) -> Option<&ModelBenchmark> {
+        self.best_by(|benchmark| benchmark.first_token_latency().map(|latency| -latency.as_secs_f64()))
+    }
+
+    /// The model with the lowest mean latency
+    pub fn fastest(&self) -> Option<&ModelBenchmark> {
+        self.best_by(|benchmark| benchmark.latency().map(|latency| -latency.as_secs_f64()))
+    }
+
+    /// The model generating the most tokens per second
+    pub fn highest_throughput(&self) -> Option<&ModelBenchmark> {
+        self.best_by(ModelBenchmark::tokens_per_second)
+    }
+
+    /// The model with the lowest mean cost in US dollars
+    pub fn cheapest(&self) -> Option<&ModelBenchmark> {
+        self.best_by(|benchmark| benchmark.cost().map(|cost| -cost.usd))
+    }
+
+    /// The model with the highest score, ignoring models without one
+    fn best_by(&self, score: impl Fn(&ModelBenchmark) -> Option<f64>) -> Option<&ModelBenchmark> {
+        self.models
+            .iter()
+            .filter_map(|benchmark| score(benchmark).map(|score| (benchmark, score)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(benchmark, _)| benchmark)
+    }
+}
+
+/// Benchmark each model on `prompt`
+pub(crate) async fn benchmark_models(
+    client: &Client,
+    prompt: &str,
+    models: &[&str],
+    options: &BenchmarkOptions,
+) -> BenchmarkReport {
+    let mut results = Vec::with_capacity(models.len());
+    for model in models {
+        let mut benchmark = ModelBenchmark {
+            model: model.to_string(),
+            runs: Vec::new(),
+            errors: Vec::new(),
+        };
+        for _ in 0..options.runs {
+            match run(client, model, prompt, options).await {
+                Ok(run) => benchmark.runs.push(run),
+                Err(err) => benchmark.errors.push(err.to_string()),
+            }
+        }
+        results.push(benchmark);
+    }
+    BenchmarkReport {
+        prompt: prompt.to_string(),
+        models: results,
+    }
+}
+
+/// Stream the prompt to a model once and measure the request
+async fn run(client: &Client, model: &str, prompt: &str, options: &BenchmarkOptions) -> VeniceResult<BenchmarkRun> {
+    let mut builder = ChatCompletionBuilder::new(model).include_usage(true);
+    if let Some(system) = &options.system {
+        builder = builder.add_system(system.clone());
+    }
+    builder = builder.add_user(prompt);
+    if let Some(max_tokens) = options.max_tokens {
+        builder = builder.max_tokens(max_tokens);
+    }
+    if let Some(temperature) = options.temperature {
+        builder = builder.temperature(temperature);
+    }
+    let request = builder.build();
+    let estimated_prompt_tokens =
+        crate::cost::estimate_message_tokens(request.messages.iter().map(|message| message.content.as_str()));
+
+    let start = Instant::now();
+    let (stream, _) = ChatApi::create_streaming_chat_completion(client, request).await?;
+    let mut stream = TimedStream::starting_at(stream, start);
+    let mut collector = StreamCollector::new();
+    while let Some(timed) = stream.next().await {
+        collector.push(&timed?.chunk);
+    }
+    let StreamStats { first_token_latency, total_duration: latency, .. } = stream.into_stats();
+
+    let response = collector.into_response();
+    let (prompt_tokens, completion_tokens) = match &response.usage {
+        Some(usage) => (usage.prompt_tokens, usage.completion_tokens),
+        None => {
+            let content = response.choices.first().map(|choice| choice.message.content.as_str());
+            (estimated_prompt_tokens, crate::cost::estimate_tokens(content.unwrap_or_default()))
+        }
+    };
+    let generation = latency.saturating_sub(first_token_latency.unwrap_or_default());
+    let tokens_per_second = (completion_tokens > 0 && !generation.is_zero())
+        .then(|| completion_tokens as f64 / generation.as_secs_f64());
+    let cost = client.cost_tracker().and_then(|tracker| {
+        tracker.calculator().cost_for_tokens(model, prompt_tokens, completion_tokens)
+    });
+
+    Ok(BenchmarkRun {
+        first_token_latency,
+        latency,
+        prompt_tokens,
+        completion_tokens,
+        tokens_per_second,
+        cost,
+    })
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+fn mean_duration(values: impl Iterator<Item = Duration>) -> Option<Duration> {
+    mean(values.map(|value| value.as_secs_f64())).map(Duration::from_secs_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream_body(model: &str) -> String {
+        let chunk = |delta: serde_json::Value, finish_reason: Option<&str>, usage: Option<serde_json::Value>| {
+            let mut chunk = serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "created": 1677652288,
+                "model": model,
+                "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+            });
+            if let Some(usage) = usage {
+                chunk["usage"] = usage;
+            }
+            format!("data: {}\n\n", chunk)
+        };
+        [
+            chunk(serde_json::json!({"role": "assistant", "content": ""}), None, None),
+            chunk(serde_json::json!({"content": "Paris"}), None, None),
+            chunk(
+                serde_json::json!({}),
+                Some("stop"),
+                Some(serde_json::json!({"prompt_tokens": 12, "completion_tokens": 2, "total_tokens": 14})),
+            ),
+            "data: [DONE]\n\n".to_string(),
+        ]
+        .concat()
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_models() {
+        let mut server = mockito::Server::new_async().await;
+        let ok = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"model": "fast-model"})))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(stream_body("fast-model"))
+            .expect(2)
+            .create_async()
+            .await;
+        let failing = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"model": "broken-model"})))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": {"code": "invalid_model", "message": "Unknown model"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+
+        let report = client
+            .benchmark_models("Capital of France?", &["fast-model", "broken-model"], BenchmarkOptions::new().runs(2))
+            .await;
+
+        let fast = report.model("fast-model").unwrap();
+        assert_eq!(fast.runs.len(), 2);
+        assert_eq!(fast.runs[0].completion_tokens, 2);
+        assert_eq!(fast.runs[0].prompt_tokens, 12);
+        assert!(fast.first_token_latency().is_some());
+        assert!(fast.cost().is_none());
+
+        let broken = report.model("broken-model").unwrap();
+        assert!(!broken.succeeded());
+        assert_eq!(broken.errors.len(), 2);
+
+        assert_eq!(report.fastest().unwrap().model, "fast-model");
+        assert!(report.cheapest().is_none());
+        ok.assert_async().await;
+        failing.assert_async().await;
+    }
+}
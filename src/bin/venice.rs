@@ -0,0 +1,273 @@
+//! `venice` command-line client
+//!
+//! A thin wrapper around the SDK, built with the `cli` feature:
+//!
+//! ```text
+//! cargo run --features cli --bin venice -- chat "Tell me about Venice"
+//! cargo run --features cli --bin venice -- models list
+//! cargo run --features cli --bin venice -- image generate "A lighthouse at dusk" -o lighthouse.png
+//! cargo run --features cli --bin venice -- keys list
+//! ```
+//!
+//! The API key is taken from `--api-key`, the `VENICE_API_KEY` environment variable, or
+//! the `api_key` field of a JSON config file (`$VENICE_CONFIG`, defaulting to
+//! `~/.config/venice/config.json`), in that order.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use serde::Deserialize;
+use venice_ai_api_sdk_rust::{
+    chat::StreamWriteOptions,
+    traits::{
+        api_keys::ApiKeysApi,
+        chat::{ChatApi, ChatCompletionBuilder},
+        image::{ImageApi, ImageGenerateBuilder},
+        models::ModelsApi,
+    },
+    Client, ClientConfig,
+};
+
+/// Command-line client for the Venice.ai API
+#[derive(Debug, Parser)]
+#[command(name = "venice", version, about)]
+struct Cli {
+    /// API key; overrides VENICE_API_KEY and the config file
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
+    /// Base URL of the API
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Send a chat prompt and print the reply
+    Chat(ChatArgs),
+    /// Work with models
+    #[command(subcommand)]
+    Models(ModelsCommand),
+    /// Work with images
+    #[command(subcommand)]
+    Image(ImageCommand),
+    /// Manage API keys
+    #[command(subcommand)]
+    Keys(KeysCommand),
+}
+
+#[derive(Debug, Args)]
+struct ChatArgs {
+    /// The prompt to send
+    prompt: String,
+    /// Model to use
+    #[arg(short, long, default_value = "llama-3.3-70b")]
+    model: String,
+    /// System prompt
+    #[arg(short, long)]
+    system: Option<String>,
+    /// Maximum number of tokens to generate
+    #[arg(long)]
+    max_tokens: Option<u32>,
+    /// Sampling temperature
+    #[arg(long)]
+    temperature: Option<f32>,
+    /// Wait for the full reply instead of streaming it
+    #[arg(long)]
+    no_stream: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum ModelsCommand {
+    /// List available models
+    List {
+        /// Print the full response as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ImageCommand {
+    /// Generate an image from a prompt
+    Generate {
+        /// The image prompt
+        prompt: String,
+        /// Model to use
+        #[arg(short, long, default_value = "fluently-xl")]
+        model: String,
+        /// Image width in pixels
+        #[arg(long)]
+        width: Option<u32>,
+        /// Image height in pixels
+        #[arg(long)]
+        height: Option<u32>,
+        /// Style preset
+        #[arg(long)]
+        style: Option<String>,
+        /// File to write the image to
+        #[arg(short, long, default_value = "image.png")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum KeysCommand {
+    /// List API keys
+    List,
+}
+
+/// Settings read from the config file
+#[derive(Debug, Default, Deserialize)]
+struct FileConfig {
+    api_key: Option<String>,
+    base_url: Option<String>,
+}
+
+impl FileConfig {
+    /// Load the config file, if there is one
+    fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = match std::env::var_os("VENICE_CONFIG") {
+            Some(path) => PathBuf::from(path),
+            None => match std::env::var_os("HOME") {
+                Some(home) => PathBuf::from(home).join(".config/venice/config.json"),
+                None => return Ok(Self::default()),
+            },
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("invalid config file {}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("cannot read config file {}: {}", path.display(), e).into()),
+        }
+    }
+}
+
+fn client(cli: &Cli) -> Result<Client, Box<dyn std::error::Error>> {
+    let file = FileConfig::load()?;
+    let api_key = cli
+        .api_key
+        .clone()
+        .or_else(|| std::env::var("VENICE_API_KEY").ok().filter(|key| !key.is_empty()))
+        .or(file.api_key)
+        .ok_or("no API key: pass --api-key, set VENICE_API_KEY or add api_key to the config file")?;
+
+    let mut config = ClientConfig::new(api_key);
+    if let Some(base_url) = cli.base_url.clone().or(file.base_url) {
+        config = config.with_base_url(base_url);
+    }
+    Ok(Client::with_config(config)?)
+}
+
+async fn chat(client: &Client, args: ChatArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut builder = ChatCompletionBuilder::new(args.model);
+    if let Some(system) = args.system {
+        builder = builder.add_system(system);
+    }
+    builder = builder.add_user(args.prompt);
+    if let Some(max_tokens) = args.max_tokens {
+        builder = builder.max_tokens(max_tokens);
+    }
+    if let Some(temperature) = args.temperature {
+        builder = builder.temperature(temperature);
+    }
+
+    if args.no_stream {
+        let (response, _) = ChatApi::create_chat_completion(client, builder.build()).await?;
+        if let Some(choice) = response.choices.first() {
+            println!("{}", choice.message.content);
+        }
+        return Ok(());
+    }
+
+    let request = builder.stream(true).build();
+    let options = StreamWriteOptions::new().flush_per_chunk(true);
+    client.stream_chat_to_writer(request, tokio::io::stdout(), options).await?;
+    println!();
+    Ok(())
+}
+
+async fn models(client: &Client, command: ModelsCommand) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ModelsCommand::List { json } => {
+            let (models, _) = ModelsApi::list_models(client).await?;
+            for model in models.data {
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::json!({
+                            "id": model.id,
+                            "owned_by": model.owned_by,
+                            "context_size": model.context_size,
+                        })
+                    );
+                } else {
+                    println!("{}", model.id);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn image(client: &Client, command: ImageCommand) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ImageCommand::Generate { prompt, model, width, height, style, output } => {
+            let mut builder = ImageGenerateBuilder::new(model, prompt);
+            if let Some(width) = width {
+                builder = builder.width(width);
+            }
+            if let Some(height) = height {
+                builder = builder.height(height);
+            }
+            if let Some(style) = style {
+                builder = builder.style_preset(style);
+            }
+
+            let (response, _) = ImageApi::generate_image(client, builder.build()).await?;
+            response.save_image(0, &output).await?;
+            println!("Saved {}", output.display());
+        }
+    }
+    Ok(())
+}
+
+async fn keys(client: &Client, command: KeysCommand) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        KeysCommand::List => {
+            let (keys, _) = ApiKeysApi::list_api_keys(client).await?;
+            for key in keys.data {
+                println!(
+                    "{}\t{}\t...{}{}",
+                    key.id,
+                    key.name.as_deref().unwrap_or("-"),
+                    key.last_chars,
+                    if key.revoked { "\t(revoked)" } else { "" }
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let result = match client(&cli) {
+        Ok(client) => match cli.command {
+            Command::Chat(args) => chat(&client, args).await,
+            Command::Models(command) => models(&client, command).await,
+            Command::Image(command) => image(&client, command).await,
+            Command::Keys(command) => keys(&client, command).await,
+        },
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
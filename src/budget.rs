@@ -0,0 +1,207 @@
+//! Spend limits
+//!
+//! A [`SpendGuard`] enforces the `max_spend_usd` and `max_spend_vcu` caps of a
+//! [`ClientConfig`](crate::ClientConfig). It measures spend from the drop in the account
+//! balance the API reports in its response headers, and from the estimates of the cost
+//! tracker attached to the client, if any. Once either reaches a cap, requests fail with
+//! [`VeniceError::BudgetExceeded`] before they are sent, until the guard is reset.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::cost::{Cost, CostTracker};
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::observer::{RequestInfo, UsageObserver};
+use crate::traits::chat::ChatCompletionUsage;
+
+/// Caps the spend of a client
+///
+/// Created by the client when its configuration sets a cap, and available from
+/// [`Client::spend_guard`](crate::Client::spend_guard).
+///
+/// Balances are compared to the first balance reported after the client is created or
+/// the guard is reset, so the spend of the request that reported it is only counted
+/// through the cost tracker's estimate. When both measures are available the larger is
+/// used. Requests already in flight when a cap is reached are not cancelled, so the
+/// spend can end up slightly above the cap.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::Client;
+///
+/// let client = Client::builder()
+///     .api_key("your-api-key")
+///     .max_spend_usd(5.0)
+///     .build()
+///     .unwrap();
+///
+/// let guard = client.spend_guard().unwrap();
+/// assert_eq!(guard.max_usd(), Some(5.0));
+/// assert!(guard.check().is_ok());
+/// ```
+#[derive(Debug)]
+pub struct SpendGuard {
+    /// Cap on the spend in US dollars
+    max_usd: Option<f64>,
+    /// Cap on the spend in Venice Compute Units
+    max_vcu: Option<f64>,
+    /// Measured spend
+    state: Mutex<SpendState>,
+}
+
+#[derive(Debug, Default)]
+struct SpendState {
+    /// Cost tracker whose estimates count towards the spend
+    tracker: Option<Arc<CostTracker>>,
+    /// Total of the tracker when the guard was last reset
+    tracked_since: Cost,
+    /// First and latest USD balance reported since the last reset
+    balance_usd: Option<(f64, f64)>,
+    /// First and latest VCU balance reported since the last reset
+    balance_vcu: Option<(f64, f64)>,
+}
+
+impl SpendGuard {
+    /// Create a guard with the given caps
+    pub fn new(max_usd: Option<f64>, max_vcu: Option<f64>) -> Self {
+        Self {
+            max_usd,
+            max_vcu,
+            state: Mutex::new(SpendState::default()),
+        }
+    }
+
+    /// Get the cap on the spend in US dollars
+    pub fn max_usd(&self) -> Option<f64> {
+        self.max_usd
+    }
+
+    /// Get the cap on the spend in Venice Compute Units
+    pub fn max_vcu(&self) -> Option<f64> {
+        self.max_vcu
+    }
+
+    /// Count the estimates of a cost tracker towards the spend
+    pub(crate) fn track(&self, tracker: Arc<CostTracker>) {
+        let mut state = self.lock();
+        state.tracked_since = tracker.total();
+        state.tracker = Some(tracker);
+    }
+
+    /// Get the spend measured since the client was created or the guard was reset
+    pub fn spent(&self) -> Cost {
+        let state = self.lock();
+        let tracked = state
+            .tracker
+            .as_ref()
+            .map(|tracker| tracker.total())
+            .unwrap_or_default();
+        let drop = |balance: Option<(f64, f64)>| balance.map(|(first, latest)| (first - latest).max(0.0));
+
+        let usd = drop(state.balance_usd).unwrap_or(0.0).max(tracked.usd - state.tracked_since.usd);
+        let vcu = match (drop(state.balance_vcu), tracked.vcu) {
+            (Some(balance), Some(total)) => Some(balance.max(total - state.tracked_since.vcu.unwrap_or(0.0))),
+            (Some(balance), None) => Some(balance),
+            (None, Some(total)) => Some(total - state.tracked_since.vcu.unwrap_or(0.0)),
+            (None, None) => None,
+        };
+        Cost { usd, vcu }
+    }
+
+    /// Fail with [`VeniceError::BudgetExceeded`] if the spend has reached a cap
+    pub fn check(&self) -> VeniceResult<()> {
+        let spent = self.spent();
+        if let Some(max_usd) = self.max_usd.filter(|max_usd| spent.usd >= *max_usd) {
+            return Err(VeniceError::BudgetExceeded(format!(
+                "spent ${:.4} of the ${:.4} limit",
+                spent.usd, max_usd
+            )));
+        }
+        if let (Some(max_vcu), Some(vcu)) = (self.max_vcu, spent.vcu) {
+            if vcu >= max_vcu {
+                return Err(VeniceError::BudgetExceeded(format!(
+                    "spent {:.4} VCU of the {:.4} VCU limit",
+                    vcu, max_vcu
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Start measuring the spend again from zero, allowing requests again
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        state.tracked_since = state
+            .tracker
+            .as_ref()
+            .map(|tracker| tracker.total())
+            .unwrap_or_default();
+        state.balance_usd = None;
+        state.balance_vcu = None;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SpendState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl UsageObserver for SpendGuard {
+    fn on_response(
+        &self,
+        _request: &RequestInfo,
+        _usage: Option<&ChatCompletionUsage>,
+        rate_limit: &RateLimitInfo,
+        _latency: Duration,
+    ) {
+        let mut state = self.lock();
+        if let Some(balance) = rate_limit.balance_usd {
+            let first = state.balance_usd.map_or(balance, |(first, _)| first);
+            state.balance_usd = Some((first, balance));
+        }
+        if let Some(balance) = rate_limit.balance_vcu {
+            let first = state.balance_vcu.map_or(balance, |(first, _)| first);
+            state.balance_vcu = Some((first, balance));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    fn info() -> RequestInfo {
+        RequestInfo {
+            method: reqwest::Method::POST,
+            endpoint: "chat/completions".to_string(),
+            model: Some("llama-3.3-70b".to_string()),
+            user: None,
+            tags: Vec::new(),
+            payload_sha256: None,
+            status: None,
+        }
+    }
+
+    fn balance(usd: &'static str) -> RateLimitInfo {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-venice-balance-usd", HeaderValue::from_static(usd));
+        RateLimitInfo::from_headers(&headers)
+    }
+
+    #[test]
+    fn test_guard_trips_on_balance_drop_until_reset() {
+        let guard = SpendGuard::new(Some(1.0), None);
+        guard.on_response(&info(), None, &balance("10.00"), Duration::ZERO);
+        guard.on_response(&info(), None, &balance("9.50"), Duration::ZERO);
+        assert!((guard.spent().usd - 0.5).abs() < 1e-9);
+        assert!(guard.check().is_ok());
+
+        guard.on_response(&info(), None, &balance("8.75"), Duration::ZERO);
+        assert!(matches!(guard.check(), Err(VeniceError::BudgetExceeded(_))));
+
+        guard.reset();
+        assert_eq!(guard.spent(), Cost::default());
+        assert!(guard.check().is_ok());
+    }
+}
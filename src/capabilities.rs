@@ -0,0 +1,165 @@
+//! Discovering what the API supports
+//!
+//! The Venice API has no version or capabilities endpoint, so
+//! [`Client::api_info`](crate::Client::api_info) infers capabilities from the models it
+//! lists: a feature is available when at least one model supports it, and each model
+//! has its own [`ApiCapabilities`]. [`ApiInfo::prepare`] uses them to check a chat
+//! request before it is sent, so programs can branch on what the API offers instead of
+//! hardcoding it.
+
+use std::collections::HashMap;
+
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::models::list::Model;
+use crate::traits::chat::ChatCompletionRequest;
+
+/// Features of the API, or of one model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiCapabilities {
+    /// Chat completions
+    pub chat_completions: bool,
+    /// Streamed responses
+    pub streaming: bool,
+    /// A final chunk with the token usage of a streamed response
+    ///
+    /// The API reports usage in every stream, so this follows `streaming`.
+    pub streaming_usage: bool,
+    /// Tool and function calling
+    pub tool_calling: bool,
+    /// Image generation
+    pub image_generation: bool,
+}
+
+impl ApiCapabilities {
+    /// The capabilities of a model
+    pub fn of_model(model: &Model) -> Self {
+        Self {
+            chat_completions: model.supports_chat_completions,
+            streaming: model.supports_streaming,
+            streaming_usage: model.supports_streaming,
+            tool_calling: model.supports_function_calling,
+            image_generation: model.supports_image_generation,
+        }
+    }
+
+    /// Combine capabilities, keeping each feature that either side has
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            chat_completions: self.chat_completions || other.chat_completions,
+            streaming: self.streaming || other.streaming,
+            streaming_usage: self.streaming_usage || other.streaming_usage,
+            tool_calling: self.tool_calling || other.tool_calling,
+            image_generation: self.image_generation || other.image_generation,
+        }
+    }
+}
+
+/// What the API supports, from [`Client::api_info`](crate::Client::api_info)
+#[derive(Debug, Clone)]
+pub struct ApiInfo {
+    /// Features supported by at least one model
+    pub capabilities: ApiCapabilities,
+    /// Features of each listed model, by model ID
+    pub models: HashMap<String, ApiCapabilities>,
+    /// Rate limits and balance of the account
+    pub rate_limit_info: RateLimitInfo,
+}
+
+impl ApiInfo {
+    /// Infer what the API supports from the models it lists
+    pub fn from_models<'a>(models: impl IntoIterator<Item = &'a Model>, rate_limit_info: RateLimitInfo) -> Self {
+        let models: HashMap<String, ApiCapabilities> = models
+            .into_iter()
+            .map(|model| (model.id.clone(), ApiCapabilities::of_model(model)))
+            .collect();
+        let capabilities = models.values().fold(ApiCapabilities::default(), |all, model| all.union(*model));
+        Self {
+            capabilities,
+            models,
+            rate_limit_info,
+        }
+    }
+
+    /// Get the features of a model, ignoring feature suffixes such as `:enable_web_search=on`
+    ///
+    /// Falls back to the features of the whole API for models that are not listed.
+    pub fn model(&self, model: &str) -> ApiCapabilities {
+        let model = model.split(':').next().unwrap_or(model);
+        self.models.get(model).copied().unwrap_or(self.capabilities)
+    }
+
+    /// Check a chat request against the features of its model
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if the request calls tools or streams
+    /// and the model cannot. A request for streamed usage is dropped rather than
+    /// rejected when the model does not report it, since the answer is still useful.
+    pub fn prepare(&self, request: &mut ChatCompletionRequest) -> VeniceResult<()> {
+        let capabilities = self.model(&request.model);
+        if request.tools.as_ref().is_some_and(|tools| !tools.is_empty()) && !capabilities.tool_calling {
+            return Err(VeniceError::InvalidInput(format!(
+                "Model {} does not support tool calling",
+                request.model
+            )));
+        }
+        if request.stream == Some(true) && !capabilities.streaming {
+            return Err(VeniceError::InvalidInput(format!(
+                "Model {} does not support streaming",
+                request.model
+            )));
+        }
+        if !capabilities.streaming_usage {
+            request.stream_options = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Client;
+    use crate::traits::chat::{ChatCompletionBuilder, FunctionDefinition, ToolDefinition};
+
+    #[tokio::test]
+    async fn test_api_info_gates_requests() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models?type=all")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"object": "list", "data": [
+                    {"id": "llama-3.3-70b", "object": "model", "owned_by": "venice", "supports_chat_completions": true, "supports_streaming": true, "supports_function_calling": true},
+                    {"id": "tiny-model", "object": "model", "owned_by": "venice", "supports_chat_completions": true},
+                    {"id": "fluently-xl", "object": "model", "owned_by": "venice", "supports_image_generation": true}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+
+        let info = client.api_info().await.unwrap();
+        mock.assert_async().await;
+        assert!(info.capabilities.tool_calling && info.capabilities.image_generation);
+        assert!(info.model("llama-3.3-70b:enable_web_search=on").streaming);
+        assert!(!info.model("tiny-model").streaming);
+
+        let tool = ToolDefinition {
+            kind: "function".to_string(),
+            function: FunctionDefinition {
+                name: "get_weather".to_string(),
+                description: None,
+                parameters: serde_json::json!({"type": "object"}),
+            },
+        };
+        let mut request = ChatCompletionBuilder::new("tiny-model").add_user("Hi").tools(vec![tool]).build();
+        assert!(info.prepare(&mut request).is_err());
+
+        let mut request = ChatCompletionBuilder::new("tiny-model").add_user("Hi").include_usage(true).build();
+        info.prepare(&mut request).unwrap();
+        assert!(request.stream_options.is_none());
+
+        let mut request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hi").include_usage(true).build();
+        info.prepare(&mut request).unwrap();
+        assert!(request.stream_options.is_some());
+    }
+}
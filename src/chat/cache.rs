@@ -0,0 +1,474 @@
+//! Caching chat completion responses
+//!
+//! A [`ResponseCache`] attached with
+//! [`Client::with_response_cache`](crate::Client::with_response_cache) answers
+//! a chat completion request that was answered before from a [`CacheStore`] instead of
+//! sending it again, so test suites and data pipelines that repeat prompts are billed
+//! once. Requests are identified by a hash of the model, the messages and every other
+//! parameter, so changing any of them is a miss. Streaming requests are not cached.
+//!
+//! Entries expire after the TTL of the cache, and the oldest entries are evicted once
+//! the store holds more entries or bytes than the caps allow.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatCompletionRequest, ChatCompletionResponse};
+
+/// A cached response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// The key of the request the response answers, see [`ResponseCache::key`]
+    pub key: String,
+    /// Unix timestamp in seconds of when the response was stored
+    pub stored_at: u64,
+    /// The response
+    pub response: ChatCompletionResponse,
+}
+
+/// Size and age of a cached response, used to enforce the caps of a cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntryInfo {
+    /// The key of the entry
+    pub key: String,
+    /// Unix timestamp in seconds of when the response was stored
+    pub stored_at: u64,
+    /// Size of the stored entry in bytes
+    pub size: u64,
+}
+
+/// Storage for cached chat completion responses
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Load an entry, returning `None` if it does not exist
+    async fn get(&self, key: &str) -> VeniceResult<Option<CacheEntry>>;
+
+    /// Save an entry, replacing any previous entry with the same key
+    async fn put(&self, entry: &CacheEntry) -> VeniceResult<()>;
+
+    /// List the size and age of all stored entries
+    async fn list(&self) -> VeniceResult<Vec<CacheEntryInfo>>;
+
+    /// Delete an entry, returning whether it existed
+    async fn remove(&self, key: &str) -> VeniceResult<bool>;
+}
+
+/// A cache store that keeps entries in memory, shared by clones
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryCacheStore {
+    entries: Arc<RwLock<HashMap<String, (CacheEntry, u64)>>>,
+}
+
+impl InMemoryCacheStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CacheStore for InMemoryCacheStore {
+    async fn get(&self, key: &str) -> VeniceResult<Option<CacheEntry>> {
+        Ok(self
+            .entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .map(|(entry, _)| entry.clone()))
+    }
+
+    async fn put(&self, entry: &CacheEntry) -> VeniceResult<()> {
+        let size = serde_json::to_vec(entry).map(|bytes| bytes.len() as u64).unwrap_or_default();
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(entry.key.clone(), (entry.clone(), size));
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<CacheEntryInfo>> {
+        Ok(self
+            .entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .map(|(entry, size)| CacheEntryInfo {
+                key: entry.key.clone(),
+                stored_at: entry.stored_at,
+                size: *size,
+            })
+            .collect())
+    }
+
+    async fn remove(&self, key: &str) -> VeniceResult<bool> {
+        Ok(self.entries.write().unwrap_or_else(|e| e.into_inner()).remove(key).is_some())
+    }
+}
+
+/// A cache store that keeps each entry in a JSON file, surviving process restarts
+///
+/// Entry `key` is stored as `<directory>/<key>.json`.
+#[derive(Debug, Clone)]
+pub struct JsonFileCacheStore {
+    directory: PathBuf,
+}
+
+impl JsonFileCacheStore {
+    /// Create a store in the given directory, which is created on first save
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory entries are stored in
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the path of an entry file
+    fn path(&self, key: &str) -> VeniceResult<PathBuf> {
+        let valid = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(VeniceError::InvalidInput(format!(
+                "Invalid cache key '{}': only ASCII letters, digits, '-' and '_' are allowed",
+                key
+            )));
+        }
+        Ok(self.directory.join(format!("{}.json", key)))
+    }
+
+    async fn read(&self, path: &Path) -> VeniceResult<Option<CacheEntry>> {
+        let contents = match tokio::fs::read(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| VeniceError::parse_error(format!("Invalid cache file {}: {}", path.display(), e)))
+    }
+}
+
+#[async_trait]
+impl CacheStore for JsonFileCacheStore {
+    async fn get(&self, key: &str) -> VeniceResult<Option<CacheEntry>> {
+        self.read(&self.path(key)?).await
+    }
+
+    async fn put(&self, entry: &CacheEntry) -> VeniceResult<()> {
+        let path = self.path(&entry.key)?;
+        let contents = serde_json::to_vec(entry)
+            .map_err(|e| VeniceError::parse_error(format!("Failed to serialize cache entry: {}", e)))?;
+
+        // Write to a temporary file first so a crash never leaves a truncated entry
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let temporary = path.with_extension("json.tmp");
+        tokio::fs::write(&temporary, contents).await?;
+        tokio::fs::rename(&temporary, &path).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<CacheEntryInfo>> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut infos = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(cached) = self.read(&path).await? {
+                infos.push(CacheEntryInfo {
+                    key: cached.key,
+                    stored_at: cached.stored_at,
+                    size: entry.metadata().await?.len(),
+                });
+            }
+        }
+        Ok(infos)
+    }
+
+    async fn remove(&self, key: &str) -> VeniceResult<bool> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Hits and misses of a [`ResponseCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from the cache
+    pub hits: u64,
+    /// Requests sent because the cache had no fresh response
+    pub misses: u64,
+}
+
+/// A cache of chat completion responses with a TTL and size caps
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     chat::ResponseCache,
+///     traits::chat::{ChatApi, ChatCompletionBuilder},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = ResponseCache::on_disk(".venice-cache")
+///     .ttl(Duration::from_secs(7 * 24 * 3600))
+///     .max_bytes(50 * 1024 * 1024);
+/// let client = Client::builder().api_key("your-api-key").response_cache(cache).build()?;
+///
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Name a prime number").build();
+/// // Only the first call is sent to the API
+/// ChatApi::create_chat_completion(&client, request.clone()).await?;
+/// ChatApi::create_chat_completion(&client, request).await?;
+/// assert_eq!(client.response_cache().unwrap().stats().hits, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttl: Option<Duration>,
+    max_entries: Option<usize>,
+    max_bytes: Option<u64>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .field("max_entries", &self.max_entries)
+            .field("max_bytes", &self.max_bytes)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl ResponseCache {
+    /// Create a cache keeping its entries in `store`, without TTL or size caps
+    pub fn new(store: impl CacheStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            ttl: None,
+            max_entries: None,
+            max_bytes: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a cache keeping its entries in memory
+    pub fn in_memory() -> Self {
+        Self::new(InMemoryCacheStore::new())
+    }
+
+    /// Create a cache keeping its entries as JSON files in `directory`
+    pub fn on_disk(directory: impl Into<PathBuf>) -> Self {
+        Self::new(JsonFileCacheStore::new(directory))
+    }
+
+    /// Set how long a response stays fresh
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Set the maximum number of entries, evicting the oldest beyond it
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Set the maximum total size of the entries in bytes, evicting the oldest beyond it
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Get the hits and misses so far
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Compute the key identifying a request
+    ///
+    /// The key is the SHA-256 hash of the request as JSON with sorted keys, leaving out
+    /// the streaming options, so it only changes when the model, the messages or another
+    /// parameter does.
+    pub fn key(request: &ChatCompletionRequest) -> String {
+        let mut request = request.clone();
+        request.stream = None;
+        request.stream_options = None;
+        let value = serde_json::to_value(&request).map(canonicalize).unwrap_or_default();
+        hex::encode(Sha256::digest(value.to_string().as_bytes()))
+    }
+
+    /// Get the fresh cached response to a request, if any
+    pub async fn get(&self, request: &ChatCompletionRequest) -> VeniceResult<Option<ChatCompletionResponse>> {
+        let key = Self::key(request);
+        let entry = match self.store.get(&key).await? {
+            Some(entry) if self.is_expired(entry.stored_at) => {
+                self.store.remove(&key).await?;
+                None
+            }
+            entry => entry,
+        };
+        let counter = if entry.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(entry.map(|entry| entry.response))
+    }
+
+    /// Store the response to a request, then evict entries beyond the caps
+    pub async fn put(&self, request: &ChatCompletionRequest, response: &ChatCompletionResponse) -> VeniceResult<()> {
+        let entry = CacheEntry {
+            key: Self::key(request),
+            stored_at: now(),
+            response: response.clone(),
+        };
+        self.store.put(&entry).await?;
+        self.evict().await
+    }
+
+    /// Delete every entry
+    pub async fn clear(&self) -> VeniceResult<()> {
+        for entry in self.store.list().await? {
+            self.store.remove(&entry.key).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete expired entries, then the oldest entries until the caps are met
+    async fn evict(&self) -> VeniceResult<()> {
+        if self.ttl.is_none() && self.max_entries.is_none() && self.max_bytes.is_none() {
+            return Ok(());
+        }
+        let mut entries = self.store.list().await?;
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.stored_at));
+
+        let mut kept = 0usize;
+        let mut kept_bytes = 0u64;
+        for entry in entries {
+            let fits = self.max_entries.is_none_or(|max| kept < max)
+                && self.max_bytes.is_none_or(|max| kept_bytes + entry.size <= max);
+            if fits && !self.is_expired(entry.stored_at) {
+                kept += 1;
+                kept_bytes += entry.size;
+            } else {
+                self.store.remove(&entry.key).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, stored_at: u64) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now().saturating_sub(stored_at) >= ttl.as_secs())
+    }
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// Sort the keys of every object, so equal values serialize to equal strings
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().map(|(key, value)| (key, canonicalize(value))).collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(entries.into_iter().collect())
+        }
+        serde_json::Value::Array(values) => serde_json::Value::Array(values.into_iter().map(canonicalize).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+
+    #[tokio::test]
+    async fn test_client_answers_repeated_requests_from_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "7"}, "finish_reason": "stop"}]
+            }"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .response_cache(ResponseCache::in_memory().max_entries(1))
+            .build()
+            .unwrap();
+
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Name a prime number").build();
+        let other = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Name a prime number").temperature(0.0).build();
+        assert_ne!(ResponseCache::key(&request), ResponseCache::key(&other));
+
+        for _ in 0..2 {
+            let (response, _) = ChatApi::create_chat_completion(&client, request.clone()).await.unwrap();
+            assert_eq!(response.choices[0].message.content, "7");
+        }
+        assert_eq!(client.response_cache().unwrap().stats(), CacheStats { hits: 1, misses: 1 });
+
+        // A different request is a miss, and its entry evicts the first one
+        ChatApi::create_chat_completion(&client, other).await.unwrap();
+        let cache = client.response_cache().unwrap();
+        assert_eq!(cache.store.list().await.unwrap().len(), 1);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_misses() {
+        let cache = ResponseCache::in_memory().ttl(Duration::ZERO);
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hi").build();
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hello"}, "finish_reason": "stop"}]
+        }))
+        .unwrap();
+
+        cache.store.put(&CacheEntry { key: ResponseCache::key(&request), stored_at: now(), response }).await.unwrap();
+        assert!(cache.get(&request).await.unwrap().is_none());
+        assert!(cache.store.list().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+//! Collecting streamed chat completions into complete responses
+
+use futures::{Stream, StreamExt};
+use std::collections::BTreeMap;
+
+use crate::chat::{StreamStats, TimedStream};
+use crate::error::VeniceResult;
+use crate::traits::chat::{
+    ChatCompletionChoice, ChatCompletionChunk, ChatCompletionChunkChoice, ChatCompletionResponse,
+    ChatCompletionUsage, ChatMessage, ChatRole, FunctionCall, ToolCall, VeniceResponseParameters,
+};
+
+/// Accumulates the chunks of a streamed chat completion into a [`ChatCompletionResponse`]
+///
+/// Only the first choice is collected; use [`MultiChoiceCollector`] for requests with
+/// `n` greater than 1. Token usage is filled in from the final usage
+/// chunk, which the API sends when the request sets
+/// [`include_usage`](crate::traits::chat::ChatCompletionBuilder::include_usage).
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     chat::StreamCollector,
+///     traits::chat::{ChatApi, ChatCompletionBuilder},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+///     .add_user("Tell me a joke")
+///     .include_usage(true)
+///     .build();
+/// let (stream, _) = ChatApi::create_streaming_chat_completion(&client, request).await?;
+///
+/// let response = StreamCollector::collect(stream).await?;
+/// println!("{}", response.choices[0].message.content);
+/// if let Some(usage) = response.usage {
+///     println!("{} tokens", usage.total_tokens);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamCollector {
+    id: String,
+    created: u64,
+    model: String,
+    choice: ChoiceAccumulator,
+    usage: Option<ChatCompletionUsage>,
+    venice_parameters: Option<VeniceResponseParameters>,
+}
+
+impl StreamCollector {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a stream to the end and collect its chunks
+    pub async fn collect<S>(stream: S) -> VeniceResult<ChatCompletionResponse>
+    where
+        S: Stream<Item = VeniceResult<ChatCompletionChunk>>,
+    {
+        let mut collector = Self::new();
+        let mut stream = std::pin::pin!(stream);
+        while let Some(chunk) = stream.next().await {
+            collector.push(&chunk?);
+        }
+        Ok(collector.into_response())
+    }
+
+    /// Read a stream to the end, collecting its chunks and timing their arrival
+    ///
+    /// Times are measured from the call, so the first-token latency leaves out the time
+    /// spent sending the request; wrap the stream in
+    /// [`TimedStream::starting_at`](crate::chat::TimedStream::starting_at) to include it.
+    pub async fn collect_with_stats<S>(stream: S) -> VeniceResult<(ChatCompletionResponse, StreamStats)>
+    where
+        S: Stream<Item = VeniceResult<ChatCompletionChunk>>,
+    {
+        let mut collector = Self::new();
+        let mut stream = TimedStream::new(Box::pin(stream));
+        while let Some(timed) = stream.next().await {
+            collector.push(&timed?.chunk);
+        }
+        Ok((collector.into_response(), stream.into_stats()))
+    }
+
+    /// Add a chunk to the collected response
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        self.header(chunk);
+        for choice in chunk.choices.iter().filter(|choice| choice.index == 0) {
+            self.choice.push(choice);
+        }
+    }
+
+    /// Record the fields of a chunk that are not specific to a choice
+    fn header(&mut self, chunk: &ChatCompletionChunk) {
+        if self.id.is_empty() {
+            self.id = chunk.id.clone();
+            self.created = chunk.created;
+            self.model = chunk.model.clone();
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage.clone();
+        }
+        if self.venice_parameters.is_none() {
+            self.venice_parameters = chunk.venice_parameters.clone();
+        }
+    }
+
+    /// Get the content collected so far
+    pub fn content(&self) -> &str {
+        self.choice.content()
+    }
+
+    /// Get why the completion finished, once it has
+    pub fn finish_reason(&self) -> Option<&str> {
+        self.choice.finish_reason()
+    }
+
+    /// Get the token usage, once the usage chunk has arrived
+    pub fn usage(&self) -> Option<&ChatCompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    /// Build the response from the chunks collected so far
+    pub fn into_response(mut self) -> ChatCompletionResponse {
+        let choice = std::mem::take(&mut self.choice).into_choice(0);
+        self.finish(vec![choice])
+    }
+
+    /// Build a response with the given choices and the collected header fields
+    fn finish(self, choices: Vec<ChatCompletionChoice>) -> ChatCompletionResponse {
+        ChatCompletionResponse {
+            id: self.id,
+            object: "chat.completion".to_string(),
+            created: self.created,
+            model: self.model,
+            choices,
+            usage: self.usage,
+            venice_parameters: self.venice_parameters,
+        }
+    }
+}
+
+/// Accumulates the chunks of a streamed chat completion with several choices
+///
+/// When a request sets `n` greater than 1, the chunks of the choices arrive interleaved.
+/// Each chunk choice is routed by its index, and the response lists the choices in
+/// index order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     chat::MultiChoiceCollector,
+///     traits::chat::{ChatApi, ChatCompletionBuilder},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+///     .add_user("Suggest a name for a cat")
+///     .n(3)
+///     .build();
+/// let (stream, _) = ChatApi::create_streaming_chat_completion(&client, request).await?;
+///
+/// let response = MultiChoiceCollector::collect(stream).await?;
+/// for choice in &response.choices {
+///     println!("{}: {}", choice.index, choice.message.content);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultiChoiceCollector {
+    header: StreamCollector,
+    choices: BTreeMap<u32, ChoiceAccumulator>,
+}
+
+impl MultiChoiceCollector {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a stream to the end and collect its chunks
+    pub async fn collect<S>(stream: S) -> VeniceResult<ChatCompletionResponse>
+    where
+        S: Stream<Item = VeniceResult<ChatCompletionChunk>>,
+    {
+        let mut collector = Self::new();
+        let mut stream = std::pin::pin!(stream);
+        while let Some(chunk) = stream.next().await {
+            collector.push(&chunk?);
+        }
+        Ok(collector.into_response())
+    }
+
+    /// Add a chunk to the collected response
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        self.header.header(chunk);
+        for choice in &chunk.choices {
+            self.choices.entry(choice.index).or_default().push(choice);
+        }
+    }
+
+    /// Get the number of choices seen so far
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Check whether no choice has been seen yet
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// Get the content collected so far for a choice
+    pub fn content(&self, index: u32) -> Option<&str> {
+        self.choices.get(&index).map(ChoiceAccumulator::content)
+    }
+
+    /// Get why a choice finished, once it has
+    pub fn finish_reason(&self, index: u32) -> Option<&str> {
+        self.choices.get(&index).and_then(ChoiceAccumulator::finish_reason)
+    }
+
+    /// Check whether every choice seen so far has finished
+    pub fn is_finished(&self) -> bool {
+        !self.choices.is_empty() && self.choices.values().all(|choice| choice.finish_reason().is_some())
+    }
+
+    /// Get the token usage, once the usage chunk has arrived
+    pub fn usage(&self) -> Option<&ChatCompletionUsage> {
+        self.header.usage()
+    }
+
+    /// Build the response from the chunks collected so far, with choices in index order
+    pub fn into_response(self) -> ChatCompletionResponse {
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, choice)| choice.into_choice(index))
+            .collect();
+        self.header.finish(choices)
+    }
+}
+
+/// The message of one choice, assembled from its deltas
+#[derive(Debug, Clone, Default)]
+struct ChoiceAccumulator {
+    role: Option<ChatRole>,
+    content: String,
+    tool_calls: Vec<ToolCall>,
+    finish_reason: Option<String>,
+}
+
+impl ChoiceAccumulator {
+    /// Apply the delta of a chunk choice
+    fn push(&mut self, choice: &ChatCompletionChunkChoice) {
+        let delta = &choice.delta;
+        if self.role.is_none() {
+            self.role = delta.role.clone();
+        }
+        if let Some(content) = &delta.content {
+            self.content.push_str(content);
+        }
+        for fragment in delta.tool_calls.iter().flatten() {
+            let position = fragment.index as usize;
+            while self.tool_calls.len() <= position {
+                self.tool_calls.push(ToolCall {
+                    id: String::new(),
+                    kind: "function".to_string(),
+                    function: FunctionCall { name: String::new(), arguments: String::new() },
+                });
+            }
+            let call = &mut self.tool_calls[position];
+            if let Some(id) = &fragment.id {
+                call.id.clone_from(id);
+            }
+            if let Some(kind) = &fragment.kind {
+                call.kind.clone_from(kind);
+            }
+            if let Some(function) = &fragment.function {
+                if let Some(name) = &function.name {
+                    call.function.name.push_str(name);
+                }
+                if let Some(arguments) = &function.arguments {
+                    call.function.arguments.push_str(arguments);
+                }
+            }
+        }
+        if choice.finish_reason.is_some() {
+            self.finish_reason = choice.finish_reason.clone();
+        }
+    }
+
+    /// Get the content collected so far
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Get why the choice finished, once it has
+    fn finish_reason(&self) -> Option<&str> {
+        self.finish_reason.as_deref()
+    }
+
+    /// Build the choice from the deltas collected so far
+    fn into_choice(self, index: u32) -> ChatCompletionChoice {
+        ChatCompletionChoice {
+            message: ChatMessage {
+                role: self.role.unwrap_or(ChatRole::Assistant),
+                content: self.content,
+                name: None,
+                tool_calls: (!self.tool_calls.is_empty()).then_some(self.tool_calls),
+                tool_call_id: None,
+            },
+            finish_reason: self.finish_reason,
+            index,
+        }
+    }
+}
@@ -2,13 +2,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{
+    endpoints,
+    chat::validation::ChatRequestFields,
     client::Client,
     error::{RateLimitInfo, VeniceResult},
 };
 
-/// The endpoint for chat completions
-const CHAT_COMPLETIONS_ENDPOINT: &str = "chat/completions";
-
 /// Request for chat completions
 #[derive(Debug, Clone, Serialize)]
 pub struct ChatCompletionRequest {
@@ -34,12 +33,24 @@ pub struct ChatCompletionRequest {
     /// Penalizes repeated topics
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence_penalty: Option<f32>,
+    /// Only sample from the k most likely tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Penalizes tokens that already appeared; 1 leaves the distribution unchanged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition_penalty: Option<f32>,
+    /// Minimum probability of a token, relative to the most likely one, between 0 and 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f32>,
     /// Generate multiple completion choices
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
     /// Whether to stream the results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// Options for streamed responses, such as reporting token usage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
     /// Used for deterministic results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<u64>,
@@ -49,21 +60,30 @@ pub struct ChatCompletionRequest {
     /// Venice-specific parameters
     #[serde(skip_serializing_if = "Option::is_none")]
     pub venice_parameters: Option<VeniceParameters>,
+    /// Tools the model may call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+    /// Controls which tool is called: "none", "auto", "required" or a specific function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<serde_json::Value>,
+    /// Format the model must reply in, such as JSON matching a schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<crate::traits::chat::ResponseFormat>,
+    /// ID of the end user, forwarded to the API for abuse tracking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Tags for grouping requests in usage analytics
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub request_tags: Vec<String>,
     /// Additional custom parameters
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
 
-/// Venice-specific parameters for chat completion requests
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VeniceParameters {
-    /// Enable web search for chat completions
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub enable_web_search: Option<String>,
-    /// Include Venice's default system prompt
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub include_venice_system_prompt: Option<bool>,
-}
+pub use crate::traits::chat::{
+    FunctionCall, FunctionDefinition, StreamOptions, ToolCall, ToolDefinition, VeniceParameters,
+    VeniceResponseParameters, WebSearchCitation,
+};
 
 /// Response from the chat completions API
 #[derive(Debug, Clone, Deserialize)]
@@ -80,6 +100,21 @@ pub struct ChatCompletionResponse {
     pub choices: Vec<ChatCompletionChoice>,
     /// The usage information for the request
     pub usage: Option<ChatCompletionUsage>,
+    /// Venice-specific response fields, such as web search citations
+    #[serde(default)]
+    pub venice_parameters: Option<VeniceResponseParameters>,
+}
+
+impl ChatCompletionResponse {
+    /// Get the web search results cited in the response
+    ///
+    /// Empty unless the request enabled web search.
+    pub fn web_search_citations(&self) -> &[WebSearchCitation] {
+        self.venice_parameters
+            .as_ref()
+            .map(|params| params.web_search_citations.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 /// A chat completion choice
@@ -104,26 +139,15 @@ pub struct ChatCompletionUsage {
     pub total_tokens: u32,
 }
 
-/// Chat message roles
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum ChatRole {
-    /// System message
-    System,
-    /// User message
-    User,
-    /// Assistant message
-    Assistant,
-    /// Function message
-    Function,
-}
+pub use crate::traits::chat::ChatRole;
 
 /// A chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     /// The role of the message author
     pub role: ChatRole,
-    /// The content of the message
+    /// The content of the message, empty for assistant messages that only call tools
+    #[serde(default, deserialize_with = "crate::traits::chat::deserialize_nullable_string")]
     pub content: String,
     /// Name of the message author
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -131,6 +155,12 @@ pub struct ChatMessage {
     /// Function call content if applicable
     #[serde(skip_serializing_if = "Option::is_none")]
     pub function_call: Option<serde_json::Value>,
+    /// Tools the assistant asked to call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// ID of the tool call a tool message answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
 }
 
 impl ChatMessage {
@@ -141,6 +171,8 @@ impl ChatMessage {
             content: content.into(),
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -151,6 +183,8 @@ impl ChatMessage {
             content: content.into(),
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -161,6 +195,8 @@ impl ChatMessage {
             content: content.into(),
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -171,6 +207,8 @@ impl ChatMessage {
             content: content.into(),
             name: Some(name.into()),
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 }
@@ -186,16 +224,50 @@ impl Default for ChatCompletionRequest {
             top_p: None,
             frequency_penalty: None,
             presence_penalty: None,
+            top_k: None,
+            repetition_penalty: None,
+            min_p: None,
             n: None,
             stream: None,
+            stream_options: None,
             seed: None,
             stop: None,
             venice_parameters: None,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            user: None,
+            request_tags: Vec::new(),
             extra: HashMap::new(),
         }
     }
 }
 
+impl ChatCompletionRequest {
+    /// Check the request for problems the API would reject
+    ///
+    /// Validates parameter ranges, that there is at least one message, that only
+    /// supported roles are used, and that `max_tokens` and `max_completion_tokens`
+    /// are not both set. All problems are reported in a single
+    /// `VeniceError::InvalidInput`. This runs automatically before a request is sent.
+    pub fn validate(&self) -> VeniceResult<()> {
+        ChatRequestFields {
+            model: &self.model,
+            roles: self.messages.iter().map(|m| m.role.as_str()).collect(),
+            max_tokens: self.max_tokens,
+            max_completion_tokens: self.max_completion_tokens,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            frequency_penalty: self.frequency_penalty,
+            presence_penalty: self.presence_penalty,
+            repetition_penalty: self.repetition_penalty,
+            min_p: self.min_p,
+            n: self.n,
+        }
+        .validate()
+    }
+}
+
 /// Builder for chat completion requests
 #[derive(Debug, Clone)]
 pub struct ChatCompletionRequestBuilder {
@@ -276,6 +348,24 @@ impl ChatCompletionRequestBuilder {
         self
     }
 
+    /// Only sample from the `top_k` most likely tokens
+    pub fn with_top_k(mut self, top_k: u32) -> Self {
+        self.request.top_k = Some(top_k);
+        self
+    }
+
+    /// Set the repetition penalty, greater than 0; values above 1 discourage repetition
+    pub fn with_repetition_penalty(mut self, repetition_penalty: f32) -> Self {
+        self.request.repetition_penalty = Some(repetition_penalty);
+        self
+    }
+
+    /// Set the minimum probability of a token relative to the most likely one, between 0 and 1
+    pub fn with_min_p(mut self, min_p: f32) -> Self {
+        self.request.min_p = Some(min_p);
+        self
+    }
+
     /// Set the number of completion choices to generate
     pub fn with_n(mut self, n: u32) -> Self {
         self.request.n = Some(n);
@@ -288,6 +378,12 @@ impl ChatCompletionRequestBuilder {
         self
     }
 
+    /// Ask for a final streamed chunk reporting the token usage of the completion
+    pub fn with_include_usage(mut self, include_usage: bool) -> Self {
+        self.request.stream_options = Some(StreamOptions { include_usage });
+        self
+    }
+
     /// Set the random seed for deterministic results
     pub fn with_seed(mut self, seed: u64) -> Self {
         self.request.seed = Some(seed);
@@ -302,21 +398,67 @@ impl ChatCompletionRequestBuilder {
 
     /// Enable Venice's web search capability
     pub fn with_web_search(mut self, enable: bool) -> Self {
-        let venice_parameters = self.request.venice_parameters.get_or_insert(VeniceParameters {
-            enable_web_search: None,
-            include_venice_system_prompt: None,
-        });
-        venice_parameters.enable_web_search = Some(if enable { "on".to_string() } else { "off".to_string() });
+        let venice_parameters = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(venice_parameters.web_search(enable));
         self
     }
 
     /// Control whether to include Venice's default system prompt
     pub fn with_venice_system_prompt(mut self, include: bool) -> Self {
-        let venice_parameters = self.request.venice_parameters.get_or_insert(VeniceParameters {
-            enable_web_search: None,
-            include_venice_system_prompt: None,
-        });
-        venice_parameters.include_venice_system_prompt = Some(include);
+        let venice_parameters = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(venice_parameters.include_venice_system_prompt(include));
+        self
+    }
+
+    /// Set the character to chat with
+    pub fn with_character_slug(mut self, slug: impl Into<String>) -> Self {
+        let venice_parameters = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(venice_parameters.character_slug(slug));
+        self
+    }
+
+    /// Set the model and Venice parameters from a [`ModelSpec`](crate::chat::ModelSpec)
+    ///
+    /// Parameters in the spec are sent as `venice_parameters` and override any set before.
+    pub fn with_model_spec(mut self, spec: crate::chat::ModelSpec) -> Self {
+        let (model, spec_params) = spec.into_parts();
+        let mut venice_parameters = self.request.venice_parameters.take().unwrap_or_default();
+        venice_parameters.merge(spec_params);
+        self.request.model = model;
+        self.request.venice_parameters = Some(venice_parameters);
+        self
+    }
+
+    /// Set the Venice-specific parameters, replacing any set before
+    pub fn with_venice_parameters(mut self, venice_parameters: VeniceParameters) -> Self {
+        self.request.venice_parameters = Some(venice_parameters);
+        self
+    }
+
+    /// Add a tool the model may call
+    pub fn with_tool(mut self, tool: ToolDefinition) -> Self {
+        self.request.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
+    /// Set which tool the model calls ("none", "auto", "required" or a specific function)
+    pub fn with_tool_choice(mut self, tool_choice: impl Into<serde_json::Value>) -> Self {
+        self.request.tool_choice = Some(tool_choice.into());
+        self
+    }
+
+    /// Set the ID of the end user, forwarded to the API for abuse tracking
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.request.user = Some(user_id.into());
+        self
+    }
+
+    /// Add a tag for grouping requests in usage analytics
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.request.request_tags.contains(&tag) {
+            self.request.request_tags.push(tag);
+        }
         self
     }
 
@@ -332,14 +474,17 @@ impl ChatCompletionRequestBuilder {
     }
 }
 
-use crate::traits::chat::ChatCompletionStream;
+use crate::http::ResponseEnvelope;
+use crate::observer::RequestLabels;
+use crate::telemetry::ApiSpan;
+use crate::traits::chat::{ChatCompletionStream, RawChatCompletionStream};
 
 impl Client {
     /// Create a chat completion
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{
     ///     Client,
     ///     chat::{ChatCompletionRequestBuilder, ChatMessage},
@@ -367,18 +512,61 @@ impl Client {
         &self,
         request: ChatCompletionRequest,
     ) -> VeniceResult<(ChatCompletionResponse, RateLimitInfo)> {
-        // Ensure streaming is disabled
+        self.create_chat_completion_raw(request)
+            .await
+            .map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Create a chat completion, keeping the status, headers and raw body of the response
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use venice_ai_api_sdk_rust::{Client, chat::ChatCompletionRequestBuilder};
+    ///
+    /// let client = Client::new("your-api-key")?;
+    /// let request = ChatCompletionRequestBuilder::new("llama-3.3-70b")
+    ///     .add_user_message("Hello")
+    ///     .build();
+    ///
+    /// let envelope = client.create_chat_completion_raw(request).await?;
+    /// println!("{} in {:?}, request ID {:?}", envelope.status, envelope.latency, envelope.request_id());
+    /// println!("{}", envelope.body.choices[0].message.content);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_chat_completion_raw(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> VeniceResult<ResponseEnvelope<ChatCompletionResponse>> {
         let mut request = request;
+        self.apply_content_policy(&mut request);
+        request.validate()?;
+        
+        // Ensure streaming is disabled
         request.stream = Some(false);
         
-        self.post(CHAT_COMPLETIONS_ENDPOINT, &request).await
+        let span = ApiSpan::chat_completions(&request.model);
+        let labels = RequestLabels::new(request.user.as_deref(), &request.request_tags);
+        let result = labels
+            .scope(span.instrument(self.post_raw(endpoints::CHAT_COMPLETIONS, &request)))
+            .await
+            .map(|envelope: ResponseEnvelope<ChatCompletionResponse>| {
+                let rate_limit_info = envelope.rate_limit_info.clone();
+                (envelope, rate_limit_info)
+            });
+        span.finish(result, |envelope| {
+            envelope.body.usage.as_ref().map(|u| (u.prompt_tokens, u.completion_tokens, u.total_tokens))
+        })
+        .map(|(envelope, _)| envelope)
     }
     
     /// Create a streaming chat completion
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use futures::StreamExt;
     /// use venice_ai_api_sdk_rust::{
     ///     Client,
@@ -420,11 +608,65 @@ impl Client {
         &self,
         request: ChatCompletionRequest,
     ) -> VeniceResult<(ChatCompletionStream, RateLimitInfo)> {
-        // Ensure streaming is enabled
         let mut request = request;
+        self.apply_content_policy(&mut request);
+        request.validate()?;
+        
+        // Ensure streaming is enabled
         request.stream = Some(true);
         
-        self.post_streaming::<_, crate::traits::chat::ChatCompletionChunk>(CHAT_COMPLETIONS_ENDPOINT, &request).await
+        let span = ApiSpan::chat_completions(&request.model);
+        let labels = RequestLabels::new(request.user.as_deref(), &request.request_tags);
+        let result = labels
+            .scope(span.instrument(self.post_streaming::<_, crate::traits::chat::ChatCompletionChunk>(endpoints::CHAT_COMPLETIONS, &request)))
+            .await;
+        span.finish(result, |_| None)
+    }
+
+    /// Create a streaming chat completion that yields untyped JSON chunks
+    ///
+    /// Unlike [`Client::create_streaming_chat_completion`], each chunk is returned as a
+    /// `serde_json::Value`, so the stream keeps working even if the API starts sending
+    /// chunk shapes this SDK does not model yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use venice_ai_api_sdk_rust::{Client, chat::ChatCompletionRequestBuilder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your-api-key")?;
+    ///
+    ///     let request = ChatCompletionRequestBuilder::new("llama-3.3-70b")
+    ///         .add_user_message("Tell me about AI")
+    ///         .build();
+    ///
+    ///     let (mut stream, _) = client.raw_stream(request).await?;
+    ///
+    ///     while let Some(chunk) = stream.next().await {
+    ///         let chunk = chunk?;
+    ///         if let Some(content) = chunk["choices"][0]["delta"]["content"].as_str() {
+    ///             print!("{}", content);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn raw_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> VeniceResult<(RawChatCompletionStream, RateLimitInfo)> {
+        let mut request = request;
+        self.apply_content_policy(&mut request);
+        request.validate()?;
+        
+        request.stream = Some(true);
+
+        let labels = RequestLabels::new(request.user.as_deref(), &request.request_tags);
+        labels.scope(self.post_streaming::<_, serde_json::Value>(endpoints::CHAT_COMPLETIONS, &request)).await
     }
 }
 
@@ -432,7 +674,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::chat::{
 ///     create_chat_completion,
 ///     ChatCompletionRequestBuilder,
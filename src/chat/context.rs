@@ -0,0 +1,228 @@
+//! Keeping conversations within a model's context window
+//!
+//! A [`ContextManager`] watches the size of a [`ChatSession`](crate::chat::ChatSession)'s
+//! history. When the next request would not fit the model's context, it asks a cheaper
+//! model to summarize the older turns and replaces them with a single summary message.
+//! Token counts are approximated from message length, so leave some headroom with
+//! [`ContextManager::reserve_tokens`].
+
+use crate::cost::estimate_message_tokens;
+use crate::error::{VeniceError, VeniceResult};
+use crate::models::list::Model;
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatMessage, ChatRole};
+
+/// Name given to summary messages, so later summaries fold them in
+pub const SUMMARY_MESSAGE_NAME: &str = "conversation_summary";
+
+/// Default instruction sent to the summarization model
+pub const DEFAULT_SUMMARY_PROMPT: &str = "Summarize the following conversation so it can replace the \
+     original messages. Keep names, facts, decisions and open questions. Reply with the summary only.";
+
+/// Summarizes older turns of a conversation when it outgrows the context window
+///
+/// Leading system messages and the most recent messages are always kept verbatim.
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, chat::{ChatSession, ContextManager}};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let mut session = ChatSession::new("support-42", "llama-3.3-70b")
+///     .with_context_manager(ContextManager::new(32_768, "llama-3.2-3b").keep_recent(6));
+///
+/// // Older turns are summarized by llama-3.2-3b whenever the history gets too long
+/// let reply = session.send(&client, "Where were we?").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextManager {
+    context_size: u32,
+    summary_model: String,
+    keep_recent: usize,
+    reserve_tokens: u32,
+    summary_prompt: String,
+}
+
+impl ContextManager {
+    /// Create a manager for a context of `context_size` tokens, summarizing with `summary_model`
+    pub fn new(context_size: u32, summary_model: impl Into<String>) -> Self {
+        Self {
+            context_size,
+            summary_model: summary_model.into(),
+            keep_recent: 4,
+            reserve_tokens: 1024,
+            summary_prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+        }
+    }
+
+    /// Create a manager for the context size a model reports, if it reports one
+    pub fn for_model(model: &Model, summary_model: impl Into<String>) -> Option<Self> {
+        model.context_size.map(|context_size| Self::new(context_size, summary_model))
+    }
+
+    /// Set the number of most recent messages never summarized, 4 by default
+    pub fn keep_recent(mut self, keep_recent: usize) -> Self {
+        self.keep_recent = keep_recent;
+        self
+    }
+
+    /// Set the tokens kept free for the reply, 1024 by default
+    pub fn reserve_tokens(mut self, reserve_tokens: u32) -> Self {
+        self.reserve_tokens = reserve_tokens;
+        self
+    }
+
+    /// Set the instruction sent to the summarization model
+    pub fn summary_prompt(mut self, summary_prompt: impl Into<String>) -> Self {
+        self.summary_prompt = summary_prompt.into();
+        self
+    }
+
+    /// Get the model used for summaries
+    pub fn summary_model(&self) -> &str {
+        &self.summary_model
+    }
+
+    /// Check whether messages leave too little room for the reply
+    pub fn needs_compaction(&self, messages: &[ChatMessage]) -> bool {
+        let tokens = estimate_message_tokens(messages.iter().map(|message| message.content.as_str()));
+        tokens.saturating_add(self.reserve_tokens) > self.context_size
+    }
+
+    /// Replace older turns with a summary if the messages do not fit
+    ///
+    /// Returns the messages unchanged if they fit or there is nothing old enough to summarize.
+    pub async fn fit<A: ChatApi + Sync + ?Sized>(
+        &self,
+        api: &A,
+        messages: Vec<ChatMessage>,
+    ) -> VeniceResult<Vec<ChatMessage>> {
+        if !self.needs_compaction(&messages) {
+            return Ok(messages);
+        }
+
+        let pinned = messages
+            .iter()
+            .take_while(|message| message.role == ChatRole::System && !is_summary(message))
+            .count();
+        let recent_start = messages.len().saturating_sub(self.keep_recent).max(pinned);
+        if recent_start == pinned {
+            return Ok(messages);
+        }
+
+        let summary = self.summarize(api, &messages[pinned..recent_start]).await?;
+        log::debug!(
+            "Summarized {} messages of a conversation into {} characters",
+            recent_start - pinned,
+            summary.content.len()
+        );
+        let mut fitted = messages;
+        fitted.splice(pinned..recent_start, [summary]);
+        Ok(fitted)
+    }
+
+    /// Ask the summary model to summarize messages
+    async fn summarize<A: ChatApi + Sync + ?Sized>(&self, api: &A, messages: &[ChatMessage]) -> VeniceResult<ChatMessage> {
+        let transcript: Vec<String> = messages
+            .iter()
+            .map(|message| {
+                let speaker = if is_summary(message) { "earlier summary" } else { message.role.as_str() };
+                format!("{}: {}", speaker, message.content)
+            })
+            .collect();
+        let request = ChatCompletionRequest {
+            model: self.summary_model.clone(),
+            messages: vec![
+                ChatMessage::system(&self.summary_prompt),
+                ChatMessage::user(transcript.join("\n\n")),
+            ],
+            ..Default::default()
+        };
+
+        let (response, _) = api.create_chat_completion(request).await?;
+        let summary = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| VeniceError::parse_error("Summary response has no choices"))?;
+        Ok(ChatMessage {
+            name: Some(SUMMARY_MESSAGE_NAME.to_string()),
+            ..ChatMessage::system(format!("Summary of the earlier conversation:\n{}", summary.trim()))
+        })
+    }
+}
+
+/// Check whether a message is a summary written by a context manager
+fn is_summary(message: &ChatMessage) -> bool {
+    message.name.as_deref() == Some(SUMMARY_MESSAGE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::ChatSession;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_session_summarizes_older_turns() {
+        let mut server = mockito::Server::new_async().await;
+        let completion = |model: &str, content: &str| {
+            format!(
+                r#"{{"id": "chatcmpl-1", "object": "chat.completion", "created": 1677652288, "model": "{}",
+                    "choices": [{{"index": 0, "message": {{"role": "assistant", "content": "{}"}}, "finish_reason": "stop"}}]}}"#,
+                model, content
+            )
+        };
+        let summary_mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(r#"{"model": "llama-3.2-3b"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion("llama-3.2-3b", "The user asked about cats."))
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(r#"{"model": "llama-3.3-70b"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion("llama-3.3-70b", "Sure."))
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let mut session = ChatSession::new("chat-1", "llama-3.3-70b")
+            .with_system_prompt("Be brief.")
+            .with_context_manager(ContextManager::new(300, "llama-3.2-3b").keep_recent(2).reserve_tokens(100));
+        for _ in 0..3 {
+            session.push(ChatMessage::user("Tell me about cats. ".repeat(10)));
+            session.push(ChatMessage::assistant("Cats are small carnivores. ".repeat(10)));
+        }
+
+        assert_eq!(session.send(&client, "And dogs?").await.unwrap(), "Sure.");
+        summary_mock.assert_async().await;
+
+        let messages = session.messages();
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "system", "assistant", "user", "assistant"]);
+        assert_eq!(messages[0].content, "Be brief.");
+        assert!(is_summary(&messages[1]));
+        assert!(messages[1].content.ends_with("The user asked about cats."));
+    }
+
+    #[test]
+    fn test_needs_compaction() {
+        let manager = ContextManager::new(100, "llama-3.2-3b").reserve_tokens(50);
+        assert!(!manager.needs_compaction(&[ChatMessage::user("Hello")]));
+        assert!(manager.needs_compaction(&[ChatMessage::user("word ".repeat(50))]));
+    }
+}
@@ -0,0 +1,132 @@
+//! Continuing truncated completions
+//!
+//! A completion that stops with `finish_reason == "length"` ran into its token limit.
+//! The API has no parameter for resuming one, so [`continue_completion`] sends the text
+//! so far back as an assistant message followed by a request to continue, and appends
+//! each answer to the content until the model finishes on its own.
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatCompletionResponse, ChatCompletionUsage, ChatMessage};
+
+/// The user message asking the model to go on with a truncated answer
+pub const CONTINUE_PROMPT: &str = "Continue exactly where you stopped. Do not repeat anything you already wrote.";
+
+/// Check whether the first choice of a response was cut off by the token limit
+pub fn is_truncated(response: &ChatCompletionResponse) -> bool {
+    response
+        .choices
+        .first()
+        .is_some_and(|choice| choice.finish_reason.as_deref() == Some("length"))
+}
+
+/// Continue the first choice of `response` while it is truncated, up to `max_continuations` times
+///
+/// `request` is the request `response` answers. The returned response has the stitched
+/// content and the finish reason of the last continuation in its first choice, and the
+/// token usage of all requests; other choices are dropped. A response that is not
+/// truncated is returned unchanged, and a response still truncated after
+/// `max_continuations` follow-ups is returned as far as it got.
+pub async fn continue_completion<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    mut response: ChatCompletionResponse,
+    mut request: ChatCompletionRequest,
+    max_continuations: usize,
+) -> VeniceResult<ChatCompletionResponse> {
+    if !is_truncated(&response) {
+        return Ok(response);
+    }
+    response.choices.truncate(1);
+
+    request.stream = None;
+    request.n = None;
+    let prompt_len = request.messages.len();
+
+    for _ in 0..max_continuations {
+        if !is_truncated(&response) {
+            break;
+        }
+        let content = response.choices[0].message.content.clone();
+        request.messages.truncate(prompt_len);
+        request.messages.push(ChatMessage::assistant(content));
+        request.messages.push(ChatMessage::user(CONTINUE_PROMPT));
+
+        let (next, _) = api.create_chat_completion(request.clone()).await?;
+        let choice = next
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+        let stitched = &mut response.choices[0];
+        stitched.message.content.push_str(&choice.message.content);
+        stitched.finish_reason = choice.finish_reason;
+        response.usage = match (response.usage, next.usage) {
+            (Some(total), Some(usage)) => Some(ChatCompletionUsage {
+                prompt_tokens: total.prompt_tokens + usage.prompt_tokens,
+                completion_tokens: total.completion_tokens + usage.completion_tokens,
+                total_tokens: total.total_tokens + usage.total_tokens,
+            }),
+            (total, usage) => total.or(usage),
+        };
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use crate::traits::chat::ChatCompletionBuilder;
+
+    fn completion(content: &str, finish_reason: &str) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "choices": [{"index": 0, "finish_reason": finish_reason, "message": {"role": "assistant", "content": content}}],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_continue_completion_stitches_content() {
+        let mut server = mockito::Server::new_async().await;
+        // The more specific mock is created first, since mocks are matched in order
+        let second = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::Regex(r#""role":"assistant","content":"One, two, three""#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion(", four.", "stop"))
+            .expect(1)
+            .create_async()
+            .await;
+        let first = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::Regex(r#""role":"assistant","content":"One, two""#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion(", three", "length"))
+            .expect(2)
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Count to four").max_tokens(2).build();
+        let response: ChatCompletionResponse = serde_json::from_str(&completion("One, two", "length")).unwrap();
+
+        let limited = continue_completion(&client, response.clone(), request.clone(), 1).await.unwrap();
+        assert_eq!(limited.choices[0].message.content, "One, two, three");
+        assert!(is_truncated(&limited));
+
+        let finished = continue_completion(&client, response, request, 3).await.unwrap();
+        assert_eq!(finished.choices[0].message.content, "One, two, three, four.");
+        assert_eq!(finished.choices[0].finish_reason.as_deref(), Some("stop"));
+        assert_eq!(finished.usage.unwrap().total_tokens, 45);
+
+        second.assert_async().await;
+        first.assert_async().await;
+    }
+}
@@ -1,7 +1,6 @@
 use crate::traits::chat as traits;
 use crate::chat::completions as chat;
 use crate::models::chat as models;
-use std::collections::HashMap;
 
 /// Implement conversion from traits::chat::ChatCompletionRequest to models::chat::ChatCompletionRequest
 impl From<traits::ChatCompletionRequest> for models::ChatCompletionRequest {
@@ -10,17 +9,26 @@ impl From<traits::ChatCompletionRequest> for models::ChatCompletionRequest {
             model: request.model,
             messages: request.messages.into_iter().map(Into::into).collect(),
             max_tokens: request.max_tokens,
-            max_completion_tokens: None,
+            max_completion_tokens: request.max_completion_tokens,
             temperature: request.temperature,
-            top_p: None,
-            frequency_penalty: None,
-            presence_penalty: None,
-            n: None,
+            top_p: request.top_p,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            top_k: request.top_k,
+            repetition_penalty: request.repetition_penalty,
+            min_p: request.min_p,
+            n: request.n,
             stream: request.stream,
-            seed: None,
-            stop: None,
-            venice_parameters: None,
-            extra: HashMap::new(),
+            stream_options: request.stream_options,
+            seed: request.seed,
+            stop: request.stop,
+            venice_parameters: request.venice_parameters,
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            response_format: request.response_format,
+            user: request.user,
+            request_tags: request.request_tags,
+            extra: request.extra,
         }
     }
 }
@@ -29,20 +37,10 @@ impl From<traits::ChatCompletionRequest> for models::ChatCompletionRequest {
 impl From<traits::ChatMessage> for models::ChatMessage {
     fn from(message: traits::ChatMessage) -> Self {
         Self {
-            role: message.role.into(),
+            role: message.role,
             content: message.content,
-        }
-    }
-}
-
-/// Implement conversion from traits::chat::ChatRole to models::chat::ChatRole
-impl From<traits::ChatRole> for models::ChatRole {
-    fn from(role: traits::ChatRole) -> Self {
-        match role {
-            traits::ChatRole::System => models::ChatRole::System,
-            traits::ChatRole::User => models::ChatRole::User,
-            traits::ChatRole::Assistant => models::ChatRole::Assistant,
-            traits::ChatRole::Function => panic!("Function role not supported in models::ChatRole"),
+            tool_calls: message.tool_calls,
+            tool_call_id: message.tool_call_id,
         }
     }
 }
@@ -54,8 +52,26 @@ impl From<models::ChatCompletionRequest> for traits::ChatCompletionRequest {
             model: request.model,
             messages: request.messages.into_iter().map(Into::into).collect(),
             max_tokens: request.max_tokens,
+            max_completion_tokens: request.max_completion_tokens,
             temperature: request.temperature,
+            top_p: request.top_p,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            top_k: request.top_k,
+            repetition_penalty: request.repetition_penalty,
+            min_p: request.min_p,
+            n: request.n,
             stream: request.stream,
+            stream_options: request.stream_options,
+            seed: request.seed,
+            stop: request.stop,
+            venice_parameters: request.venice_parameters,
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            response_format: request.response_format,
+            user: request.user,
+            request_tags: request.request_tags,
+            extra: request.extra,
         }
     }
 }
@@ -64,20 +80,11 @@ impl From<models::ChatCompletionRequest> for traits::ChatCompletionRequest {
 impl From<models::ChatMessage> for traits::ChatMessage {
     fn from(message: models::ChatMessage) -> Self {
         Self {
-            role: message.role.into(),
+            role: message.role,
             content: message.content,
             name: None,
-        }
-    }
-}
-
-/// Implement conversion from models::chat::ChatRole to traits::chat::ChatRole
-impl From<models::ChatRole> for traits::ChatRole {
-    fn from(role: models::ChatRole) -> Self {
-        match role {
-            models::ChatRole::System => traits::ChatRole::System,
-            models::ChatRole::User => traits::ChatRole::User,
-            models::ChatRole::Assistant => traits::ChatRole::Assistant,
+            tool_calls: message.tool_calls,
+            tool_call_id: message.tool_call_id,
         }
     }
 }
@@ -89,17 +96,26 @@ impl From<traits::ChatCompletionRequest> for chat::ChatCompletionRequest {
             model: request.model,
             messages: request.messages.into_iter().map(Into::into).collect(),
             max_tokens: request.max_tokens,
-            max_completion_tokens: None,
+            max_completion_tokens: request.max_completion_tokens,
             temperature: request.temperature,
-            top_p: None,
-            frequency_penalty: None,
-            presence_penalty: None,
-            n: None,
+            top_p: request.top_p,
+            frequency_penalty: request.frequency_penalty,
+            presence_penalty: request.presence_penalty,
+            top_k: request.top_k,
+            repetition_penalty: request.repetition_penalty,
+            min_p: request.min_p,
+            n: request.n,
             stream: request.stream,
-            seed: None,
-            stop: None,
-            venice_parameters: None,
-            extra: HashMap::new(),
+            stream_options: request.stream_options,
+            seed: request.seed,
+            stop: request.stop,
+            venice_parameters: request.venice_parameters,
+            tools: request.tools,
+            tool_choice: request.tool_choice,
+            response_format: request.response_format,
+            user: request.user,
+            request_tags: request.request_tags,
+            extra: request.extra,
         }
     }
 }
@@ -108,34 +124,12 @@ impl From<traits::ChatCompletionRequest> for chat::ChatCompletionRequest {
 impl From<traits::ChatMessage> for chat::ChatMessage {
     fn from(message: traits::ChatMessage) -> Self {
         Self {
-            role: message.role.into(),
+            role: message.role,
             content: message.content,
             name: message.name,
             function_call: None,
-        }
-    }
-}
-
-/// Implement conversion from traits::chat::ChatRole to chat::completions::ChatRole
-impl From<traits::ChatRole> for chat::ChatRole {
-    fn from(role: traits::ChatRole) -> Self {
-        match role {
-            traits::ChatRole::System => chat::ChatRole::System,
-            traits::ChatRole::User => chat::ChatRole::User,
-            traits::ChatRole::Assistant => chat::ChatRole::Assistant,
-            traits::ChatRole::Function => chat::ChatRole::Function,
-        }
-    }
-}
-
-/// Implement conversion from chat::completions::ChatRole to traits::chat::ChatRole
-impl From<chat::ChatRole> for traits::ChatRole {
-    fn from(role: chat::ChatRole) -> Self {
-        match role {
-            chat::ChatRole::System => traits::ChatRole::System,
-            chat::ChatRole::User => traits::ChatRole::User,
-            chat::ChatRole::Assistant => traits::ChatRole::Assistant,
-            chat::ChatRole::Function => traits::ChatRole::Function,
+            tool_calls: message.tool_calls,
+            tool_call_id: message.tool_call_id,
         }
     }
 }
@@ -153,11 +147,16 @@ mod tests {
                     role: traits::ChatRole::User,
                     content: "Hello".to_string(),
                     name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
             ],
             max_tokens: Some(100),
             temperature: Some(0.7),
+            top_p: Some(0.9),
             stream: Some(true),
+            venice_parameters: Some(traits::VeniceParameters::new().web_search(true)),
+            ..Default::default()
         };
         
         let chat_request: chat::ChatCompletionRequest = traits_request.into();
@@ -168,7 +167,9 @@ mod tests {
         assert_eq!(chat_request.messages[0].content, "Hello");
         assert_eq!(chat_request.max_tokens, Some(100));
         assert_eq!(chat_request.temperature, Some(0.7));
+        assert_eq!(chat_request.top_p, Some(0.9));
         assert_eq!(chat_request.stream, Some(true));
+        assert_eq!(chat_request.venice_parameters.unwrap().enable_web_search.as_deref(), Some("on"));
     }
     
     #[test]
@@ -180,11 +181,16 @@ mod tests {
                     role: traits::ChatRole::User,
                     content: "Hello".to_string(),
                     name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
             ],
             max_tokens: Some(100),
             temperature: Some(0.7),
+            top_p: Some(0.9),
             stream: Some(true),
+            venice_parameters: Some(traits::VeniceParameters::new().web_search(true)),
+            ..Default::default()
         };
         
         let models_request: models::ChatCompletionRequest = traits_request.into();
@@ -196,6 +202,7 @@ mod tests {
         assert_eq!(models_request.max_tokens, Some(100));
         assert_eq!(models_request.temperature, Some(0.7));
         assert_eq!(models_request.stream, Some(true));
+        assert!(models_request.venice_parameters.is_some());
     }
     
     #[test]
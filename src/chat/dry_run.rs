@@ -0,0 +1,159 @@
+//! Dry runs of chat completion requests
+//!
+//! [`Client::dry_run`](crate::Client::dry_run) prepares a chat completion request the
+//! way sending it would, applying the content policy, validating it and serializing it,
+//! but returns what would be sent instead of sending it. [`ChatDryRun::diff`] compares
+//! two prepared requests field by field, which helps to find where two requests that
+//! should behave alike actually differ.
+
+use serde_json::Value;
+
+use crate::cost::CostEstimate;
+
+/// Header names whose values are redacted, matched as substrings of the lowercase name
+const SECRET_HEADER_MARKERS: &[&str] = &["authorization", "key", "token", "secret"];
+
+/// A chat completion request prepared but not sent
+#[derive(Debug, Clone)]
+pub struct ChatDryRun {
+    /// HTTP method of the request
+    pub method: String,
+    /// URL the request would be sent to
+    pub url: String,
+    /// Headers of the request, with credentials redacted
+    pub headers: Vec<(String, String)>,
+    /// The JSON body of the request, exactly as it would be sent
+    pub payload: Value,
+    /// Estimated number of prompt tokens
+    pub prompt_tokens: u32,
+    /// Estimated cost, if a cost tracker with pricing for the model is attached
+    pub cost: Option<CostEstimate>,
+    /// Problems found while serializing, such as extra parameters that replace request fields
+    pub warnings: Vec<String>,
+}
+
+impl ChatDryRun {
+    /// Get the body of the request as pretty-printed JSON
+    pub fn payload_json(&self) -> String {
+        serde_json::to_string_pretty(&self.payload).unwrap_or_default()
+    }
+
+    /// List the differences between the body of this request and another one
+    ///
+    /// Each difference is one line naming the JSON path of the field, such as
+    /// `temperature: 0.7 -> 0.2`, `+ stop: ["END"]` or `- seed: 42`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::ChatCompletionBuilder};
+    ///
+    /// let client = Client::new("your-api-key").unwrap();
+    /// let before = client
+    ///     .dry_run(ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hi").temperature(0.5).build())
+    ///     .unwrap();
+    /// let after = client
+    ///     .dry_run(ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hi").temperature(0.25).build())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(before.diff(&after), vec!["temperature: 0.5 -> 0.25"]);
+    /// ```
+    pub fn diff(&self, other: &ChatDryRun) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff_values("", &self.payload, &other.payload, &mut changes);
+        changes
+    }
+}
+
+/// Redact the value of a header if its name suggests it holds a credential
+pub(crate) fn redact_header(name: &str, value: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    if !SECRET_HEADER_MARKERS.iter().any(|marker| name.contains(marker)) {
+        return value.to_string();
+    }
+    match value.split_once(' ') {
+        Some((scheme, secret)) => format!("{} {}", scheme, crate::secret::SecretString::new(secret).redacted()),
+        None => crate::secret::SecretString::new(value).redacted(),
+    }
+}
+
+/// Collect the differences between two JSON values under `path`
+fn diff_values(path: &str, before: &Value, after: &Value, changes: &mut Vec<String>) {
+    let child = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                match after.get(key) {
+                    Some(other) => diff_values(&child(key), value, other, changes),
+                    None => changes.push(format!("- {}: {}", child(key), value)),
+                }
+            }
+            for (key, value) in after.iter().filter(|(key, _)| !before.contains_key(*key)) {
+                changes.push(format!("+ {}: {}", child(key), value));
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for index in 0..before.len().max(after.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (before.get(index), after.get(index)) {
+                    (Some(value), Some(other)) => diff_values(&path, value, other, changes),
+                    (Some(value), None) => changes.push(format!("- {}: {}", path, value)),
+                    (None, Some(other)) => changes.push(format!("+ {}: {}", path, other)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if before != after => changes.push(format!("{}: {} -> {}", path, before, after)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use crate::traits::chat::ChatCompletionBuilder;
+
+    #[test]
+    fn test_dry_run_reports_payload_without_sending() {
+        let client = Client::builder()
+            .api_key("sk-1234567890abcd")
+            .base_url("http://127.0.0.1:9")
+            .build()
+            .unwrap();
+        let request = ChatCompletionBuilder::new("llama-3.3-70b")
+            .add_user("Hello")
+            .temperature(0.5)
+            .extra("temperature", 1.5)
+            .extra("top_k", 40)
+            .build();
+
+        let dry_run = client.dry_run(request).unwrap();
+        assert_eq!(dry_run.url, "http://127.0.0.1:9/chat/completions");
+        assert_eq!(dry_run.payload["top_k"], 40);
+        assert_eq!(dry_run.payload["messages"][0]["content"], "Hello");
+        assert!(dry_run.warnings[0].contains("temperature"));
+        let authorization = dry_run.headers.iter().find(|(name, _)| name == "authorization").unwrap();
+        assert_eq!(authorization.1, "Bearer sk-****abcd");
+
+        let invalid = ChatCompletionBuilder::new("llama-3.3-70b").max_tokens(0).add_user("Hello").build();
+        assert!(client.dry_run(invalid).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = serde_json::json!({"model": "a", "messages": [{"content": "Hi"}], "seed": 1});
+        let after = serde_json::json!({"model": "b", "messages": [{"content": "Hi"}, {"content": "Bye"}], "stop": ["END"]});
+        let mut changes = Vec::new();
+        diff_values("", &before, &after, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                r#"+ messages[1]: {"content":"Bye"}"#,
+                r#"model: "a" -> "b""#,
+                "- seed: 1",
+                r#"+ stop: ["END"]"#,
+            ]
+        );
+    }
+}
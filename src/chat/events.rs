@@ -0,0 +1,158 @@
+//! Structured events for streaming chat completions
+//!
+//! [`ChatStreamExt::into_events`] turns a stream of raw chunks into [`ChatStreamEvent`]s,
+//! so callers can match on what happened instead of digging through choices and deltas.
+
+use futures::{stream, Stream, StreamExt};
+use std::collections::HashSet;
+use std::pin::Pin;
+
+use crate::error::VeniceResult;
+use crate::traits::chat::{ChatCompletionChunk, ChatCompletionChunkToolCall, ChatCompletionUsage, ChatRole};
+
+/// Something that happened in a streaming chat completion
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatStreamEvent {
+    /// A choice started; sent once per choice before any of its other events
+    MessageStart {
+        /// ID of the chat completion
+        id: String,
+        /// The model generating the completion
+        model: String,
+        /// Index of the choice
+        index: u32,
+        /// Role of the message author
+        role: ChatRole,
+    },
+    /// A piece of the content of a choice
+    ContentDelta {
+        /// Index of the choice
+        index: u32,
+        /// The text to append to the content
+        text: String,
+    },
+    /// A fragment of a tool call requested by a choice
+    ToolCallDelta {
+        /// Index of the choice
+        index: u32,
+        /// The fragment; fragments with the same `tool_call.index` belong to the same call
+        tool_call: ChatCompletionChunkToolCall,
+    },
+    /// Token usage of the whole completion
+    UsageReport(ChatCompletionUsage),
+    /// A choice finished
+    Done {
+        /// Index of the choice
+        index: u32,
+        /// Why the choice finished, such as `stop`, `length` or `tool_calls`
+        finish_reason: String,
+    },
+    /// The connection dropped and the stream is being reconnected
+    ///
+    /// Only sent by [`Client::stream_chat_events`](crate::Client::stream_chat_events).
+    Reconnecting {
+        /// Number of the attempt, counted since the last event received
+        attempt: u32,
+        /// ID of the last event received, sent to the server to resume after it
+        last_event_id: Option<String>,
+    },
+}
+
+/// Type alias for a stream of chat completion events
+pub type ChatEventStream = Pin<Box<dyn Stream<Item = VeniceResult<ChatStreamEvent>> + Send>>;
+
+/// Adapters for streams of chat completion chunks
+pub trait ChatStreamExt {
+    /// Translate the chunks of the stream into [`ChatStreamEvent`]s
+    ///
+    /// Errors are passed through unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use venice_ai_api_sdk_rust::{
+    ///     Client,
+    ///     chat::{ChatStreamEvent, ChatStreamExt},
+    ///     traits::chat::{ChatApi, ChatCompletionBuilder},
+    /// };
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Tell me a joke").build();
+    /// let (stream, _) = ChatApi::create_streaming_chat_completion(&client, request).await?;
+    ///
+    /// let mut events = stream.into_events();
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         ChatStreamEvent::ContentDelta { text, .. } => print!("{}", text),
+    ///         ChatStreamEvent::UsageReport(usage) => println!("\n{} tokens", usage.total_tokens),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn into_events(self) -> ChatEventStream;
+
+    /// Record when each chunk arrives, see [`TimedStream`](crate::chat::TimedStream)
+    fn timed(self) -> crate::chat::TimedStream<Self>
+    where
+        Self: Sized;
+}
+
+impl<S> ChatStreamExt for S
+where
+    S: Stream<Item = VeniceResult<ChatCompletionChunk>> + Send + 'static,
+{
+    fn into_events(self) -> ChatEventStream {
+        let mut translator = EventTranslator::default();
+        Box::pin(
+            self.map(move |chunk| match chunk {
+                Ok(chunk) => translator.translate(chunk).into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            })
+            .flat_map(stream::iter),
+        )
+    }
+
+    fn timed(self) -> crate::chat::TimedStream<Self> {
+        crate::chat::TimedStream::new(self)
+    }
+}
+
+/// Turns chunks into events, remembering which choices have started
+#[derive(Debug, Default)]
+pub(crate) struct EventTranslator {
+    started: HashSet<u32>,
+}
+
+impl EventTranslator {
+    pub(crate) fn translate(&mut self, chunk: ChatCompletionChunk) -> Vec<ChatStreamEvent> {
+        let mut events = Vec::new();
+        for choice in chunk.choices {
+            let index = choice.index;
+            if self.started.insert(index) {
+                events.push(ChatStreamEvent::MessageStart {
+                    id: chunk.id.clone(),
+                    model: chunk.model.clone(),
+                    index,
+                    role: choice.delta.role.unwrap_or(ChatRole::Assistant),
+                });
+            }
+            if let Some(text) = choice.delta.content.filter(|text| !text.is_empty()) {
+                events.push(ChatStreamEvent::ContentDelta { index, text });
+            }
+            for tool_call in choice.delta.tool_calls.unwrap_or_default() {
+                events.push(ChatStreamEvent::ToolCallDelta { index, tool_call });
+            }
+            if let Some(finish_reason) = choice.finish_reason {
+                events.push(ChatStreamEvent::Done { index, finish_reason });
+            }
+        }
+        if let Some(usage) = chunk.usage {
+            events.push(ChatStreamEvent::UsageReport(usage));
+        }
+        events
+    }
+}
@@ -0,0 +1,249 @@
+//! Few-shot prompting
+//!
+//! A [`FewShotBuilder`] turns a task instruction and example input/output pairs into the
+//! messages that precede the real input of a chat request, in the order models expect:
+//! the instruction, then each example input followed by its output.
+
+use crate::traits::chat::{ChatCompletionBuilder, ChatMessage, ChatRole};
+
+/// An example input and the output the model should produce for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FewShotExample {
+    /// The example input
+    pub input: String,
+    /// The expected output
+    pub output: String,
+    /// Role of the input message, overriding the builder's input role
+    pub input_role: Option<ChatRole>,
+    /// Role of the output message, overriding the builder's output role
+    pub output_role: Option<ChatRole>,
+}
+
+impl FewShotExample {
+    /// Create an example that uses the builder's roles
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            input_role: None,
+            output_role: None,
+        }
+    }
+
+    /// Set the roles of this example's messages
+    pub fn roles(mut self, input_role: ChatRole, output_role: ChatRole) -> Self {
+        self.input_role = Some(input_role);
+        self.output_role = Some(output_role);
+        self
+    }
+}
+
+/// Builds the instruction and example messages of a few-shot prompt
+///
+/// By default every example becomes a user message followed by an assistant message.
+/// With a [separator](FewShotBuilder::separator), the examples are written into the
+/// system message instead, labelled and separated, which suits models that follow
+/// instructions better than they imitate turns.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::chat::FewShotBuilder;
+/// use venice_ai_api_sdk_rust::traits::chat::ChatCompletionBuilder;
+///
+/// let examples = FewShotBuilder::new("Classify the sentiment as positive or negative.")
+///     .example("I love this phone", "positive")
+///     .example("The battery died in an hour", "negative");
+///
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+///     .few_shot(examples)
+///     .add_user("Best purchase this year")
+///     .build();
+///
+/// let roles: Vec<&str> = request.messages.iter().map(|m| m.role.as_str()).collect();
+/// assert_eq!(roles, ["system", "user", "assistant", "user", "assistant", "user"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FewShotBuilder {
+    instruction: Option<String>,
+    examples: Vec<FewShotExample>,
+    input_role: ChatRole,
+    output_role: ChatRole,
+    separator: Option<String>,
+    input_label: String,
+    output_label: String,
+}
+
+impl Default for FewShotBuilder {
+    fn default() -> Self {
+        Self {
+            instruction: None,
+            examples: Vec::new(),
+            input_role: ChatRole::User,
+            output_role: ChatRole::Assistant,
+            separator: None,
+            input_label: "Input:".to_string(),
+            output_label: "Output:".to_string(),
+        }
+    }
+}
+
+impl FewShotBuilder {
+    /// Create a builder for a task instruction, sent as the system message
+    pub fn new(instruction: impl Into<String>) -> Self {
+        Self {
+            instruction: Some(instruction.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create a builder without an instruction
+    pub fn without_instruction() -> Self {
+        Self::default()
+    }
+
+    /// Add an example input and its expected output
+    pub fn example(self, input: impl Into<String>, output: impl Into<String>) -> Self {
+        self.add_example(FewShotExample::new(input, output))
+    }
+
+    /// Add an example, which may have its own roles
+    pub fn add_example(mut self, example: FewShotExample) -> Self {
+        self.examples.push(example);
+        self
+    }
+
+    /// Add several examples
+    pub fn examples<I, S, T>(self, examples: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+        T: Into<String>,
+    {
+        examples
+            .into_iter()
+            .fold(self, |builder, (input, output)| builder.example(input, output))
+    }
+
+    /// Set the roles of example messages, user and assistant by default
+    pub fn roles(mut self, input_role: ChatRole, output_role: ChatRole) -> Self {
+        self.input_role = input_role;
+        self.output_role = output_role;
+        self
+    }
+
+    /// Write the examples into the system message, separated by `separator`
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    /// Set the labels before inputs and outputs written into the system message
+    pub fn labels(mut self, input_label: impl Into<String>, output_label: impl Into<String>) -> Self {
+        self.input_label = input_label.into();
+        self.output_label = output_label.into();
+        self
+    }
+
+    /// Get the examples added so far
+    pub fn example_pairs(&self) -> &[FewShotExample] {
+        &self.examples
+    }
+
+    /// Build the messages of the prompt, to be followed by the real input
+    pub fn messages(&self) -> Vec<ChatMessage> {
+        match &self.separator {
+            Some(separator) => self.inline_messages(separator),
+            None => self.turn_messages(),
+        }
+    }
+
+    /// The instruction as a system message, followed by two messages per example
+    fn turn_messages(&self) -> Vec<ChatMessage> {
+        let instruction = self.instruction.iter().map(ChatMessage::system);
+        let examples = self.examples.iter().flat_map(|example| {
+            [
+                ChatMessage::new(example.input_role.clone().unwrap_or(self.input_role.clone()), &example.input),
+                ChatMessage::new(example.output_role.clone().unwrap_or(self.output_role.clone()), &example.output),
+            ]
+        });
+        instruction.chain(examples).collect()
+    }
+
+    /// A single system message holding the instruction and the labelled examples
+    fn inline_messages(&self, separator: &str) -> Vec<ChatMessage> {
+        let examples: Vec<String> = self
+            .examples
+            .iter()
+            .map(|example| {
+                format!(
+                    "{} {}\n{} {}",
+                    self.input_label, example.input, self.output_label, example.output
+                )
+            })
+            .collect();
+        let sections: Vec<&str> = self
+            .instruction
+            .as_deref()
+            .into_iter()
+            .chain(examples.iter().map(String::as_str))
+            .collect();
+        if sections.is_empty() {
+            return Vec::new();
+        }
+        vec![ChatMessage::system(sections.join(separator))]
+    }
+}
+
+impl ChatCompletionBuilder {
+    /// Add the instruction and example messages of a few-shot prompt
+    ///
+    /// Call this before adding the real input, so the examples come first.
+    pub fn few_shot(self, examples: FewShotBuilder) -> Self {
+        examples
+            .messages()
+            .into_iter()
+            .fold(self, |builder, message| builder.add_message(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_messages_and_roles() {
+        let messages = FewShotBuilder::new("Translate to French.")
+            .example("cat", "chat")
+            .add_example(FewShotExample::new("dog", "chien").roles(ChatRole::System, ChatRole::System))
+            .messages();
+
+        let turns: Vec<(&str, &str)> = messages.iter().map(|m| (m.role.as_str(), m.content.as_str())).collect();
+        assert_eq!(
+            turns,
+            [
+                ("system", "Translate to French."),
+                ("user", "cat"),
+                ("assistant", "chat"),
+                ("system", "dog"),
+                ("system", "chien"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_examples_with_separator() {
+        let messages = FewShotBuilder::new("Translate to French.")
+            .examples([("cat", "chat"), ("dog", "chien")])
+            .separator("\n###\n")
+            .labels("English:", "French:")
+            .messages();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].content,
+            "Translate to French.\n###\nEnglish: cat\nFrench: chat\n###\nEnglish: dog\nFrench: chien"
+        );
+        assert!(FewShotBuilder::without_instruction().separator("---").messages().is_empty());
+    }
+}
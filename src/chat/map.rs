@@ -0,0 +1,186 @@
+//! Running many prompts concurrently
+//!
+//! [`Client::map_chat`](crate::Client::map_chat) sends one chat request per prompt with
+//! bounded concurrency and collects the answers in prompt order, the usual shape of
+//! data-labeling and evaluation scripts.
+
+use std::fmt;
+use std::sync::Arc;
+
+use futures::stream::{self, StreamExt};
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+use crate::traits::image::{BatchProgress, BatchProgressCallback};
+
+/// Options for [`Client::map_chat`](crate::Client::map_chat)
+#[derive(Clone)]
+pub struct MapChatOptions {
+    /// Model answering the prompts, the client's default chat model if `None`
+    pub model: Option<String>,
+    /// System message sent before each prompt
+    pub system: Option<String>,
+    /// Maximum number of tokens of each answer
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Maximum number of requests in flight at once
+    pub concurrency: usize,
+    /// Callback invoked after each prompt is answered or fails
+    pub on_progress: Option<BatchProgressCallback>,
+}
+
+impl fmt::Debug for MapChatOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapChatOptions")
+            .field("model", &self.model)
+            .field("system", &self.system)
+            .field("max_tokens", &self.max_tokens)
+            .field("temperature", &self.temperature)
+            .field("concurrency", &self.concurrency)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl Default for MapChatOptions {
+    fn default() -> Self {
+        Self {
+            model: None,
+            system: None,
+            max_tokens: None,
+            temperature: None,
+            concurrency: 4,
+            on_progress: None,
+        }
+    }
+}
+
+impl MapChatOptions {
+    /// Create options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the model answering the prompts
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set a system message sent before each prompt
+    pub fn system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    /// Set the maximum number of tokens of each answer
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the maximum number of requests in flight at once, 4 by default
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set a callback invoked after each prompt is answered or fails
+    pub fn on_progress(mut self, callback: impl Fn(BatchProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Build the request for one prompt
+    fn request(&self, model: &str, prompt: String) -> crate::traits::chat::ChatCompletionRequest {
+        let mut builder = ChatCompletionBuilder::new(model);
+        if let Some(system) = &self.system {
+            builder = builder.add_system(system.clone());
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            builder = builder.max_tokens(max_tokens);
+        }
+        if let Some(temperature) = self.temperature {
+            builder = builder.temperature(temperature);
+        }
+        builder.add_user(prompt).build()
+    }
+}
+
+/// Answer every prompt with `model`, returning the answers in prompt order
+pub(crate) async fn map_chat<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    model: &str,
+    prompts: Vec<String>,
+    options: MapChatOptions,
+) -> Vec<VeniceResult<String>> {
+    let total = prompts.len();
+    let mut progress = BatchProgress {
+        completed: 0,
+        succeeded: 0,
+        failed: 0,
+        total,
+    };
+
+    let options = &options;
+    let mut answers = stream::iter(prompts.into_iter().enumerate())
+        .map(|(index, prompt)| async move {
+            let result = api.create_chat_completion(options.request(model, prompt)).await.and_then(|(response, _)| {
+                response
+                    .choices
+                    .into_iter()
+                    .next()
+                    .map(|choice| choice.message.content)
+                    .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))
+            });
+            (index, result)
+        })
+        .buffer_unordered(options.concurrency.max(1));
+
+    let mut results: Vec<Option<VeniceResult<String>>> = (0..total).map(|_| None).collect();
+    while let Some((index, result)) = answers.next().await {
+        progress.completed += 1;
+        if result.is_ok() {
+            progress.succeeded += 1;
+        } else {
+            progress.failed += 1;
+        }
+        if let Some(callback) = &options.on_progress {
+            callback(progress);
+        }
+        results[index] = Some(result);
+    }
+
+    results.into_iter().map(|result| result.expect("every prompt is answered")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{Fault, FaultInjector, TestChatClient};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_answers_keep_prompt_order_with_per_item_errors() {
+        let api = FaultInjector::new(TestChatClient::new()).then_fail(Fault::service_unavailable());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let options = MapChatOptions::new().concurrency(1).on_progress(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let prompts = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let answers = map_chat(&api, "llama-3.3-70b", prompts, options).await;
+        assert!(answers[0].is_err());
+        assert_eq!(answers[1].as_ref().unwrap(), "This is a test response");
+        assert_eq!(answers[2].as_ref().unwrap(), "This is a test response");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -2,17 +2,52 @@
 //!
 //! This module contains types and functions for working with Venice.ai's chat API.
 
+mod cache;
+mod collector;
 mod completions;
+mod context;
+mod continuation;
+pub(crate) mod dry_run;
 mod conversions;
+mod events;
+mod few_shot;
+pub(crate) mod map;
 mod model_feature_suffix;
+mod multiplex;
+mod reconnect;
+mod session;
+mod timing;
+mod tools;
 mod streaming;
+mod summarize;
+mod writer;
+pub(crate) mod validation;
 #[cfg(test)]
 mod tests;
-#[cfg(test)]
-mod test_client;
+pub(crate) mod test_client;
 
+pub use cache::{CacheEntry, CacheEntryInfo, CacheStats, CacheStore, InMemoryCacheStore, JsonFileCacheStore, ResponseCache};
+pub use collector::{MultiChoiceCollector, StreamCollector};
 pub use completions::*;
+pub use continuation::{continue_completion, is_truncated, CONTINUE_PROMPT};
+pub use dry_run::ChatDryRun;
+pub use validation::{ValidationReport, ValidationWarning};
+pub use context::{ContextManager, DEFAULT_SUMMARY_PROMPT, SUMMARY_MESSAGE_NAME};
+pub use events::*;
+pub use few_shot::{FewShotBuilder, FewShotExample};
+pub use map::MapChatOptions;
 pub use model_feature_suffix::*;
+pub use multiplex::{SourceStatus, StreamMultiplexer};
+pub use reconnect::ReconnectPolicy;
+pub(crate) use reconnect::reconnecting_events;
+pub use session::*;
+pub use timing::{StreamStats, TimedChunk, TimedStream};
+pub use tools::*;
 pub use streaming::*;
+pub use summarize::{
+    summarize_document, DocumentSummary, SummarizeOptions, SummarizeProgress, SummarizeProgressCallback, SummarizeStage,
+    DEFAULT_CHUNK_PROMPT, DEFAULT_REDUCE_PROMPT,
+};
+pub use writer::{stream_chat_to_writer, StreamWriteOptions, StreamWriteSummary};
 #[cfg(test)]
 pub use test_client::*;
\ No newline at end of file
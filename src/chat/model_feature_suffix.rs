@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 use crate::{
     client::Client,
-    error::{RateLimitInfo, VeniceResult},
+    endpoints,
+    error::{RateLimitInfo, VeniceError, VeniceResult},
+    traits::chat::VeniceParameters,
 };
 
-/// The endpoint for retrieving model feature suffixes
-const FEATURE_SUFFIX_ENDPOINT: &str = "chat/model_feature_suffix";
-
 /// Request parameters for retrieving model feature suffixes
 #[derive(Debug, Serialize, Default)]
 pub struct ModelFeatureSuffixRequest {
@@ -39,12 +40,172 @@ pub struct ModelFeatureSuffixResponse {
     pub object: String,
 }
 
+/// A model ID together with the feature suffixes appended to it
+///
+/// Venice accepts Venice parameters appended to the model name, as in
+/// `llama-3.3-70b:enable_web_search=on:character=alan-watts`. `ModelSpec` parses such
+/// strings into the base model and [`VeniceParameters`], and formats them back.
+/// Parameters may be separated by `:` or `&`; `character` is accepted as an alias
+/// for `character_slug`.
+///
+/// A spec converts into a `String`, so it can be passed anywhere a model ID is expected.
+/// Chat builders can instead split it into the model and `venice_parameters` with
+/// `model_spec`/`with_model_spec`.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::chat::ModelSpec;
+///
+/// let spec: ModelSpec = "llama-3.3-70b:enable_web_search=on:character=alan-watts".parse().unwrap();
+/// assert_eq!(spec.model(), "llama-3.3-70b");
+/// assert_eq!(spec.parameters().enable_web_search.as_deref(), Some("on"));
+/// assert_eq!(spec.parameters().character_slug.as_deref(), Some("alan-watts"));
+///
+/// let spec = ModelSpec::new("llama-3.3-70b")
+///     .web_search(true)
+///     .include_venice_system_prompt(false);
+/// assert_eq!(spec.to_string(), "llama-3.3-70b:enable_web_search=on:include_venice_system_prompt=false");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelSpec {
+    model: String,
+    parameters: VeniceParameters,
+}
+
+impl ModelSpec {
+    /// Create a spec for a model with no feature suffixes
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            parameters: VeniceParameters::default(),
+        }
+    }
+
+    /// Parse a model ID with optional feature suffixes
+    pub fn parse(spec: &str) -> VeniceResult<Self> {
+        let mut parts = spec.split(':');
+        let model = parts.next().unwrap_or_default().trim();
+        if model.is_empty() {
+            return Err(VeniceError::InvalidInput(format!("model spec has no model: {:?}", spec)));
+        }
+
+        let mut parameters = VeniceParameters::default();
+        for param in parts.flat_map(|part| part.split('&')) {
+            let (key, value) = param
+                .split_once('=')
+                .filter(|(key, _)| !key.trim().is_empty())
+                .ok_or_else(|| {
+                    VeniceError::InvalidInput(format!("model spec parameter must be key=value, got {:?}", param))
+                })?;
+            let key = match key.trim() {
+                "character" => "character_slug",
+                key => key,
+            };
+            let value = match value.trim() {
+                "true" => serde_json::Value::Bool(true),
+                "false" => serde_json::Value::Bool(false),
+                value => serde_json::Value::String(value.to_string()),
+            };
+            parameters.set(key, value);
+        }
+
+        Ok(Self {
+            model: model.to_string(),
+            parameters,
+        })
+    }
+
+    /// The base model ID, without suffixes
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The Venice parameters carried by the suffixes
+    pub fn parameters(&self) -> &VeniceParameters {
+        &self.parameters
+    }
+
+    /// Split the spec into the base model ID and its Venice parameters
+    pub fn into_parts(self) -> (String, VeniceParameters) {
+        (self.model, self.parameters)
+    }
+
+    /// Turn web search on or off
+    pub fn web_search(mut self, enable: bool) -> Self {
+        self.parameters = self.parameters.web_search(enable);
+        self
+    }
+
+    /// Set the web search mode ("on", "off" or "auto")
+    pub fn enable_web_search(mut self, mode: impl Into<String>) -> Self {
+        self.parameters = self.parameters.enable_web_search(mode);
+        self
+    }
+
+    /// Set whether to include Venice's default system prompt
+    pub fn include_venice_system_prompt(mut self, include: bool) -> Self {
+        self.parameters = self.parameters.include_venice_system_prompt(include);
+        self
+    }
+
+    /// Set the character to chat with
+    pub fn character_slug(mut self, slug: impl Into<String>) -> Self {
+        self.parameters = self.parameters.character_slug(slug);
+        self
+    }
+
+    /// Set any other parameter by name
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.parameters.set(key, value);
+        self
+    }
+}
+
+impl fmt::Display for ModelSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.model)?;
+        let fields = match serde_json::to_value(&self.parameters) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        let known = ["enable_web_search", "include_venice_system_prompt", "character_slug"];
+        let mut extra: Vec<_> = fields.iter().filter(|(key, _)| !known.contains(&key.as_str())).collect();
+        extra.sort_by(|a, b| a.0.cmp(b.0));
+        let ordered = known
+            .iter()
+            .filter_map(|key| fields.get_key_value(*key))
+            .chain(extra);
+        for (key, value) in ordered {
+            match value {
+                serde_json::Value::String(value) => write!(f, ":{}={}", key, value)?,
+                value => write!(f, ":{}={}", key, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ModelSpec {
+    type Err = VeniceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<ModelSpec> for String {
+    fn from(spec: ModelSpec) -> Self {
+        spec.to_string()
+    }
+}
+
 impl Client {
     /// Get model feature suffixes
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{Client, chat::ModelFeatureSuffixRequest};
     ///
     /// #[tokio::main]
@@ -90,17 +251,17 @@ impl Client {
                 
                 // Append query parameters to endpoint
                 if query_params.is_empty() {
-                    self.get(FEATURE_SUFFIX_ENDPOINT).await
+                    self.get(endpoints::CHAT_MODEL_FEATURE_SUFFIX).await
                 } else {
                     let endpoint = format!(
                         "{}?{}",
-                        FEATURE_SUFFIX_ENDPOINT,
+                        endpoints::CHAT_MODEL_FEATURE_SUFFIX,
                         serde_urlencoded::to_string(query_params).unwrap_or_default()
                     );
                     self.get(&endpoint).await
                 }
             }
-            None => self.get(FEATURE_SUFFIX_ENDPOINT).await,
+            None => self.get(endpoints::CHAT_MODEL_FEATURE_SUFFIX).await,
         }
     }
 }
@@ -109,7 +270,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::chat::{get_model_feature_suffixes, ModelFeatureSuffixRequest};
 ///
 /// #[tokio::main]
@@ -0,0 +1,211 @@
+//! Merging several streaming chat completions
+//!
+//! A [`StreamMultiplexer`] reads several chat completion streams at once, such as the
+//! answers of different models to the same question, and yields their chunks as they
+//! arrive, each tagged with the ID of its source. It tracks which sources have finished,
+//! for comparison views and for combining the answers once they are all in.
+
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::error::VeniceResult;
+use crate::traits::chat::{ChatCompletionChunk, ChatCompletionStream};
+
+/// Progress of one source of a [`StreamMultiplexer`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceStatus {
+    /// Number of chunks received
+    pub chunks: usize,
+    /// Finish reason of the first choice, once it finished
+    pub finish_reason: Option<String>,
+    /// Whether the source yielded an error
+    pub failed: bool,
+    /// Whether the stream of the source has ended
+    pub finished: bool,
+}
+
+/// Merges chat completion streams into one stream of `(source, chunk)` items
+///
+/// Sources are read in turn, so a fast source cannot starve the others. The merged
+/// stream ends once every source has ended.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use venice_ai_api_sdk_rust::{Client, traits::chat::ChatCompletionBuilder};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Why is the sky blue?").build();
+///
+/// let mut streams = client
+///     .stream_models(request, &["llama-3.3-70b", "qwen-2.5-qwq-32b", "mistral-31-24b"])
+///     .await?;
+/// while let Some((model, chunk)) = streams.next().await {
+///     if let Some(text) = chunk?.choices.first().and_then(|choice| choice.delta.content.as_deref()) {
+///         println!("[{}] {}", model, text);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct StreamMultiplexer {
+    sources: Vec<Source>,
+    /// Index of the source to read first on the next poll
+    next: usize,
+}
+
+struct Source {
+    id: String,
+    stream: ChatCompletionStream,
+    status: SourceStatus,
+}
+
+impl StreamMultiplexer {
+    /// Create a multiplexer without sources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stream, tagging its chunks with `id`
+    pub fn add(&mut self, id: impl Into<String>, stream: ChatCompletionStream) {
+        self.sources.push(Source {
+            id: id.into(),
+            stream,
+            status: SourceStatus::default(),
+        });
+    }
+
+    /// Add a stream, tagging its chunks with `id`
+    pub fn with_stream(mut self, id: impl Into<String>, stream: ChatCompletionStream) -> Self {
+        self.add(id, stream);
+        self
+    }
+
+    /// Get the progress of a source
+    pub fn status(&self, id: &str) -> Option<&SourceStatus> {
+        self.sources.iter().find(|source| source.id == id).map(|source| &source.status)
+    }
+
+    /// Get the progress of every source, in the order they were added
+    pub fn statuses(&self) -> impl Iterator<Item = (&str, &SourceStatus)> {
+        self.sources.iter().map(|source| (source.id.as_str(), &source.status))
+    }
+
+    /// Get the IDs of the sources whose streams have not ended
+    pub fn pending(&self) -> Vec<&str> {
+        self.statuses()
+            .filter(|(_, status)| !status.finished)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Check whether every stream has ended
+    pub fn is_finished(&self) -> bool {
+        self.sources.iter().all(|source| source.status.finished)
+    }
+}
+
+impl<I: Into<String>> FromIterator<(I, ChatCompletionStream)> for StreamMultiplexer {
+    fn from_iter<T: IntoIterator<Item = (I, ChatCompletionStream)>>(streams: T) -> Self {
+        let mut multiplexer = Self::new();
+        for (id, stream) in streams {
+            multiplexer.add(id, stream);
+        }
+        multiplexer
+    }
+}
+
+impl std::fmt::Debug for StreamMultiplexer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamMultiplexer")
+            .field("sources", &self.statuses().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Stream for StreamMultiplexer {
+    type Item = (String, VeniceResult<ChatCompletionChunk>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let count = self.sources.len();
+        for offset in 0..count {
+            let index = (self.next + offset) % count;
+            let source = &mut self.sources[index];
+            if source.status.finished {
+                continue;
+            }
+            match source.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    match &item {
+                        Ok(chunk) => {
+                            source.status.chunks += 1;
+                            if let Some(reason) = chunk.choices.iter().find(|choice| choice.index == 0).and_then(|choice| choice.finish_reason.clone()) {
+                                source.status.finish_reason = Some(reason);
+                            }
+                        }
+                        Err(_) => source.status.failed = true,
+                    }
+                    let id = source.id.clone();
+                    self.next = (index + 1) % count;
+                    return Poll::Ready(Some((id, item)));
+                }
+                Poll::Ready(None) => source.status.finished = true,
+                Poll::Pending => {}
+            }
+        }
+        if self.is_finished() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VeniceError;
+    use futures::{stream, StreamExt};
+
+    fn chunk(model: &str, content: &str, finish_reason: Option<&str>) -> VeniceResult<ChatCompletionChunk> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 1677652288,
+            "model": model,
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish_reason}]
+        }))
+        .unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_multiplexer_interleaves_and_tracks_sources() {
+        let a: ChatCompletionStream = Box::pin(stream::iter(vec![
+            chunk("a", "1", None),
+            chunk("a", "2", None),
+            chunk("a", "3", Some("stop")),
+        ]));
+        let b: ChatCompletionStream = Box::pin(stream::iter(vec![
+            chunk("b", "x", None),
+            Err(VeniceError::parse_error("bad chunk")),
+        ]));
+        let mut multiplexer: StreamMultiplexer = vec![("a", a), ("b", b)].into_iter().collect();
+
+        let mut order = Vec::new();
+        while let Some((id, item)) = multiplexer.next().await {
+            order.push(format!("{}:{}", id, item.map(|chunk| chunk.choices[0].delta.content.clone().unwrap()).unwrap_or_default()));
+        }
+        assert_eq!(order, vec!["a:1", "b:x", "a:2", "b:", "a:3"]);
+
+        assert!(multiplexer.is_finished());
+        assert!(multiplexer.pending().is_empty());
+        let a = multiplexer.status("a").unwrap();
+        assert_eq!((a.chunks, a.finish_reason.as_deref(), a.failed), (3, Some("stop"), false));
+        let b = multiplexer.status("b").unwrap();
+        assert_eq!((b.chunks, b.finish_reason.as_deref(), b.failed), (1, None, true));
+    }
+}
@@ -0,0 +1,312 @@
+//! Reconnecting streaming chat completions
+//!
+//! A streaming chat completion ends with a `[DONE]` marker. When the connection drops
+//! before it, [`Client::stream_chat_events`](crate::Client::stream_chat_events) sends the
+//! request again with a `Last-Event-ID` header naming the last event received, so a server
+//! that supports it can resume the stream where it stopped. Each attempt is reported as a
+//! [`ChatStreamEvent::Reconnecting`] event, after waiting the delay of the server's last
+//! `retry` directive or else [`ReconnectPolicy::delay`].
+//!
+//! A stream that dropped after events without IDs cannot be resumed, since sending the
+//! request again would start the completion over. Such a stream ends with the error that
+//! dropped it.
+
+use futures::{stream, StreamExt};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::events::{ChatEventStream, ChatStreamEvent, EventTranslator};
+use crate::endpoints;
+use crate::error::{VeniceError, VeniceResult};
+use crate::http::{parse_event, SharedHttpClient, SseStream};
+use crate::models::chat::ChatCompletionRequest;
+use crate::traits::chat::ChatCompletionChunk;
+
+/// When and how often a dropped chat completion stream is reconnected
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::chat::ReconnectPolicy;
+///
+/// let policy = ReconnectPolicy::new(5).delay(Duration::from_millis(500));
+/// assert_eq!(policy.max_attempts, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of attempts in a row; receiving an event starts the count over
+    pub max_attempts: u32,
+    /// Delay before an attempt when the server sent no `retry` directive
+    pub delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Create a policy allowing `max_attempts` attempts in a row
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Never reconnect
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Set the delay before an attempt when the server sent no `retry` directive
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Translate the events of a chat completion stream, reconnecting it when it drops
+pub(crate) fn reconnecting_events(
+    http_client: SharedHttpClient,
+    request: ChatCompletionRequest,
+    events: SseStream,
+    policy: ReconnectPolicy,
+) -> ChatEventStream {
+    let state = ReconnectingStream {
+        http_client,
+        request,
+        policy,
+        events: Some(events),
+        translator: EventTranslator::default(),
+        pending: VecDeque::new(),
+        last_event_id: None,
+        retry: None,
+        attempts: 0,
+        received: false,
+        finished: false,
+    };
+    Box::pin(stream::unfold(state, |mut state| async move {
+        let item = state.next().await?;
+        Some((item, state))
+    }))
+}
+
+/// State of a reconnecting chat completion stream
+struct ReconnectingStream {
+    http_client: SharedHttpClient,
+    request: ChatCompletionRequest,
+    policy: ReconnectPolicy,
+    /// Events of the current connection, `None` while reconnecting
+    events: Option<SseStream>,
+    translator: EventTranslator,
+    /// Items to return before reading more events
+    pending: VecDeque<VeniceResult<ChatStreamEvent>>,
+    /// ID of the last event received over any connection
+    last_event_id: Option<String>,
+    /// Delay of the last `retry` directive received over any connection
+    retry: Option<Duration>,
+    /// Attempts since the last event was received
+    attempts: u32,
+    /// Whether any event was received
+    received: bool,
+    /// Whether the stream has ended
+    finished: bool,
+}
+
+impl ReconnectingStream {
+    async fn next(&mut self) -> Option<VeniceResult<ChatStreamEvent>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+            let Some(events) = self.events.as_mut() else {
+                self.reconnect().await;
+                continue;
+            };
+            match events.next().await {
+                Some(Ok(event)) => {
+                    self.received = true;
+                    self.attempts = 0;
+                    if event.last_event_id.is_some() {
+                        self.last_event_id = event.last_event_id.clone();
+                    }
+                    self.retry = event.retry.or(self.retry);
+                    if event.data == "[DONE]" {
+                        self.finished = true;
+                        continue;
+                    }
+                    match parse_event::<ChatCompletionChunk>(&event) {
+                        Ok(chunk) => self.pending.extend(self.translator.translate(chunk).into_iter().map(Ok)),
+                        Err(err) => {
+                            self.finished = true;
+                            return Some(Err(err));
+                        }
+                    }
+                }
+                Some(Err(err)) => self.dropped(Some(err)),
+                None => self.dropped(None),
+            }
+        }
+    }
+
+    /// Handle the loss of the connection, scheduling an attempt if one is allowed
+    fn dropped(&mut self, err: Option<VeniceError>) {
+        self.events = None;
+        let resumable = self.last_event_id.is_some() || !self.received;
+        if resumable && self.attempts < self.policy.max_attempts {
+            self.attempts += 1;
+            self.pending.push_back(Ok(ChatStreamEvent::Reconnecting {
+                attempt: self.attempts,
+                last_event_id: self.last_event_id.clone(),
+            }));
+        } else {
+            self.finished = true;
+            self.pending.extend(err.map(Err));
+        }
+    }
+
+    /// Send the request again, resuming after the last event received
+    async fn reconnect(&mut self) {
+        tokio::time::sleep(self.retry.unwrap_or(self.policy.delay)).await;
+        let result = self
+            .http_client
+            .post_event_stream(endpoints::CHAT_COMPLETIONS, &self.request, self.last_event_id.as_deref())
+            .await;
+        match result {
+            Ok((events, _)) => self.events = Some(events),
+            // Connection failures may pass; errors from the API will not
+            Err(err) if matches!(err.root(), VeniceError::HttpError(_)) => self.dropped(Some(err)),
+            Err(err) => {
+                self.finished = true;
+                self.pending.push_back(Err(err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+    use crate::traits::chat::ChatCompletionBuilder;
+
+    fn chunk(content: &str, finish_reason: Option<&str>) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish_reason}]
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_stream_resumes_after_last_event_id() {
+        let mut server = mockito::Server::new_async().await;
+        // The more specific mock is created first, since mocks are matched in order
+        let resumed = server
+            .mock("POST", "/chat/completions")
+            .match_header("last-event-id", "1")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(format!("id: 2\ndata: {}\n\ndata: [DONE]\n\n", chunk("lo", Some("stop"))))
+            .expect(1)
+            .create_async()
+            .await;
+        // Ends without the [DONE] marker, as if the connection dropped
+        let dropped = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(format!(": keep-alive\n\nretry: 0\nid: 1\ndata: {}\n\n", chunk("Hel", None)))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Say hello").build();
+        let events: Vec<_> = client
+            .stream_chat_events(request, ReconnectPolicy::new(1))
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<VeniceResult<_>>()
+            .unwrap();
+
+        assert!(matches!(events[0], ChatStreamEvent::MessageStart { .. }));
+        assert_eq!(events[1], ChatStreamEvent::ContentDelta { index: 0, text: "Hel".to_string() });
+        assert_eq!(
+            events[2],
+            ChatStreamEvent::Reconnecting { attempt: 1, last_event_id: Some("1".to_string()) }
+        );
+        assert_eq!(events[3], ChatStreamEvent::ContentDelta { index: 0, text: "lo".to_string() });
+        assert_eq!(events[4], ChatStreamEvent::Done { index: 0, finish_reason: "stop".to_string() });
+        assert_eq!(events.len(), 5);
+
+        resumed.assert_async().await;
+        dropped.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_reconnect_attempt_is_retried() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Drops the stream, then the connection of the first attempt, then resumes
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let bodies = [
+                Some(format!("retry: 0\nid: 1\ndata: {}\n\n", chunk("Hel", None))),
+                None,
+                Some(format!("id: 2\ndata: {}\n\ndata: [DONE]\n\n", chunk("lo", Some("stop")))),
+            ];
+            for body in bodies {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let Some(body) = body else { continue };
+                let mut request = [0; 8192];
+                let _ = socket.read(&mut request).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        let client = Client::builder().api_key("test_api_key").base_url(url).build().unwrap();
+
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Say hello").build();
+        let events: Vec<_> = client
+            .stream_chat_events(request, ReconnectPolicy::new(2).delay(Duration::ZERO))
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<VeniceResult<_>>()
+            .unwrap();
+
+        let attempts: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ChatStreamEvent::Reconnecting { attempt, .. } => Some(*attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attempts, [1, 2]);
+        assert_eq!(events.last(), Some(&ChatStreamEvent::Done { index: 0, finish_reason: "stop".to_string() }));
+        server.await.unwrap();
+    }
+}
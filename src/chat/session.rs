@@ -0,0 +1,410 @@
+//! Chat sessions with persistent history
+//!
+//! A [`ChatSession`] keeps the messages of a conversation and sends them with every
+//! turn. Sessions can be saved to a [`ConversationStore`] and resumed later, using
+//! either the built-in [`InMemoryConversationStore`] and [`JsonFileConversationStore`]
+//! or a custom implementation.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::chat::ContextManager;
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatMessage};
+
+/// The persisted state of a chat session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversation {
+    /// Unique ID of the conversation
+    pub id: String,
+    /// ID of the model used for the conversation
+    pub model: String,
+    /// The messages exchanged so far, in order
+    pub messages: Vec<ChatMessage>,
+    /// Unix timestamp of when the conversation was created
+    pub created_at: u64,
+    /// Unix timestamp of when the conversation was last changed
+    pub updated_at: u64,
+}
+
+impl Conversation {
+    /// Create an empty conversation
+    pub fn new(id: impl Into<String>, model: impl Into<String>) -> Self {
+        let now = chrono::Utc::now().timestamp() as u64;
+        Self {
+            id: id.into(),
+            model: model.into(),
+            messages: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Storage for chat session history
+#[async_trait]
+pub trait ConversationStore: Send + Sync {
+    /// Load a conversation, returning `None` if it does not exist
+    async fn load(&self, id: &str) -> VeniceResult<Option<Conversation>>;
+
+    /// Save a conversation, replacing any previous version
+    async fn save(&self, conversation: &Conversation) -> VeniceResult<()>;
+
+    /// List the IDs of all stored conversations
+    async fn list(&self) -> VeniceResult<Vec<String>>;
+
+    /// Delete a conversation, returning whether it existed
+    async fn delete(&self, id: &str) -> VeniceResult<bool>;
+}
+
+/// A conversation store that keeps conversations in memory, shared by clones
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryConversationStore {
+    conversations: Arc<RwLock<HashMap<String, Conversation>>>,
+}
+
+impl InMemoryConversationStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ConversationStore for InMemoryConversationStore {
+    async fn load(&self, id: &str) -> VeniceResult<Option<Conversation>> {
+        Ok(self.conversations.read().unwrap_or_else(|e| e.into_inner()).get(id).cloned())
+    }
+
+    async fn save(&self, conversation: &Conversation) -> VeniceResult<()> {
+        self.conversations
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(conversation.id.clone(), conversation.clone());
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<String>> {
+        let mut ids: Vec<String> = self
+            .conversations
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn delete(&self, id: &str) -> VeniceResult<bool> {
+        Ok(self.conversations.write().unwrap_or_else(|e| e.into_inner()).remove(id).is_some())
+    }
+}
+
+/// A conversation store that keeps each conversation in a JSON file
+///
+/// Conversation `id` is stored as `<directory>/<id>.json`. IDs may only contain
+/// ASCII letters, digits, `-` and `_`.
+#[derive(Debug, Clone)]
+pub struct JsonFileConversationStore {
+    directory: PathBuf,
+}
+
+impl JsonFileConversationStore {
+    /// Create a store in the given directory, which is created on first save
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory conversations are stored in
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the path of a conversation file
+    fn path(&self, id: &str) -> VeniceResult<PathBuf> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(VeniceError::InvalidInput(format!(
+                "Invalid conversation ID '{}': only ASCII letters, digits, '-' and '_' are allowed",
+                id
+            )));
+        }
+        Ok(self.directory.join(format!("{}.json", id)))
+    }
+}
+
+#[async_trait]
+impl ConversationStore for JsonFileConversationStore {
+    async fn load(&self, id: &str) -> VeniceResult<Option<Conversation>> {
+        let path = self.path(id)?;
+        let contents = match tokio::fs::read(&path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| VeniceError::parse_error(format!("Invalid conversation file {}: {}", path.display(), e)))
+    }
+
+    async fn save(&self, conversation: &Conversation) -> VeniceResult<()> {
+        let path = self.path(&conversation.id)?;
+        let contents = serde_json::to_vec_pretty(conversation)
+            .map_err(|e| VeniceError::parse_error(format!("Failed to serialize conversation: {}", e)))?;
+
+        // Write to a temporary file first so a crash never leaves a truncated conversation
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let temporary = path.with_extension("json.tmp");
+        tokio::fs::write(&temporary, contents).await?;
+        tokio::fs::rename(&temporary, &path).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<String>> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut ids = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn delete(&self, id: &str) -> VeniceResult<bool> {
+        match tokio::fs::remove_file(self.path(id)?).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// A multi-turn chat conversation
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::Client;
+/// use venice_ai_api_sdk_rust::chat::{ChatSession, JsonFileConversationStore};
+///
+/// let client = Client::new("your-api-key")?;
+/// let store = Arc::new(JsonFileConversationStore::new("conversations"));
+///
+/// let mut session = match ChatSession::resume(store.clone(), "support-1").await? {
+///     Some(session) => session,
+///     None => ChatSession::new("support-1", "llama-3.3-70b")
+///         .with_system_prompt("You are a helpful assistant.")
+///         .with_store(store),
+/// };
+///
+/// let reply = session.send(&client, "Hello!").await?;
+/// println!("{}", reply);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ChatSession {
+    conversation: Conversation,
+    store: Option<Arc<dyn ConversationStore>>,
+    context_manager: Option<ContextManager>,
+}
+
+impl std::fmt::Debug for ChatSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatSession")
+            .field("conversation", &self.conversation)
+            .field("store", &self.store.is_some())
+            .field("context_manager", &self.context_manager)
+            .finish()
+    }
+}
+
+impl ChatSession {
+    /// Start a new conversation
+    pub fn new(id: impl Into<String>, model: impl Into<String>) -> Self {
+        Self::from_conversation(Conversation::new(id, model))
+    }
+
+    /// Continue an existing conversation
+    pub fn from_conversation(conversation: Conversation) -> Self {
+        Self {
+            conversation,
+            store: None,
+            context_manager: None,
+        }
+    }
+
+    /// Load a conversation from a store, returning `None` if it does not exist
+    ///
+    /// The returned session saves itself back to the same store.
+    pub async fn resume(store: Arc<dyn ConversationStore>, id: &str) -> VeniceResult<Option<Self>> {
+        Ok(store
+            .load(id)
+            .await?
+            .map(|conversation| Self::from_conversation(conversation).with_store(store)))
+    }
+
+    /// Add a system prompt to the conversation
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.push(ChatMessage::system(prompt));
+        self
+    }
+
+    /// Save the conversation to a store after every turn
+    pub fn with_store(mut self, store: Arc<dyn ConversationStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Summarize older turns whenever the history outgrows the model's context
+    pub fn with_context_manager(mut self, context_manager: ContextManager) -> Self {
+        self.context_manager = Some(context_manager);
+        self
+    }
+
+    /// Get the ID of the conversation
+    pub fn id(&self) -> &str {
+        &self.conversation.id
+    }
+
+    /// Get the ID of the model used for the conversation
+    pub fn model(&self) -> &str {
+        &self.conversation.model
+    }
+
+    /// Get the messages exchanged so far
+    pub fn messages(&self) -> &[ChatMessage] {
+        &self.conversation.messages
+    }
+
+    /// Get the persisted state of the session
+    pub fn conversation(&self) -> &Conversation {
+        &self.conversation
+    }
+
+    /// Append a message without sending it
+    pub fn push(&mut self, message: ChatMessage) {
+        self.conversation.messages.push(message);
+        self.conversation.updated_at = chrono::Utc::now().timestamp() as u64;
+    }
+
+    /// Send a user message and return the assistant's reply
+    ///
+    /// Both messages are appended to the history and the session is saved, if it has
+    /// a store. If the request fails the history is left unchanged.
+    ///
+    /// With a [`ContextManager`], older turns may first be replaced by a summary.
+    pub async fn send<A: ChatApi + Sync + ?Sized>(&mut self, api: &A, content: impl Into<String>) -> VeniceResult<String> {
+        let mut messages = self.conversation.messages.clone();
+        messages.push(ChatMessage::user(content));
+        if let Some(context_manager) = &self.context_manager {
+            messages = context_manager.fit(api, messages).await?;
+        }
+
+        let request = ChatCompletionRequest {
+            model: self.conversation.model.clone(),
+            messages,
+            ..Default::default()
+        };
+        let (response, _) = api.create_chat_completion(request.clone()).await?;
+        let reply = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+
+        self.conversation.messages = request.messages;
+        let content = reply.content.clone();
+        self.push(reply);
+        self.save().await?;
+        Ok(content)
+    }
+
+    /// Save the conversation to the session's store, if it has one
+    pub async fn save(&self) -> VeniceResult<()> {
+        match &self.store {
+            Some(store) => store.save(&self.conversation).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_json_file_store_round_trip() {
+        let directory = std::env::temp_dir().join(format!("venice-conversations-{}", std::process::id()));
+        let store = JsonFileConversationStore::new(&directory);
+
+        let mut conversation = Conversation::new("chat-1", "llama-3.3-70b");
+        conversation.messages.push(ChatMessage::user("Hello"));
+        store.save(&conversation).await.unwrap();
+
+        let loaded = store.load("chat-1").await.unwrap().unwrap();
+        assert_eq!(loaded.model, "llama-3.3-70b");
+        assert_eq!(loaded.messages[0].content, "Hello");
+        assert_eq!(store.list().await.unwrap(), vec!["chat-1".to_string()]);
+        assert!(store.load("chat-2").await.unwrap().is_none());
+        assert!(store.load("../chat-1").await.is_err());
+
+        assert!(store.delete("chat-1").await.unwrap());
+        assert!(store.list().await.unwrap().is_empty());
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_is_saved_after_each_turn() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi there"}, "finish_reason": "stop"}]
+            }"#)
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let store = Arc::new(InMemoryConversationStore::new());
+        let mut session = ChatSession::new("chat-1", "llama-3.3-70b")
+            .with_system_prompt("Be brief.")
+            .with_store(store.clone());
+        assert_eq!(session.send(&client, "Hello").await.unwrap(), "Hi there");
+        mock.assert_async().await;
+
+        let resumed = ChatSession::resume(store, "chat-1").await.unwrap().unwrap();
+        let roles: Vec<&str> = resumed.messages().iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["system", "user", "assistant"]);
+    }
+}
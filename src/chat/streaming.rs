@@ -8,7 +8,8 @@ use crate::{
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
+/// use futures::StreamExt;
 /// use venice_ai_api_sdk_rust::chat::{
 ///     create_streaming_chat_completion,
 ///     ChatCompletionRequestBuilder,
@@ -0,0 +1,294 @@
+//! Summarizing long documents
+//!
+//! [`summarize_document`] splits a document with [`chunk_text`], summarizes the chunks
+//! concurrently like [`Client::map_chat`](crate::Client::map_chat), and then combines
+//! the summaries, in several rounds if they do not fit in one request, until a single
+//! summary is left. Every request goes through [`ChatApi`], so the rate limiter and
+//! retries of a client apply to each of them.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::chat::map::{map_chat, MapChatOptions};
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::ChatApi;
+use crate::utils::text::{chunk_text, ChunkingStrategy};
+
+/// Instructions for summarizing one chunk unless the options replace them
+pub const DEFAULT_CHUNK_PROMPT: &str = "Summarize the following part of a longer document. \
+Keep the key facts, names, figures and conclusions, and do not add anything that is not in the text.";
+
+/// Instructions for combining summaries unless the options replace them
+pub const DEFAULT_REDUCE_PROMPT: &str = "The following are summaries of consecutive parts of one document. \
+Combine them into a single coherent summary, removing repetition but keeping every key fact.";
+
+/// Stage of a document summarization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummarizeStage {
+    /// Summarizing the chunks of the document
+    Chunks,
+    /// Combining summaries, in the given round starting at 1
+    Reduce(usize),
+}
+
+/// Progress of a document summarization, reported after each request finishes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SummarizeProgress {
+    /// The stage the request belongs to
+    pub stage: SummarizeStage,
+    /// Number of requests of the stage that have finished
+    pub completed: usize,
+    /// Number of requests of the stage that failed
+    pub failed: usize,
+    /// Total number of requests of the stage
+    pub total: usize,
+}
+
+/// Callback invoked after each request of a summarization finishes
+pub type SummarizeProgressCallback = Arc<dyn Fn(SummarizeProgress) + Send + Sync>;
+
+/// Options for [`summarize_document`]
+#[derive(Clone)]
+pub struct SummarizeOptions {
+    /// Model writing the summaries, the client's default chat model if `None`
+    pub model: Option<String>,
+    /// How the document is split; the token budget also bounds each combining request
+    pub chunking: ChunkingStrategy,
+    /// Instructions sent as the system message when summarizing a chunk
+    pub chunk_prompt: String,
+    /// Instructions sent as the system message when combining summaries
+    pub reduce_prompt: String,
+    /// Maximum number of tokens of each summary
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Maximum number of requests in flight at once
+    pub concurrency: usize,
+    /// Callback invoked after each request finishes
+    pub on_progress: Option<SummarizeProgressCallback>,
+}
+
+impl fmt::Debug for SummarizeOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SummarizeOptions")
+            .field("model", &self.model)
+            .field("chunking", &self.chunking)
+            .field("chunk_prompt", &self.chunk_prompt)
+            .field("reduce_prompt", &self.reduce_prompt)
+            .field("max_tokens", &self.max_tokens)
+            .field("temperature", &self.temperature)
+            .field("concurrency", &self.concurrency)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl Default for SummarizeOptions {
+    fn default() -> Self {
+        Self {
+            model: None,
+            chunking: ChunkingStrategy::sentences(3000).overlap(100),
+            chunk_prompt: DEFAULT_CHUNK_PROMPT.to_string(),
+            reduce_prompt: DEFAULT_REDUCE_PROMPT.to_string(),
+            max_tokens: Some(512),
+            temperature: None,
+            concurrency: 4,
+            on_progress: None,
+        }
+    }
+}
+
+impl SummarizeOptions {
+    /// Create options with default values: chunks of 3000 tokens split between sentences
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the model writing the summaries
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set how the document is split
+    pub fn chunking(mut self, chunking: ChunkingStrategy) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// Replace the instructions for summarizing a chunk
+    pub fn chunk_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.chunk_prompt = prompt.into();
+        self
+    }
+
+    /// Replace the instructions for combining summaries
+    pub fn reduce_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.reduce_prompt = prompt.into();
+        self
+    }
+
+    /// Set the maximum number of tokens of each summary
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the maximum number of requests in flight at once, 4 by default
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set a callback invoked after each request finishes
+    pub fn on_progress(mut self, callback: impl Fn(SummarizeProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Options for one stage of requests
+    fn stage(&self, stage: SummarizeStage, system: &str) -> MapChatOptions {
+        let mut options = MapChatOptions::new().system(system).concurrency(self.concurrency);
+        options.max_tokens = self.max_tokens;
+        options.temperature = self.temperature;
+        if let Some(callback) = self.on_progress.clone() {
+            options = options.on_progress(move |progress| {
+                callback(SummarizeProgress {
+                    stage,
+                    completed: progress.completed,
+                    failed: progress.failed,
+                    total: progress.total,
+                })
+            });
+        }
+        options
+    }
+}
+
+/// The result of [`summarize_document`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentSummary {
+    /// The summary of the whole document
+    pub summary: String,
+    /// The summary of each chunk, in document order
+    pub chunk_summaries: Vec<String>,
+    /// Number of rounds spent combining summaries
+    pub reduce_rounds: usize,
+}
+
+/// Summarize a document of any length
+///
+/// Fails with [`VeniceError::InvalidInput`] if the document is empty or the options name
+/// no model; [`Client::summarize_document`](crate::Client::summarize_document) falls back
+/// to the default chat model. If a request fails, the other requests of its stage still
+/// finish and the first error is returned, since a summary with gaps would be misleading.
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, chat::{summarize_document, SummarizeOptions}};
+///
+/// # async fn example(report: String) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?.with_retries();
+/// let options = SummarizeOptions::new()
+///     .model("llama-3.3-70b")
+///     .on_progress(|progress| println!("{:?}: {}/{}", progress.stage, progress.completed, progress.total));
+///
+/// let summary = summarize_document(&client, &report, options).await?;
+/// println!("{}", summary.summary);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn summarize_document<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    text: &str,
+    options: SummarizeOptions,
+) -> VeniceResult<DocumentSummary> {
+    let model = options
+        .model
+        .clone()
+        .ok_or_else(|| VeniceError::InvalidInput("No model is set to summarize the document".to_string()))?;
+    let chunks: Vec<String> = chunk_text(text, &options.chunking).into_iter().map(str::to_string).collect();
+    if chunks.is_empty() {
+        return Err(VeniceError::InvalidInput("Cannot summarize an empty document".to_string()));
+    }
+
+    let chunk_options = options.stage(SummarizeStage::Chunks, &options.chunk_prompt);
+    let chunk_summaries = collect(map_chat(api, &model, chunks, chunk_options).await)?;
+
+    let budget = ChunkingStrategy::separator("\n\n", options.chunking.max_tokens());
+    let mut summaries = chunk_summaries.clone();
+    let mut reduce_rounds = 0;
+    while summaries.len() > 1 {
+        reduce_rounds += 1;
+        let joined = summaries.join("\n\n");
+        let mut groups: Vec<String> = chunk_text(&joined, &budget).into_iter().map(str::to_string).collect();
+        // Summaries too long to group would never shrink, so combine them all at once
+        if groups.len() >= summaries.len() {
+            groups = vec![joined];
+        }
+        let reduce_options = options.stage(SummarizeStage::Reduce(reduce_rounds), &options.reduce_prompt);
+        summaries = collect(map_chat(api, &model, groups, reduce_options).await)?;
+    }
+
+    Ok(DocumentSummary {
+        summary: summaries.pop().unwrap_or_default(),
+        chunk_summaries,
+        reduce_rounds,
+    })
+}
+
+/// Get every answer, or the first error
+fn collect(answers: Vec<VeniceResult<String>>) -> VeniceResult<Vec<String>> {
+    answers.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{Fault, FaultInjector, TestChatClient};
+    use std::sync::Mutex;
+
+    fn document() -> String {
+        (1..=12).map(|n| format!("Sentence number {} of the report.", n)).collect::<Vec<_>>().join(" ")
+    }
+
+    #[tokio::test]
+    async fn test_summarize_document_reduces_to_one_summary() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let options = SummarizeOptions::new()
+            .model("llama-3.3-70b")
+            .chunking(ChunkingStrategy::sentences(20))
+            .concurrency(2)
+            .on_progress(move |progress| recorded.lock().unwrap().push(progress));
+
+        let summary = summarize_document(&TestChatClient::new(), &document(), options).await.unwrap();
+        assert_eq!(summary.chunk_summaries.len(), 6);
+        assert_eq!(summary.summary, "This is a test response");
+        assert!(summary.reduce_rounds >= 1);
+
+        let events = events.lock().unwrap();
+        let chunk_events = events.iter().filter(|progress| progress.stage == SummarizeStage::Chunks).count();
+        assert_eq!(chunk_events, 6);
+        assert_eq!(events.last().unwrap().stage, SummarizeStage::Reduce(summary.reduce_rounds));
+        assert_eq!(events.last().unwrap().total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_document_fails_on_errors() {
+        let api = FaultInjector::new(TestChatClient::new()).then_fail(Fault::service_unavailable());
+        let options = SummarizeOptions::new().model("llama-3.3-70b").chunking(ChunkingStrategy::sentences(20));
+        assert!(summarize_document(&api, &document(), options).await.is_err());
+
+        let err = summarize_document(&api, " ", SummarizeOptions::new().model("llama-3.3-70b")).await.unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(_)));
+        assert!(summarize_document(&api, &document(), SummarizeOptions::new()).await.is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use futures::stream;
+use std::collections::HashMap;
 
 use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
 use crate::traits::chat::{
@@ -33,6 +34,12 @@ pub struct ErrorConfig {
     pub message: String,
 }
 
+impl Default for TestChatClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestChatClient {
     /// Create a new TestChatClient with default values
     pub fn new() -> Self {
@@ -50,6 +57,7 @@ impl TestChatClient {
                 reset_tokens: Some(3600),
                 balance_vcu: Some(100.0),
                 balance_usd: Some(10.0),
+                ..Default::default()
             },
         }
     }
@@ -96,11 +104,14 @@ impl TestChatClient {
                     role: ChatRole::Assistant,
                     content: "This is a test response".to_string(),
                     name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 finish_reason: Some("stop".to_string()),
                 index: 0,
             }],
             usage: None,
+            venice_parameters: None,
         }
     }
 
@@ -117,9 +128,15 @@ impl TestChatClient {
                     delta: ChatCompletionChunkDelta {
                         role: Some(ChatRole::Assistant),
                         content: Some("This ".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: None,
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
             ChatCompletionChunk {
                 id: "chatcmpl-123".to_string(),
@@ -131,9 +148,15 @@ impl TestChatClient {
                     delta: ChatCompletionChunkDelta {
                         role: None,
                         content: Some("is ".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: None,
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
             ChatCompletionChunk {
                 id: "chatcmpl-123".to_string(),
@@ -145,9 +168,15 @@ impl TestChatClient {
                     delta: ChatCompletionChunkDelta {
                         role: None,
                         content: Some("a ".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: None,
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
             ChatCompletionChunk {
                 id: "chatcmpl-123".to_string(),
@@ -159,9 +188,15 @@ impl TestChatClient {
                     delta: ChatCompletionChunkDelta {
                         role: None,
                         content: Some("test ".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: None,
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
             ChatCompletionChunk {
                 id: "chatcmpl-123".to_string(),
@@ -173,9 +208,15 @@ impl TestChatClient {
                     delta: ChatCompletionChunkDelta {
                         role: None,
                         content: Some("response".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: Some("stop".to_string()),
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
         ]
     }
@@ -250,11 +291,14 @@ mod tests {
                     role: ChatRole::Assistant,
                     content: "Hello, world!".to_string(),
                     name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 finish_reason: Some("stop".to_string()),
                 index: 0,
             }],
             usage: None,
+            venice_parameters: None,
         };
 
         let client = TestChatClient::new().with_chat_completion_response(response.clone());
@@ -266,10 +310,13 @@ mod tests {
                 role: ChatRole::User,
                 content: "Hello".to_string(),
                 name: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             max_tokens: None,
             temperature: None,
             stream: None,
+            ..Default::default()
         };
 
         // Send the request
@@ -294,9 +341,15 @@ mod tests {
                     delta: ChatCompletionChunkDelta {
                         role: Some(ChatRole::Assistant),
                         content: Some("Hello".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: None,
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
             ChatCompletionChunk {
                 id: "test-id".to_string(),
@@ -308,9 +361,15 @@ mod tests {
                     delta: ChatCompletionChunkDelta {
                         role: None,
                         content: Some(", world!".to_string()),
+                        tool_calls: None,
+                        extra: HashMap::new(),
                     },
                     finish_reason: Some("stop".to_string()),
+                    extra: HashMap::new(),
                 }],
+                venice_parameters: None,
+                usage: None,
+                extra: HashMap::new(),
             },
         ];
 
@@ -323,10 +382,13 @@ mod tests {
                 role: ChatRole::User,
                 content: "Hello".to_string(),
                 name: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             max_tokens: None,
             temperature: None,
             stream: Some(true),
+            ..Default::default()
         };
 
         // Send the request
@@ -362,10 +424,13 @@ mod tests {
                 role: ChatRole::User,
                 content: "Hello".to_string(),
                 name: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             max_tokens: None,
             temperature: None,
             stream: None,
+            ..Default::default()
         };
 
         // Send the request and expect an error
@@ -400,10 +465,13 @@ mod tests {
                 role: ChatRole::User,
                 content: "Hello".to_string(),
                 name: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             max_tokens: None,
             temperature: None,
             stream: Some(true),
+            ..Default::default()
         };
 
         // Send the request and expect an error
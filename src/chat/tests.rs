@@ -1,101 +1,423 @@
-#[cfg(test)]
-mod tests {
-    use crate::{
-        traits::chat::{ChatApi, ChatCompletionBuilder, ChatCompletionChunk, ChatRole},
-        error::VeniceError,
-        chat::test_client::{TestChatClient, ErrorConfig},
+//! Tests for the chat API using the test client
+
+use crate::{
+    traits::chat::{ChatApi, ChatCompletionBuilder, ChatCompletionChunk, ChatRole},
+    error::VeniceError,
+    chat::test_client::{TestChatClient, ErrorConfig},
+};
+use futures::StreamExt;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn test_streaming_chat_completion() {
+    // Create a test client with default responses
+    let client = TestChatClient::new();
+
+    // Create a streaming chat completion request
+    let request = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .stream(true)
+        .build();
+
+    // Send the streaming request using the ChatApi trait
+    let (stream, _) = client.create_streaming_chat_completion(request).await.unwrap();
+
+    // Collect all chunks from the stream
+    let chunks: Vec<ChatCompletionChunk> = stream
+        .map(|result| result.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    // Verify we got the expected number of chunks (default is 5)
+    assert_eq!(chunks.len(), 5);
+
+    // Verify the content of the first chunk
+    assert_eq!(chunks[0].choices[0].delta.role.as_ref().unwrap(), &ChatRole::Assistant);
+    assert_eq!(chunks[0].choices[0].delta.content.as_ref().unwrap(), "This ");
+    
+    // Verify the content of the last chunk
+    assert_eq!(chunks[4].choices[0].delta.role, None);
+    assert_eq!(chunks[4].choices[0].delta.content.as_ref().unwrap(), "response");
+    assert_eq!(chunks[4].choices[0].finish_reason.as_ref().unwrap(), "stop");
+}
+
+#[tokio::test]
+async fn test_streaming_chat_completion_error() {
+    // Create a test client with a predefined error
+    let error_config = ErrorConfig {
+        status: StatusCode::UNAUTHORIZED,
+        code: "api_error".to_string(),
+        message: "Authentication failed".to_string(),
     };
-    use futures::StreamExt;
-    use reqwest::StatusCode;
-
-    #[tokio::test]
-    async fn test_streaming_chat_completion() {
-        // Create a test client with default responses
-        let client = TestChatClient::new();
-
-        // Create a streaming chat completion request
-        let request = ChatCompletionBuilder::new("llama-3.3-70b")
-            .add_user("Hello")
-            .stream(true)
-            .build();
-
-        // Send the streaming request using the ChatApi trait
-        let (stream, _) = client.create_streaming_chat_completion(request).await.unwrap();
-
-        // Collect all chunks from the stream
-        let chunks: Vec<ChatCompletionChunk> = stream
-            .map(|result| result.unwrap())
-            .collect::<Vec<_>>()
-            .await;
-
-        // Verify we got the expected number of chunks (default is 5)
-        assert_eq!(chunks.len(), 5);
-
-        // Verify the content of the first chunk
-        assert_eq!(chunks[0].choices[0].delta.role.as_ref().unwrap(), &ChatRole::Assistant);
-        assert_eq!(chunks[0].choices[0].delta.content.as_ref().unwrap(), "This ");
-        
-        // Verify the content of the last chunk
-        assert_eq!(chunks[4].choices[0].delta.role, None);
-        assert_eq!(chunks[4].choices[0].delta.content.as_ref().unwrap(), "response");
-        assert_eq!(chunks[4].choices[0].finish_reason.as_ref().unwrap(), "stop");
+    let client = TestChatClient::new().with_streaming_error(error_config);
+
+    // Create a streaming chat completion request
+    let request = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .stream(true)
+        .build();
+
+    // Send the streaming request and expect an error
+    let result = client.create_streaming_chat_completion(request).await;
+    
+    // Verify we got the expected error
+    assert!(result.is_err());
+    if let Err(VeniceError::ApiError { code, message, .. }) = result {
+        assert_eq!(code, "api_error");
+        assert_eq!(message, "Authentication failed");
+    } else {
+        panic!("Expected ApiError");
     }
+}
+
+#[tokio::test]
+async fn test_streaming_builder_convenience_method() {
+    // Create a test client with custom chunks
+    let chunks = vec![
+        TestChatClient::default_streaming_chunks(&ChatCompletionBuilder::new("llama-3.3-70b").build())[0].clone(),
+    ];
+    let client = TestChatClient::new().with_streaming_chunks(chunks);
+
+    // Use the builder's convenience method
+    let (stream, _) = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .stream_with_client(&client)
+        .await
+        .unwrap();
+
+    // Collect all chunks from the stream
+    let chunks: Vec<ChatCompletionChunk> = stream
+        .map(|result| result.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    // Verify we got the expected number of chunks
+    assert_eq!(chunks.len(), 1);
+
+    // Verify the content of the chunk
+    assert_eq!(chunks[0].choices[0].delta.role.as_ref().unwrap(), &ChatRole::Assistant);
+    assert_eq!(chunks[0].choices[0].delta.content.as_ref().unwrap(), "This ");
+}
+
+#[test]
+#[cfg(not(feature = "strict-schema"))]
+fn test_chunk_with_unknown_fields_deserializes() {
+    let json = r#"{
+        "id": "chatcmpl-123",
+        "object": "chat.completion.chunk",
+        "created": 1677652288,
+        "model": "llama-3.3-70b",
+        "venice_citations": [],
+        "choices": [{
+            "index": 0,
+            "delta": {"role": "assistant", "content": "Hi", "reasoning_content": "..."},
+            "finish_reason": null
+        }]
+    }"#;
+
+    let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+    assert!(chunk.extra.contains_key("venice_citations"));
+    assert_eq!(chunk.choices[0].delta.content.as_deref(), Some("Hi"));
+    assert!(chunk.choices[0].delta.extra.contains_key("reasoning_content"));
+}
+
+#[test]
+fn test_unknown_role_deserializes() {
+    let json = r#"{
+        "id": "chatcmpl-123",
+        "object": "chat.completion.chunk",
+        "created": 1677652288,
+        "model": "llama-3.3-70b",
+        "choices": [{"index": 0, "delta": {"role": "developer", "content": "Hi"}, "finish_reason": null}]
+    }"#;
+
+    let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+    let role = chunk.choices[0].delta.role.clone().unwrap();
+    assert_eq!(role, ChatRole::Other("developer".to_string()));
+    assert_eq!(serde_json::to_string(&role).unwrap(), r#""developer""#);
+    assert_eq!(serde_json::from_str::<ChatRole>(r#""tool""#).unwrap(), ChatRole::Tool);
+    assert_eq!(crate::models::chat::ChatRole::Function.to_string(), "function");
+}
+
+#[test]
+fn test_chunk_web_search_citations() {
+    let json = r#"{
+        "id": "chatcmpl-123",
+        "object": "chat.completion.chunk",
+        "created": 1677652288,
+        "model": "llama-3.3-70b",
+        "venice_parameters": {"web_search_citations": [{"title": "Venice", "url": "https://venice.ai"}]},
+        "choices": [{"index": 0, "delta": {"role": "assistant", "content": ""}, "finish_reason": null}]
+    }"#;
+
+    let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+    assert_eq!(chunk.web_search_citations()[0].title, "Venice");
+    assert!(!chunk.extra.contains_key("venice_parameters"));
+}
+
+#[test]
+fn test_builder_sampling_parameters() {
+    let request = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .top_p(0.9)
+        .frequency_penalty(0.5)
+        .presence_penalty(-0.5)
+        .top_k(40)
+        .repetition_penalty(1.1)
+        .min_p(0.05)
+        .n(2)
+        .seed(42)
+        .add_stop("\n")
+        .add_stop("END")
+        .web_search(true)
+        .extra("logprobs", true)
+        .build();
+
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["top_p"], 0.9f32 as f64);
+    assert_eq!(json["top_k"], 40);
+    assert_eq!(json["repetition_penalty"], 1.1f32 as f64);
+    assert_eq!(json["min_p"], 0.05f32 as f64);
+    assert_eq!(json["n"], 2);
+    assert_eq!(json["seed"], 42);
+    assert_eq!(json["stop"], serde_json::json!(["\n", "END"]));
+    assert_eq!(json["venice_parameters"]["enable_web_search"], "on");
+    assert_eq!(json["logprobs"], true);
+    assert!(request.validate().is_ok());
+}
+
+#[test]
+fn test_builder_sampling_parameter_ranges() {
+    let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+    let json = serde_json::to_value(&request).unwrap();
+    assert!(json.get("top_k").is_none());
+    assert!(json.get("repetition_penalty").is_none());
+    assert!(json.get("min_p").is_none());
+
+    let request = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .top_k(0)
+        .repetition_penalty(1.0)
+        .min_p(1.0)
+        .build();
+    assert!(request.validate().is_ok());
+
+    let request = ChatCompletionBuilder::new("llama-3.3-70b")
+        .add_user("Hello")
+        .repetition_penalty(-1.0)
+        .min_p(-0.1)
+        .build();
+    let err = request.validate().unwrap_err();
+    assert!(matches!(&err, VeniceError::InvalidInput(msg)
+        if msg.contains("repetition_penalty must be greater than 0") && msg.contains("min_p must be between 0 and 1, got -0.1")));
+}
 
-    #[tokio::test]
-    async fn test_streaming_chat_completion_error() {
-        // Create a test client with a predefined error
-        let error_config = ErrorConfig {
-            status: StatusCode::UNAUTHORIZED,
-            code: "api_error".to_string(),
-            message: "Authentication failed".to_string(),
-        };
-        let client = TestChatClient::new().with_streaming_error(error_config);
-
-        // Create a streaming chat completion request
-        let request = ChatCompletionBuilder::new("llama-3.3-70b")
-            .add_user("Hello")
-            .stream(true)
-            .build();
-
-        // Send the streaming request and expect an error
-        let result = client.create_streaming_chat_completion(request).await;
-        
-        // Verify we got the expected error
-        assert!(result.is_err());
-        if let Err(VeniceError::ApiError { code, message, .. }) = result {
-            assert_eq!(code, "api_error");
-            assert_eq!(message, "Authentication failed");
-        } else {
-            panic!("Expected ApiError");
+#[test]
+fn test_model_spec_parse_and_format() {
+    use crate::chat::ModelSpec;
+
+    let spec: ModelSpec = "llama-3.3-70b:enable_web_search=auto&include_venice_system_prompt=false:character=alan-watts:strip_thinking_response=true"
+        .parse()
+        .unwrap();
+    assert_eq!(spec.model(), "llama-3.3-70b");
+    assert_eq!(spec.parameters().enable_web_search.as_deref(), Some("auto"));
+    assert_eq!(spec.parameters().include_venice_system_prompt, Some(false));
+    assert_eq!(spec.parameters().character_slug.as_deref(), Some("alan-watts"));
+    assert_eq!(spec.parameters().extra["strip_thinking_response"], true);
+    assert_eq!(
+        spec.to_string(),
+        "llama-3.3-70b:enable_web_search=auto:include_venice_system_prompt=false:character_slug=alan-watts:strip_thinking_response=true"
+    );
+    assert_eq!(spec.to_string().parse::<ModelSpec>().unwrap(), spec);
+
+    assert_eq!(ModelSpec::parse("llama-3.3-70b").unwrap().to_string(), "llama-3.3-70b");
+    assert!(matches!(ModelSpec::parse(":enable_web_search=on"), Err(VeniceError::InvalidInput(_))));
+    assert!(matches!(ModelSpec::parse("llama-3.3-70b:web"), Err(VeniceError::InvalidInput(_))));
+}
+
+#[test]
+fn test_builders_accept_model_spec() {
+    use crate::chat::{ChatCompletionRequestBuilder, ModelSpec};
+
+    let spec = ModelSpec::new("llama-3.3-70b").web_search(true).character_slug("alan-watts");
+
+    let request = ChatCompletionBuilder::new("placeholder")
+        .venice_system_prompt(false)
+        .model_spec(spec.clone())
+        .build();
+    assert_eq!(request.model, "llama-3.3-70b");
+    let params = request.venice_parameters.unwrap();
+    assert_eq!(params.enable_web_search.as_deref(), Some("on"));
+    assert_eq!(params.include_venice_system_prompt, Some(false));
+    assert_eq!(params.character_slug.as_deref(), Some("alan-watts"));
+
+    let request = ChatCompletionRequestBuilder::new("placeholder").with_model_spec(spec.clone()).build();
+    assert_eq!(request.model, "llama-3.3-70b");
+    assert_eq!(request.venice_parameters.unwrap().character_slug.as_deref(), Some("alan-watts"));
+
+    // Builders that only take a model ID get the suffixed form
+    let request = ChatCompletionBuilder::new(spec).build();
+    assert_eq!(request.model, "llama-3.3-70b:enable_web_search=on:character_slug=alan-watts");
+}
+
+#[tokio::test]
+async fn test_stream_events() {
+    use crate::chat::{ChatStreamEvent, ChatStreamExt};
+
+    let client = TestChatClient::new();
+    let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").stream(true).build();
+    let (stream, _) = client.create_streaming_chat_completion(request).await.unwrap();
+
+    let events: Vec<ChatStreamEvent> = stream.into_events().map(|event| event.unwrap()).collect().await;
+    assert_eq!(events.len(), 7);
+    assert!(matches!(
+        &events[0],
+        ChatStreamEvent::MessageStart { index: 0, role: ChatRole::Assistant, .. }
+    ));
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            ChatStreamEvent::ContentDelta { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "This is a test response");
+    assert_eq!(events[6], ChatStreamEvent::Done { index: 0, finish_reason: "stop".to_string() });
+}
+
+#[tokio::test]
+async fn test_stream_events_tool_calls_and_usage() {
+    use crate::chat::{ChatStreamEvent, ChatStreamExt};
+
+    let chunks: Vec<ChatCompletionChunk> = serde_json::from_value(serde_json::json!([
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [{"index": 0, "delta": {"role": "assistant", "tool_calls": [
+                {"index": 0, "id": "call_1", "type": "function", "function": {"name": "add", "arguments": ""}}
+            ]}, "finish_reason": null}]
+        },
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "function": {"arguments": "{\"a\": 2}"}}
+            ]}, "finish_reason": "tool_calls"}]
+        },
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        }
+    ]))
+    .unwrap();
+
+    let events: Vec<ChatStreamEvent> = futures::stream::iter(chunks.into_iter().map(Ok))
+        .into_events()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+    assert_eq!(events.len(), 5);
+    match &events[1] {
+        ChatStreamEvent::ToolCallDelta { index: 0, tool_call } => {
+            assert_eq!(tool_call.id.as_deref(), Some("call_1"));
+            assert_eq!(tool_call.function.as_ref().unwrap().name.as_deref(), Some("add"));
         }
+        event => panic!("unexpected event: {:?}", event),
     }
+    assert!(matches!(&events[2], ChatStreamEvent::ToolCallDelta { tool_call, .. } if tool_call.id.is_none()));
+    assert_eq!(events[3], ChatStreamEvent::Done { index: 0, finish_reason: "tool_calls".to_string() });
+    assert!(matches!(&events[4], ChatStreamEvent::UsageReport(usage) if usage.total_tokens == 15));
+}
+
+#[tokio::test]
+async fn test_stream_collector_reports_usage() {
+    use crate::chat::{ChatCompletionRequestBuilder, StreamCollector};
+
+    let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").stream(true).include_usage(true).build();
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["stream_options"]["include_usage"], true);
+    let request = ChatCompletionRequestBuilder::new("llama-3.3-70b").with_include_usage(true).build();
+    assert_eq!(serde_json::to_value(&request).unwrap()["stream_options"]["include_usage"], true);
+
+    let chunks: Vec<ChatCompletionChunk> = serde_json::from_value(serde_json::json!([
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [{"index": 0, "delta": {"role": "assistant", "content": "Hi"}, "finish_reason": null}]
+        },
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "id": "call_1", "type": "function", "function": {"name": "add", "arguments": "{\"a\""}}
+            ]}, "finish_reason": null}]
+        },
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "function": {"arguments": ": 2}"}}
+            ]}, "finish_reason": "tool_calls"}]
+        },
+        {
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "qwen3-235b",
+            "choices": [],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        }
+    ]))
+    .unwrap();
+
+    let response = StreamCollector::collect(futures::stream::iter(chunks.into_iter().map(Ok))).await.unwrap();
+    assert_eq!(response.id, "chatcmpl-1");
+    assert_eq!(response.choices[0].message.content, "Hi");
+    assert_eq!(response.choices[0].finish_reason.as_deref(), Some("tool_calls"));
+    let calls = response.choices[0].message.tool_calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].id, "call_1");
+    assert_eq!(calls[0].function.name, "add");
+    assert_eq!(calls[0].function.arguments, "{\"a\": 2}");
+    assert_eq!(response.usage.unwrap().total_tokens, 15);
+}
+
+#[tokio::test]
+async fn test_multi_choice_collector_demultiplexes_choices() {
+    use crate::chat::MultiChoiceCollector;
 
-    #[tokio::test]
-    async fn test_streaming_builder_convenience_method() {
-        // Create a test client with custom chunks
-        let chunks = vec![
-            TestChatClient::default_streaming_chunks(&ChatCompletionBuilder::new("llama-3.3-70b").build())[0].clone(),
-        ];
-        let client = TestChatClient::new().with_streaming_chunks(chunks);
-
-        // Use the builder's convenience method
-        let (stream, _) = ChatCompletionBuilder::new("llama-3.3-70b")
-            .add_user("Hello")
-            .stream_with_client(&client)
-            .await
-            .unwrap();
-
-        // Collect all chunks from the stream
-        let chunks: Vec<ChatCompletionChunk> = stream
-            .map(|result| result.unwrap())
-            .collect::<Vec<_>>()
-            .await;
-
-        // Verify we got the expected number of chunks
-        assert_eq!(chunks.len(), 1);
-
-        // Verify the content of the chunk
-        assert_eq!(chunks[0].choices[0].delta.role.as_ref().unwrap(), &ChatRole::Assistant);
-        assert_eq!(chunks[0].choices[0].delta.content.as_ref().unwrap(), "This ");
+    let chunk = |index: u32, content: &str, finish_reason: Option<&str>| {
+        serde_json::from_value::<ChatCompletionChunk>(serde_json::json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "llama-3.3-70b",
+            "choices": [{"index": index, "delta": {"content": content}, "finish_reason": finish_reason}]
+        }))
+        .unwrap()
+    };
+    let chunks = vec![
+        chunk(1, "Whis", None),
+        chunk(0, "Mit", None),
+        chunk(1, "kers", Some("stop")),
+        chunk(0, "tens", None),
+    ];
+
+    let mut collector = MultiChoiceCollector::new();
+    for chunk in &chunks {
+        collector.push(chunk);
     }
-}
\ No newline at end of file
+    assert_eq!(collector.len(), 2);
+    assert_eq!(collector.content(0), Some("Mittens"));
+    assert_eq!(collector.finish_reason(1), Some("stop"));
+    assert!(!collector.is_finished());
+
+    collector.push(&chunk(0, "", Some("length")));
+    assert!(collector.is_finished());
+    let response = collector.into_response();
+    let contents: Vec<&str> = response.choices.iter().map(|choice| choice.message.content.as_str()).collect();
+    assert_eq!(contents, ["Mittens", "Whiskers"]);
+    assert_eq!(response.choices[1].index, 1);
+
+    let response = MultiChoiceCollector::collect(futures::stream::iter(chunks.into_iter().map(Ok))).await.unwrap();
+    assert_eq!(response.choices.len(), 2);
+}
+
+#[test]
+fn test_n_rejected_for_single_choice_models() {
+    let request = ChatCompletionBuilder::new("qwen-2.5-qwq-32b").add_user("Hello").n(2).build();
+    assert!(matches!(request.validate(), Err(VeniceError::InvalidInput(_))));
+
+    let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").n(2).build();
+    assert!(request.validate().is_ok());
+}
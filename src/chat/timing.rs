@@ -0,0 +1,216 @@
+//! Timing streamed chat completions
+//!
+//! [`TimedStream`] wraps a stream of chunks and records when each one arrived, as
+//! [`TimedChunk`]s and as a running [`StreamStats`] summary with the first-token latency,
+//! the gaps between chunks and the total duration. [`ChatStreamExt::timed`] creates one,
+//! and [`StreamCollector::collect_with_stats`] collects a stream together with its stats.
+//!
+//! [`ChatStreamExt::timed`]: crate::chat::ChatStreamExt::timed
+//! [`StreamCollector::collect_with_stats`]: crate::chat::StreamCollector::collect_with_stats
+
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::error::VeniceResult;
+use crate::traits::chat::ChatCompletionChunk;
+
+/// A chunk with the time it arrived
+#[derive(Debug, Clone)]
+pub struct TimedChunk {
+    /// The chunk
+    pub chunk: ChatCompletionChunk,
+    /// Time from the start of the stream to the chunk
+    pub since_start: Duration,
+    /// Time from the previous chunk, or from the start for the first chunk
+    pub since_previous: Duration,
+}
+
+/// Timing summary of a streamed chat completion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamStats {
+    /// Time from the start to the first chunk with content
+    pub first_token_latency: Option<Duration>,
+    /// Time from the start to the last chunk, or to the end once the stream ended
+    pub total_duration: Duration,
+    /// Number of chunks received
+    pub chunks: usize,
+    /// Time between consecutive chunks, in order
+    pub inter_chunk_deltas: Vec<Duration>,
+}
+
+impl StreamStats {
+    /// Mean time between consecutive chunks
+    pub fn mean_delta(&self) -> Option<Duration> {
+        let count = u32::try_from(self.inter_chunk_deltas.len()).ok().filter(|count| *count > 0)?;
+        Some(self.inter_chunk_deltas.iter().sum::<Duration>() / count)
+    }
+
+    /// Longest time between consecutive chunks, the worst stall of the stream
+    pub fn max_delta(&self) -> Option<Duration> {
+        self.inter_chunk_deltas.iter().max().copied()
+    }
+
+    /// Time between consecutive chunks at a percentile between 0 and 100, such as 95
+    pub fn delta_percentile(&self, percentile: f64) -> Option<Duration> {
+        let mut deltas = self.inter_chunk_deltas.clone();
+        deltas.sort();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * deltas.len().saturating_sub(1) as f64).round() as usize;
+        deltas.get(rank).copied()
+    }
+}
+
+/// A stream of chunks that records when each one arrived
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     chat::ChatStreamExt,
+///     traits::chat::{ChatApi, ChatCompletionBuilder},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Tell me a joke").build();
+/// let (stream, _) = ChatApi::create_streaming_chat_completion(&client, request).await?;
+///
+/// let mut stream = stream.timed();
+/// while let Some(timed) = stream.next().await {
+///     let timed = timed?;
+///     println!("+{:?}: {:?}", timed.since_previous, timed.chunk.choices[0].delta.content);
+/// }
+/// println!("first token after {:?}", stream.stats().first_token_latency);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TimedStream<S> {
+    inner: S,
+    start: Instant,
+    previous: Option<Instant>,
+    ended: bool,
+    stats: StreamStats,
+}
+
+impl<S> TimedStream<S> {
+    /// Time a stream from now
+    pub fn new(inner: S) -> Self {
+        Self::starting_at(inner, Instant::now())
+    }
+
+    /// Time a stream from `start`, such as when its request was sent
+    pub fn starting_at(inner: S, start: Instant) -> Self {
+        Self {
+            inner,
+            start,
+            previous: None,
+            ended: false,
+            stats: StreamStats::default(),
+        }
+    }
+
+    /// Get the timing summary of the chunks received so far
+    pub fn stats(&self) -> &StreamStats {
+        &self.stats
+    }
+
+    /// Get the timing summary, consuming the stream
+    pub fn into_stats(self) -> StreamStats {
+        self.stats
+    }
+}
+
+impl<S> Stream for TimedStream<S>
+where
+    S: Stream<Item = VeniceResult<ChatCompletionChunk>> + Unpin,
+{
+    type Item = VeniceResult<TimedChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        let now = Instant::now();
+        let chunk = match item {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+            None => {
+                if !self.ended {
+                    self.ended = true;
+                    self.stats.total_duration = now - self.start;
+                }
+                return Poll::Ready(None);
+            }
+        };
+
+        let since_start = now - self.start;
+        let since_previous = now - self.previous.unwrap_or(self.start);
+        let stats = &mut self.stats;
+        if stats.chunks > 0 {
+            stats.inter_chunk_deltas.push(since_previous);
+        }
+        let has_content = chunk
+            .choices
+            .iter()
+            .any(|choice| choice.delta.content.as_deref().is_some_and(|content| !content.is_empty()));
+        if has_content && stats.first_token_latency.is_none() {
+            stats.first_token_latency = Some(since_start);
+        }
+        stats.chunks += 1;
+        stats.total_duration = since_start;
+        self.previous = Some(now);
+
+        Poll::Ready(Some(Ok(TimedChunk {
+            chunk,
+            since_start,
+            since_previous,
+        })))
+    }
+}
+
+impl<S> std::fmt::Debug for TimedStream<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimedStream").field("stats", &self.stats).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::StreamCollector;
+    use futures::{stream, StreamExt};
+
+    fn chunk(content: &str) -> VeniceResult<ChatCompletionChunk> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]
+        }))
+        .unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_timed_stream_records_stats() {
+        let chunks = stream::iter(vec![chunk(""), chunk("Hel"), chunk("lo")]).then(|chunk| async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            chunk
+        });
+        let (response, stats) = StreamCollector::collect_with_stats(Box::pin(chunks)).await.unwrap();
+
+        assert_eq!(response.choices[0].message.content, "Hello");
+        assert_eq!(stats.chunks, 3);
+        assert_eq!(stats.inter_chunk_deltas.len(), 2);
+        let first_token_latency = stats.first_token_latency.unwrap();
+        assert!(first_token_latency >= Duration::from_millis(10));
+        assert!(stats.total_duration >= first_token_latency);
+        assert!(stats.max_delta().unwrap() >= stats.mean_delta().unwrap());
+        assert!(stats.delta_percentile(50.0).is_some());
+        assert_eq!(StreamStats::default().mean_delta(), None);
+    }
+}
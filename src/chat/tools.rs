@@ -0,0 +1,373 @@
+//! Typed tool execution
+//!
+//! A [`ToolRegistry`] maps tool names to Rust async functions whose arguments are
+//! deserialized with serde. [`run_tool_loop`] sends a [`ChatSession`] to the model
+//! with the registered tools, runs every tool call the model makes and feeds the
+//! results back until the model produces a final answer.
+
+use futures::future::BoxFuture;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::chat::ChatSession;
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatMessage, ToolCall, ToolDefinition};
+
+/// A type-erased tool function, taking JSON arguments and returning JSON output
+type ToolHandler = Arc<dyn Fn(serde_json::Value) -> BoxFuture<'static, Result<serde_json::Value, String>> + Send + Sync>;
+
+/// A registered tool
+#[derive(Clone)]
+struct RegisteredTool {
+    definition: ToolDefinition,
+    handler: ToolHandler,
+}
+
+/// A set of tools the model may call, keyed by name
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use venice_ai_api_sdk_rust::chat::ToolRegistry;
+///
+/// #[derive(Deserialize)]
+/// struct WeatherArgs {
+///     city: String,
+/// }
+///
+/// let registry = ToolRegistry::new().register(
+///     "get_weather",
+///     "Get the current weather in a city",
+///     json!({
+///         "type": "object",
+///         "properties": {"city": {"type": "string"}},
+///         "required": ["city"]
+///     }),
+///     |args: WeatherArgs| async move { Ok::<_, String>(format!("Sunny in {}", args.city)) },
+/// );
+///
+/// assert_eq!(registry.definitions().len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, RegisteredTool>,
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ToolRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an async function as a tool
+    ///
+    /// `parameters` is the JSON schema of `P` shown to the model. The model's arguments
+    /// are deserialized into `P` and the function's output is serialized to JSON and
+    /// sent back to the model. Registering a name again replaces the previous tool.
+    pub fn register<P, R, E, F, Fut>(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: serde_json::Value,
+        function: F,
+    ) -> Self
+    where
+        P: DeserializeOwned + Send + 'static,
+        R: Serialize,
+        E: fmt::Display,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+    {
+        let name = name.into();
+        let function = Arc::new(function);
+        let handler: ToolHandler = Arc::new(move |arguments| {
+            let function = function.clone();
+            Box::pin(async move {
+                let arguments: P =
+                    serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
+                let output = function(arguments).await.map_err(|e| e.to_string())?;
+                serde_json::to_value(output).map_err(|e| format!("Failed to serialize output: {}", e))
+            })
+        });
+
+        let definition = ToolDefinition::function(name.clone(), description, parameters);
+        self.tools.insert(name, RegisteredTool { definition, handler });
+        self
+    }
+
+    /// Get the definitions of all registered tools, sorted by name
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.values().map(|tool| tool.definition.clone()).collect()
+    }
+
+    /// Check whether a tool is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// Run a tool call, returning the content of the tool message answering it
+    ///
+    /// Errors are returned as messages meant for the model: an unknown tool, arguments
+    /// that are not valid JSON or do not match the tool's parameter type, or an error
+    /// returned by the tool itself.
+    pub async fn call(&self, call: &ToolCall) -> Result<String, String> {
+        let tool = self
+            .tools
+            .get(&call.function.name)
+            .ok_or_else(|| format!("Unknown tool: {}", call.function.name))?;
+
+        let arguments = if call.function.arguments.trim().is_empty() {
+            serde_json::Value::Object(Default::default())
+        } else {
+            serde_json::from_str(&call.function.arguments).map_err(|e| format!("Invalid arguments: {}", e))?
+        };
+
+        match (tool.handler)(arguments).await? {
+            serde_json::Value::String(output) => Ok(output),
+            output => Ok(output.to_string()),
+        }
+    }
+}
+
+/// How [`run_tool_loop`] handles a failed tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToolErrorPolicy {
+    /// Send the error to the model as the tool result and let it recover
+    #[default]
+    ReportToModel,
+    /// Stop the loop and return a [`VeniceError::ToolError`]
+    Abort,
+}
+
+/// Options for [`run_tool_loop_with_options`]
+#[derive(Debug, Clone)]
+pub struct ToolLoopOptions {
+    /// Maximum number of requests sent to the model
+    pub max_iterations: usize,
+    /// How failed tool calls are handled
+    pub on_tool_error: ToolErrorPolicy,
+}
+
+impl Default for ToolLoopOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: 10,
+            on_tool_error: ToolErrorPolicy::ReportToModel,
+        }
+    }
+}
+
+impl ToolLoopOptions {
+    /// Create options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of requests sent to the model
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set how failed tool calls are handled
+    pub fn on_tool_error(mut self, on_tool_error: ToolErrorPolicy) -> Self {
+        self.on_tool_error = on_tool_error;
+        self
+    }
+}
+
+/// Run the model→tool→model loop with default options
+///
+/// See [`run_tool_loop_with_options`].
+pub async fn run_tool_loop<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    session: &mut ChatSession,
+    registry: &ToolRegistry,
+) -> VeniceResult<String> {
+    run_tool_loop_with_options(api, session, registry, &ToolLoopOptions::default()).await
+}
+
+/// Run the model→tool→model loop until the model produces a final answer
+///
+/// The session's messages are sent with the registered tools. Whenever the model
+/// calls tools, each call is run and its result appended to the session as a tool
+/// message before asking the model again. The assistant's final answer is returned;
+/// every message along the way is kept in the session, which is saved at the end.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(registry: venice_ai_api_sdk_rust::chat::ToolRegistry) -> Result<(), Box<dyn std::error::Error>> {
+/// use venice_ai_api_sdk_rust::Client;
+/// use venice_ai_api_sdk_rust::chat::{run_tool_loop, ChatSession};
+/// use venice_ai_api_sdk_rust::traits::chat::ChatMessage;
+///
+/// let client = Client::new("your-api-key")?;
+/// let mut session = ChatSession::new("weather", "llama-3.3-70b");
+/// session.push(ChatMessage::user("What's the weather in Paris?"));
+///
+/// let answer = run_tool_loop(&client, &mut session, &registry).await?;
+/// println!("{}", answer);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_tool_loop_with_options<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    session: &mut ChatSession,
+    registry: &ToolRegistry,
+    options: &ToolLoopOptions,
+) -> VeniceResult<String> {
+    for _ in 0..options.max_iterations {
+        let request = ChatCompletionRequest {
+            model: session.model().to_string(),
+            messages: session.messages().to_vec(),
+            tools: Some(registry.definitions()),
+            ..Default::default()
+        };
+        let (response, _) = api.create_chat_completion(request).await?;
+        let reply = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+
+        let calls = reply.tool_calls().to_vec();
+        let content = reply.content.clone();
+        session.push(reply);
+
+        if calls.is_empty() {
+            session.save().await?;
+            return Ok(content);
+        }
+
+        for call in &calls {
+            let output = match registry.call(call).await {
+                Ok(output) => output,
+                Err(error) if options.on_tool_error == ToolErrorPolicy::Abort => {
+                    session.save().await?;
+                    return Err(VeniceError::ToolError(format!("{}: {}", call.function.name, error)));
+                }
+                Err(error) => format!("Error: {}", error),
+            };
+            session.push(ChatMessage::tool(call.id.clone(), output));
+        }
+    }
+
+    session.save().await?;
+    Err(VeniceError::ToolError(format!(
+        "The model did not produce a final answer within {} iterations",
+        options.max_iterations
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::chat::FunctionCall;
+    use crate::Client;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct AddArgs {
+        a: i64,
+        b: i64,
+    }
+
+    fn registry() -> ToolRegistry {
+        ToolRegistry::new().register(
+            "add",
+            "Add two numbers",
+            json!({"type": "object", "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}}}),
+            |args: AddArgs| async move { Ok::<_, String>(args.a + args.b) },
+        )
+    }
+
+    fn call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".to_string(),
+            kind: "function".to_string(),
+            function: FunctionCall {
+                name: name.to_string(),
+                arguments: arguments.to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registry_call() {
+        let registry = registry();
+        assert_eq!(registry.call(&call("add", r#"{"a": 2, "b": 3}"#)).await, Ok("5".to_string()));
+        assert!(registry.call(&call("add", r#"{"a": "two"}"#)).await.unwrap_err().starts_with("Invalid arguments"));
+        assert_eq!(registry.call(&call("mul", "{}")).await, Err("Unknown tool: mul".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop() {
+        let mut server = mockito::Server::new_async().await;
+        // The more specific mock is created first, since mocks are matched in order
+        let answer = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::Regex(r#""role":"tool","content":"5","tool_call_id":"call_1""#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-2",
+                "object": "chat.completion",
+                "created": 1677652289,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "finish_reason": "stop", "message": {"role": "assistant", "content": "2 + 3 = 5"}}]
+            }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let tool_call = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::Regex(r#""tools":\[\{"type":"function","function":\{"name":"add","description":"Add two numbers""#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "finish_reason": "tool_calls", "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "add", "arguments": "{\"a\": 2, \"b\": 3}"}}]
+                }}]
+            }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let mut session = ChatSession::new("math", "llama-3.3-70b");
+        session.push(ChatMessage::user("What is 2 + 3?"));
+        let result = run_tool_loop(&client, &mut session, &registry()).await.unwrap();
+
+        assert_eq!(result, "2 + 3 = 5");
+        let roles: Vec<&str> = session.messages().iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool", "assistant"]);
+        tool_call.assert_async().await;
+        answer.assert_async().await;
+    }
+}
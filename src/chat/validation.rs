@@ -0,0 +1,305 @@
+//! Client-side validation of chat completion requests
+//!
+//! The request types in `chat`, `models::chat` and `traits::chat` all describe the
+//! same API call, so they share the checks in this module. Checks that need model data,
+//! such as the context window, are reported in a [`ValidationReport`] instead of failing.
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::utils::validation::{validate_non_empty_string, validate_non_empty_vec, validate_number_range};
+
+/// Roles accepted by Venice chat models
+const SUPPORTED_ROLES: &[&str] = &["system", "user", "assistant", "tool"];
+
+/// Models that only generate a single choice per request
+///
+/// These are reasoning models, which reject `n` greater than 1.
+const SINGLE_CHOICE_MODELS: &[&str] = &["deepseek-r1-671b", "deepseek-r1-llama-70b", "qwen-2.5-qwq-32b"];
+
+/// Check whether a model can generate more than one choice per request
+///
+/// Feature suffixes such as `:enable_web_search=on` are ignored.
+pub(crate) fn supports_multiple_choices(model: &str) -> bool {
+    let base = model.split(':').next().unwrap_or(model);
+    !SINGLE_CHOICE_MODELS.contains(&base)
+}
+
+/// The fields of a chat completion request that are checked before sending
+#[derive(Debug, Default)]
+pub(crate) struct ChatRequestFields<'a> {
+    /// ID of the target model
+    pub model: &'a str,
+    /// Role of each message, in order
+    pub roles: Vec<&'a str>,
+    /// Maximum number of tokens to generate
+    pub max_tokens: Option<u32>,
+    /// Alternative to max_tokens
+    pub max_completion_tokens: Option<u32>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Nucleus sampling parameter
+    pub top_p: Option<f32>,
+    /// Frequency penalty
+    pub frequency_penalty: Option<f32>,
+    /// Presence penalty
+    pub presence_penalty: Option<f32>,
+    /// Repetition penalty
+    pub repetition_penalty: Option<f32>,
+    /// Minimum relative token probability
+    pub min_p: Option<f32>,
+    /// Number of choices to generate
+    pub n: Option<u32>,
+}
+
+impl ChatRequestFields<'_> {
+    /// Check the fields, collecting every problem into a single `InvalidInput` error
+    pub(crate) fn validate(&self) -> VeniceResult<()> {
+        let mut errors = Vec::new();
+
+        if let Err(e) = validate_non_empty_string(self.model, "model") {
+            errors.push(e);
+        }
+        if let Err(e) = validate_non_empty_vec(&self.roles, "messages") {
+            errors.push(e);
+        }
+        for (index, role) in self.roles.iter().enumerate() {
+            if !SUPPORTED_ROLES.contains(role) {
+                errors.push(format!(
+                    "messages[{}].role '{}' is not supported by model {} (expected one of: {})",
+                    index,
+                    role,
+                    self.model,
+                    SUPPORTED_ROLES.join(", ")
+                ));
+            }
+        }
+
+        if self.max_tokens.is_some() && self.max_completion_tokens.is_some() {
+            errors.push("max_tokens and max_completion_tokens cannot both be set".to_string());
+        }
+        if self.max_tokens == Some(0) {
+            errors.push("max_tokens must be greater than 0".to_string());
+        }
+        if self.max_completion_tokens == Some(0) {
+            errors.push("max_completion_tokens must be greater than 0".to_string());
+        }
+        match self.n {
+            Some(0) => errors.push("n must be greater than 0".to_string()),
+            Some(n) if n > 1 && !supports_multiple_choices(self.model) => {
+                errors.push(format!("n = {} is not supported by model {}, which generates a single choice", n, self.model));
+            }
+            _ => {}
+        }
+
+        let ranges = [
+            (self.temperature, 0.0, 2.0, "temperature"),
+            (self.top_p, 0.0, 1.0, "top_p"),
+            (self.frequency_penalty, -2.0, 2.0, "frequency_penalty"),
+            (self.presence_penalty, -2.0, 2.0, "presence_penalty"),
+            (self.min_p, 0.0, 1.0, "min_p"),
+        ];
+        for (value, min, max, field_name) in ranges {
+            if let Some(value) = value {
+                if value.is_nan() {
+                    errors.push(format!("{} must be a number", field_name));
+                } else if let Err(e) = validate_number_range(value, min, max, field_name) {
+                    errors.push(e);
+                }
+            }
+        }
+
+        if let Some(repetition_penalty) = self.repetition_penalty {
+            if repetition_penalty.is_nan() || repetition_penalty <= 0.0 {
+                errors.push("repetition_penalty must be greater than 0".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(VeniceError::InvalidInput(format!(
+                "Invalid chat completion request: {}",
+                errors.join("; ")
+            )))
+        }
+    }
+}
+
+/// A problem found by checking a request against the data of its model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// The models list has no context size for the model
+    UnknownContextSize {
+        /// ID of the model
+        model: String,
+    },
+    /// The messages alone take more tokens than the context window holds
+    PromptExceedsContext {
+        /// Estimated number of prompt tokens
+        prompt_tokens: u32,
+        /// Context size of the model
+        context_size: u32,
+    },
+    /// The messages fit, but not together with the largest completion the request allows
+    CompletionExceedsContext {
+        /// Estimated number of prompt tokens
+        prompt_tokens: u32,
+        /// Value of `max_tokens` or `max_completion_tokens`
+        max_completion_tokens: u32,
+        /// Context size of the model
+        context_size: u32,
+    },
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationWarning::UnknownContextSize { model } => write!(f, "context size of model {} is unknown", model),
+            ValidationWarning::PromptExceedsContext { prompt_tokens, context_size } => write!(
+                f,
+                "the messages take about {} tokens, more than the {} token context window",
+                prompt_tokens, context_size
+            ),
+            ValidationWarning::CompletionExceedsContext { prompt_tokens, max_completion_tokens, context_size } => write!(
+                f,
+                "the messages take about {} tokens, leaving fewer than the {} completion tokens allowed in the {} token context window",
+                prompt_tokens, max_completion_tokens, context_size
+            ),
+        }
+    }
+}
+
+/// The outcome of checking a chat completion request against the data of its model
+///
+/// Returned by [`ChatCompletionBuilder::build_checked`](crate::traits::chat::ChatCompletionBuilder::build_checked).
+/// Token counts are estimates, so a request close to the limit may still fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// ID of the model, without feature suffixes
+    pub model: String,
+    /// Estimated number of prompt tokens
+    pub prompt_tokens: u32,
+    /// Value of `max_tokens` or `max_completion_tokens`
+    pub max_completion_tokens: Option<u32>,
+    /// Context size of the model, from the models list
+    pub context_size: Option<u32>,
+    /// Problems found
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationReport {
+    /// Check the token counts of a request against the context size of its model
+    pub fn new(model: &str, prompt_tokens: u32, max_completion_tokens: Option<u32>, context_size: Option<u32>) -> Self {
+        let model = model.split(':').next().unwrap_or(model).to_string();
+        let warning = match context_size {
+            None => Some(ValidationWarning::UnknownContextSize { model: model.clone() }),
+            Some(context_size) if prompt_tokens > context_size => {
+                Some(ValidationWarning::PromptExceedsContext { prompt_tokens, context_size })
+            }
+            Some(context_size) => max_completion_tokens
+                .filter(|max| prompt_tokens.saturating_add(*max) > context_size)
+                .map(|max_completion_tokens| ValidationWarning::CompletionExceedsContext {
+                    prompt_tokens,
+                    max_completion_tokens,
+                    context_size,
+                }),
+        };
+        Self {
+            model,
+            prompt_tokens,
+            max_completion_tokens,
+            context_size,
+            warnings: warning.into_iter().collect(),
+        }
+    }
+
+    /// Check whether no problems were found
+    pub fn is_ok(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_report() {
+        assert!(ValidationReport::new("llama-3.3-70b", 1000, Some(1000), Some(4096)).is_ok());
+
+        let report = ValidationReport::new("llama-3.3-70b:enable_web_search=on", 5000, None, Some(4096));
+        assert_eq!(report.model, "llama-3.3-70b");
+        assert_eq!(report.warnings, vec![ValidationWarning::PromptExceedsContext { prompt_tokens: 5000, context_size: 4096 }]);
+
+        let report = ValidationReport::new("llama-3.3-70b", 3000, Some(2000), Some(4096));
+        assert!(matches!(report.warnings[0], ValidationWarning::CompletionExceedsContext { .. }));
+
+        let report = ValidationReport::new("unknown", 10, None, None);
+        assert_eq!(report.warnings[0].to_string(), "context size of model unknown is unknown");
+    }
+
+    fn valid_fields() -> ChatRequestFields<'static> {
+        ChatRequestFields {
+            model: "llama-3.3-70b",
+            roles: vec!["system", "user"],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let mut fields = valid_fields();
+        fields.temperature = Some(0.7);
+        fields.top_p = Some(1.0);
+        fields.max_tokens = Some(100);
+        assert!(fields.validate().is_ok());
+    }
+
+    #[test]
+    fn test_errors_are_collected() {
+        let fields = ChatRequestFields {
+            model: "llama-3.3-70b",
+            roles: vec![],
+            max_tokens: Some(100),
+            max_completion_tokens: Some(100),
+            temperature: Some(2.5),
+            top_p: Some(-0.1),
+            repetition_penalty: Some(0.0),
+            min_p: Some(1.5),
+            ..Default::default()
+        };
+
+        let message = match fields.validate() {
+            Err(VeniceError::InvalidInput(message)) => message,
+            other => panic!("expected InvalidInput, got {:?}", other),
+        };
+        assert!(message.contains("messages cannot be empty"));
+        assert!(message.contains("max_tokens and max_completion_tokens cannot both be set"));
+        assert!(message.contains("temperature must be between 0 and 2, got 2.5"));
+        assert!(message.contains("top_p must be between 0 and 1, got -0.1"));
+        assert!(message.contains("min_p must be between 0 and 1, got 1.5"));
+        assert!(message.contains("repetition_penalty must be greater than 0"));
+    }
+
+    #[test]
+    fn test_unsupported_role() {
+        let mut fields = valid_fields();
+        fields.roles.push("function");
+
+        let err = fields.validate().unwrap_err();
+        assert!(err.to_string().contains("messages[2].role 'function'"));
+    }
+
+    #[test]
+    fn test_multiple_choices() {
+        let mut fields = valid_fields();
+        fields.n = Some(3);
+        assert!(fields.validate().is_ok());
+
+        fields.model = "deepseek-r1-671b:enable_web_search=on";
+        let err = fields.validate().unwrap_err();
+        assert!(err.to_string().contains("n = 3 is not supported by model deepseek-r1-671b"));
+
+        fields.n = Some(1);
+        assert!(fields.validate().is_ok());
+    }
+}
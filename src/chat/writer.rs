@@ -0,0 +1,141 @@
+//! Writing streamed chat completions to a writer
+//!
+//! [`stream_chat_to_writer`] pipes the content of a streamed completion to anything
+//! implementing [`AsyncWrite`], such as stdout, a file or a socket, and reports what was
+//! written once the stream ends.
+
+use futures::StreamExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::error::VeniceResult;
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatCompletionUsage};
+
+/// Options for [`stream_chat_to_writer`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamWriteOptions {
+    /// Flush the writer after every chunk instead of only at the end
+    pub flush_per_chunk: bool,
+}
+
+impl StreamWriteOptions {
+    /// Create options that flush only at the end of the stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flush the writer after every chunk, so text appears as it arrives
+    pub fn flush_per_chunk(mut self, flush_per_chunk: bool) -> Self {
+        self.flush_per_chunk = flush_per_chunk;
+        self
+    }
+}
+
+/// What [`stream_chat_to_writer`] wrote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamWriteSummary {
+    /// Number of characters written
+    pub chars: usize,
+    /// Number of chunks received
+    pub chunks: usize,
+    /// Why the model stopped, as reported in the last chunk of the first choice
+    pub finish_reason: Option<String>,
+    /// Token usage, if the request asked for it with
+    /// [`include_usage`](crate::traits::chat::ChatCompletionBuilder::include_usage)
+    pub usage: Option<ChatCompletionUsage>,
+}
+
+/// Stream a chat completion and write the content of its first choice to `writer`
+///
+/// The writer is flushed before returning, including when the stream fails part way;
+/// the text received up to the failure stays written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     chat::{stream_chat_to_writer, StreamWriteOptions},
+///     traits::chat::ChatCompletionBuilder,
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+///     .add_user("Write a haiku about rust")
+///     .build();
+///
+/// let options = StreamWriteOptions::new().flush_per_chunk(true);
+/// let summary = stream_chat_to_writer(&client, request, tokio::io::stdout(), options).await?;
+/// println!("\n{} characters, finished with {:?}", summary.chars, summary.finish_reason);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn stream_chat_to_writer<A, W>(
+    api: &A,
+    request: ChatCompletionRequest,
+    writer: W,
+    options: StreamWriteOptions,
+) -> VeniceResult<StreamWriteSummary>
+where
+    A: ChatApi + Sync + ?Sized,
+    W: AsyncWrite + Unpin,
+{
+    let mut writer = writer;
+    let (mut stream, _) = api.create_streaming_chat_completion(request).await?;
+    let mut summary = StreamWriteSummary::default();
+
+    let result: VeniceResult<()> = async {
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            summary.chunks += 1;
+            if chunk.usage.is_some() {
+                summary.usage = chunk.usage;
+            }
+            for choice in chunk.choices.iter().filter(|choice| choice.index == 0) {
+                if let Some(content) = choice.delta.content.as_deref().filter(|content| !content.is_empty()) {
+                    writer.write_all(content.as_bytes()).await?;
+                    summary.chars += content.chars().count();
+                    if options.flush_per_chunk {
+                        writer.flush().await?;
+                    }
+                }
+                if choice.finish_reason.is_some() {
+                    summary.finish_reason = choice.finish_reason.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    let flushed = writer.flush().await;
+    result?;
+    flushed?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::TestChatClient;
+    use crate::traits::chat::ChatCompletionBuilder;
+
+    #[tokio::test]
+    async fn test_writes_content_and_reports_summary() {
+        let api = TestChatClient::new();
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+        let expected: String = TestChatClient::default_streaming_chunks(&request)
+            .iter()
+            .filter_map(|chunk| chunk.choices.first().and_then(|choice| choice.delta.content.clone()))
+            .collect();
+
+        let mut written = Vec::new();
+        let summary = stream_chat_to_writer(&api, request, &mut written, StreamWriteOptions::new().flush_per_chunk(true))
+            .await
+            .unwrap();
+
+        assert_eq!(String::from_utf8(written).unwrap(), expected);
+        assert_eq!(summary.chars, expected.chars().count());
+        assert_eq!(summary.finish_reason.as_deref(), Some("stop"));
+    }
+}
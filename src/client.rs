@@ -1,27 +1,68 @@
 use futures::Stream;
-use reqwest::Client as ReqwestClient;
+use reqwest::Method;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::api::{ApiKeysApiImpl, ChatApiImpl, ImageApiImpl, ModelsApiImpl};
+use crate::budget::SpendGuard;
+use crate::chat::ResponseCache;
 use crate::config::ClientConfig;
-use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
-use crate::http::{self, HttpClientConfig, new_shared_http_client};
+use crate::content_policy::{ApplyContentPolicy, ContentPolicy};
+use crate::cost::{CostEstimate, CostTracker};
+use crate::endpoints::{self, EndpointPaths};
+use crate::error::{ErrorContext, RateLimitInfo, VeniceError, VeniceResult};
+use crate::http::{self, CompressionStats, HttpClient, HttpClientConfig, JsonBody, RequestCompression, RequestSigner, ResponseEnvelope};
+use crate::key_pool::{self, KeyPool};
+use crate::latency::{LatencyConfig, LatencyStats, LatencyTracker};
+use crate::lifecycle::{Connection, ConnectionStats, Lifecycle, ShutdownReport};
+use crate::models::list::ListModelsRequest;
+use crate::observer::{UsageObserver, UsageObservers};
+use crate::profile::Profile;
 use crate::rate_limit::{RateLimiter, RateLimiterConfig};
-use crate::retry::{RetryConfig, with_retry};
+use crate::retry::{RetryConfig, RetryReport, with_retry_report};
+use crate::secret::SecretString;
+
+/// The outcome of [`Client::ping`]
+#[derive(Debug, Clone)]
+pub struct PingReport {
+    /// Time taken for the API to answer
+    pub latency: Duration,
+    /// Whether the API accepted the credentials of the client
+    pub authenticated: bool,
+    /// Rate limit information from the response, when the credentials were accepted
+    pub rate_limit_info: Option<RateLimitInfo>,
+}
 
 /// The main client for the Venice.ai API
 #[derive(Debug, Clone)]
 pub struct Client {
-    /// The underlying HTTP client
-    client: ReqwestClient,
+    /// The underlying HTTP connection pool
+    connection: Connection,
+    /// Shutdown state shared with the API implementations
+    lifecycle: Lifecycle,
     /// The client configuration
     config: ClientConfig,
     /// Retry configuration
     retry_config: Option<RetryConfig>,
     /// Rate limiter for managing API rate limits
     rate_limiter: Option<Arc<RateLimiter>>,
+    /// Observers notified about every request
+    observers: UsageObservers,
+    /// Content policy applied to chat and image requests
+    content_policy: Option<Arc<ContentPolicy>>,
+    /// Tracker accumulating the spend of this client
+    cost_tracker: Option<Arc<CostTracker>>,
+    /// Guard refusing requests once the spend reaches a cap of the configuration
+    spend_guard: Option<Arc<SpendGuard>>,
+    /// Latencies per endpoint, if enabled in the configuration
+    latency_tracker: Option<Arc<LatencyTracker>>,
+    /// Cache answering repeated chat completion requests
+    response_cache: Option<Arc<ResponseCache>>,
+    /// Compression of large request bodies, shared with the API implementations
+    compression: Option<Arc<RequestCompression>>,
     /// Chat API implementation
     chat_api: ChatApiImpl,
     /// Models API implementation
@@ -45,16 +86,64 @@ impl Client {
 
     /// Create a new client with the given configuration
     pub fn with_config(config: ClientConfig) -> VeniceResult<Self> {
-        let client = http::create_client(&config)?;
+        let lifecycle = Lifecycle::default();
+        let mut connection = Connection::new(http::create_client(&config)?, lifecycle.clone());
         
         // Create the HTTP client for the API implementations
         let http_client_config = HttpClientConfig {
             api_key: config.api_key.clone(),
             base_url: config.base_url.clone(),
-            custom_headers: config.custom_headers.clone(),
+            custom_headers: {
+                let mut headers = config.custom_headers.clone();
+                headers.extend(config.feature_flag_headers()?);
+                headers
+            },
             timeout_secs: config.timeout_secs,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: None,
+            tcp_keepalive_secs: None,
+            http1_only: false,
+            http2_prior_knowledge: false,
+            endpoint_paths: config.endpoint_paths.clone(),
+            allowed_base_urls: config.allowed_base_urls.clone(),
         };
-        let http_client = new_shared_http_client(http_client_config)?;
+        let observers = UsageObservers::default();
+        let mut http_client = HttpClient::new(http_client_config)?
+            .with_observers(observers.clone())
+            .with_lifecycle(lifecycle.clone())
+            .with_lenient_parsing(config.lenient_parsing)
+            .with_request_coalescing(config.coalesce_requests);
+        if let Some(key_pool) = &config.key_pool {
+            http_client = http_client.with_key_pool(key_pool.clone());
+        }
+        if let Some(signer) = &config.request_signer {
+            http_client = http_client.with_request_signer(signer.clone());
+        }
+        let compression = config
+            .request_compression_threshold
+            .map(|threshold| Arc::new(RequestCompression::new(threshold)));
+        if let Some(compression) = &compression {
+            http_client = http_client.with_compression(compression.clone());
+        }
+        if let Some(max_bytes) = config.max_request_bytes {
+            http_client = http_client.with_max_request_size(max_bytes);
+        }
+        let spend_guard = (config.max_spend_usd.is_some() || config.max_spend_vcu.is_some())
+            .then(|| Arc::new(SpendGuard::new(config.max_spend_usd, config.max_spend_vcu)));
+        if let Some(spend_guard) = &spend_guard {
+            observers.add(spend_guard.clone());
+            connection = connection.with_spend_guard(spend_guard.clone());
+            http_client = http_client.with_spend_guard(spend_guard.clone());
+        }
+        let latency_tracker = config
+            .latency_tracking
+            .clone()
+            .map(|latency_config| Arc::new(LatencyTracker::new(latency_config)));
+        if let Some(latency_tracker) = &latency_tracker {
+            observers.add(latency_tracker.clone());
+            http_client = http_client.with_latency_tracker(latency_tracker.clone());
+        }
+        let http_client = Arc::new(http_client);
         
         // Create the API implementations
         let chat_api = ChatApiImpl::new(http_client.clone());
@@ -63,10 +152,18 @@ impl Client {
         let api_keys_api = ApiKeysApiImpl::new(http_client);
         
         Ok(Self {
-            client,
+            connection,
+            lifecycle,
             config,
             retry_config: None,
             rate_limiter: None,
+            observers,
+            content_policy: None,
+            cost_tracker: None,
+            spend_guard,
+            latency_tracker,
+            response_cache: None,
+            compression,
             chat_api,
             models_api,
             image_api,
@@ -79,6 +176,76 @@ impl Client {
         &self.config
     }
     
+    /// Get the model used by chat requests that do not name one
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if no default chat model is configured.
+    pub fn default_chat_model(&self) -> VeniceResult<&str> {
+        self.config.default_chat_model.as_deref().ok_or_else(|| {
+            VeniceError::InvalidInput(
+                "No default chat model is configured; set one with ClientBuilder::default_chat_model".to_string(),
+            )
+        })
+    }
+    
+    /// Get the model used by image requests that do not name one
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if no default image model is configured.
+    pub fn default_image_model(&self) -> VeniceResult<&str> {
+        self.config.default_image_model.as_deref().ok_or_else(|| {
+            VeniceError::InvalidInput(
+                "No default image model is configured; set one with ClientBuilder::default_image_model".to_string(),
+            )
+        })
+    }
+    
+    /// Start deriving a handle that shares the connection pools of this client
+    ///
+    /// The derived client keeps every setting of this one, including its observers,
+    /// cost tracker and shutdown state, except those changed on the builder. Nothing is
+    /// reconnected, so deriving a handle per tenant or per request is cheap.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, RetryConfig};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let batch = client
+    ///     .derive()
+    ///     .retry_config(RetryConfig::new().max_retries(6))
+    ///     .default_chat_model("llama-3.3-70b")
+    ///     .header("x-tenant-id", "acme")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn derive(&self) -> DerivedClientBuilder {
+        DerivedClientBuilder {
+            client: self.clone(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Derive a handle that shares the connection pools of this client, configured by a closure
+    ///
+    /// Shorthand for [`Client::derive`] followed by [`DerivedClientBuilder::build`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let tenant = client.with(|handle| handle.header("x-tenant-id", "acme").no_retries())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with(&self, configure: impl FnOnce(DerivedClientBuilder) -> DerivedClientBuilder) -> VeniceResult<Client> {
+        configure(self.derive()).build()
+    }
+    
     /// Set the retry configuration
     pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
         self.retry_config = Some(retry_config);
@@ -117,37 +284,301 @@ impl Client {
     pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
         self.rate_limiter.as_ref()
     }
+    
+    /// Register a usage observer
+    ///
+    /// The observer is notified about every request sent by this client, including
+    /// requests made through the API traits. Observers are shared by clones of the client.
+    pub fn with_usage_observer(self, observer: Arc<dyn UsageObserver>) -> Self {
+        self.observers.add(observer);
+        self
+    }
+    
+    /// Set the content policy
+    ///
+    /// The policy is applied to every chat and image generation request sent by this
+    /// client, including requests made through the API traits.
+    pub fn with_content_policy(mut self, content_policy: ContentPolicy) -> Self {
+        let content_policy = Arc::new(content_policy);
+        self.chat_api = self.chat_api.with_content_policy(content_policy.clone());
+        self.image_api = self.image_api.with_content_policy(content_policy.clone());
+        self.content_policy = Some(content_policy);
+        self
+    }
+    
+    /// Get the content policy
+    pub fn content_policy(&self) -> Option<&ContentPolicy> {
+        self.content_policy.as_deref()
+    }
+    
+    /// Attach a cost tracker
+    ///
+    /// The tracker is registered as a usage observer, so the spend of every response
+    /// that reports token usage is recorded, including requests made through the API traits.
+    pub fn with_cost_tracker(mut self, cost_tracker: Arc<CostTracker>) -> Self {
+        self.observers.add(cost_tracker.clone());
+        if let Some(spend_guard) = &self.spend_guard {
+            spend_guard.track(cost_tracker.clone());
+        }
+        self.cost_tracker = Some(cost_tracker);
+        self
+    }
+    
+    /// Get the cost tracker
+    pub fn cost_tracker(&self) -> Option<&Arc<CostTracker>> {
+        self.cost_tracker.as_ref()
+    }
+    
+    /// Answer repeated chat completion requests from a cache
+    ///
+    /// Only non-streaming requests made through [`ChatApi`](crate::traits::chat::ChatApi)
+    /// are cached. Failures to read or write the cache are logged and the request is sent
+    /// as if there were no cache.
+    pub fn with_response_cache(mut self, response_cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+    
+    /// Get the response cache
+    pub fn response_cache(&self) -> Option<&Arc<ResponseCache>> {
+        self.response_cache.as_ref()
+    }
+    
+    /// Get the guard enforcing the spend caps of the configuration
+    ///
+    /// `None` unless [`ClientConfig::max_spend_usd`] or [`ClientConfig::max_spend_vcu`] is
+    /// set. Reset it to allow requests again once a cap is reached. The estimates of a
+    /// cost tracker attached with [`Client::with_cost_tracker`] count towards the spend.
+    pub fn spend_guard(&self) -> Option<&Arc<SpendGuard>> {
+        self.spend_guard.as_ref()
+    }
+    
+    /// Get the latency tracker enabled with [`ClientConfig::with_latency_tracking`]
+    pub fn latency_tracker(&self) -> Option<&Arc<LatencyTracker>> {
+        self.latency_tracker.as_ref()
+    }
+
+    /// Get the latency percentiles of every endpoint called through the API traits
+    ///
+    /// Empty unless latency tracking is enabled with [`ClientConfig::with_latency_tracking`].
+    pub fn latency_stats(&self) -> HashMap<String, LatencyStats> {
+        self.latency_tracker
+            .as_ref()
+            .map(|tracker| tracker.stats())
+            .unwrap_or_default()
+    }
+    
+    /// Estimate the cost of a chat completion request using the cost tracker's pricing
+    ///
+    /// Returns `None` if no cost tracker is attached or the model has no pricing.
+    pub fn estimate_cost(&self, request: &crate::chat::ChatCompletionRequest) -> Option<CostEstimate> {
+        self.cost_tracker
+            .as_ref()
+            .and_then(|tracker| tracker.calculator().estimate_cost(request))
+    }
+    
+    /// Apply the content policy, if any, to a request
+    pub(crate) fn apply_content_policy<R: ApplyContentPolicy>(&self, request: &mut R) {
+        if let Some(content_policy) = &self.content_policy {
+            request.apply_content_policy(content_policy);
+        }
+    }
+
+    /// Charge the token usage reported by a response against the client-side token budget
+    fn record_token_usage<T>(&self, result: &VeniceResult<(T, RateLimitInfo, http::ResponseMetadata)>) {
+        if let (Some(rate_limiter), Ok((_, _, metadata))) = (&self.rate_limiter, result) {
+            if let Some(usage) = &metadata.usage {
+                rate_limiter.record_tokens(usage.total_tokens);
+            }
+        }
+    }
+
+    /// Charge the token usage of a chat completion sent through the API traits
+    fn record_chat_tokens(&self, usage: Option<&crate::traits::chat::ChatCompletionUsage>) {
+        if let (Some(rate_limiter), Some(usage)) = (&self.rate_limiter, usage) {
+            rate_limiter.record_tokens(usage.total_tokens);
+        }
+    }
+
+    /// Run a call made through the API traits under the rate limits of its endpoint
+    ///
+    /// Calls refused before they were sent give their permit back.
+    async fn rate_limited<T>(
+        &self,
+        endpoint: &str,
+        call: impl std::future::Future<Output = VeniceResult<(T, RateLimitInfo)>>,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        let Some(rate_limiter) = &self.rate_limiter else {
+            return call.await;
+        };
+        rate_limiter.acquire_for_endpoint(endpoint).await?;
+        let result = call.await;
+        match &result {
+            Ok((_, rate_limit_info)) => rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info),
+            Err(err) if matches!(
+                err.root(),
+                VeniceError::PayloadTooLarge { .. } | VeniceError::InvalidInput(_) | VeniceError::ClientShutdown
+            ) => rate_limiter.release_for_endpoint(endpoint),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Shut the client down gracefully
+    ///
+    /// New requests fail with [`VeniceError::ClientShutdown`] as soon as this is called.
+    /// Requests and streams already in flight, including those of clones of this client,
+    /// are given until `deadline` to finish, after which the connection pools are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use venice_ai_api_sdk_rust::Client;
+    ///
+    /// let client = Client::new("your-api-key")?;
+    /// let report = client.shutdown(Duration::from_secs(10)).await;
+    /// if !report.drained {
+    ///     eprintln!("{} requests were still in flight", report.abandoned);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, deadline: std::time::Duration) -> ShutdownReport {
+        self.lifecycle.shutdown(deadline).await
+    }
+
+    /// Check whether [`Client::shutdown`] has been called on this client or one of its clones
+    pub fn is_shut_down(&self) -> bool {
+        self.lifecycle.is_closed()
+    }
+
+    /// Get the number of requests and streams currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.lifecycle.active()
+    }
+
+    /// Get the bandwidth saved by compressing request bodies
+    ///
+    /// Returns `None` unless compression was enabled with
+    /// [`ClientConfig::with_request_compression`] or [`ClientBuilder::request_compression`].
+    pub fn compression_stats(&self) -> Option<CompressionStats> {
+        self.compression.as_ref().map(|compression| compression.stats())
+    }
+
+    /// Get a snapshot of the requests in flight and the connection pools of this client and its clones
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.lifecycle.stats()
+    }
+
+    /// Check that the API is reachable and accepts the credentials of this client
+    ///
+    /// Lists a single model, which is cheap for the API to answer. Rejected credentials
+    /// are reported with `authenticated` set to false; any other failure, such as a
+    /// network error, is returned as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use venice_ai_api_sdk_rust::Client;
+    ///
+    /// let client = Client::new("your-api-key")?;
+    /// let report = client.ping().await?;
+    /// let stats = client.connection_stats();
+    /// println!(
+    ///     "authenticated: {}, latency: {:?}, in flight: {}",
+    ///     report.authenticated, report.latency, stats.in_flight
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ping(&self) -> VeniceResult<PingReport> {
+        let started = Instant::now();
+        let result = self
+            .get_with_query::<_, serde_json::Value>("models", &ListModelsRequest::new().limit(1))
+            .await;
+        let latency = started.elapsed();
+
+        match result {
+            Ok((_, rate_limit_info)) => Ok(PingReport {
+                latency,
+                authenticated: true,
+                rate_limit_info: Some(rate_limit_info),
+            }),
+            Err(error) => match error.root() {
+                VeniceError::AuthenticationFailed(_) => Ok(PingReport { latency, authenticated: false, rate_limit_info: None }),
+                VeniceError::ApiError { status, .. }
+                    if *status == reqwest::StatusCode::UNAUTHORIZED || *status == reqwest::StatusCode::FORBIDDEN =>
+                {
+                    Ok(PingReport { latency, authenticated: false, rate_limit_info: None })
+                }
+                _ => Err(error),
+            },
+        }
+    }
 
     /// Send a GET request to the API
     pub async fn get<T: DeserializeOwned>(
         &self,
         endpoint: &str,
     ) -> VeniceResult<(T, RateLimitInfo)> {
+        self.get_raw(endpoint).await.map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Send a GET request to the API, keeping the status, headers and raw body of the response
+    pub async fn get_raw<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> VeniceResult<ResponseEnvelope<T>> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start(Method::GET, endpoint);
         
-        let result = if let Some(retry_config) = &self.retry_config {
-            with_retry(|| async {
-                let response = self.client.get(url.clone()).send().await.map_err(VeniceError::HttpError)?;
-                http::process_response(response).await
-            }, retry_config).await
+        let (result, retry_report) = if let Some(retry_config) = &self.retry_config {
+            with_retry_report(|| key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = self.connection.authorize(client.get(url.clone()), key);
+                async move {
+                    let started = Instant::now();
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_envelope(response, started, self.config.lenient_parsing).await
+                }
+            }), retry_config).await
         } else {
-            let response = self.client.get(url).send().await.map_err(VeniceError::HttpError)?;
-            http::process_response(response).await
+            (key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = self.connection.authorize(client.get(url.clone()), key);
+                async move {
+                    let started = Instant::now();
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_envelope(response, started, self.config.lenient_parsing).await
+                }
+            }).await, RetryReport::default())
         };
+        self.observers.retried(&observed, &retry_report);
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("GET", endpoint)));
+        self.record_token_usage(&result);
+        let result = self.observers.finish(observed, result);
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
-        result
+        result.map(|(mut envelope, _)| {
+            envelope.retry = retry_report;
+            envelope
+        })
     }
     
     /// Send a GET request with query parameters to the API
@@ -156,46 +587,53 @@ impl Client {
         endpoint: &str,
         query: &Q,
     ) -> VeniceResult<(T, RateLimitInfo)> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start(Method::GET, endpoint);
         
-        let result = if let Some(retry_config) = &self.retry_config {
+        let (result, retry_report) = if let Some(retry_config) = &self.retry_config {
             // For retries, we need to clone the query parameters
             // Since we can't easily clone Q, we'll rebuild the request each time
             let endpoint = endpoint.to_string();
             
-            with_retry(|| async {
-                let url = http::build_url(&self.config.base_url, &endpoint)?;
+            with_retry_report(|| async {
+                let url = self.config.endpoint_url(&endpoint)?;
                 
                 // For each retry, we'll use the original query
-                let response = self.client
-                    .get(url)
-                    .query(query)
-                    .send()
-                    .await
-                    .map_err(VeniceError::HttpError)?;
-                
-                http::process_response(response).await
+                key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                    let request = self.connection.authorize(client.get(url.clone()), key).query(query);
+                    async move {
+                        let response = request.send().await.map_err(VeniceError::HttpError)?;
+                        http::process_response_with_usage(response, self.config.lenient_parsing).await
+                    }
+                }).await
             }, retry_config).await
         } else {
-            let response = self.client
-                .get(url)
-                .query(query)
-                .send()
-                .await
-                .map_err(VeniceError::HttpError)?;
-            
-            http::process_response(response).await
+            (key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = self.connection.authorize(client.get(url.clone()), key).query(query);
+                async move {
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_with_usage(response, self.config.lenient_parsing).await
+                }
+            }).await, RetryReport::default())
         };
+        self.observers.retried(&observed, &retry_report);
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("GET", endpoint)));
+        self.record_token_usage(&result);
+        let result = self.observers.finish(observed, result);
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
@@ -208,52 +646,110 @@ impl Client {
         endpoint: &str,
         body: &S,
     ) -> VeniceResult<(T, RateLimitInfo)> {
+        self.post_raw(endpoint, body).await.map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Send a POST request to the API, keeping the status, headers and raw body of the response
+    pub async fn post_raw<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> VeniceResult<ResponseEnvelope<T>> {
+        self.send_json_raw(Method::POST, endpoint, body).await
+    }
+    
+    /// Send a PUT request to the API
+    pub async fn put<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        self.send_json_raw(Method::PUT, endpoint, body).await.map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Send a PATCH request to the API
+    pub async fn patch<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        self.send_json_raw(Method::PATCH, endpoint, body).await.map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Send a DELETE request with a JSON body to the API
+    pub async fn delete_with_body<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        self.send_json_raw(Method::DELETE, endpoint, body).await.map(ResponseEnvelope::into_parts)
+    }
+    
+    /// Send a request with a JSON body, with rate limiting and retries
+    async fn send_json_raw<S: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: &S,
+    ) -> VeniceResult<ResponseEnvelope<T>> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        // Refuse oversized bodies before they use up rate limit capacity
+        let url = self.config.endpoint_url(endpoint)?;
+        let body = JsonBody::new(body, self.compression.as_deref())?.within_limit(self.config.max_request_bytes)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start_with_payload(method.clone(), endpoint, Some(body.bytes()));
         
-        let result = if let Some(retry_config) = &self.retry_config {
+        let (result, retry_report) = if let Some(retry_config) = &self.retry_config {
             // For retries, we need to clone the body
             // Since we can't easily clone S, we'll rebuild the request each time
             let endpoint = endpoint.to_string();
             
-            with_retry(|| async {
-                let url = http::build_url(&self.config.base_url, &endpoint)?;
+            with_retry_report(|| async {
+                let url = self.config.endpoint_url(&endpoint)?;
                 
                 // For each retry, we'll use the original body
-                let response = self
-                    .client
-                    .post(url)
-                    .json(body)
-                    .send()
-                    .await
-                    .map_err(VeniceError::HttpError)?;
-                
-                http::process_response(response).await
+                key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                    let request = body.attach(self.connection.authorize(client.request(method.clone(), url.clone()), key));
+                    async move {
+                        let started = Instant::now();
+                        let response = request.send().await.map_err(VeniceError::HttpError)?;
+                        http::process_response_envelope(response, started, self.config.lenient_parsing).await
+                    }
+                }).await
             }, retry_config).await
         } else {
-            let response = self
-                .client
-                .post(url)
-                .json(body)
-                .send()
-                .await
-                .map_err(VeniceError::HttpError)?;
-            
-            http::process_response(response).await
+            (key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = body.attach(self.connection.authorize(client.request(method.clone(), url.clone()), key));
+                async move {
+                    let started = Instant::now();
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_envelope(response, started, self.config.lenient_parsing).await
+                }
+            }).await, RetryReport::default())
         };
+        self.observers.retried(&observed, &retry_report);
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request(method.as_str(), endpoint)));
+        self.record_token_usage(&result);
+        let result = self.observers.finish(observed, result);
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
-        result
+        result.map(|(mut envelope, _)| {
+            envelope.retry = retry_report;
+            envelope
+        })
     }
 
     /// Send a DELETE request to the API
@@ -261,39 +757,44 @@ impl Client {
         &self,
         endpoint: &str,
     ) -> VeniceResult<(T, RateLimitInfo)> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start(Method::DELETE, endpoint);
         
-        let result = if let Some(retry_config) = &self.retry_config {
-            with_retry(|| async {
-                let response = self
-                    .client
-                    .delete(url.clone())
-                    .send()
-                    .await
-                    .map_err(VeniceError::HttpError)?;
-                
-                http::process_response(response).await
-            }, retry_config).await
+        let (result, retry_report) = if let Some(retry_config) = &self.retry_config {
+            with_retry_report(|| key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = self.connection.authorize(client.delete(url.clone()), key);
+                async move {
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_with_usage(response, self.config.lenient_parsing).await
+                }
+            }), retry_config).await
         } else {
-            let response = self
-                .client
-                .delete(url)
-                .send()
-                .await
-                .map_err(VeniceError::HttpError)?;
-            
-            http::process_response(response).await
+            (key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = self.connection.authorize(client.delete(url.clone()), key);
+                async move {
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_response_with_usage(response, self.config.lenient_parsing).await
+                }
+            }).await, RetryReport::default())
         };
+        self.observers.retried(&observed, &retry_report);
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("DELETE", endpoint)));
+        self.record_token_usage(&result);
+        let result = self.observers.finish(observed, result);
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
@@ -301,34 +802,43 @@ impl Client {
     }
 
     /// Send a multipart POST request to the API
+    ///
+    /// Parts created with [`UploadOptions::part`](crate::UploadOptions::part) are streamed
+    /// from their reader instead of being buffered in memory.
     pub async fn post_multipart<T: DeserializeOwned>(
         &self,
         endpoint: &str,
         form: reqwest::multipart::Form,
     ) -> VeniceResult<(T, RateLimitInfo)> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start(Method::POST, endpoint);
         
         // Multipart forms can't be easily cloned for retries
         // For now, we don't support retries for multipart requests
-        let response = self
-            .client
-            .post(url)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled_once(self.config.key_pool.as_ref(), |key| {
+            let request = self.connection.authorize(client.post(url), key).multipart(form);
+            async move {
+                let response = request.send().await.map_err(VeniceError::HttpError)?;
+                http::process_response_with_usage(response, self.config.lenient_parsing).await
+            }
+        }).await;
         
-        let result = http::process_response(response).await;
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        self.record_token_usage(&result);
+        let result = self.observers.finish(observed, result);
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
@@ -336,92 +846,175 @@ impl Client {
     }
 
     /// Send a multipart POST request to the API and get a binary response
+    ///
+    /// Parts created with [`UploadOptions::part`](crate::UploadOptions::part) are streamed
+    /// from their reader instead of being buffered in memory.
     pub async fn post_multipart_binary(
         &self,
         endpoint: &str,
         form: reqwest::multipart::Form,
     ) -> VeniceResult<(Vec<u8>, String, RateLimitInfo)> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start(Method::POST, endpoint);
         
         // Multipart forms can't be easily cloned for retries
         // For now, we don't support retries for multipart requests
-        let response = self
-            .client
-            .post(url)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled_once(self.config.key_pool.as_ref(), |key| {
+            let request = self.connection.authorize(client.post(url), key).multipart(form);
+            async move {
+                let response = request.send().await.map_err(VeniceError::HttpError)?;
+                http::process_binary_response(response).await
+            }
+        }).await;
         
-        let result = http::process_binary_response(response).await;
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok((_, _, rate_limit_info)) => self.observers.response(&observed, None, rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
         
         // Update rate limit information
         if let Ok((_, _, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
         result
     }
     
+    /// Send a multipart POST request to the API and stream the binary response
+    ///
+    /// The body is not buffered, so large files such as upscaled images can be written
+    /// to disk with bounded memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = venice_ai_api_sdk_rust::Client::new("your-api-key")?;
+    /// let form = reqwest::multipart::Form::new()
+    ///     .text("image_url", "https://example.com/image.png")
+    ///     .text("scale", "4");
+    ///
+    /// let body = client
+    ///     .post_multipart_binary_stream("image/upscale", form)
+    ///     .await?
+    ///     .on_download_progress(|received, total| println!("{} / {:?} bytes", received, total));
+    /// let mut file = tokio::fs::File::create("upscaled.png").await?;
+    /// body.write_to(&mut file).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_multipart_binary_stream(
+        &self,
+        endpoint: &str,
+        form: reqwest::multipart::Form,
+    ) -> VeniceResult<http::BinaryStream> {
+        let (client, in_flight) = self.connection.checkout()?;
+        
+        let url = self.config.endpoint_url(endpoint)?;
+        
+        // Check rate limits before making the request
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
+        }
+        
+        let observed = self.observers.start(Method::POST, endpoint);
+        
+        let result = key_pool::send_pooled_once(self.config.key_pool.as_ref(), |key| {
+            let request = self.connection.authorize(client.post(url), key).multipart(form);
+            async move {
+                let response = request.send().await.map_err(VeniceError::HttpError)?;
+                http::process_binary_stream(response).await
+            }
+        }).await;
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok(body) => self.observers.response(&observed, None, &body.rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
+        
+        // Update rate limit information
+        if let (Ok(body), Some(rate_limiter)) = (&result, &self.rate_limiter) {
+            rate_limiter.update_from_response_for_endpoint(endpoint, &body.rate_limit_info);
+        }
+        
+        // Keep the request in flight until the body is dropped
+        result.map(|body| body.attach(in_flight))
+    }
+    
     /// Send a POST request to the API and get a streaming response
     pub async fn post_streaming<S: Serialize, T: DeserializeOwned + 'static + Send>(
         &self,
         endpoint: &str,
         body: &S,
     ) -> VeniceResult<(Pin<Box<dyn Stream<Item = VeniceResult<T>> + Send>>, RateLimitInfo)> {
+        let (client, in_flight) = self.connection.checkout()?;
+        
+        // Refuse oversized bodies before they use up rate limit capacity
+        let url = self.config.endpoint_url(endpoint)?;
+        let body = JsonBody::new(body, self.compression.as_deref())?.within_limit(self.config.max_request_bytes)?;
+        
         // Check rate limits before making the request
         if let Some(rate_limiter) = &self.rate_limiter {
-            rate_limiter.acquire().await?;
+            rate_limiter.acquire_for_endpoint(endpoint).await?;
         }
         
-        let url = http::build_url(&self.config.base_url, endpoint)?;
+        let observed = self.observers.start_with_payload(Method::POST, endpoint, Some(body.bytes()));
         
-        let result = if let Some(retry_config) = &self.retry_config {
+        let (result, retry_report) = if let Some(retry_config) = &self.retry_config {
             // For retries, we need to clone the body
             // Since we can't easily clone S, we'll rebuild the request each time
             let endpoint = endpoint.to_string();
             
-            with_retry(|| async {
-                let url = http::build_url(&self.config.base_url, &endpoint)?;
+            with_retry_report(|| async {
+                let url = self.config.endpoint_url(&endpoint)?;
                 
                 // For each retry, we'll use the original body
-                let response = self
-                    .client
-                    .post(url)
-                    .json(body)
-                    .send()
-                    .await
-                    .map_err(VeniceError::HttpError)?;
-                
-                http::process_streaming_response(response).await
+                key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                    let request = body.attach(self.connection.authorize(client.post(url.clone()), key));
+                    async move {
+                        let response = request.send().await.map_err(VeniceError::HttpError)?;
+                        http::process_streaming_response(response).await
+                    }
+                }).await
             }, retry_config).await
         } else {
-            let response = self
-                .client
-                .post(url)
-                .json(body)
-                .send()
-                .await
-                .map_err(VeniceError::HttpError)?;
-            
-            http::process_streaming_response(response).await
+            (key_pool::send_pooled(self.config.key_pool.as_ref(), |key| {
+                let request = body.attach(self.connection.authorize(client.post(url.clone()), key));
+                async move {
+                    let response = request.send().await.map_err(VeniceError::HttpError)?;
+                    http::process_streaming_response(response).await
+                }
+            }).await, RetryReport::default())
         };
+        self.observers.retried(&observed, &retry_report);
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok((_, rate_limit_info)) => self.observers.response(&observed, None, rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
         
         // Update rate limit information
         if let Ok((_, ref rate_limit_info)) = result {
             if let Some(rate_limiter) = &self.rate_limiter {
-                rate_limiter.update_from_response(rate_limit_info);
+                rate_limiter.update_from_response_for_endpoint(endpoint, rate_limit_info);
             }
         }
         
-        result
+        // Keep the request in flight until the stream is dropped
+        result.map(|(stream, rate_limit_info)| (in_flight.attach(stream), rate_limit_info))
     }
 }
 
@@ -431,10 +1024,30 @@ pub type SharedClient = Arc<Client>;
 /// A builder for creating a client
 #[derive(Debug, Clone)]
 pub struct ClientBuilder {
-    api_key: Option<String>,
+    api_key: Option<SecretString>,
     base_url: Option<String>,
     retry_config: Option<RetryConfig>,
     rate_limiter: Option<Arc<RateLimiter>>,
+    observers: UsageObservers,
+    content_policy: Option<ContentPolicy>,
+    cost_tracker: Option<Arc<CostTracker>>,
+    response_cache: Option<Arc<ResponseCache>>,
+    headers: Vec<(String, String)>,
+    timeout_secs: Option<u64>,
+    lenient_parsing: bool,
+    coalesce_requests: bool,
+    key_pool: Option<Arc<KeyPool>>,
+    request_signer: Option<Arc<dyn RequestSigner>>,
+    request_compression_threshold: Option<usize>,
+    endpoint_paths: EndpointPaths,
+    allowed_base_urls: Vec<String>,
+    default_chat_model: Option<String>,
+    default_image_model: Option<String>,
+    max_spend_usd: Option<f64>,
+    max_spend_vcu: Option<f64>,
+    latency_tracking: Option<LatencyConfig>,
+    feature_flags: HashMap<String, String>,
+    max_request_bytes: Option<usize>,
 }
 
 impl ClientBuilder {
@@ -445,12 +1058,56 @@ impl ClientBuilder {
             base_url: None,
             retry_config: None,
             rate_limiter: None,
+            observers: UsageObservers::default(),
+            content_policy: None,
+            cost_tracker: None,
+            response_cache: None,
+            headers: Vec::new(),
+            timeout_secs: None,
+            lenient_parsing: false,
+            coalesce_requests: true,
+            key_pool: None,
+            request_signer: None,
+            request_compression_threshold: None,
+            endpoint_paths: EndpointPaths::default(),
+            allowed_base_urls: Vec::new(),
+            default_chat_model: None,
+            default_image_model: None,
+            max_spend_usd: None,
+            max_spend_vcu: None,
+            latency_tracking: None,
+            feature_flags: HashMap::new(),
+            max_request_bytes: None,
         }
     }
 
     /// Set the API key
     pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+        self.api_key = Some(SecretString::new(api_key));
+        self
+    }
+
+    /// Send requests with the keys of a pool
+    ///
+    /// The API key is optional when a pool is set. See [`ClientConfig::with_key_pool`].
+    pub fn key_pool(mut self, key_pool: KeyPool) -> Self {
+        self.key_pool = Some(Arc::new(key_pool));
+        self
+    }
+
+    /// Sign requests sent through the API traits
+    ///
+    /// The API key is optional when a signer is set. See [`ClientConfig::with_request_signer`].
+    pub fn request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
+    /// Gzip JSON request bodies of at least `threshold` bytes
+    ///
+    /// See [`ClientConfig::with_request_compression`].
+    pub fn request_compression(mut self, threshold: usize) -> Self {
+        self.request_compression_threshold = Some(threshold);
         self
     }
 
@@ -459,6 +1116,85 @@ impl ClientBuilder {
         self.base_url = Some(base_url.into());
         self
     }
+
+    /// Add a header sent with every request
+    ///
+    /// Invalid header names or values are reported by [`ClientBuilder::build`].
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Send requests for an endpoint to another path under the base URL
+    ///
+    /// See [`ClientConfig::with_endpoint_path`].
+    pub fn endpoint_path(mut self, endpoint: impl Into<String>, path: impl Into<String>) -> Self {
+        self.endpoint_paths.insert(endpoint, path);
+        self
+    }
+
+    /// Set the model used by chat requests that do not name one
+    ///
+    /// See [`ClientConfig::with_default_chat_model`].
+    pub fn default_chat_model(mut self, model: impl Into<String>) -> Self {
+        self.default_chat_model = Some(model.into());
+        self
+    }
+
+    /// Set the model used by image requests that do not name one
+    ///
+    /// See [`ClientConfig::with_default_image_model`].
+    pub fn default_image_model(mut self, model: impl Into<String>) -> Self {
+        self.default_image_model = Some(model.into());
+        self
+    }
+
+    /// Allow single calls to be sent to another base URL
+    ///
+    /// See [`ClientConfig::with_allowed_base_url`].
+    pub fn allowed_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.allowed_base_urls.push(base_url.into());
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request
+    pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+        self.header(reqwest::header::USER_AGENT.as_str(), user_agent)
+    }
+
+    /// Apply the timeout, retry policy and rate limiter of a preset profile
+    ///
+    /// Call this before other settings; [`ClientBuilder::timeout_secs`],
+    /// [`ClientBuilder::retry_config`] and the rate limiter setters override the
+    /// profile's values when called after it.
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.timeout_secs = Some(profile.timeout_secs());
+        self.retry_config = Some(profile.retry_config());
+        self.rate_limiter = Some(Arc::new(RateLimiter::with_config(profile.rate_limiter_config())));
+        self
+    }
+
+    /// Set the request timeout in seconds
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    
+    /// Drop response fields that fail to deserialize instead of failing the call
+    ///
+    /// See [`ClientConfig::with_lenient_parsing`].
+    pub fn lenient_parsing(mut self, lenient: bool) -> Self {
+        self.lenient_parsing = lenient;
+        self
+    }
+    
+    /// Set whether identical concurrent GET calls share one request
+    ///
+    /// See [`ClientConfig::with_request_coalescing`].
+    pub fn request_coalescing(mut self, enabled: bool) -> Self {
+        self.coalesce_requests = enabled;
+        self
+    }
     
     /// Set the retry configuration
     pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
@@ -489,18 +1225,102 @@ impl ClientBuilder {
         let rate_limiter = Arc::new(RateLimiter::with_config(config));
         self.rate_limiter(rate_limiter)
     }
+    
+    /// Register a usage observer
+    pub fn usage_observer(self, observer: Arc<dyn UsageObserver>) -> Self {
+        self.observers.add(observer);
+        self
+    }
+
+    /// Set the content policy applied to chat and image requests
+    pub fn content_policy(mut self, content_policy: ContentPolicy) -> Self {
+        self.content_policy = Some(content_policy);
+        self
+    }
+
+    /// Attach a cost tracker
+    pub fn cost_tracker(mut self, cost_tracker: Arc<CostTracker>) -> Self {
+        self.cost_tracker = Some(cost_tracker);
+        self
+    }
+
+    /// Answer repeated chat completion requests from a cache
+    pub fn response_cache(mut self, response_cache: ResponseCache) -> Self {
+        self.response_cache = Some(Arc::new(response_cache));
+        self
+    }
+
+    /// Cap the spend of the client in US dollars
+    ///
+    /// See [`ClientConfig::with_max_spend_usd`].
+    pub fn max_spend_usd(mut self, max_spend_usd: f64) -> Self {
+        self.max_spend_usd = Some(max_spend_usd);
+        self
+    }
+
+    /// Cap the spend of the client in Venice Compute Units
+    pub fn max_spend_vcu(mut self, max_spend_vcu: f64) -> Self {
+        self.max_spend_vcu = Some(max_spend_vcu);
+        self
+    }
+
+    /// Track the latency of every endpoint
+    ///
+    /// See [`ClientConfig::with_latency_tracking`].
+    pub fn latency_tracking(mut self, config: LatencyConfig) -> Self {
+        self.latency_tracking = Some(config);
+        self
+    }
+
+    /// Turn on a feature flag for every request
+    ///
+    /// See [`ClientConfig::with_feature_flag`].
+    pub fn feature_flag(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.feature_flags.insert(name.into(), value.into());
+        self
+    }
+
+    /// Refuse to send request bodies larger than `max_bytes`
+    ///
+    /// See [`ClientConfig::with_max_request_size`].
+    pub fn max_request_size(mut self, max_bytes: usize) -> Self {
+        self.max_request_bytes = Some(max_bytes);
+        self
+    }
 
     /// Build the client
     pub fn build(self) -> VeniceResult<Client> {
-        let api_key = self.api_key.ok_or_else(|| VeniceError::InvalidInput("API key is required".to_string()))?;
+        let api_key = match (self.api_key, &self.key_pool) {
+            (Some(api_key), _) => api_key,
+            (None, Some(key_pool)) => SecretString::new(key_pool.primary_key()),
+            (None, None) if self.request_signer.is_some() => SecretString::default(),
+            (None, None) => return Err(VeniceError::InvalidInput("API key is required".to_string())),
+        };
         let base_url = self.base_url.unwrap_or_else(|| crate::config::DEFAULT_BASE_URL.to_string());
         
-        let config = ClientConfig {
+        let mut config = ClientConfig {
             api_key,
             base_url,
             custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
+            timeout_secs: self.timeout_secs,
+            lenient_parsing: self.lenient_parsing,
+            key_pool: self.key_pool,
+            coalesce_requests: self.coalesce_requests,
+            request_signer: self.request_signer,
+            request_compression_threshold: self.request_compression_threshold,
+            endpoint_paths: self.endpoint_paths,
+            allowed_base_urls: self.allowed_base_urls,
+            default_chat_model: self.default_chat_model,
+            default_image_model: self.default_image_model,
+            max_spend_usd: self.max_spend_usd,
+            max_spend_vcu: self.max_spend_vcu,
+            latency_tracking: self.latency_tracking,
+            feature_flags: self.feature_flags,
+            max_request_bytes: self.max_request_bytes,
         };
+        for (name, value) in &self.headers {
+            config = config.with_header(name, value)?;
+        }
         
         let mut client = Client::with_config(config)?;
         
@@ -512,30 +1332,157 @@ impl ClientBuilder {
             client = client.with_rate_limiter(rate_limiter);
         }
         
+        for observer in self.observers.all() {
+            client = client.with_usage_observer(observer);
+        }
+        
+        if let Some(content_policy) = self.content_policy {
+            client = client.with_content_policy(content_policy);
+        }
+        
+        if let Some(cost_tracker) = self.cost_tracker {
+            client = client.with_cost_tracker(cost_tracker);
+        }
+        
+        if let Some(response_cache) = self.response_cache {
+            client = client.with_response_cache(response_cache);
+        }
+        
         Ok(client)
     }
 }
 
-/// Create a new shared client with the given API key
-pub fn new_shared_client(api_key: impl Into<String>) -> VeniceResult<SharedClient> {
-    Ok(Arc::new(Client::new(api_key)?))
+/// A builder for a handle sharing the connection pools of a client, from [`Client::derive`]
+#[derive(Debug, Clone)]
+pub struct DerivedClientBuilder {
+    client: Client,
+    headers: Vec<(String, String)>,
 }
 
-// Implement the ChatApi trait for Client by delegating to the chat_api
+impl DerivedClientBuilder {
+    /// Set the retry configuration
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.client.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Send requests of the derived client only once
+    pub fn no_retries(mut self) -> Self {
+        self.client.retry_config = None;
+        self
+    }
+
+    /// Set the rate limiter
+    ///
+    /// Share a limiter between handles to keep them under one budget, or give each
+    /// handle its own.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.client.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Send requests of the derived client without client-side rate limiting
+    pub fn no_rate_limiter(mut self) -> Self {
+        self.client.rate_limiter = None;
+        self
+    }
+
+    /// Set the model used by chat requests that do not name one
+    pub fn default_chat_model(mut self, model: impl Into<String>) -> Self {
+        self.client.config.default_chat_model = Some(model.into());
+        self
+    }
+
+    /// Set the model used by image requests that do not name one
+    pub fn default_image_model(mut self, model: impl Into<String>) -> Self {
+        self.client.config.default_image_model = Some(model.into());
+        self
+    }
+
+    /// Add a header to every request of the derived client
+    ///
+    /// Replaces a header of the same name set when the client was built.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Build the derived client
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if a header name or value is invalid.
+    pub fn build(self) -> VeniceResult<Client> {
+        let mut client = self.client;
+        if self.headers.is_empty() {
+            return Ok(client);
+        }
+        
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let (name, value) = crate::config::parse_header(name, value)?;
+            client.config.custom_headers.insert(name.clone(), value.clone());
+            headers.insert(name, value);
+        }
+        
+        client.connection = client.connection.with_headers(&headers);
+        let http_client = Arc::new(client.chat_api.http_client().as_ref().clone().with_headers(&headers));
+        client.chat_api = client.chat_api.with_http_client(http_client.clone());
+        client.models_api = client.models_api.with_http_client(http_client.clone());
+        client.image_api = client.image_api.with_http_client(http_client.clone());
+        client.api_keys_api = client.api_keys_api.with_http_client(http_client);
+        Ok(client)
+    }
+}
+
+/// Create a new shared client with the given API key
+pub fn new_shared_client(api_key: impl Into<String>) -> VeniceResult<SharedClient> {
+    Ok(Arc::new(Client::new(api_key)?))
+}
+
+// Implement the ChatApi trait for Client by delegating to the chat_api
 #[async_trait::async_trait]
 impl crate::traits::chat::ChatApi for Client {
     async fn create_chat_completion(
         &self,
         request: crate::traits::chat::ChatCompletionRequest,
     ) -> VeniceResult<(crate::traits::chat::ChatCompletionResponse, RateLimitInfo)> {
-        self.chat_api.create_chat_completion(request).await
+        let cache = self.response_cache.as_ref();
+        if let Some(cache) = cache {
+            match cache.get(&request).await {
+                Ok(Some(response)) => return Ok((response, RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new()))),
+                Ok(None) => {}
+                Err(err) => log::warn!("Failed to read the response cache: {}", err),
+            }
+        }
+        let cached_request = cache.map(|_| request.clone());
+        let (response, rate_limit_info) = self
+            .rate_limited(endpoints::CHAT_COMPLETIONS, self.chat_api.create_chat_completion(request))
+            .await?;
+        self.record_chat_tokens(response.usage.as_ref());
+        if let (Some(cache), Some(request)) = (cache, cached_request) {
+            if let Err(err) = cache.put(&request, &response).await {
+                log::warn!("Failed to write the response cache: {}", err);
+            }
+        }
+        Ok((response, rate_limit_info))
     }
     
     async fn create_streaming_chat_completion(
         &self,
         request: crate::traits::chat::ChatCompletionRequest,
     ) -> VeniceResult<(crate::traits::chat::ChatCompletionStream, RateLimitInfo)> {
-        self.chat_api.create_streaming_chat_completion(request).await
+        let (stream, rate_limit_info) = self
+            .rate_limited(endpoints::CHAT_COMPLETIONS, self.chat_api.create_streaming_chat_completion(request))
+            .await?;
+        let Some(rate_limiter) = self.rate_limiter.clone() else {
+            return Ok((stream, rate_limit_info));
+        };
+        // Usage arrives in the last chunk, if the request asked for it
+        let stream = futures::StreamExt::inspect(stream, move |chunk| {
+            if let Ok(crate::traits::chat::ChatCompletionChunk { usage: Some(usage), .. }) = chunk {
+                rate_limiter.record_tokens(usage.total_tokens);
+            }
+        });
+        Ok((Box::pin(stream), rate_limit_info))
     }
 }
 
@@ -553,7 +1500,7 @@ impl crate::traits::models::ModelsApi for Client {
         self.models_api.list_models_with_params(request).await
     }
     
-    fn list_models_paginator(&self, params: crate::pagination::PaginationParams) -> impl crate::pagination::Paginator<crate::models::list::Model> {
+    fn list_models_paginator(&self, params: crate::pagination::PaginationParams) -> crate::pagination::BoxPaginator<crate::models::list::Model> {
         self.models_api.list_models_paginator(params)
     }
     
@@ -580,27 +1527,557 @@ impl crate::traits::image::ImageApi for Client {
         &self,
         request: crate::traits::image::ImageGenerateRequest,
     ) -> VeniceResult<(crate::traits::image::ImageGenerateResponse, RateLimitInfo)> {
-        self.image_api.generate_image(request).await
+        self.rate_limited(endpoints::IMAGE_GENERATE, self.image_api.generate_image(request)).await
     }
     
     async fn upscale_image(
         &self,
         request: crate::traits::image::ImageUpscaleRequest,
-    ) -> VeniceResult<crate::traits::image::ImageUpscaleResponse> {
-        self.image_api.upscale_image(request).await
+    ) -> VeniceResult<(crate::traits::image::ImageUpscaleResponse, RateLimitInfo)> {
+        self.rate_limited(endpoints::IMAGE_UPSCALE, self.image_api.upscale_image(request)).await
     }
     
     async fn list_styles(&self) -> VeniceResult<(crate::traits::image::ListImageStylesResponse, RateLimitInfo)> {
-        self.image_api.list_styles().await
+        self.rate_limited(endpoints::IMAGE_STYLES, self.image_api.list_styles()).await
+    }
+}
+
+// Additional models API methods not part of the ModelsApi trait
+impl Client {
+    /// Get the cached index of model traits
+    ///
+    /// The index is fetched once and shared by all compatibility checks made through
+    /// this client, including [`ModelsApi::is_model_compatible`](crate::traits::models::ModelsApi::is_model_compatible).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = venice_ai_api_sdk_rust::Client::new("your-api-key")?;
+    ///
+    /// if client.compatibility().supports("qwen-2.5-vl", "vision").await? {
+    ///     println!("vision supported");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compatibility(&self) -> &crate::models::CompatibilityIndex {
+        self.models_api.compatibility()
+    }
+
+    /// Set how long the model compatibility index is cached before it is refreshed
+    pub fn with_compatibility_ttl(self, ttl: std::time::Duration) -> Self {
+        self.models_api.compatibility().set_ttl(ttl);
+        self
+    }
+}
+
+// Additional chat API methods not part of the ChatApi trait
+impl Client {
+    /// Ask a model to answer a prompt with JSON matching `T`'s schema, and parse it
+    ///
+    /// See [`schema::extract`](crate::schema::extract).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use serde::Deserialize;
+    /// use venice_ai_api_sdk_rust::{Client, schema::VeniceSchema};
+    ///
+    /// #[derive(Deserialize, VeniceSchema)]
+    /// struct Invoice {
+    ///     /// Invoice number
+    ///     number: String,
+    ///     total: f64,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let invoice: Invoice = client.extract("llama-3.3-70b", "Invoice INV-7, total $120.50").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn extract<T>(&self, model: &str, prompt: impl Into<String>) -> VeniceResult<T>
+    where
+        T: crate::schema::VeniceSchema + DeserializeOwned,
+    {
+        crate::schema::extract(self, model, prompt).await
+    }
+
+    /// Ask a model to answer a prompt with JSON matching `T`'s schema, asking it to fix
+    /// malformed replies up to `max_repairs` times
+    ///
+    /// See [`schema::extract_with_repair`](crate::schema::extract_with_repair).
+    pub async fn extract_with_repair<T>(&self, model: &str, prompt: impl Into<String>, max_repairs: u32) -> VeniceResult<T>
+    where
+        T: crate::schema::VeniceSchema + DeserializeOwned,
+    {
+        crate::schema::extract_with_repair(self, model, prompt, max_repairs).await
+    }
+    
+    /// Summarize a document of any length by summarizing its chunks and combining the summaries
+    ///
+    /// Uses the default chat model unless the options name one, and fails if neither is
+    /// set. See [`summarize_document`](crate::chat::summarize_document).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, chat::SummarizeOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .default_chat_model("llama-3.3-70b")
+    ///     .build()?;
+    /// let text = std::fs::read_to_string("report.txt")?;
+    /// let summary = client.summarize_document(&text, SummarizeOptions::new()).await?;
+    /// println!("{} chunks: {}", summary.chunk_summaries.len(), summary.summary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn summarize_document(
+        &self,
+        text: &str,
+        mut options: crate::chat::SummarizeOptions,
+    ) -> VeniceResult<crate::chat::DocumentSummary> {
+        if options.model.is_none() {
+            options.model = Some(self.default_chat_model()?.to_string());
+        }
+        crate::chat::summarize_document(self, text, options).await
+    }
+    
+    /// Answer many prompts concurrently, returning the answers in prompt order
+    ///
+    /// Each prompt is sent as its own request through
+    /// [`ChatApi::create_chat_completion`](crate::traits::chat::ChatApi::create_chat_completion),
+    /// so the rate limiter and retries of the client apply to it. A failed prompt does
+    /// not stop the others; its error takes its place in the results. Prompts use the
+    /// default chat model unless the options name one, and the call fails if neither is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, chat::MapChatOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?.with_rate_limiting();
+    /// let reviews = vec!["Loved it".to_string(), "Broke after a day".to_string()];
+    /// let options = MapChatOptions::new()
+    ///     .model("llama-3.3-70b")
+    ///     .system("Label the sentiment of the review as positive or negative.")
+    ///     .concurrency(8);
+    ///
+    /// let labels = client.map_chat(reviews.clone(), options).await?;
+    /// for (review, label) in reviews.iter().zip(labels) {
+    ///     println!("{}: {}", review, label?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn map_chat(
+        &self,
+        prompts: Vec<String>,
+        options: crate::chat::MapChatOptions,
+    ) -> VeniceResult<Vec<VeniceResult<String>>> {
+        let model = match &options.model {
+            Some(model) => model.clone(),
+            None => self.default_chat_model()?.to_string(),
+        };
+        Ok(crate::chat::map::map_chat(self, &model, prompts, options).await)
+    }
+    
+    /// Stream a chat completion and write its content to `writer`
+    ///
+    /// See [`chat::stream_chat_to_writer`](crate::chat::stream_chat_to_writer).
+    pub async fn stream_chat_to_writer<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        request: crate::traits::chat::ChatCompletionRequest,
+        writer: W,
+        options: crate::chat::StreamWriteOptions,
+    ) -> VeniceResult<crate::chat::StreamWriteSummary> {
+        crate::chat::stream_chat_to_writer(self, request, writer, options).await
+    }
+    
+    /// Check a chat completion request against the context window of its model
+    ///
+    /// Fails if the request is invalid. Otherwise the report lists the problems found,
+    /// comparing the estimated prompt tokens and `max_tokens` to the context size from the
+    /// models list, which is fetched once and cached.
+    pub async fn check_chat_request(
+        &self,
+        request: &crate::traits::chat::ChatCompletionRequest,
+    ) -> VeniceResult<crate::chat::ValidationReport> {
+        request.validate()?;
+        let prompt_tokens = crate::cost::estimate_message_tokens(request.messages.iter().map(|message| message.content.as_str()));
+        let context_size = self.models_api.context_size(&request.model).await?;
+        Ok(crate::chat::ValidationReport::new(
+            &request.model,
+            prompt_tokens,
+            request.max_tokens.or(request.max_completion_tokens),
+            context_size,
+        ))
+    }
+    
+    /// Prepare a chat completion request without sending it
+    ///
+    /// The content policy is applied and the request is validated as it would be when
+    /// sent, then serialized. The result has the URL, the headers with credentials
+    /// redacted, the exact JSON body, and the token and cost estimates. Extra parameters
+    /// that replace a field of the request are reported in
+    /// [`ChatDryRun::warnings`](crate::chat::ChatDryRun::warnings). Headers added by a
+    /// request signer or key pool are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::ChatCompletionBuilder};
+    ///
+    /// let client = Client::new("your-api-key").unwrap();
+    /// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+    ///     .add_user("Hello")
+    ///     .extra("top_k", 40)
+    ///     .build();
+    ///
+    /// let dry_run = client.dry_run(request).unwrap();
+    /// assert_eq!(dry_run.payload["top_k"], 40);
+    /// println!("{}", dry_run.payload_json());
+    /// ```
+    pub fn dry_run(&self, mut request: crate::traits::chat::ChatCompletionRequest) -> VeniceResult<crate::chat::ChatDryRun> {
+        self.apply_content_policy(&mut request);
+        request.validate()?;
+        
+        let prompt_tokens = crate::cost::estimate_message_tokens(request.messages.iter().map(|message| message.content.as_str()));
+        let cost = self.estimate_cost(&request.clone().into());
+        let mut request: crate::models::chat::ChatCompletionRequest = request.into();
+        
+        // Extra parameters are flattened into the body, so one named like a field replaces it
+        let extra = std::mem::take(&mut request.extra);
+        let fields = serde_json::to_value(&request).map_err(|e| VeniceError::InvalidInput(format!("Failed to serialize request: {}", e)))?;
+        let mut warnings: Vec<String> = extra
+            .keys()
+            .filter(|key| fields.get(key.as_str()).is_some())
+            .map(|key| format!("extra parameter '{}' replaces the request field of the same name", key))
+            .collect();
+        warnings.sort();
+        request.extra = extra;
+        let payload = serde_json::to_value(&request).map_err(|e| VeniceError::InvalidInput(format!("Failed to serialize request: {}", e)))?;
+        
+        let headers = self
+            .config
+            .create_default_headers()?
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes());
+                (name.as_str().to_string(), crate::chat::dry_run::redact_header(name.as_str(), &value))
+            })
+            .collect();
+        
+        Ok(crate::chat::ChatDryRun {
+            method: Method::POST.to_string(),
+            url: self.config.endpoint_url(endpoints::CHAT_COMPLETIONS)?.to_string(),
+            headers,
+            payload,
+            prompt_tokens,
+            cost,
+            warnings,
+        })
+    }
+    
+    /// Continue a completion cut off by its token limit, up to `max_continuations` times
+    ///
+    /// See [`chat::continue_completion`](crate::chat::continue_completion).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::{ChatApi, ChatCompletionBuilder}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let request = ChatCompletionBuilder::new("llama-3.3-70b")
+    ///     .add_user("Write a long story")
+    ///     .max_tokens(500)
+    ///     .build();
+    /// let (response, _) = ChatApi::create_chat_completion(&client, request.clone()).await?;
+    ///
+    /// let response = client.continue_completion(response, request, 4).await?;
+    /// println!("{}", response.choices[0].message.content);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn continue_completion(
+        &self,
+        response: crate::traits::chat::ChatCompletionResponse,
+        request: crate::traits::chat::ChatCompletionRequest,
+        max_continuations: usize,
+    ) -> VeniceResult<crate::traits::chat::ChatCompletionResponse> {
+        crate::chat::continue_completion(self, response, request, max_continuations).await
+    }
+    
+    /// Stream a chat completion as events, reconnecting the stream if it drops
+    ///
+    /// The stream is resumed with the ID of the last event received where the server
+    /// supports it, and each attempt is reported as a
+    /// [`ChatStreamEvent::Reconnecting`](crate::chat::ChatStreamEvent::Reconnecting) event.
+    /// Errors of the first request are returned directly. See
+    /// [`ReconnectPolicy`](crate::chat::ReconnectPolicy).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use venice_ai_api_sdk_rust::{
+    ///     Client,
+    ///     chat::{ChatStreamEvent, ReconnectPolicy},
+    ///     traits::chat::ChatCompletionBuilder,
+    /// };
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Tell me a story").build();
+    ///
+    /// let mut events = client.stream_chat_events(request, ReconnectPolicy::default()).await?;
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         ChatStreamEvent::ContentDelta { text, .. } => print!("{}", text),
+    ///         ChatStreamEvent::Reconnecting { attempt, .. } => eprintln!("\nreconnecting ({})", attempt),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stream_chat_events(
+        &self,
+        request: crate::traits::chat::ChatCompletionRequest,
+        policy: crate::chat::ReconnectPolicy,
+    ) -> VeniceResult<crate::chat::ChatEventStream> {
+        let (events, _) = self.chat_api.stream_events(request, policy).await?;
+        Ok(events)
+    }
+    
+    /// Send the same streaming request to several models and merge their streams
+    ///
+    /// The chunks of each stream are tagged with the model that produced it. Fails if
+    /// any of the requests fails before its stream starts.
+    ///
+    /// See [`StreamMultiplexer`](crate::chat::StreamMultiplexer) for an example.
+    pub async fn stream_models(
+        &self,
+        request: crate::traits::chat::ChatCompletionRequest,
+        models: &[&str],
+    ) -> VeniceResult<crate::chat::StreamMultiplexer> {
+        let streams = futures::future::try_join_all(models.iter().map(|model| {
+            let mut request = request.clone();
+            request.model = model.to_string();
+            async move {
+                let (stream, _) = crate::traits::chat::ChatApi::create_streaming_chat_completion(self, request).await?;
+                Ok::<_, VeniceError>((model.to_string(), stream))
+            }
+        }))
+        .await?;
+        Ok(streams.into_iter().collect())
+    }
+    
+    /// Measure how each model answers a prompt
+    ///
+    /// Runs that fail are recorded in the report instead of failing the benchmark. See
+    /// the [`benchmark`](crate::benchmark) module.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, benchmark::BenchmarkOptions};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let report = client
+    ///     .benchmark_models(
+    ///         "Summarize the plot of Hamlet in two sentences.",
+    ///         &["llama-3.3-70b", "mistral-31-24b"],
+    ///         BenchmarkOptions::new().runs(3),
+    ///     )
+    ///     .await;
+    ///
+    /// for model in &report.models {
+    ///     println!("{}: {:?} to first token, {:?} tokens/s", model.model, model.first_token_latency(), model.tokens_per_second());
+    /// }
+    /// if let Some(fastest) = report.fastest_first_token() {
+    ///     println!("Fastest: {}", fastest.model);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn benchmark_models(
+        &self,
+        prompt: &str,
+        models: &[&str],
+        options: crate::benchmark::BenchmarkOptions,
+    ) -> crate::benchmark::BenchmarkReport {
+        crate::benchmark::benchmark_models(self, prompt, models, &options).await
+    }
+
+    /// Find out what the API supports, such as tool calling and streaming
+    ///
+    /// The API has no capabilities endpoint, so this lists models of every type and
+    /// infers the capabilities from them. See the [`capabilities`](crate::capabilities) module.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::{ChatApi, ChatCompletionBuilder}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let info = client.api_info().await?;
+    ///
+    /// if !info.capabilities.tool_calling {
+    ///     println!("No model supports tool calling");
+    /// }
+    /// let mut request = ChatCompletionBuilder::new("llama-3.3-70b")
+    ///     .add_user("Tell me a joke")
+    ///     .include_usage(true)
+    ///     .build();
+    /// info.prepare(&mut request)?;
+    /// let (response, _) = ChatApi::create_chat_completion(&client, request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn api_info(&self) -> VeniceResult<crate::capabilities::ApiInfo> {
+        let request = ListModelsRequest::new().model_type(crate::models::list::ModelType::All);
+        let (models, rate_limit_info) =
+            crate::traits::models::ModelsApi::list_models_with_params(self, request).await?;
+        Ok(crate::capabilities::ApiInfo::from_models(&models.data, rate_limit_info))
+    }
+
+    /// Ask the default chat model a question and get the text of its answer
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if no default chat model is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = venice_ai_api_sdk_rust::Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .default_chat_model("llama-3.3-70b")
+    ///     .build()?;
+    ///
+    /// println!("{}", client.ask("What is the capital of France?").await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ask(&self, question: impl Into<String>) -> VeniceResult<String> {
+        let request = crate::traits::chat::ChatCompletionBuilder::default_model(self)?
+            .add_user(question)
+            .build();
+        let (response, _) = crate::traits::chat::ChatApi::create_chat_completion(self, request).await?;
+        response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))
     }
 }
 
 // Additional image API methods not part of the ImageApi trait
 impl Client {
+    /// Rewrite a short idea into a detailed image prompt and a negative prompt
+    ///
+    /// Uses the default chat model unless the options name one, and fails if neither is
+    /// set. The reply is requested in JSON schema mode and fails with a parse error if it
+    /// does not match.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, image::EnhanceOptions, traits::image::ImageApi};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let enhanced = client
+    ///     .enhance_prompt("a lighthouse in a storm", EnhanceOptions::new().model("llama-3.3-70b").style("oil painting"))
+    ///     .await?;
+    /// println!("{}\nNot: {}", enhanced.prompt, enhanced.negative_prompt);
+    ///
+    /// let request = enhanced.builder("fluently-xl").build();
+    /// let (response, _) = ImageApi::generate_image(&client, request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn enhance_prompt(
+        &self,
+        prompt: &str,
+        options: crate::image::EnhanceOptions,
+    ) -> VeniceResult<crate::image::EnhancedPrompt> {
+        let model = match &options.model {
+            Some(model) => model.clone(),
+            None => self.default_chat_model()?.to_string(),
+        };
+        crate::image::enhance_prompt(self, &model, prompt, &options).await
+    }
+    
     /// Get models that are compatible with image generation
-    pub async fn get_compatible_models(&self) -> VeniceResult<(Vec<crate::models::list::Model>, RateLimitInfo)> {
+    pub async fn get_compatible_models(&self) -> VeniceResult<(crate::models::list::ListModelsResponse, RateLimitInfo)> {
         self.image_api.get_compatible_models().await
     }
+    
+    /// Get the generation constraints of an image model
+    ///
+    /// Constraints are fetched on first use and cached for the lifetime of the client.
+    pub async fn image_constraints(&self, model: &str) -> VeniceResult<Option<crate::image::ImageConstraints>> {
+        self.image_api.image_constraints(model).await
+    }
+    
+    /// Check an image generation request against the constraints of its model
+    pub(crate) async fn check_image_request(&self, fields: &crate::image::ImageRequestFields<'_>) -> VeniceResult<()> {
+        self.image_api.check_request(fields).await
+    }
+    
+    /// List available image styles with their metadata
+    pub async fn list_styles_detailed(&self) -> VeniceResult<(crate::traits::image::ListImageStylesDetailedResponse, RateLimitInfo)> {
+        self.image_api.list_styles_detailed().await
+    }
+    
+    /// Check that a style preset is offered by the API
+    pub async fn validate_style_preset(&self, preset: &crate::traits::image::StylePreset) -> VeniceResult<()> {
+        self.image_api.validate_style_preset(preset).await
+    }
+    
+    /// Find the style a loosely written name refers to, such as `"cinematic"`
+    ///
+    /// The style list is fetched once and cached. The error for a name that matches no
+    /// style lists the closest ones.
+    pub async fn find_style(&self, query: &str) -> VeniceResult<crate::traits::image::ImageStyle> {
+        self.image_api.find_style(query).await
+    }
+    
+    /// Generate a PNG image with the default image model and get its bytes
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if no default image model is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = venice_ai_api_sdk_rust::Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .default_image_model("fluently-xl")
+    ///     .build()?;
+    ///
+    /// std::fs::write("lighthouse.png", client.imagine("A lighthouse at dusk").await?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn imagine(&self, prompt: impl Into<String>) -> VeniceResult<Vec<u8>> {
+        let request = crate::traits::image::ImageGenerateBuilder::default_model(self, prompt)?
+            .format(crate::image::ImageFormat::Png)
+            .build();
+        let (response, _) = crate::traits::image::ImageApi::generate_image(self, request).await?;
+        response.image_bytes(0)
+    }
 }
 
 // Implement the ApiKeysApi trait for Client by delegating to the api_keys_api
@@ -617,7 +2094,7 @@ impl crate::traits::api_keys::ApiKeysApi for Client {
         self.api_keys_api.list_api_keys_with_params(request).await
     }
     
-    fn list_api_keys_paginator(&self, params: crate::pagination::PaginationParams) -> impl crate::pagination::Paginator<crate::api_keys::list::ApiKey> {
+    fn list_api_keys_paginator(&self, params: crate::pagination::PaginationParams) -> crate::pagination::BoxPaginator<crate::api_keys::list::ApiKey> {
         self.api_keys_api.list_api_keys_paginator(params)
     }
     
@@ -697,7 +2174,7 @@ mod tests {
         assert_eq!(client_retry_config.initial_delay_ms, 1000);
         assert_eq!(client_retry_config.max_delay_ms, 20000);
         assert_eq!(client_retry_config.backoff_factor, 3.0);
-        assert_eq!(client_retry_config.add_jitter, false);
+        assert!(!client_retry_config.add_jitter);
     }
     
     #[test]
@@ -716,6 +2193,7 @@ mod tests {
         let rate_limiter_config = RateLimiterConfig {
             auto_wait: false,
             max_wait_time: 30,
+            ..Default::default()
         };
         
         let client = Client::builder()
@@ -726,4 +2204,1089 @@ mod tests {
         
         assert!(client.rate_limiter().is_some());
     }
+    
+    #[test]
+    fn test_client_with_usage_observer() {
+        struct NoopObserver;
+        impl UsageObserver for NoopObserver {}
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .usage_observer(Arc::new(NoopObserver))
+            .build()
+            .unwrap();
+        
+        assert_eq!(client.observers.len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_latency_stats_per_endpoint() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(5)
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .timeout_secs(1)
+            .request_coalescing(false)
+            .latency_tracking(LatencyConfig::new().max_timeout(Duration::from_secs(30)))
+            .build()
+            .unwrap();
+        assert!(Client::new("test_api_key").unwrap().latency_stats().is_empty());
+
+        for _ in 0..5 {
+            crate::traits::models::ModelsApi::list_models(&client).await.unwrap();
+        }
+
+        let stats = client.latency_stats();
+        assert_eq!(stats["models"].samples, 5);
+        assert!(stats["models"].p95 >= stats["models"].p50);
+        let tracker = client.latency_tracker().unwrap();
+        assert_eq!(tracker.timeout_for("models", Duration::from_secs(1)), Duration::from_secs(1));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cost_tracker_records_chat_usage() {
+        use crate::chat::ChatCompletionRequestBuilder;
+        use crate::cost::CostCalculator;
+        use crate::models::list::ModelPricing;
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 1000, "completion_tokens": 500, "total_tokens": 1500}
+            }"#)
+            .create_async()
+            .await;
+        
+        let calculator = CostCalculator::new().with_pricing(
+            "llama-3.3-70b",
+            ModelPricing { prompt: Some(0.001), completion: Some(0.002) },
+        );
+        let tracker = Arc::new(CostTracker::new(calculator));
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .cost_tracker(tracker.clone())
+            .build()
+            .unwrap();
+        
+        let request = ChatCompletionRequestBuilder::new("llama-3.3-70b")
+            .add_user_message("Hello")
+            .with_max_tokens(100)
+            .build();
+        assert!(client.estimate_cost(&request).is_some());
+        client.create_chat_completion(request).await.unwrap();
+        
+        mock.assert_async().await;
+        let spend = tracker.model_spend("llama-3.3-70b").unwrap();
+        assert_eq!(spend.requests, 1);
+        assert!((tracker.total().usd - 0.002).abs() < 1e-9);
+    }
+    
+    #[tokio::test]
+    async fn test_build_checked_reports_context_window() {
+        use crate::chat::ValidationWarning;
+        use crate::traits::chat::ChatCompletionBuilder;
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models?type=text")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": [{"id": "small-model", "object": "model", "owned_by": "venice", "context_size": 100}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        
+        let (_, report) = ChatCompletionBuilder::new("small-model").add_user("Hello").build_checked(&client).await.unwrap();
+        assert!(report.is_ok());
+        
+        let (request, report) = ChatCompletionBuilder::new("small-model")
+            .add_user("a".repeat(1000))
+            .build_checked(&client)
+            .await
+            .unwrap();
+        assert_eq!(request.messages.len(), 1);
+        assert!(matches!(report.warnings[0], ValidationWarning::PromptExceedsContext { context_size: 100, .. }));
+        
+        let (_, report) = ChatCompletionBuilder::new("other-model").add_user("Hello").build_checked(&client).await.unwrap();
+        assert!(matches!(report.warnings[0], ValidationWarning::UnknownContextSize { .. }));
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_max_spend_refuses_requests_until_reset() {
+        use crate::chat::ChatCompletionRequestBuilder;
+        use crate::cost::CostCalculator;
+        use crate::models::list::ModelPricing;
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 1000, "completion_tokens": 500, "total_tokens": 1500}
+            }"#)
+            .expect(3)
+            .create_async()
+            .await;
+        
+        let calculator = CostCalculator::new().with_pricing(
+            "llama-3.3-70b",
+            ModelPricing { prompt: Some(0.001), completion: Some(0.002) },
+        );
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .cost_tracker(Arc::new(CostTracker::new(calculator)))
+            .max_spend_usd(0.003)
+            .build()
+            .unwrap();
+        let request = ChatCompletionRequestBuilder::new("llama-3.3-70b").add_user_message("Hello").build();
+        
+        client.create_chat_completion(request.clone()).await.unwrap();
+        client.create_chat_completion(request.clone()).await.unwrap();
+        let err = client.create_chat_completion(request.clone()).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::BudgetExceeded(_)));
+        let err = client.get::<serde_json::Value>("models").await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::BudgetExceeded(_)));
+        
+        client.spend_guard().unwrap().reset();
+        client.create_chat_completion(request).await.unwrap();
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_shared_client_implements_api_traits() {
+        use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+        use crate::traits::VeniceApi;
+        
+        async fn reply(api: &impl ChatApi) -> String {
+            let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+            api.create_chat_completion(request).await.unwrap().0.choices[0].message.content.clone()
+        }
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}]
+            }"#)
+            .expect(3)
+            .create_async()
+            .await;
+        
+        let client: SharedClient = Arc::new(Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap());
+        assert_eq!(reply(&client).await, "Hi");
+        assert_eq!(reply(&&*client).await, "Hi");
+        
+        let api: Arc<dyn VeniceApi> = client;
+        assert_eq!(reply(&api).await, "Hi");
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_request_and_response_compression() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"ok": true}"#).unwrap();
+        let gzipped_response = encoder.finish().unwrap();
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_header("content-encoding", "gzip")
+            .match_header("accept-encoding", mockito::Matcher::Regex("gzip".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("content-encoding", "gzip")
+            .with_body(gzipped_response)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .request_compression(1024)
+            .build()
+            .unwrap();
+        let history = "Summarize the conversation so far. ".repeat(1000);
+        let (response, _): (serde_json::Value, _) = client
+            .post("chat/completions", &serde_json::json!({"messages": [{"role": "user", "content": history}]}))
+            .await
+            .unwrap();
+        assert_eq!(response["ok"], true);
+        mock.assert_async().await;
+        
+        let stats = client.compression_stats().unwrap();
+        assert_eq!(stats.requests_compressed, 1);
+        assert!(stats.bytes_saved() > 30_000);
+    }
+    
+    #[tokio::test]
+    async fn test_ping() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::UrlEncoded("limit".into(), "1".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .create_async()
+            .await;
+        
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let report = client.ping().await.unwrap();
+        assert!(report.authenticated);
+        assert!(report.rate_limit_info.is_some());
+        mock.assert_async().await;
+        mock.remove_async().await;
+        
+        let stats = client.connection_stats();
+        assert!(stats.is_ready());
+        assert_eq!(stats.in_flight, 0);
+        assert_eq!(stats.total_requests, 1);
+        
+        server
+            .mock("GET", "/models")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": {"code": "unauthorized", "message": "Invalid API key"}}"#)
+            .create_async()
+            .await;
+        
+        let client = Client::builder().api_key("revoked_key").base_url(server.url()).build().unwrap();
+        let report = client.ping().await.unwrap();
+        assert!(!report.authenticated);
+        assert!(report.rate_limit_info.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_shutdown_rejects_new_requests() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let clone = client.clone();
+        
+        let _: (serde_json::Value, RateLimitInfo) = client.get("/models").await.unwrap();
+        assert_eq!(client.in_flight(), 0);
+        
+        let report = client.shutdown(std::time::Duration::from_secs(1)).await;
+        assert!(report.drained);
+        assert!(clone.is_shut_down());
+        
+        let result: VeniceResult<(serde_json::Value, RateLimitInfo)> = clone.get("/models").await;
+        assert!(matches!(result, Err(VeniceError::ClientShutdown)));
+        let result = crate::traits::models::ModelsApi::list_models(&clone).await;
+        assert!(matches!(result, Err(VeniceError::ClientShutdown)));
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_builder_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .match_header("x-org-id", "org-42")
+            .match_header("user-agent", "my-app/1.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .header("X-Org-Id", "org-42")
+            .user_agent("my-app/1.0")
+            .timeout_secs(30)
+            .build()
+            .unwrap();
+        assert_eq!(client.config().timeout_secs, Some(30));
+        
+        let _: (serde_json::Value, RateLimitInfo) = client.get("/models").await.unwrap();
+        crate::traits::models::ModelsApi::list_models(&client).await.unwrap();
+        mock.assert_async().await;
+        
+        let result = Client::builder()
+            .api_key("test_api_key")
+            .header("X-Org-Id", "bad\nvalue")
+            .build();
+        assert!(matches!(result, Err(VeniceError::InvalidInput(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_raw_response_envelope() {
+        let mut server = mockito::Server::new_async().await;
+        let body = r#"{"id": "chatcmpl-1", "object": "chat.completion", "created": 1677652288, "model": "llama-3.3-70b", "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}], "surprise": true}"#;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("x-request-id", "req-123")
+            .with_body(body)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let request = crate::chat::ChatCompletionRequestBuilder::new("llama-3.3-70b")
+            .add_user_message("Hello")
+            .build();
+        
+        let envelope = client.create_chat_completion_raw(request).await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(envelope.status, reqwest::StatusCode::OK);
+        assert_eq!(envelope.request_id(), Some("req-123"));
+        assert_eq!(envelope.raw_body, body);
+        assert_eq!(envelope.body.choices[0].message.content, "Hi");
+    }
+    
+    #[tokio::test]
+    async fn test_retry_report() {
+        use std::sync::Mutex;
+        
+        #[derive(Default)]
+        struct RetryRecorder {
+            reports: Mutex<Vec<RetryReport>>,
+        }
+        
+        impl UsageObserver for RetryRecorder {
+            fn on_retry(&self, _request: &crate::RequestInfo, report: &RetryReport) {
+                self.reports.lock().unwrap().push(report.clone());
+            }
+        }
+        
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/models")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"error": {"code": "unavailable", "message": "Try again later"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let recorder = Arc::new(RetryRecorder::default());
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .retry_config(RetryConfig::new().max_retries(3).initial_delay_ms(5).add_jitter(false))
+            .build()
+            .unwrap()
+            .with_usage_observer(recorder.clone());
+        
+        let request = client.get_raw::<serde_json::Value>("models");
+        let recover = async {
+            // Let the first two attempts fail before the endpoint recovers
+            while !unavailable.matched_async().await {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+            server
+                .mock("GET", "/models")
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(r#"{"object": "list", "data": []}"#)
+                .create_async()
+                .await
+        };
+        let (envelope, _ok) = tokio::join!(request, recover);
+        let envelope = envelope.unwrap();
+        
+        let report = &envelope.retry;
+        assert_eq!(report.attempts, 3);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].status, Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(report.total_backoff, std::time::Duration::from_millis(10 + 20));
+        assert_eq!(*recorder.reports.lock().unwrap(), vec![report.clone()]);
+    }
+    
+    #[tokio::test]
+    async fn test_error_context() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_header("x-request-id", "req-503")
+            .with_body(r#"{"error": {"code": "unavailable", "message": "Try again later"}}"#)
+            .expect(3)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .retry_config(RetryConfig::new().max_retries(2).initial_delay_ms(1).add_jitter(false))
+            .build()
+            .unwrap();
+        
+        let err = client.get::<serde_json::Value>("/models").await.unwrap_err();
+        mock.assert_async().await;
+        
+        assert!(matches!(err.root(), VeniceError::ApiError { status, .. } if status.as_u16() == 503));
+        let context = err.context().unwrap();
+        assert_eq!(context.method.as_deref(), Some("GET"));
+        assert_eq!(context.endpoint.as_deref(), Some("/models"));
+        assert_eq!(context.attempts, Some(3));
+        assert_eq!(err.request_id(), Some("req-503"));
+        assert_eq!(
+            err.to_string(),
+            "API error: unavailable - Try again later (GET /models, request ID req-503, 3 attempts)"
+        );
+    }
+    
+    #[test]
+    fn test_default_models() {
+        use crate::traits::chat::ChatCompletionBuilder;
+        use crate::traits::image::ImageGenerateBuilder;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .default_image_model("fluently-xl")
+            .build()
+            .unwrap();
+        let request = ImageGenerateBuilder::default_model(&client, "A lighthouse").unwrap().build();
+        assert_eq!(request.model, "fluently-xl");
+        
+        let err = ChatCompletionBuilder::default_model(&client).unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(msg) if msg.contains("default_chat_model")));
+    }
+    
+    #[test]
+    fn test_profile_can_be_overridden() {
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .profile(crate::Profile::Interactive)
+            .timeout_secs(10)
+            .build()
+            .unwrap();
+        assert_eq!(client.config().timeout_secs, Some(10));
+        assert_eq!(client.retry_config().unwrap().max_retries, 1);
+        assert!(client.rate_limiter().is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_ask_and_imagine() {
+        let mut server = mockito::Server::new_async().await;
+        let _chat = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(r#"{"model": "llama-3.3-70b"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Paris"}, "finish_reason": "stop"}]
+            }"#)
+            .create_async()
+            .await;
+        let _models = server
+            .mock("GET", "/models?type=image")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": [{"id": "fluently-xl"}]}"#)
+            .create_async()
+            .await;
+        let _generate = server
+            .mock("POST", "/image/generate")
+            .match_body(mockito::Matcher::PartialJsonString(r#"{"model": "fluently-xl", "format": "png"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id": "generate-1", "images": ["aGVsbG8="]}"#)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .default_chat_model("llama-3.3-70b")
+            .default_image_model("fluently-xl")
+            .build()
+            .unwrap();
+        
+        assert_eq!(client.ask("What is the capital of France?").await.unwrap(), "Paris");
+        assert_eq!(client.imagine("A lighthouse").await.unwrap(), b"hello");
+    }
+    
+    #[tokio::test]
+    async fn test_base_url_override_routes_single_calls() {
+        use crate::traits::models::ModelsApi;
+        use crate::RequestOptions;
+        
+        let mut primary = mockito::Server::new_async().await;
+        let mut staging = mockito::Server::new_async().await;
+        let listing = r#"{"object": "list", "data": []}"#;
+        let primary_mock = primary.mock("GET", "/models").with_status(200).with_body(listing).expect(1).create_async().await;
+        let staging_mock = staging.mock("GET", "/models").with_status(200).with_body(listing).expect(2).create_async().await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(primary.url())
+            .allowed_base_url(staging.url())
+            .build()
+            .unwrap();
+        
+        let staging_options = RequestOptions::new().base_url_override(staging.url());
+        staging_options.clone().scope(client.get::<serde_json::Value>("models")).await.unwrap();
+        staging_options.scope(ModelsApi::list_models(&client)).await.unwrap();
+        client.get::<serde_json::Value>("models").await.unwrap();
+        
+        let err = RequestOptions::new()
+            .base_url_override("https://gateway.example.com")
+            .scope(client.get::<serde_json::Value>("models"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(_)));
+        
+        primary_mock.assert_async().await;
+        staging_mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_feature_flags_with_per_request_overrides() {
+        use crate::traits::models::ModelsApi;
+        use crate::RequestOptions;
+        
+        let mut server = mockito::Server::new_async().await;
+        let listing = r#"{"object": "list", "data": []}"#;
+        // The overridden flag is matched first, since the client's flags are sent too
+        let override_mock = server
+            .mock("GET", "/models")
+            .match_header("x-venice-feature-reasoning", "off")
+            .match_header("x-venice-feature-web-search", "beta")
+            .with_status(200)
+            .with_body(listing)
+            .expect(2)
+            .create_async()
+            .await;
+        let default_mock = server
+            .mock("GET", "/models")
+            .match_header("x-venice-feature-reasoning", "on")
+            .with_status(200)
+            .with_body(listing)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .feature_flag("reasoning", "on")
+            .build()
+            .unwrap();
+        client.get::<serde_json::Value>("models").await.unwrap();
+        ModelsApi::list_models(&client).await.unwrap();
+        
+        let options = RequestOptions::new()
+            .feature_flag("reasoning", "off")
+            .unwrap()
+            .feature_flag("web-search", "beta")
+            .unwrap();
+        options.clone().scope(client.get::<serde_json::Value>("models")).await.unwrap();
+        options.scope(ModelsApi::list_models(&client)).await.unwrap();
+        
+        default_mock.assert_async().await;
+        override_mock.assert_async().await;
+        
+        assert!(RequestOptions::new().feature_flag("", "on").is_err());
+        let err = Client::builder().api_key("test_api_key").feature_flag("bad flag", "on").build().unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(_)));
+    }
+    
+    #[tokio::test]
+    async fn test_oversized_payloads_are_not_sent() {
+        use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("POST", "/chat/completions").expect(0).create_async().await;
+        let rate_limiter = Arc::new(RateLimiter::with_config(
+            crate::rate_limit::RateLimiterConfig::new().auto_wait(false).requests_per_minute(1),
+        ));
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .max_request_size(1024)
+            .rate_limiter(rate_limiter.clone())
+            .build()
+            .unwrap();
+        
+        let history = "An earlier turn of a long conversation. ".repeat(100);
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user(history).build();
+        let err = ChatApi::create_chat_completion(&client, request.clone()).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::PayloadTooLarge { size, limit: 1024 } if *size > 4000));
+        assert_eq!(err.status_hint(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+        
+        let err = client.post::<_, serde_json::Value>("chat/completions", &request).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::PayloadTooLarge { .. }));
+        let err = client.post_streaming::<_, serde_json::Value>("chat/completions", &request).await.err().unwrap();
+        assert!(matches!(err.root(), VeniceError::PayloadTooLarge { .. }));
+        mock.assert_async().await;
+        
+        // Refused requests leave the rate limit budget untouched
+        rate_limiter.acquire_for_endpoint("chat/completions").await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_chat_api_calls_are_charged_to_the_token_budget() {
+        use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+                "usage": {"prompt_tokens": 400, "completion_tokens": 400, "total_tokens": 800}
+            }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let rate_limiter = Arc::new(RateLimiter::with_config(
+            crate::rate_limit::RateLimiterConfig::new().auto_wait(false).tokens_per_minute(600),
+        ));
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .rate_limiter(rate_limiter.clone())
+            .build()
+            .unwrap();
+        
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+        ChatApi::create_chat_completion(&client, request.clone()).await.unwrap();
+        
+        // The reply used more tokens than the budget holds, so the next call has to wait
+        let err = ChatApi::create_chat_completion(&client, request).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::RateLimitExceeded(_)));
+        assert!(rate_limiter.acquire().await.is_err());
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_chat_api_calls_take_request_permits() {
+        use crate::rate_limit::LimitClass;
+        use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("x-ratelimit-limit-requests", "20")
+            .with_header("x-ratelimit-remaining-requests", "7")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}]
+            }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let rate_limiter = Arc::new(RateLimiter::with_config(
+            crate::rate_limit::RateLimiterConfig::new().auto_wait(false).requests_per_minute(1),
+        ));
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .rate_limiter(rate_limiter.clone())
+            .build()
+            .unwrap();
+        
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+        ChatApi::create_chat_completion(&client, request.clone()).await.unwrap();
+        let snapshot = rate_limiter.for_class(LimitClass::Chat).snapshot();
+        assert_eq!(snapshot.limit_requests, Some(20));
+        assert_eq!(snapshot.remaining_requests, Some(7));
+        
+        // The request budget is used up, so neither call is sent
+        let err = ChatApi::create_chat_completion(&client, request.clone()).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::RateLimitExceeded(_)));
+        let err = ChatApi::create_streaming_chat_completion(&client, request).await.err().unwrap();
+        assert!(matches!(err.root(), VeniceError::RateLimitExceeded(_)));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_delete_with_body_is_retried_with_method_in_context() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("DELETE", "/jobs")
+            .match_body(r#"{"ids":["job-1"]}"#)
+            .with_status(503)
+            .with_body(r#"{"error": {"code": "unavailable", "message": "Try again later"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .retry_config(RetryConfig::new().max_retries(1).initial_delay_ms(1).add_jitter(false))
+            .build()
+            .unwrap();
+        
+        let err = client
+            .delete_with_body::<_, serde_json::Value>("jobs", &serde_json::json!({"ids": ["job-1"]}))
+            .await
+            .unwrap_err();
+        mock.assert_async().await;
+        
+        let context = err.context().unwrap();
+        assert_eq!(context.method.as_deref(), Some("DELETE"));
+        assert_eq!(context.attempts, Some(2));
+    }
+    
+    #[tokio::test]
+    async fn test_image_api_calls_update_the_rate_limiter() {
+        use crate::rate_limit::LimitClass;
+        use crate::traits::image::{ImageApi, ImageGenerateBuilder, ImageUpscaleBuilder};
+        
+        let mut server = mockito::Server::new_async().await;
+        let upscale = server
+            .mock("POST", "/image/upscale")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_header("x-ratelimit-limit-requests", "20")
+            .with_header("x-ratelimit-remaining-requests", "19")
+            .with_body("0123456789")
+            .create_async()
+            .await;
+        let styles = server
+            .mock("GET", "/image/styles")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("x-ratelimit-limit-requests", "20")
+            .with_header("x-ratelimit-remaining-requests", "0")
+            .with_body(r#"{"data": ["Anime"]}"#)
+            .create_async()
+            .await;
+        let generate = server.mock("POST", "/image/generate").expect(0).create_async().await;
+        let rate_limiter = Arc::new(RateLimiter::with_config(
+            crate::rate_limit::RateLimiterConfig::new().auto_wait(false),
+        ));
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .rate_limiter(rate_limiter.clone())
+            .build()
+            .unwrap();
+        
+        let request = ImageUpscaleBuilder::with_url("upscale-xl", "https://example.com/image.png").build();
+        ImageApi::upscale_image(&client, request).await.unwrap();
+        let snapshot = rate_limiter.for_class(LimitClass::Image).snapshot();
+        assert_eq!(snapshot.limit_requests, Some(20));
+        assert_eq!(snapshot.remaining_requests, Some(19));
+        
+        ImageApi::list_styles(&client).await.unwrap();
+        assert_eq!(rate_limiter.for_class(LimitClass::Image).snapshot().remaining_requests, Some(0));
+        
+        // The image limits are used up, so the generation is not sent
+        let request = ImageGenerateBuilder::new("fluently-xl", "A lighthouse at dusk").build();
+        let err = ImageApi::generate_image(&client, request).await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::RateLimitExceeded(_)));
+        
+        upscale.assert_async().await;
+        styles.assert_async().await;
+        generate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_post_multipart_binary_stream() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/image/upscale")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_body("0123456789")
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        
+        let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+        let form = reqwest::multipart::Form::new().text("scale", "2");
+        let body = client
+            .post_multipart_binary_stream("image/upscale", form)
+            .await
+            .unwrap()
+            .on_download_progress(move |received, total| recorded.lock().unwrap().push((received, total)));
+        assert_eq!(body.mime_type, "image/png");
+        assert_eq!(body.content_length, Some(10));
+        assert_eq!(client.in_flight(), 1);
+        
+        let mut written = Vec::new();
+        assert_eq!(body.write_to(&mut written).await.unwrap(), 10);
+        assert_eq!(written, b"0123456789");
+        assert_eq!(progress.lock().unwrap().last(), Some(&(10, Some(10))));
+        assert_eq!(client.in_flight(), 0);
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_user_and_tags_are_sent_and_observed() {
+        use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+        
+        #[derive(Default)]
+        struct LabelRecorder {
+            labels: std::sync::Mutex<Vec<(Option<String>, Vec<String>)>>,
+        }
+        
+        impl UsageObserver for LabelRecorder {
+            fn on_request(&self, request: &crate::observer::RequestInfo) {
+                self.labels.lock().unwrap().push((request.user.clone(), request.tags.clone()));
+            }
+        }
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "user": "user-42",
+                "request_tags": ["support", "beta"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}]
+            }"#)
+            .create_async()
+            .await;
+        
+        let recorder = Arc::new(LabelRecorder::default());
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .usage_observer(recorder.clone())
+            .build()
+            .unwrap();
+        let request = ChatCompletionBuilder::new("llama-3.3-70b")
+            .add_user("Hello")
+            .user_id("user-42")
+            .tag("support")
+            .tag("beta")
+            .tag("support")
+            .build();
+        ChatApi::create_chat_completion(&client, request).await.unwrap();
+        
+        mock.assert_async().await;
+        let labels = recorder.labels.lock().unwrap();
+        assert_eq!(
+            *labels,
+            vec![(Some("user-42".to_string()), vec!["support".to_string(), "beta".to_string()])]
+        );
+    }
+    
+    #[tokio::test]
+    async fn test_lenient_parsing_reports_warnings() {
+        #[derive(Default)]
+        struct WarningRecorder {
+            paths: std::sync::Mutex<Vec<String>>,
+        }
+        
+        impl UsageObserver for WarningRecorder {
+            fn on_parse_warning(&self, _request: &crate::observer::RequestInfo, warning: &http::ParseWarning) {
+                self.paths.lock().unwrap().push(warning.path.clone());
+            }
+        }
+        
+        #[derive(Debug, serde::Deserialize)]
+        struct Listing {
+            data: Vec<String>,
+            #[serde(default)]
+            total: Option<u32>,
+        }
+        
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": ["a"], "total": "many"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let strict = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let err = strict.get::<Listing>("models").await.unwrap_err();
+        assert!(matches!(err.root(), VeniceError::ParseError { path: Some(path), .. } if path == "total"));
+        
+        let recorder = Arc::new(WarningRecorder::default());
+        let lenient = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .lenient_parsing(true)
+            .usage_observer(recorder.clone())
+            .build()
+            .unwrap();
+        let (listing, _) = lenient.get::<Listing>("models").await.unwrap();
+        assert_eq!(listing.data, vec!["a".to_string()]);
+        assert_eq!(listing.total, None);
+        assert_eq!(*recorder.paths.lock().unwrap(), vec!["total".to_string()]);
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_endpoint_path_override_applies_to_every_entry_point() {
+        use crate::traits::image::{ImageApi, ImageGenerateBuilder};
+        
+        let mut server = mockito::Server::new_async().await;
+        let _models = server
+            .mock("GET", "/models?type=image")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": [{"id": "fluently-xl"}]}"#)
+            .create_async()
+            .await;
+        let generate = server
+            .mock("POST", "/proxy/images")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id": "generate-1", "images": ["aGVsbG8="]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .endpoint_path(endpoints::IMAGE_GENERATE, "proxy/images")
+            .build()
+            .unwrap();
+        
+        let request = crate::image::ImageGenerateRequestBuilder::new("fluently-xl", "A lighthouse").build();
+        client.generate_image(request).await.unwrap();
+        let request = ImageGenerateBuilder::new("fluently-xl", "A lighthouse").build();
+        ImageApi::generate_image(&client, request).await.unwrap();
+        
+        generate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_derived_client_shares_pools_and_varies_settings() {
+        use crate::traits::models::ModelsApi;
+        
+        let mut server = mockito::Server::new_async().await;
+        // Created first so requests carrying the tenant header match it
+        let tenant = server
+            .mock("GET", "/models")
+            .match_header("x-tenant-id", "acme")
+            .match_header("x-source", "derived")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let plain = server
+            .mock("GET", "/models")
+            .match_header("x-tenant-id", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .header("x-source", "builder")
+            .retry_config(RetryConfig::new())
+            .build()
+            .unwrap();
+        let derived = client
+            .with(|handle| {
+                handle
+                    .header("x-tenant-id", "acme")
+                    .header("x-source", "derived")
+                    .default_chat_model("llama-3.3-70b")
+                    .no_retries()
+            })
+            .unwrap();
+        
+        derived.get::<serde_json::Value>("models").await.unwrap();
+        ModelsApi::list_models(&derived).await.unwrap();
+        client.get::<serde_json::Value>("models").await.unwrap();
+        tenant.assert_async().await;
+        plain.assert_async().await;
+        
+        assert_eq!(derived.default_chat_model().unwrap(), "llama-3.3-70b");
+        assert!(client.default_chat_model().is_err());
+        assert!(derived.retry_config().is_none());
+        assert!(client.retry_config().is_some());
+        assert_eq!(client.connection_stats().total_requests, 3);
+        assert!(client.derive().header("bad header", "value").build().is_err());
+    }
 }
@@ -1,20 +1,60 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use crate::endpoints::EndpointPaths;
 use crate::error::{VeniceError, VeniceResult};
+use crate::http::RequestSigner;
+use crate::key_pool::KeyPool;
+use crate::latency::LatencyConfig;
+use crate::request_options::resolve_base_url;
+use crate::secret::SecretString;
 
 /// Default base URL for the Venice.ai API
 pub const DEFAULT_BASE_URL: &str = "https://api.venice.ai/api/v1";
 
+/// Prefix of the headers carrying feature flags, followed by the flag name
+pub const FEATURE_FLAG_HEADER_PREFIX: &str = "X-Venice-Feature-";
+
 /// Configuration for the Venice.ai API client
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
     /// Base URL for the API
     pub base_url: String,
-    /// API key for authentication
-    pub api_key: String,
+    /// API key for authentication, redacted in `Debug` output
+    pub api_key: SecretString,
     /// Custom headers to include in all requests
     pub custom_headers: HeaderMap,
     /// Timeout in seconds for requests
     pub timeout_secs: Option<u64>,
+    /// Drop response fields that fail to deserialize instead of failing the call
+    pub lenient_parsing: bool,
+    /// Pool of API keys used instead of `api_key`, with failover between them
+    pub key_pool: Option<Arc<KeyPool>>,
+    /// Share one request between identical GET calls made while it is in flight
+    pub coalesce_requests: bool,
+    /// Adds signature headers to requests sent through the API traits
+    pub request_signer: Option<Arc<dyn RequestSigner>>,
+    /// Gzip JSON request bodies of at least this many bytes
+    pub request_compression_threshold: Option<usize>,
+    /// Paths that replace endpoints in request URLs, for proxies
+    pub endpoint_paths: EndpointPaths,
+    /// Base URLs that [`RequestOptions::base_url_override`](crate::RequestOptions::base_url_override) may send requests to
+    pub allowed_base_urls: Vec<String>,
+    /// Model used by chat requests that do not name one
+    pub default_chat_model: Option<String>,
+    /// Model used by image requests that do not name one
+    pub default_image_model: Option<String>,
+    /// Cap on the spend of the client in US dollars, see [`SpendGuard`](crate::SpendGuard)
+    pub max_spend_usd: Option<f64>,
+    /// Cap on the spend of the client in Venice Compute Units, see [`SpendGuard`](crate::SpendGuard)
+    pub max_spend_vcu: Option<f64>,
+    /// Track latencies per endpoint, see [`LatencyTracker`](crate::LatencyTracker)
+    pub latency_tracking: Option<LatencyConfig>,
+    /// Feature flags sent with every request as `X-Venice-Feature-<name>` headers
+    pub feature_flags: HashMap<String, String>,
+    /// Largest JSON request body sent, in bytes, after compression
+    pub max_request_bytes: Option<usize>,
 }
 
 impl ClientConfig {
@@ -22,9 +62,23 @@ impl ClientConfig {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
-            api_key: api_key.into(),
+            api_key: SecretString::new(api_key),
             custom_headers: HeaderMap::new(),
             timeout_secs: None,
+            lenient_parsing: false,
+            key_pool: None,
+            coalesce_requests: true,
+            request_signer: None,
+            request_compression_threshold: None,
+            endpoint_paths: EndpointPaths::default(),
+            allowed_base_urls: Vec::new(),
+            default_chat_model: None,
+            default_image_model: None,
+            max_spend_usd: None,
+            max_spend_vcu: None,
+            latency_tracking: None,
+            feature_flags: HashMap::new(),
+            max_request_bytes: None,
         }
     }
 
@@ -40,14 +94,153 @@ impl ClientConfig {
         self
     }
 
+    /// Set whether response fields that fail to deserialize are dropped instead of failing the call
+    ///
+    /// Dropped fields are logged and reported to
+    /// [`UsageObserver::on_parse_warning`](crate::UsageObserver::on_parse_warning).
+    pub fn with_lenient_parsing(mut self, lenient: bool) -> Self {
+        self.lenient_parsing = lenient;
+        self
+    }
+
+    /// Send requests with the keys of a pool instead of the single API key
+    ///
+    /// The pool is shared by every API of the client. A key that is rate limited or
+    /// rejected is skipped and the request is sent again with the next key.
+    pub fn with_key_pool(mut self, key_pool: KeyPool) -> Self {
+        self.key_pool = Some(Arc::new(key_pool));
+        self
+    }
+
+    /// Set whether identical GET calls made while one is in flight share its response
+    ///
+    /// Enabled by default, so concurrent calls such as `list_models` send a single request.
+    pub fn with_request_coalescing(mut self, enabled: bool) -> Self {
+        self.coalesce_requests = enabled;
+        self
+    }
+
+    /// Sign requests sent through the API traits, such as [`ChatApi`](crate::traits::chat::ChatApi)
+    ///
+    /// Use an empty API key to authenticate with the signature alone.
+    pub fn with_request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+
+    /// Gzip JSON request bodies of at least `threshold` bytes
+    ///
+    /// Worth enabling when sending long chat histories. Responses are decompressed whether
+    /// or not this is set; see [`Client::compression_stats`](crate::Client::compression_stats)
+    /// for the bandwidth saved.
+    pub fn with_request_compression(mut self, threshold: usize) -> Self {
+        self.request_compression_threshold = Some(threshold);
+        self
+    }
+
+    /// Send requests for an endpoint to another path under the base URL
+    ///
+    /// For proxies that serve an endpoint elsewhere, e.g.
+    /// `with_endpoint_path(endpoints::IMAGE_GENERATE, "proxy/images")`.
+    /// See [`EndpointPaths`].
+    pub fn with_endpoint_path(mut self, endpoint: impl Into<String>, path: impl Into<String>) -> Self {
+        self.endpoint_paths.insert(endpoint, path);
+        self
+    }
+
+    /// Set the model used by chat requests that do not name one
+    ///
+    /// See [`ChatCompletionBuilder::default_model`](crate::traits::chat::ChatCompletionBuilder::default_model).
+    pub fn with_default_chat_model(mut self, model: impl Into<String>) -> Self {
+        self.default_chat_model = Some(model.into());
+        self
+    }
+
+    /// Set the model used by image requests that do not name one
+    ///
+    /// See [`ImageGenerateBuilder::default_model`](crate::traits::image::ImageGenerateBuilder::default_model).
+    pub fn with_default_image_model(mut self, model: impl Into<String>) -> Self {
+        self.default_image_model = Some(model.into());
+        self
+    }
+
+    /// Cap the spend of the client in US dollars
+    ///
+    /// Once the cap is reached, requests fail with
+    /// [`VeniceError::BudgetExceeded`] until the client's
+    /// [`SpendGuard`](crate::SpendGuard) is reset.
+    pub fn with_max_spend_usd(mut self, max_spend_usd: f64) -> Self {
+        self.max_spend_usd = Some(max_spend_usd);
+        self
+    }
+
+    /// Cap the spend of the client in Venice Compute Units
+    ///
+    /// See [`ClientConfig::with_max_spend_usd`].
+    pub fn with_max_spend_vcu(mut self, max_spend_vcu: f64) -> Self {
+        self.max_spend_vcu = Some(max_spend_vcu);
+        self
+    }
+
+    /// Track the latency of every endpoint called through the API traits
+    ///
+    /// Read the percentiles with [`Client::latency_stats`](crate::Client::latency_stats).
+    /// With [`LatencyConfig::adaptive_timeouts`], endpoints that get slow have their
+    /// timeout extended instead of failing; this needs a timeout to extend.
+    pub fn with_latency_tracking(mut self, config: LatencyConfig) -> Self {
+        self.latency_tracking = Some(config);
+        self
+    }
+
+    /// Allow single calls to be sent to another base URL
+    ///
+    /// Requests carry the API key of the client, so only URLs added here can be chosen
+    /// with [`RequestOptions::base_url_override`](crate::RequestOptions::base_url_override).
+    pub fn with_allowed_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.allowed_base_urls.push(base_url.into());
+        self
+    }
+
+    /// Build the URL of an endpoint, applying path and base URL overrides
+    pub(crate) fn endpoint_url(&self, endpoint: &str) -> VeniceResult<url::Url> {
+        let base_url = resolve_base_url(&self.base_url, &self.allowed_base_urls)?;
+        crate::http::build_url(&base_url, &self.endpoint_paths.resolve(endpoint))
+    }
+
+    /// Refuse to send request bodies larger than `max_bytes`
+    ///
+    /// Long chat histories and base64 images can outgrow what the API accepts; with a
+    /// limit, such requests fail locally with [`VeniceError::PayloadTooLarge`] instead of
+    /// uploading megabytes only to be rejected. JSON bodies are measured as they are sent,
+    /// after compression; of multipart uploads, the images sent for upscaling are checked.
+    pub fn with_max_request_size(mut self, max_bytes: usize) -> Self {
+        self.max_request_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Turn on a feature flag for every request, such as a beta feature of the API
+    ///
+    /// The flag is sent as an `X-Venice-Feature-<name>` header, which
+    /// [`RequestOptions::feature_flag`](crate::RequestOptions::feature_flag) can override
+    /// for single calls. Invalid names or values are reported when the client is built.
+    pub fn with_feature_flag(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.feature_flags.insert(name.into(), value.into());
+        self
+    }
+
+    /// Get the headers carrying the feature flags
+    pub(crate) fn feature_flag_headers(&self) -> VeniceResult<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.feature_flags {
+            let (name, value) = parse_feature_flag(name, value)?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
     /// Add a custom header
     pub fn with_header(mut self, name: &str, value: &str) -> VeniceResult<Self> {
-        let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
-            .map_err(|_| VeniceError::InvalidInput(format!("Invalid header name: {}", name)))?;
-        
-        let header_value = HeaderValue::from_str(value)
-            .map_err(|_| VeniceError::InvalidInput(format!("Invalid header value: {}", value)))?;
-        
+        let (header_name, header_value) = parse_header(name, value)?;
         self.custom_headers.insert(header_name, header_value);
         Ok(self)
     }
@@ -59,7 +252,7 @@ impl ClientConfig {
         // Add authorization header
         headers.insert(
             AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).map_err(|_| {
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key.expose_secret())).map_err(|_| {
                 VeniceError::InvalidInput("Invalid API key format".to_string())
             })?,
         );
@@ -81,6 +274,28 @@ impl ClientConfig {
             headers.insert(name.clone(), value.clone());
         }
         
+        // Add feature flag headers
+        headers.extend(self.feature_flag_headers()?);
+        
         Ok(headers)
     }
-}
\ No newline at end of file
+}
+
+/// Parse a custom header, failing with [`VeniceError::InvalidInput`] if it is invalid
+pub(crate) fn parse_header(name: &str, value: &str) -> VeniceResult<(reqwest::header::HeaderName, HeaderValue)> {
+    let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| VeniceError::InvalidInput(format!("Invalid header name: {}", name)))?;
+    
+    let header_value = HeaderValue::from_str(value)
+        .map_err(|_| VeniceError::InvalidInput(format!("Invalid header value: {}", value)))?;
+    
+    Ok((header_name, header_value))
+}
+
+/// Parse a feature flag into its header, failing with [`VeniceError::InvalidInput`] if it is invalid
+pub(crate) fn parse_feature_flag(name: &str, value: &str) -> VeniceResult<(reqwest::header::HeaderName, HeaderValue)> {
+    if name.is_empty() {
+        return Err(VeniceError::InvalidInput("Feature flag name cannot be empty".to_string()));
+    }
+    parse_header(&format!("{}{}", FEATURE_FLAG_HEADER_PREFIX, name), value)
+}
@@ -0,0 +1,162 @@
+//! Content policy enforcement
+//!
+//! A [`ContentPolicy`] registered on a [`crate::Client`] is applied to every chat and
+//! image generation request the client sends, so organisation-wide guardrails can be
+//! configured in one place instead of at each call site.
+
+use crate::chat::ChatCompletionRequest;
+use crate::image::ImageGenerateRequest;
+use crate::models::chat::ChatCompletionRequest as ModelsChatCompletionRequest;
+use crate::traits::chat::ChatCompletionRequest as TraitsChatCompletionRequest;
+use crate::traits::image::ImageGenerateRequest as TraitsImageGenerateRequest;
+
+/// Guardrails applied to outgoing requests
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::{Client, ContentPolicy};
+///
+/// let policy = ContentPolicy::new()
+///     .force_safe_mode(true)
+///     .moderation_prompt("Refuse to produce hateful or sexual content.");
+///
+/// let client = Client::new("your-api-key").unwrap().with_content_policy(policy);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContentPolicy {
+    /// Force `safe_mode=true` on all image generation requests
+    pub force_safe_mode: bool,
+    /// System prompt prepended to every chat request
+    pub moderation_prompt: Option<String>,
+}
+
+impl ContentPolicy {
+    /// Create a new content policy that does not change requests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether to force safe mode on image generation requests
+    pub fn force_safe_mode(mut self, force_safe_mode: bool) -> Self {
+        self.force_safe_mode = force_safe_mode;
+        self
+    }
+
+    /// Set the moderation system prompt prepended to chat requests
+    pub fn moderation_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.moderation_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Prepend the moderation prompt to a list of messages
+    ///
+    /// The prompt is not added again if the conversation already starts with it,
+    /// so applying the policy twice leaves the request unchanged.
+    fn inject_prompt<M>(
+        &self,
+        messages: &mut Vec<M>,
+        content: impl Fn(&M) -> (&str, &str),
+        system: impl FnOnce(String) -> M,
+    ) {
+        if let Some(prompt) = &self.moderation_prompt {
+            let already_applied = messages
+                .first()
+                .map(|m| content(m) == ("system", prompt.as_str()))
+                .unwrap_or(false);
+            if !already_applied {
+                messages.insert(0, system(prompt.clone()));
+            }
+        }
+    }
+}
+
+/// Requests that a content policy can be applied to
+pub(crate) trait ApplyContentPolicy {
+    /// Apply the policy to the request in place
+    fn apply_content_policy(&mut self, policy: &ContentPolicy);
+}
+
+impl ApplyContentPolicy for ChatCompletionRequest {
+    fn apply_content_policy(&mut self, policy: &ContentPolicy) {
+        policy.inject_prompt(
+            &mut self.messages,
+            |m| (m.role.as_str(), m.content.as_str()),
+            crate::chat::ChatMessage::system,
+        );
+    }
+}
+
+impl ApplyContentPolicy for ModelsChatCompletionRequest {
+    fn apply_content_policy(&mut self, policy: &ContentPolicy) {
+        policy.inject_prompt(
+            &mut self.messages,
+            |m| (m.role.as_str(), m.content.as_str()),
+            crate::models::chat::ChatMessage::system,
+        );
+    }
+}
+
+impl ApplyContentPolicy for TraitsChatCompletionRequest {
+    fn apply_content_policy(&mut self, policy: &ContentPolicy) {
+        policy.inject_prompt(
+            &mut self.messages,
+            |m| (m.role.as_str(), m.content.as_str()),
+            crate::traits::chat::ChatMessage::system,
+        );
+    }
+}
+
+impl ApplyContentPolicy for ImageGenerateRequest {
+    fn apply_content_policy(&mut self, policy: &ContentPolicy) {
+        if policy.force_safe_mode {
+            self.safe_mode = Some(true);
+        }
+    }
+}
+
+impl ApplyContentPolicy for TraitsImageGenerateRequest {
+    fn apply_content_policy(&mut self, policy: &ContentPolicy) {
+        if policy.force_safe_mode {
+            self.safe_mode = Some(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::ChatCompletionRequestBuilder;
+    use crate::image::ImageGenerateRequestBuilder;
+
+    #[test]
+    fn test_moderation_prompt_is_prepended_once() {
+        let policy = ContentPolicy::new().moderation_prompt("Be safe.");
+        let mut request = ChatCompletionRequestBuilder::new("llama-3.3-70b")
+            .add_system_message("You are helpful.")
+            .add_user_message("Hi")
+            .build();
+
+        request.apply_content_policy(&policy);
+        request.apply_content_policy(&policy);
+
+        assert_eq!(request.messages.len(), 3);
+        assert_eq!(request.messages[0].content, "Be safe.");
+        assert_eq!(request.messages[1].content, "You are helpful.");
+    }
+
+    #[test]
+    fn test_safe_mode_is_forced() {
+        let policy = ContentPolicy::new().force_safe_mode(true);
+        let mut request = ImageGenerateRequestBuilder::new("fluently-xl", "A lighthouse")
+            .with_safe_mode(false)
+            .build();
+
+        request.apply_content_policy(&policy);
+        assert_eq!(request.safe_mode, Some(true));
+
+        let mut request = ImageGenerateRequestBuilder::new("fluently-xl", "A lighthouse").build();
+        request.apply_content_policy(&ContentPolicy::new());
+        assert_eq!(request.safe_mode, None);
+    }
+}
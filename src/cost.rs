@@ -0,0 +1,374 @@
+//! Cost estimation and accounting
+//!
+//! [`CostCalculator`] turns token counts into spend using the per-model pricing
+//! returned by `list_models()`. [`CostTracker`] accumulates the spend of every
+//! chat completion a client makes, and can be attached to a [`crate::Client`] so
+//! accounting does not depend on each call site remembering to record usage.
+
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::chat::ChatCompletionRequest;
+use crate::error::RateLimitInfo;
+use crate::models::list::{Model, ModelPricing};
+use crate::observer::{RequestInfo, UsageObserver};
+use crate::traits::chat::ChatCompletionUsage;
+
+/// Rough number of characters per token, used for pre-flight estimates
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens added by the chat format for each message
+const TOKENS_PER_MESSAGE: u32 = 4;
+
+/// An amount of spend
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Cost {
+    /// Cost in US dollars
+    pub usd: f64,
+    /// Cost in Venice Compute Units, if a VCU exchange rate is configured
+    pub vcu: Option<f64>,
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, other: Cost) -> Cost {
+        let vcu = match (self.vcu, other.vcu) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        Cost {
+            usd: self.usd + other.usd,
+            vcu,
+        }
+    }
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, other: Cost) {
+        *self = *self + other;
+    }
+}
+
+/// A pre-flight estimate of the cost of a chat completion request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    /// Estimated number of prompt tokens
+    pub prompt_tokens: u32,
+    /// Upper bound on completion tokens, from `max_tokens` or `max_completion_tokens`
+    pub max_completion_tokens: Option<u32>,
+    /// Estimated cost of the prompt
+    pub prompt_cost: Cost,
+    /// Cost if the full completion budget is used, or just the prompt cost if no budget is set
+    pub max_cost: Cost,
+}
+
+/// Converts token usage into cost using per-model pricing
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::CostCalculator;
+/// use venice_ai_api_sdk_rust::models::list::ModelPricing;
+/// use venice_ai_api_sdk_rust::traits::chat::ChatCompletionUsage;
+///
+/// let calculator = CostCalculator::new().with_pricing(
+///     "llama-3.3-70b",
+///     ModelPricing { prompt: Some(0.0007), completion: Some(0.0028) },
+/// );
+///
+/// let usage = ChatCompletionUsage { prompt_tokens: 1000, completion_tokens: 500, total_tokens: 1500 };
+/// let cost = calculator.cost("llama-3.3-70b", &usage).unwrap();
+/// assert!((cost.usd - 0.0021).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CostCalculator {
+    /// Pricing keyed by model ID
+    pricing: HashMap<String, ModelPricing>,
+    /// Exchange rate from USD to VCU
+    vcu_per_usd: Option<f64>,
+}
+
+impl CostCalculator {
+    /// Create a calculator without any pricing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a calculator from the models returned by `list_models()`
+    ///
+    /// Models without pricing information are skipped.
+    pub fn from_models<'a>(models: impl IntoIterator<Item = &'a Model>) -> Self {
+        let pricing = models
+            .into_iter()
+            .filter_map(|model| model.pricing.clone().map(|pricing| (model.id.clone(), pricing)))
+            .collect();
+        Self {
+            pricing,
+            vcu_per_usd: None,
+        }
+    }
+
+    /// Set the pricing of a model
+    pub fn with_pricing(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.pricing.insert(model.into(), pricing);
+        self
+    }
+
+    /// Set the USD to VCU exchange rate, enabling VCU amounts in results
+    pub fn vcu_per_usd(mut self, rate: f64) -> Self {
+        self.vcu_per_usd = Some(rate);
+        self
+    }
+
+    /// Get the pricing of a model
+    pub fn pricing(&self, model: &str) -> Option<&ModelPricing> {
+        self.pricing.get(model)
+    }
+
+    /// Calculate the cost of the given token counts, or `None` if the model has no pricing
+    pub fn cost_for_tokens(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) -> Option<Cost> {
+        let pricing = self.pricing.get(model)?;
+        let usd = per_thousand(pricing.prompt, prompt_tokens)
+            + per_thousand(pricing.completion, completion_tokens);
+        Some(Cost {
+            usd,
+            vcu: self.vcu_per_usd.map(|rate| usd * rate),
+        })
+    }
+
+    /// Calculate the cost of a completed request, or `None` if the model has no pricing
+    pub fn cost(&self, model: &str, usage: &ChatCompletionUsage) -> Option<Cost> {
+        self.cost_for_tokens(model, usage.prompt_tokens, usage.completion_tokens)
+    }
+
+    /// Estimate the cost of a request before sending it
+    ///
+    /// Prompt tokens are approximated from the message length, so the estimate is
+    /// only suitable for budgeting. Returns `None` if the model has no pricing.
+    pub fn estimate_cost(&self, request: &ChatCompletionRequest) -> Option<CostEstimate> {
+        let prompt_tokens = estimate_message_tokens(request.messages.iter().map(|message| message.content.as_str()));
+        let max_completion_tokens = request.max_tokens.or(request.max_completion_tokens);
+        let choices = request.n.unwrap_or(1);
+
+        let prompt_cost = self.cost_for_tokens(&request.model, prompt_tokens, 0)?;
+        let completion_tokens = max_completion_tokens.unwrap_or(0).saturating_mul(choices);
+        let max_cost = self.cost_for_tokens(&request.model, prompt_tokens, completion_tokens)?;
+
+        Some(CostEstimate {
+            prompt_tokens,
+            max_completion_tokens,
+            prompt_cost,
+            max_cost,
+        })
+    }
+}
+
+/// Price `tokens` at a per-1K-token rate
+fn per_thousand(rate: Option<f64>, tokens: u32) -> f64 {
+    rate.unwrap_or(0.0) * tokens as f64 / 1000.0
+}
+
+/// Approximate the number of prompt tokens taken by messages with the given contents
+pub(crate) fn estimate_message_tokens<'a>(contents: impl IntoIterator<Item = &'a str>) -> u32 {
+    contents
+        .into_iter()
+        .map(|content| estimate_tokens(content) + TOKENS_PER_MESSAGE)
+        .sum()
+}
+
+/// Approximate the number of tokens in a piece of text
+pub(crate) fn estimate_tokens(text: &str) -> u32 {
+    let chars = text.chars().count();
+    chars.div_ceil(CHARS_PER_TOKEN) as u32
+}
+
+/// Accumulated spend for one model
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelSpend {
+    /// Number of requests recorded
+    pub requests: u64,
+    /// Total prompt tokens
+    pub prompt_tokens: u64,
+    /// Total completion tokens
+    pub completion_tokens: u64,
+    /// Total cost of the priced requests
+    pub cost: Cost,
+}
+
+/// Running totals of spend, per model
+///
+/// A tracker covers one accounting session: attach it to a client with
+/// [`crate::Client::with_cost_tracker`] and it records the usage of every
+/// response that reports one. Use a separate tracker per session, or call
+/// [`CostTracker::reset`] to start over.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::{Client, CostCalculator, CostTracker};
+///
+/// let tracker = Arc::new(CostTracker::new(CostCalculator::new()));
+/// let client = Client::new("your-api-key").unwrap().with_cost_tracker(tracker.clone());
+///
+/// // ... make requests ...
+///
+/// println!("Spent ${:.4}", tracker.total().usd);
+/// ```
+#[derive(Debug, Default)]
+pub struct CostTracker {
+    /// Pricing used to convert usage into cost
+    calculator: CostCalculator,
+    /// Totals keyed by model ID
+    spend: RwLock<HashMap<String, ModelSpend>>,
+}
+
+impl CostTracker {
+    /// Create a tracker that prices usage with the given calculator
+    pub fn new(calculator: CostCalculator) -> Self {
+        Self {
+            calculator,
+            spend: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the calculator used by this tracker
+    pub fn calculator(&self) -> &CostCalculator {
+        &self.calculator
+    }
+
+    /// Record the usage of a completed request
+    ///
+    /// Returns the cost of the request, or `None` if the model has no pricing. The
+    /// tokens are counted either way.
+    pub fn record(&self, model: &str, usage: &ChatCompletionUsage) -> Option<Cost> {
+        let cost = self.calculator.cost(model, usage);
+        let mut spend = self.spend.write().unwrap_or_else(|e| e.into_inner());
+        let entry = spend.entry(model.to_string()).or_default();
+        entry.requests += 1;
+        entry.prompt_tokens += usage.prompt_tokens as u64;
+        entry.completion_tokens += usage.completion_tokens as u64;
+        if let Some(cost) = cost {
+            entry.cost += cost;
+        }
+        cost
+    }
+
+    /// Get the accumulated spend of a model
+    pub fn model_spend(&self, model: &str) -> Option<ModelSpend> {
+        self.spend.read().unwrap_or_else(|e| e.into_inner()).get(model).cloned()
+    }
+
+    /// Get the accumulated spend of every model
+    pub fn by_model(&self) -> HashMap<String, ModelSpend> {
+        self.spend.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Get the total cost across all models
+    pub fn total(&self) -> Cost {
+        self.spend
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .fold(Cost::default(), |total, spend| total + spend.cost)
+    }
+
+    /// Clear the accumulated totals
+    pub fn reset(&self) {
+        self.spend.write().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl UsageObserver for CostTracker {
+    fn on_response(
+        &self,
+        request: &RequestInfo,
+        usage: Option<&ChatCompletionUsage>,
+        _rate_limit: &RateLimitInfo,
+        _latency: Duration,
+    ) {
+        if let (Some(model), Some(usage)) = (request.model.as_deref(), usage) {
+            self.record(model, usage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::ChatCompletionRequestBuilder;
+
+    fn calculator() -> CostCalculator {
+        CostCalculator::new()
+            .with_pricing(
+                "llama-3.3-70b",
+                ModelPricing {
+                    prompt: Some(0.001),
+                    completion: Some(0.002),
+                },
+            )
+            .vcu_per_usd(10.0)
+    }
+
+    fn usage(prompt_tokens: u32, completion_tokens: u32) -> ChatCompletionUsage {
+        ChatCompletionUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+
+    #[test]
+    fn test_cost() {
+        let calculator = calculator();
+        let cost = calculator.cost("llama-3.3-70b", &usage(2000, 500)).unwrap();
+        assert!((cost.usd - 0.003).abs() < 1e-9);
+        assert!((cost.vcu.unwrap() - 0.03).abs() < 1e-9);
+        assert!(calculator.cost("unknown", &usage(2000, 500)).is_none());
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let request = ChatCompletionRequestBuilder::new("llama-3.3-70b")
+            .add_user_message("a".repeat(400))
+            .with_max_tokens(1000)
+            .build();
+
+        let estimate = calculator().estimate_cost(&request).unwrap();
+        assert_eq!(estimate.prompt_tokens, 100 + TOKENS_PER_MESSAGE);
+        assert_eq!(estimate.max_completion_tokens, Some(1000));
+        assert!((estimate.prompt_cost.usd - 0.000104).abs() < 1e-9);
+        assert!((estimate.max_cost.usd - 0.002104).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tracker_accumulates_per_model() {
+        let tracker = CostTracker::new(calculator());
+        let info = RequestInfo {
+            method: reqwest::Method::POST,
+            endpoint: "chat/completions".to_string(),
+            model: Some("llama-3.3-70b".to_string()),
+            user: None,
+            tags: Vec::new(),
+            payload_sha256: None,
+            status: None,
+        };
+        let rate_limit = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+
+        tracker.on_response(&info, Some(&usage(1000, 1000)), &rate_limit, Duration::ZERO);
+        tracker.on_response(&info, Some(&usage(1000, 0)), &rate_limit, Duration::ZERO);
+        tracker.record("unpriced-model", &usage(10, 10));
+
+        let spend = tracker.model_spend("llama-3.3-70b").unwrap();
+        assert_eq!(spend.requests, 2);
+        assert_eq!(spend.prompt_tokens, 2000);
+        assert!((tracker.total().usd - 0.004).abs() < 1e-9);
+        assert_eq!(tracker.by_model()["unpriced-model"].cost, Cost::default());
+
+        tracker.reset();
+        assert_eq!(tracker.total(), Cost::default());
+    }
+}
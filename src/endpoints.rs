@@ -0,0 +1,96 @@
+//! API endpoint paths
+//!
+//! Every request names its endpoint with one of these constants, relative to the base
+//! URL. Rate limits, observers and audit records use the same names. A proxy that
+//! serves an endpoint under another path can be targeted with [`EndpointPaths`],
+//! which changes only the URL requests are sent to.
+
+use std::collections::HashMap;
+
+/// Chat completions
+pub const CHAT_COMPLETIONS: &str = "chat/completions";
+/// Model feature suffixes for chat models
+pub const CHAT_MODEL_FEATURE_SUFFIX: &str = "chat/model_feature_suffix";
+/// Image generation
+pub const IMAGE_GENERATE: &str = "image/generate";
+/// Image upscaling
+pub const IMAGE_UPSCALE: &str = "image/upscale";
+/// Image style presets
+pub const IMAGE_STYLES: &str = "image/styles";
+/// Model listing
+pub const MODELS: &str = "models";
+/// Model traits
+pub const MODELS_TRAITS: &str = "models/traits";
+/// Mapping of compatible model names
+pub const MODELS_COMPATIBILITY_MAPPING: &str = "models/compatibility_mapping";
+/// API key management
+pub const API_KEYS: &str = "api_keys";
+/// Web3 API key generation
+pub const API_KEYS_GENERATE_WEB3_KEY: &str = "api_keys/generate_web3_key";
+
+/// Paths that replace endpoints in request URLs
+///
+/// An override applies to the endpoint exactly as named, such as [`IMAGE_GENERATE`];
+/// query strings are kept. Requests still report the original endpoint name.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::endpoints::{self, EndpointPaths};
+///
+/// let paths = EndpointPaths::new().set(endpoints::IMAGE_GENERATE, "proxy/images");
+/// assert_eq!(paths.resolve("image/generate"), "proxy/images");
+/// assert_eq!(paths.resolve("models?type=image"), "models?type=image");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointPaths {
+    overrides: HashMap<String, String>,
+}
+
+impl EndpointPaths {
+    /// Create an empty set of overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send requests for `endpoint` to `path` instead
+    pub fn set(mut self, endpoint: impl Into<String>, path: impl Into<String>) -> Self {
+        self.insert(endpoint, path);
+        self
+    }
+
+    /// Send requests for `endpoint` to `path` instead
+    pub fn insert(&mut self, endpoint: impl Into<String>, path: impl Into<String>) {
+        self.overrides.insert(endpoint.into(), path.into());
+    }
+
+    /// Check whether no endpoint is overridden
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Get the path requests for an endpoint are sent to
+    pub fn resolve(&self, endpoint: &str) -> String {
+        let (path, query) = match endpoint.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (endpoint, None),
+        };
+        match (self.overrides.get(path), query) {
+            (Some(path), Some(query)) => format!("{}?{}", path, query),
+            (Some(path), None) => path.clone(),
+            (None, _) => endpoint.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_keeps_query() {
+        let paths = EndpointPaths::new().set(MODELS, "v2/models");
+        assert_eq!(paths.resolve("models?type=image"), "v2/models?type=image");
+        assert_eq!(paths.resolve(MODELS_TRAITS), MODELS_TRAITS);
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 /// Represents all possible errors that can occur when using the Venice.ai API SDK
@@ -19,9 +21,23 @@ pub enum VeniceError {
     #[error("HTTP error: {0}")]
     HttpError(#[from] reqwest::Error),
 
+    /// Error occurred while reading or writing local files
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     /// Error occurred while parsing the response
-    #[error("Failed to parse response: {0}")]
-    ParseError(String),
+    #[error(
+        "Failed to parse response: {message}{}",
+        .path.as_ref().map(|path| format!(" (at {})", path)).unwrap_or_default()
+    )]
+    ParseError {
+        /// What went wrong
+        message: String,
+        /// Path of the field that failed to deserialize, such as `choices[0].message`
+        path: Option<String>,
+        /// The raw response body, truncated to [`MAX_PARSE_ERROR_BODY_LEN`] bytes
+        body: Option<String>,
+    },
 
     /// Error occurred due to invalid input
     #[error("Invalid input: {0}")]
@@ -39,13 +55,362 @@ pub enum VeniceError {
     #[error("Invalid webhook signature: {0}")]
     InvalidWebhookSignature(String),
 
+    /// A tool called by the model failed, or the tool loop did not finish
+    #[error("Tool error: {0}")]
+    ToolError(String),
+
+    /// The client has been shut down and no longer accepts requests
+    #[error("Client is shut down")]
+    ClientShutdown,
+
+    /// The spend of the client reached a cap of its configuration
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
+
+    /// A request body was larger than the client allows, so it was not sent
+    ///
+    /// See [`ClientConfig::with_max_request_size`](crate::ClientConfig::with_max_request_size).
+    #[error("Request payload of {size} bytes exceeds the limit of {limit} bytes")]
+    PayloadTooLarge {
+        /// Size of the body as it would have been sent, in bytes
+        size: usize,
+        /// Largest body the client sends, in bytes
+        limit: usize,
+    },
+
     /// Error occurred due to an unknown cause
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    /// An error together with details about the request that caused it
+    ///
+    /// Use [`VeniceError::root`] to match on the underlying error.
+    #[error("{source} ({context})")]
+    WithContext {
+        /// The underlying error
+        source: Box<VeniceError>,
+        /// Details about the failed request
+        context: ErrorContext,
+    },
+}
+
+/// Maximum number of bytes of a response body kept in a [`VeniceError::ParseError`]
+pub const MAX_PARSE_ERROR_BODY_LEN: usize = 4096;
+
+impl VeniceError {
+    /// Create a parse error without a field path or response body
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        VeniceError::ParseError {
+            message: message.into(),
+            path: None,
+            body: None,
+        }
+    }
+
+    /// Create a parse error for a response body that failed to deserialize
+    ///
+    /// The body is truncated to [`MAX_PARSE_ERROR_BODY_LEN`] bytes.
+    pub(crate) fn parse_body_error(
+        err: serde_path_to_error::Error<serde_json::Error>,
+        body: &[u8],
+    ) -> Self {
+        let path = err.path().to_string();
+        let mut body = String::from_utf8_lossy(body).into_owned();
+        if body.len() > MAX_PARSE_ERROR_BODY_LEN {
+            let mut end = MAX_PARSE_ERROR_BODY_LEN;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+            body.push_str("...");
+        }
+        VeniceError::ParseError {
+            message: err.into_inner().to_string(),
+            path: (path != ".").then_some(path),
+            body: Some(body),
+        }
+    }
+
+    /// Get the underlying error, looking through any request context
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use venice_ai_api_sdk_rust::{ErrorContext, VeniceError};
+    ///
+    /// let err = VeniceError::RateLimitExceeded("slow down".to_string())
+    ///     .with_context(ErrorContext::request("POST", "chat/completions"));
+    ///
+    /// assert!(matches!(err.root(), VeniceError::RateLimitExceeded(_)));
+    /// assert_eq!(err.to_string(), "Rate limit exceeded: slow down (POST chat/completions)");
+    /// ```
+    pub fn root(&self) -> &VeniceError {
+        match self {
+            VeniceError::WithContext { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Take the underlying error, discarding any request context
+    pub fn into_root(self) -> VeniceError {
+        match self {
+            VeniceError::WithContext { source, .. } => source.into_root(),
+            error => error,
+        }
+    }
+
+    /// Get the details about the request that caused the error, if known
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            VeniceError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Get the request ID assigned by the API to the failed request, if known
+    pub fn request_id(&self) -> Option<&str> {
+        self.context().and_then(|context| context.request_id.as_deref())
+    }
+
+    /// Get the HTTP status a service built on the SDK should answer with for this error
+    ///
+    /// API errors keep the status of the API, except server errors, which become
+    /// 502 Bad Gateway unless the API was unavailable (503) or timed out (504).
+    /// Transport failures become 504 on timeouts and 502 otherwise, and local errors map
+    /// to their nearest status, such as 400 for invalid input and 402 for budget caps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest::StatusCode;
+    /// use venice_ai_api_sdk_rust::VeniceError;
+    ///
+    /// let err = VeniceError::RateLimitExceeded("slow down".to_string());
+    /// assert_eq!(err.status_hint(), StatusCode::TOO_MANY_REQUESTS);
+    ///
+    /// let err = VeniceError::ApiError {
+    ///     status: StatusCode::INTERNAL_SERVER_ERROR,
+    ///     code: "server_error".to_string(),
+    ///     message: "Internal error".to_string(),
+    /// };
+    /// assert_eq!(err.status_hint(), StatusCode::BAD_GATEWAY);
+    /// ```
+    pub fn status_hint(&self) -> reqwest::StatusCode {
+        use reqwest::StatusCode;
+
+        match self.root() {
+            VeniceError::ApiError { status, .. } => match *status {
+                StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => *status,
+                status if status.is_server_error() => StatusCode::BAD_GATEWAY,
+                status => status,
+            },
+            VeniceError::HttpError(err) if err.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
+            VeniceError::HttpError(_) | VeniceError::ParseError { .. } => StatusCode::BAD_GATEWAY,
+            VeniceError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            VeniceError::RateLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+            VeniceError::AuthenticationFailed(_) | VeniceError::InvalidWebhookSignature(_) => StatusCode::UNAUTHORIZED,
+            VeniceError::BudgetExceeded(_) => StatusCode::PAYMENT_REQUIRED,
+            VeniceError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            VeniceError::ClientShutdown => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Attach request details to the error
+    ///
+    /// Details already attached take precedence; the new context only fills in
+    /// missing fields. An empty context leaves the error unchanged.
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            VeniceError::WithContext { source, context: existing } => VeniceError::WithContext {
+                source,
+                context: existing.or(context),
+            },
+            error if context.is_empty() => error,
+            error => VeniceError::WithContext {
+                source: Box::new(error),
+                context,
+            },
+        }
+    }
+
+    /// Copy the error for another caller waiting on the same request
+    ///
+    /// HTTP and I/O errors cannot be cloned, so their copies keep only the message.
+    pub(crate) fn duplicate(&self) -> VeniceError {
+        match self {
+            VeniceError::ApiError { status, code, message } => VeniceError::ApiError {
+                status: *status,
+                code: code.clone(),
+                message: message.clone(),
+            },
+            VeniceError::HttpError(err) => VeniceError::Unknown(format!("HTTP error: {}", err)),
+            VeniceError::IoError(err) => VeniceError::IoError(std::io::Error::new(err.kind(), err.to_string())),
+            VeniceError::ParseError { message, path, body } => VeniceError::ParseError {
+                message: message.clone(),
+                path: path.clone(),
+                body: body.clone(),
+            },
+            VeniceError::InvalidInput(message) => VeniceError::InvalidInput(message.clone()),
+            VeniceError::RateLimitExceeded(message) => VeniceError::RateLimitExceeded(message.clone()),
+            VeniceError::AuthenticationFailed(message) => VeniceError::AuthenticationFailed(message.clone()),
+            VeniceError::InvalidWebhookSignature(message) => VeniceError::InvalidWebhookSignature(message.clone()),
+            VeniceError::ToolError(message) => VeniceError::ToolError(message.clone()),
+            VeniceError::ClientShutdown => VeniceError::ClientShutdown,
+            VeniceError::BudgetExceeded(message) => VeniceError::BudgetExceeded(message.clone()),
+            VeniceError::PayloadTooLarge { size, limit } => VeniceError::PayloadTooLarge {
+                size: *size,
+                limit: *limit,
+            },
+            VeniceError::Unknown(message) => VeniceError::Unknown(message.clone()),
+            VeniceError::WithContext { source, context } => VeniceError::WithContext {
+                source: Box::new(source.duplicate()),
+                context: context.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<&VeniceError> for http::StatusCode {
+    fn from(err: &VeniceError) -> Self {
+        http::StatusCode::from_u16(err.status_hint().as_u16()).unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
 }
 
+#[cfg(feature = "http")]
+impl From<VeniceError> for http::StatusCode {
+    fn from(err: VeniceError) -> Self {
+        http::StatusCode::from(&err)
+    }
+}
+
+/// Details about the request that caused an error
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// HTTP method of the request
+    pub method: Option<String>,
+    /// API endpoint of the request
+    pub endpoint: Option<String>,
+    /// Request ID assigned by the API
+    pub request_id: Option<String>,
+    /// Number of attempts made, if the request was retried
+    pub attempts: Option<u32>,
+    /// How long the API asked the client to wait, from the `Retry-After` header or
+    /// the time until the request limit resets
+    pub retry_after: Option<Duration>,
+}
+
+impl ErrorContext {
+    /// Create an empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context for a request to an endpoint
+    pub fn request(method: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        Self {
+            method: Some(method.into()),
+            endpoint: Some(endpoint.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create a context holding the request ID found in response headers, if any
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        Self {
+            request_id: request_id_from_headers(headers).map(str::to_string),
+            retry_after: retry_after_from_headers(headers),
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of attempts made
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    /// Check whether no details are known
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill in fields missing from this context from another one
+    fn or(self, other: ErrorContext) -> Self {
+        Self {
+            method: self.method.or(other.method),
+            endpoint: self.endpoint.or(other.endpoint),
+            request_id: self.request_id.or(other.request_id),
+            attempts: self.attempts.or(other.attempts),
+            retry_after: self.retry_after.or(other.retry_after),
+        }
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        match (&self.method, &self.endpoint) {
+            (Some(method), Some(endpoint)) => parts.push(format!("{} {}", method, endpoint)),
+            (None, Some(endpoint)) => parts.push(endpoint.clone()),
+            (Some(method), None) => parts.push(method.clone()),
+            (None, None) => {}
+        }
+        if let Some(request_id) = &self.request_id {
+            parts.push(format!("request ID {}", request_id));
+        }
+        if let Some(attempts) = self.attempts {
+            parts.push(format!("{} attempts", attempts));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// Get the request ID from response headers
+pub(crate) fn request_id_from_headers(headers: &reqwest::header::HeaderMap) -> Option<&str> {
+    ["x-request-id", "cf-ray"]
+        .iter()
+        .find_map(|name| headers.get(*name).and_then(|value| value.to_str().ok()))
+}
+
+/// Get how long to wait before sending the request again from response headers
+///
+/// A `Retry-After` header in seconds takes precedence over the request limit reset
+/// timestamp, which is only used while it is in the future.
+fn retry_after_from_headers(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    if let Some(seconds) = header("retry-after") {
+        return Some(Duration::from_secs(seconds));
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    header("x-ratelimit-reset-requests")
+        .filter(|reset| *reset > now)
+        .map(|reset| Duration::from_secs(reset - now))
+}
+
+/// Prefixes of the response headers kept in [`RateLimitInfo::raw`]
+const RATE_LIMIT_HEADER_PREFIXES: &[&str] = &["x-ratelimit-", "x-venice-balance-"];
+
+/// Headers parsed into the fields of [`RateLimitInfo`]
+const KNOWN_RATE_LIMIT_HEADERS: &[&str] = &[
+    "x-ratelimit-limit-requests",
+    "x-ratelimit-remaining-requests",
+    "x-ratelimit-reset-requests",
+    "x-ratelimit-limit-tokens",
+    "x-ratelimit-remaining-tokens",
+    "x-ratelimit-reset-tokens",
+    "x-venice-balance-vcu",
+    "x-venice-balance-usd",
+];
+
 /// Represents the rate limit information returned in the response headers
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RateLimitInfo {
     /// Total requests limit
     pub limit_requests: Option<u32>,
@@ -63,6 +428,11 @@ pub struct RateLimitInfo {
     pub balance_vcu: Option<f64>,
     /// User's USD balance
     pub balance_usd: Option<f64>,
+    /// Every `x-ratelimit-*` and `x-venice-balance-*` header of the response, keyed by
+    /// lowercase name, including headers the SDK does not parse yet
+    ///
+    /// Read through [`RateLimitInfo::header`] and [`RateLimitInfo::unknown_headers`].
+    pub(crate) raw: HashMap<String, String>,
 }
 
 impl fmt::Display for RateLimitInfo {
@@ -100,12 +470,50 @@ impl RateLimitInfo {
             reset_tokens: parse_header(headers, "x-ratelimit-reset-tokens"),
             balance_vcu: parse_header(headers, "x-venice-balance-vcu"),
             balance_usd: parse_header(headers, "x-venice-balance-usd"),
+            raw: headers
+                .iter()
+                .filter(|(name, _)| RATE_LIMIT_HEADER_PREFIXES.iter().any(|prefix| name.as_str().starts_with(prefix)))
+                .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.to_string())))
+                .collect(),
         }
     }
 
+    /// Get a rate limit header by name, such as `x-ratelimit-limit-images`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    /// use venice_ai_api_sdk_rust::RateLimitInfo;
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-ratelimit-limit-images", HeaderValue::from_static("50"));
+    /// let info = RateLimitInfo::from_headers(&headers);
+    ///
+    /// assert_eq!(info.header("X-RateLimit-Limit-Images"), Some("50"));
+    /// assert_eq!(info.parse_header::<u32>("x-ratelimit-limit-images"), Some(50));
+    /// assert_eq!(info.unknown_headers().count(), 1);
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.raw.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// Parse a rate limit header by name, `None` if it is missing or does not parse
+    pub fn parse_header<T: std::str::FromStr>(&self, name: &str) -> Option<T> {
+        self.header(name).and_then(|value| value.trim().parse().ok())
+    }
+
+    /// Iterate over the rate limit headers not parsed into the fields of this struct
+    pub fn unknown_headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.raw
+            .iter()
+            .filter(|(name, _)| !KNOWN_RATE_LIMIT_HEADERS.contains(&name.as_str()))
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
     /// Check if rate limit is exceeded
     pub fn is_rate_limited(&self) -> bool {
-        self.remaining_requests.map_or(false, |r| r == 0) || self.remaining_tokens.map_or(false, |t| t == 0)
+        self.remaining_requests == Some(0) || self.remaining_tokens == Some(0)
     }
 }
 
@@ -0,0 +1,281 @@
+//! Kotlin and Swift bindings
+//!
+//! With the `uniffi` feature, this module exports the core client operations through
+//! [uniffi](https://mozilla.github.io/uniffi-rs/) proc-macro annotations: chat
+//! completions, streamed chat completions delivered to a callback, image generation and
+//! model listing. Build the library as a `cdylib` and run `uniffi-bindgen generate`
+//! against it to get the Kotlin and Swift sources; no UDL file is needed.
+//!
+//! The exported types are flat records so they map onto plain data classes and structs.
+//! Anything not covered here can be reached through [`crate::json_api`].
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use crate::client::Client;
+use crate::error::VeniceError;
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder, ChatMessage, ChatRole};
+use crate::traits::image::{ImageApi, ImageGenerateBuilder};
+use crate::traits::models::ModelsApi;
+
+/// An error returned to the foreign caller
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ClientError {
+    /// The API answered with an error status
+    #[error("API error {status}: {message}")]
+    Api {
+        /// HTTP status code
+        status: u16,
+        /// Error message
+        message: String,
+    },
+    /// The request was rejected before it was sent
+    #[error("Invalid input: {message}")]
+    InvalidInput {
+        /// Error message
+        message: String,
+    },
+    /// The request failed for another reason, such as a network or parse error
+    #[error("{message}")]
+    Other {
+        /// Error message
+        message: String,
+    },
+}
+
+impl From<VeniceError> for ClientError {
+    fn from(err: VeniceError) -> Self {
+        let message = err.to_string();
+        match err.root() {
+            VeniceError::ApiError { status, .. } => ClientError::Api {
+                status: status.as_u16(),
+                message,
+            },
+            VeniceError::InvalidInput(_) => ClientError::InvalidInput { message },
+            _ => ClientError::Other { message },
+        }
+    }
+}
+
+/// The author of a chat message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum Role {
+    /// System message
+    System,
+    /// User message
+    User,
+    /// Assistant message
+    Assistant,
+}
+
+impl From<Role> for ChatRole {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::System => ChatRole::System,
+            Role::User => ChatRole::User,
+            Role::Assistant => ChatRole::Assistant,
+        }
+    }
+}
+
+/// A chat message
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Message {
+    /// The author of the message
+    pub role: Role,
+    /// The content of the message
+    pub content: String,
+}
+
+/// Options of a chat completion
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct ChatOptions {
+    /// Maximum number of tokens to generate
+    #[uniffi(default = None)]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    #[uniffi(default = None)]
+    pub temperature: Option<f32>,
+}
+
+/// The answer of a chat completion
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ChatReply {
+    /// Content of the first choice
+    pub content: String,
+    /// Why the model stopped
+    pub finish_reason: Option<String>,
+}
+
+/// Information about a model
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ModelInfo {
+    /// The model identifier
+    pub id: String,
+    /// Maximum context size for this model
+    pub context_size: Option<u32>,
+    /// Whether the model supports streaming
+    pub supports_streaming: bool,
+    /// Whether the model supports image generation
+    pub supports_image_generation: bool,
+    /// Whether the model supports chat completions
+    pub supports_chat_completions: bool,
+}
+
+/// Receives the content of a streamed chat completion
+///
+/// Implemented in Kotlin or Swift. Calls are made from a background thread.
+#[uniffi::export(callback_interface)]
+pub trait ChatStreamListener: Send + Sync {
+    /// Called with each piece of content as it arrives
+    fn on_delta(&self, delta: String);
+    /// Called once when the stream ends, with why the model stopped
+    fn on_complete(&self, finish_reason: Option<String>);
+}
+
+/// A Venice.ai client for Kotlin and Swift
+#[derive(Debug, uniffi::Object)]
+pub struct VeniceClient {
+    client: Client,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl VeniceClient {
+    /// Create a client, optionally with another base URL
+    #[uniffi::constructor(default(base_url = None))]
+    pub fn new(api_key: String, base_url: Option<String>) -> Result<Arc<Self>, ClientError> {
+        let mut builder = Client::builder().api_key(api_key);
+        if let Some(base_url) = base_url {
+            builder = builder.base_url(base_url);
+        }
+        Ok(Arc::new(Self { client: builder.build()? }))
+    }
+
+    /// Send a chat completion and return its first choice
+    pub async fn chat(&self, model: String, messages: Vec<Message>, options: ChatOptions) -> Result<ChatReply, ClientError> {
+        let request = chat_request(model, messages, options).build();
+        let (response, _) = ChatApi::create_chat_completion(&self.client, request).await?;
+        let choice = response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+        Ok(ChatReply {
+            content: choice.message.content,
+            finish_reason: choice.finish_reason,
+        })
+    }
+
+    /// Stream a chat completion to a listener and return the whole content
+    ///
+    /// `on_complete` is only called when the stream ends without an error.
+    pub async fn stream_chat(
+        &self,
+        model: String,
+        messages: Vec<Message>,
+        options: ChatOptions,
+        listener: Box<dyn ChatStreamListener>,
+    ) -> Result<String, ClientError> {
+        let request = chat_request(model, messages, options).stream(true).build();
+        let (mut stream, _) = ChatApi::create_streaming_chat_completion(&self.client, request).await?;
+        let mut content = String::new();
+        let mut finish_reason = None;
+        while let Some(chunk) = stream.next().await {
+            for choice in chunk?.choices.into_iter().filter(|choice| choice.index == 0) {
+                if let Some(delta) = choice.delta.content.filter(|delta| !delta.is_empty()) {
+                    content.push_str(&delta);
+                    listener.on_delta(delta);
+                }
+                if choice.finish_reason.is_some() {
+                    finish_reason = choice.finish_reason;
+                }
+            }
+        }
+        listener.on_complete(finish_reason);
+        Ok(content)
+    }
+
+    /// Generate a PNG image and return its bytes
+    pub async fn generate_image(&self, model: String, prompt: String) -> Result<Vec<u8>, ClientError> {
+        let request = ImageGenerateBuilder::new(model, prompt)
+            .format(crate::image::ImageFormat::Png)
+            .build();
+        let (response, _) = ImageApi::generate_image(&self.client, request).await?;
+        Ok(response.image_bytes(0)?)
+    }
+
+    /// List the available models
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>, ClientError> {
+        let (response, _) = ModelsApi::list_models(&self.client).await?;
+        Ok(response
+            .data
+            .into_iter()
+            .map(|model| ModelInfo {
+                id: model.id,
+                context_size: model.context_size,
+                supports_streaming: model.supports_streaming,
+                supports_image_generation: model.supports_image_generation,
+                supports_chat_completions: model.supports_chat_completions,
+            })
+            .collect())
+    }
+}
+
+/// Build a chat request from exported records
+fn chat_request(model: String, messages: Vec<Message>, options: ChatOptions) -> ChatCompletionBuilder {
+    let mut builder = messages.into_iter().fold(ChatCompletionBuilder::new(model), |builder, message| {
+        builder.add_message(ChatMessage::new(message.role.into(), message.content))
+    });
+    if let Some(max_tokens) = options.max_tokens {
+        builder = builder.max_tokens(max_tokens);
+    }
+    if let Some(temperature) = options.temperature {
+        builder = builder.temperature(temperature);
+    }
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Collect(Arc<Mutex<Vec<String>>>);
+
+    impl ChatStreamListener for Collect {
+        fn on_delta(&self, delta: String) {
+            self.0.lock().unwrap().push(delta);
+        }
+
+        fn on_complete(&self, finish_reason: Option<String>) {
+            self.0.lock().unwrap().push(format!("<{}>", finish_reason.unwrap_or_default()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_chat_calls_listener() {
+        let mut server = mockito::Server::new_async().await;
+        let body = concat!(
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":0,\"model\":\"m\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"},\"finish_reason\":\"stop\"}]}\n\n",
+        );
+        let _mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(body)
+            .create_async()
+            .await;
+        let client = VeniceClient::new("test_api_key".to_string(), Some(server.url())).unwrap();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let messages = vec![Message { role: Role::User, content: "Hi".to_string() }];
+        let content = client
+            .stream_chat("m".to_string(), messages, ChatOptions::default(), Box::new(Collect(received.clone())))
+            .await
+            .unwrap();
+        assert_eq!(content, "Hello");
+        assert_eq!(*received.lock().unwrap(), vec!["Hello", "<stop>"]);
+    }
+}
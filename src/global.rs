@@ -0,0 +1,133 @@
+//! A process-wide client
+//!
+//! With the `global` feature, small tools and tests can set up one client for the whole
+//! process and call free functions such as [`chat`] instead of passing a [`Client`]
+//! through every function. The client is set with [`init`] or [`init_with`]; otherwise
+//! it is created on first use from the `VENICE_API_KEY` environment variable.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use venice_ai_api_sdk_rust::global;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! global::init("your-api-key")?;
+//!
+//! let answer = global::chat("llama-3.3-70b", "What is the capital of France?").await?;
+//! println!("{}", answer);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::{Arc, OnceLock};
+
+use crate::client::{Client, SharedClient};
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder};
+use crate::traits::models::{ListModelsResponse, ModelsApi};
+
+/// Environment variable the client is created from when it was not initialized
+pub const API_KEY_VAR: &str = "VENICE_API_KEY";
+
+static CLIENT: OnceLock<SharedClient> = OnceLock::new();
+
+/// Set up the global client with an API key
+///
+/// Fails if the global client is already set up.
+pub fn init(api_key: impl Into<String>) -> VeniceResult<()> {
+    init_with(Client::new(api_key)?)
+}
+
+/// Use a configured client as the global client
+///
+/// Fails if the global client is already set up.
+pub fn init_with(client: Client) -> VeniceResult<()> {
+    CLIENT
+        .set(Arc::new(client))
+        .map_err(|_| VeniceError::InvalidInput("The global client is already initialized".to_string()))
+}
+
+/// Check whether the global client is set up
+pub fn is_initialized() -> bool {
+    CLIENT.get().is_some()
+}
+
+/// Get the global client, creating it from `VENICE_API_KEY` if it was not initialized
+pub fn client() -> VeniceResult<SharedClient> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client.clone());
+    }
+    let api_key = std::env::var(API_KEY_VAR)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            VeniceError::InvalidInput(format!(
+                "The global client is not initialized; call global::init or set {}",
+                API_KEY_VAR
+            ))
+        })?;
+    let client = Arc::new(Client::new(api_key)?);
+    // Another thread may have initialized the client meanwhile; its client wins
+    Ok(CLIENT.get_or_init(|| client).clone())
+}
+
+/// Send a prompt to a model and get the text of its answer
+pub async fn chat(model: &str, prompt: impl Into<String>) -> VeniceResult<String> {
+    let client = client()?;
+    let request = ChatCompletionBuilder::new(model).add_user(prompt).build();
+    let (response, _) = ChatApi::create_chat_completion(client.as_ref(), request).await?;
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content)
+        .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))
+}
+
+/// Ask the default chat model a question, see [`Client::ask`]
+pub async fn ask(question: impl Into<String>) -> VeniceResult<String> {
+    client()?.ask(question).await
+}
+
+/// Generate a PNG image with the default image model, see [`Client::imagine`]
+pub async fn imagine(prompt: impl Into<String>) -> VeniceResult<Vec<u8>> {
+    client()?.imagine(prompt).await
+}
+
+/// List the available models
+pub async fn list_models() -> VeniceResult<ListModelsResponse> {
+    let client = client()?;
+    let (response, _) = ModelsApi::list_models(client.as_ref()).await?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The only test touching the global client, since it is shared by the whole test binary
+    #[tokio::test]
+    async fn test_global_client() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "Paris"}, "finish_reason": "stop"}]
+            }"#)
+            .create_async()
+            .await;
+
+        init_with(Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap()).unwrap();
+        assert!(is_initialized());
+        assert!(matches!(init("another_key"), Err(VeniceError::InvalidInput(_))));
+
+        assert_eq!(chat("llama-3.3-70b", "Capital of France?").await.unwrap(), "Paris");
+        mock.assert_async().await;
+    }
+}
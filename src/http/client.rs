@@ -2,25 +2,210 @@
 //!
 //! This module provides a dedicated HTTP client layer that only handles HTTP communication.
 
-use reqwest::Client as ReqwestClient;
+use reqwest::{Client as ReqwestClient, Method, RequestBuilder};
 use serde::{de::DeserializeOwned, Serialize};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::endpoints::EndpointPaths;
+use crate::error::{ErrorContext, RateLimitInfo, VeniceError, VeniceResult};
+use crate::http::compression::{self, CompressionStats, JsonBody, RequestCompression};
 use crate::http::response_processor;
+use crate::http::signer::{self, RequestSigner};
+use crate::http::single_flight::SingleFlight;
+use crate::http::sse::SseStream;
 use crate::http::url;
+use crate::key_pool::{self, KeyPool};
+use crate::budget::SpendGuard;
+use crate::latency::LatencyTracker;
+use crate::lifecycle::{Connection, Lifecycle};
+use crate::observer::UsageObservers;
+use crate::request_options::{self, resolve_base_url};
+use crate::secret::SecretString;
 
 /// Configuration for the HTTP client
 #[derive(Debug, Clone)]
 pub struct HttpClientConfig {
-    /// API key for authentication
-    pub api_key: String,
+    /// API key for authentication, redacted in `Debug` output
+    pub api_key: SecretString,
     /// Base URL for the API
     pub base_url: String,
     /// Custom headers to include in all requests
     pub custom_headers: reqwest::header::HeaderMap,
     /// Timeout in seconds
     pub timeout_secs: Option<u64>,
+    /// Maximum number of idle connections kept open per host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle connection is kept open before it is closed
+    pub pool_idle_timeout_secs: Option<u64>,
+    /// Interval in seconds of TCP keepalive probes on open connections
+    pub tcp_keepalive_secs: Option<u64>,
+    /// Only use HTTP/1
+    pub http1_only: bool,
+    /// Use HTTP/2 without negotiating it first
+    pub http2_prior_knowledge: bool,
+    /// Paths that replace endpoints in request URLs, for proxies
+    pub endpoint_paths: EndpointPaths,
+    /// Base URLs that single calls may be sent to instead of `base_url`
+    pub allowed_base_urls: Vec<String>,
+}
+
+impl HttpClientConfig {
+    /// Create a builder for an HTTP client configuration
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use venice_ai_api_sdk_rust::HttpClientConfig;
+    ///
+    /// let config = HttpClientConfig::builder()
+    ///     .api_key("your-api-key")
+    ///     .header("x-request-source", "docs")
+    ///     .pool_max_idle_per_host(8)
+    ///     .tcp_keepalive_secs(30)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(config.base_url, venice_ai_api_sdk_rust::DEFAULT_BASE_URL);
+    /// ```
+    pub fn builder() -> HttpClientConfigBuilder {
+        HttpClientConfigBuilder::default()
+    }
+    
+    /// Build the URL of an endpoint, applying path and base URL overrides
+    pub(crate) fn endpoint_url(&self, endpoint: &str) -> VeniceResult<::url::Url> {
+        let base_url = resolve_base_url(&self.base_url, &self.allowed_base_urls)?;
+        url::build_url(&base_url, &self.endpoint_paths.resolve(endpoint))
+    }
+}
+
+/// Builder for [`HttpClientConfig`]
+///
+/// Values are checked by [`HttpClientConfigBuilder::build`], which reports the first problem.
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientConfigBuilder {
+    api_key: SecretString,
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout_secs: Option<u64>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout_secs: Option<u64>,
+    tcp_keepalive_secs: Option<u64>,
+    http1_only: bool,
+    http2_prior_knowledge: bool,
+    endpoint_paths: EndpointPaths,
+    allowed_base_urls: Vec<String>,
+}
+
+impl HttpClientConfigBuilder {
+    /// Set the API key; leave it empty to send no bearer token
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = SecretString::new(api_key);
+        self
+    }
+    
+    /// Set the base URL, which must be an absolute `http` or `https` URL
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+    
+    /// Add a header sent with every request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+    
+    /// Set the request timeout in seconds
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+    
+    /// Set the maximum number of idle connections kept open per host
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+    
+    /// Set how many seconds an idle connection is kept open
+    pub fn pool_idle_timeout_secs(mut self, secs: u64) -> Self {
+        self.pool_idle_timeout_secs = Some(secs);
+        self
+    }
+    
+    /// Send TCP keepalive probes on open connections at this interval in seconds
+    pub fn tcp_keepalive_secs(mut self, secs: u64) -> Self {
+        self.tcp_keepalive_secs = Some(secs);
+        self
+    }
+    
+    /// Only use HTTP/1
+    pub fn http1_only(mut self, enabled: bool) -> Self {
+        self.http1_only = enabled;
+        self
+    }
+    
+    /// Use HTTP/2 without negotiating it first
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+    
+    /// Send requests for an endpoint to another path under the base URL
+    pub fn endpoint_path(mut self, endpoint: impl Into<String>, path: impl Into<String>) -> Self {
+        self.endpoint_paths.insert(endpoint, path);
+        self
+    }
+    
+    /// Allow single calls to be sent to another base URL
+    ///
+    /// See [`RequestOptions::base_url_override`](crate::RequestOptions::base_url_override).
+    pub fn allowed_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.allowed_base_urls.push(base_url.into());
+        self
+    }
+    
+    /// Check the settings and build the configuration
+    pub fn build(self) -> VeniceResult<HttpClientConfig> {
+        let base_url = self.base_url.unwrap_or_else(|| crate::config::DEFAULT_BASE_URL.to_string());
+        let parsed = ::url::Url::parse(&base_url)
+            .map_err(|e| VeniceError::InvalidInput(format!("Invalid base URL {}: {}", base_url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") || parsed.cannot_be_a_base() {
+            return Err(VeniceError::InvalidInput(format!(
+                "Invalid base URL {}: expected an http or https URL",
+                base_url
+            )));
+        }
+        
+        if self.http1_only && self.http2_prior_knowledge {
+            return Err(VeniceError::InvalidInput(
+                "http1_only and http2_prior_knowledge cannot both be enabled".to_string(),
+            ));
+        }
+        
+        let mut custom_headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| VeniceError::InvalidInput(format!("Invalid header name: {}", name)))?;
+            let header_value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|_| VeniceError::InvalidInput(format!("Invalid header value: {}", value)))?;
+            custom_headers.insert(header_name, header_value);
+        }
+        
+        Ok(HttpClientConfig {
+            api_key: self.api_key,
+            base_url,
+            custom_headers,
+            timeout_secs: self.timeout_secs,
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            pool_idle_timeout_secs: self.pool_idle_timeout_secs,
+            tcp_keepalive_secs: self.tcp_keepalive_secs,
+            http1_only: self.http1_only,
+            http2_prior_knowledge: self.http2_prior_knowledge,
+            endpoint_paths: self.endpoint_paths,
+            allowed_base_urls: self.allowed_base_urls,
+        })
+    }
 }
 
 /// Result type for HTTP operations
@@ -32,10 +217,26 @@ pub type HttpResult<T> = VeniceResult<(T, RateLimitInfo)>;
 /// including authentication, request building, and response processing.
 #[derive(Debug, Clone)]
 pub struct HttpClient {
-    /// The underlying HTTP client
-    client: ReqwestClient,
+    /// The underlying HTTP connection pool
+    connection: Connection,
     /// The client configuration
     config: HttpClientConfig,
+    /// Observers notified about every request
+    observers: UsageObservers,
+    /// Drop response fields that fail to deserialize instead of failing the call
+    lenient_parsing: bool,
+    /// Keys used in turn instead of the configured API key
+    key_pool: Option<Arc<KeyPool>>,
+    /// GET requests in flight, shared by identical concurrent calls
+    single_flight: Option<Arc<SingleFlight>>,
+    /// Adds signature headers to every request
+    request_signer: Option<Arc<dyn RequestSigner>>,
+    /// Compresses large request bodies
+    compression: Option<Arc<RequestCompression>>,
+    /// Extends the timeout of endpoints that got slow
+    latency_tracker: Option<Arc<LatencyTracker>>,
+    /// Largest JSON request body sent, in bytes
+    max_request_bytes: Option<usize>,
 }
 
 impl HttpClient {
@@ -43,11 +244,13 @@ impl HttpClient {
     pub fn new(config: HttpClientConfig) -> VeniceResult<Self> {
         let mut headers = reqwest::header::HeaderMap::new();
         
-        // Add API key header
-        let auth_value = format!("Bearer {}", config.api_key);
-        let auth_header = reqwest::header::HeaderValue::from_str(&auth_value)
-            .map_err(|e| VeniceError::InvalidInput(format!("Invalid API key: {}", e)))?;
-        headers.insert(reqwest::header::AUTHORIZATION, auth_header);
+        // Add API key header, unless requests are authenticated some other way
+        if !config.api_key.is_empty() {
+            let auth_value = format!("Bearer {}", config.api_key.expose_secret());
+            let auth_header = reqwest::header::HeaderValue::from_str(&auth_value)
+                .map_err(|e| VeniceError::InvalidInput(format!("Invalid API key: {}", e)))?;
+            headers.insert(reqwest::header::AUTHORIZATION, auth_header);
+        }
         
         // Add custom headers
         for (key, value) in config.custom_headers.iter() {
@@ -63,15 +266,151 @@ impl HttpClient {
             client_builder = client_builder.timeout(std::time::Duration::from_secs(timeout_secs));
         }
         
+        // Connection pool and protocol settings
+        if let Some(max) = config.pool_max_idle_per_host {
+            client_builder = client_builder.pool_max_idle_per_host(max);
+        }
+        if let Some(secs) = config.pool_idle_timeout_secs {
+            client_builder = client_builder.pool_idle_timeout(std::time::Duration::from_secs(secs));
+        }
+        if let Some(secs) = config.tcp_keepalive_secs {
+            client_builder = client_builder.tcp_keepalive(std::time::Duration::from_secs(secs));
+        }
+        if config.http1_only {
+            client_builder = client_builder.http1_only();
+        }
+        if config.http2_prior_knowledge {
+            client_builder = client_builder.http2_prior_knowledge();
+        }
+        
         let client = client_builder.build()
             .map_err(|e| VeniceError::InvalidInput(format!("Failed to create HTTP client: {}", e)))?;
         
         Ok(Self {
-            client,
+            connection: Connection::new(client, Lifecycle::default()),
             config,
+            observers: UsageObservers::default(),
+            lenient_parsing: false,
+            key_pool: None,
+            single_flight: Some(Arc::default()),
+            request_signer: None,
+            compression: None,
+            latency_tracker: None,
+            max_request_bytes: None,
         })
     }
     
+    /// Share a set of usage observers with this client
+    pub(crate) fn with_observers(mut self, observers: UsageObservers) -> Self {
+        self.observers = observers;
+        self
+    }
+    
+    /// Set whether response fields that fail to deserialize are dropped instead of failing the call
+    pub(crate) fn with_lenient_parsing(mut self, lenient: bool) -> Self {
+        self.lenient_parsing = lenient;
+        self
+    }
+    
+    /// Authenticate requests with keys from a pool instead of the configured API key
+    ///
+    /// Requests that are rate limited or rejected as unauthorized are sent again with the
+    /// next key in the pool, except multipart uploads, which cannot be repeated.
+    pub fn with_key_pool(mut self, key_pool: Arc<KeyPool>) -> Self {
+        self.key_pool = Some(key_pool);
+        self
+    }
+    
+    /// Add the headers computed by a signer to every request
+    ///
+    /// The signer sees the exact bytes of JSON bodies. With an empty API key, no bearer
+    /// token is sent, so the signature alone authenticates the request.
+    pub fn with_request_signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.request_signer = Some(signer);
+        self
+    }
+    
+    /// Gzip JSON request bodies of at least `threshold` bytes
+    ///
+    /// Use [`DEFAULT_COMPRESSION_THRESHOLD`](crate::http::DEFAULT_COMPRESSION_THRESHOLD)
+    /// unless payloads are known to be large. Responses are decompressed regardless.
+    pub fn with_request_compression(self, threshold: usize) -> Self {
+        self.with_compression(Arc::new(RequestCompression::new(threshold)))
+    }
+    
+    /// Refuse to send JSON request bodies larger than `max_bytes`
+    ///
+    /// Oversized requests fail with [`VeniceError::PayloadTooLarge`] before anything is
+    /// sent. The size is measured after compression, as the body goes on the wire.
+    pub fn with_max_request_size(mut self, max_bytes: usize) -> Self {
+        self.max_request_bytes = Some(max_bytes);
+        self
+    }
+    
+    /// Fail with [`VeniceError::PayloadTooLarge`] if an upload of `size` bytes is over the limit
+    pub(crate) fn check_request_size(&self, size: usize) -> VeniceResult<()> {
+        compression::check_size(size, self.max_request_bytes)
+    }
+    
+    /// Share request compression, and its statistics, with a [`crate::Client`]
+    pub(crate) fn with_compression(mut self, compression: Arc<RequestCompression>) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+    
+    /// Get the bandwidth saved by request compression, if it is enabled
+    pub fn compression_stats(&self) -> Option<CompressionStats> {
+        self.compression.as_ref().map(|compression| compression.stats())
+    }
+    
+    /// Set whether identical concurrent GET requests share one request
+    pub(crate) fn with_request_coalescing(mut self, enabled: bool) -> Self {
+        self.single_flight = enabled.then(Arc::default);
+        self
+    }
+    
+    /// Add headers to every request, sharing the connection pool of this client
+    ///
+    /// Identical concurrent GET requests are no longer shared with clients without the
+    /// headers.
+    pub(crate) fn with_headers(mut self, headers: &reqwest::header::HeaderMap) -> Self {
+        self.connection = self.connection.with_headers(headers);
+        if self.single_flight.is_some() {
+            self.single_flight = Some(Arc::default());
+        }
+        self
+    }
+    
+    /// Extend the timeout of endpoints whose recent latencies approach it
+    pub(crate) fn with_latency_tracker(mut self, latency_tracker: Arc<LatencyTracker>) -> Self {
+        self.latency_tracker = Some(latency_tracker);
+        self
+    }
+    
+    /// Set the deadline of a request to an endpoint, if latency tracking extends it
+    fn deadline(&self, request: RequestBuilder, endpoint: &str) -> RequestBuilder {
+        match (&self.latency_tracker, self.config.timeout_secs) {
+            (Some(tracker), Some(timeout_secs)) => {
+                let timeout = Duration::from_secs(timeout_secs);
+                let deadline = tracker.timeout_for(endpoint, timeout);
+                if deadline > timeout { request.timeout(deadline) } else { request }
+            }
+            _ => request,
+        }
+    }
+    
+    /// Share the shutdown state of a [`crate::Client`] with this client
+    pub(crate) fn with_lifecycle(mut self, lifecycle: Lifecycle) -> Self {
+        self.connection = self.connection.with_lifecycle(lifecycle);
+        self
+    }
+    
+    /// Refuse new requests once the spend of a [`crate::Client`] reaches a cap
+    pub(crate) fn with_spend_guard(mut self, spend_guard: Arc<SpendGuard>) -> Self {
+        self.connection = self.connection.with_spend_guard(spend_guard);
+        self
+    }
+    
     /// Get the client configuration
     pub fn config(&self) -> &HttpClientConfig {
         &self.config
@@ -79,14 +418,8 @@ impl HttpClient {
     
     /// Send a GET request to the API
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> HttpResult<T> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
-        
-        let response = self.client.get(url)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
-        
-        response_processor::process_response(response).await
+        let url = self.config.endpoint_url(endpoint)?;
+        self.get_url(endpoint, url).await
     }
     
     /// Send a GET request with query parameters to the API
@@ -95,15 +428,46 @@ impl HttpClient {
         endpoint: &str,
         query: &Q,
     ) -> HttpResult<T> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        let mut url = self.config.endpoint_url(endpoint)?;
+        let query = serde_urlencoded::to_string(query)
+            .map_err(|e| VeniceError::InvalidInput(format!("Invalid query parameters: {}", e)))?;
+        if !query.is_empty() {
+            url.set_query(Some(&query));
+        }
+        self.get_url(endpoint, url).await
+    }
+    
+    /// Send a GET request to a full URL, sharing the response with identical requests in flight
+    async fn get_url<T: DeserializeOwned>(&self, endpoint: &str, url: ::url::Url) -> HttpResult<T> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        let observed = self.observers.start(Method::GET, endpoint);
+        let started = Instant::now();
         
-        let response = self.client.get(url)
-            .query(query)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let fetch = || key_pool::send_pooled(self.key_pool.as_ref(), |key| {
+            let request = self.deadline(self.connection.authorize(client.get(url.clone()), key), endpoint);
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::GET, &url, &[]);
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::read_response(response).await
+            }
+        });
+        let result = match &self.single_flight {
+            Some(single_flight) => {
+                // Calls overriding feature flags only share requests sent with the same flags
+                let key = match request_options::feature_flag_key() {
+                    Some(flags) => format!("{} {}", url, flags),
+                    None => url.to_string(),
+                };
+                single_flight.run(&key, fetch).await
+            }
+            None => fetch().await,
+        };
+        let result = result
+            .and_then(|raw| response_processor::parse_raw_response::<T>(raw, started, self.lenient_parsing))
+            .map(|(envelope, rate_limit_info, metadata)| (envelope.body, rate_limit_info, metadata));
         
-        response_processor::process_response(response).await
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("GET", endpoint)));
+        self.observers.finish(observed, result)
     }
     
     /// Send a POST request to the API
@@ -112,61 +476,166 @@ impl HttpClient {
         endpoint: &str,
         body: &S,
     ) -> HttpResult<T> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        self.send_json(Method::POST, endpoint, body).await
+    }
+    
+    /// Send a PUT request to the API
+    pub async fn put<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> HttpResult<T> {
+        self.send_json(Method::PUT, endpoint, body).await
+    }
+    
+    /// Send a PATCH request to the API
+    pub async fn patch<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> HttpResult<T> {
+        self.send_json(Method::PATCH, endpoint, body).await
+    }
+    
+    /// Send a DELETE request with a JSON body to the API
+    pub async fn delete_with_body<S: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &S,
+    ) -> HttpResult<T> {
+        self.send_json(Method::DELETE, endpoint, body).await
+    }
+    
+    /// Send a request with a JSON body to the API
+    async fn send_json<S: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: &S,
+    ) -> HttpResult<T> {
+        let (client, _in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let body = JsonBody::new(body, self.compression.as_deref())?.within_limit(self.max_request_bytes)?;
+        let observed = self.observers.start_with_payload(method.clone(), endpoint, Some(body.bytes()));
         
-        let response = self.client.post(url)
-            .json(body)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled(self.key_pool.as_ref(), |key| {
+            let request = body.attach(self.deadline(self.connection.authorize(client.request(method.clone(), url.clone()), key), endpoint));
+            let request = signer::sign(request, self.request_signer.as_ref(), method.clone(), &url, body.bytes());
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_response_with_usage(response, self.lenient_parsing).await
+            }
+        }).await;
         
-        response_processor::process_response(response).await
+        let result = result.map_err(|e| e.with_context(ErrorContext::request(method.as_str(), endpoint)));
+        self.observers.finish(observed, result)
     }
     
     /// Send a DELETE request to the API
     pub async fn delete<T: DeserializeOwned>(&self, endpoint: &str) -> HttpResult<T> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        let (client, _in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let observed = self.observers.start(Method::DELETE, endpoint);
         
-        let response = self.client.delete(url)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled(self.key_pool.as_ref(), |key| {
+            let request = self.deadline(self.connection.authorize(client.delete(url.clone()), key), endpoint);
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::DELETE, &url, &[]);
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_response_with_usage(response, self.lenient_parsing).await
+            }
+        }).await;
         
-        response_processor::process_response(response).await
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("DELETE", endpoint)));
+        self.observers.finish(observed, result)
     }
     
     /// Send a multipart POST request to the API
+    ///
+    /// Parts created with [`UploadOptions::part`](crate::UploadOptions::part) are streamed
+    /// from their reader instead of being buffered in memory.
     pub async fn post_multipart<T: DeserializeOwned>(
         &self,
         endpoint: &str,
         form: reqwest::multipart::Form,
     ) -> HttpResult<T> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        let (client, _in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let observed = self.observers.start(Method::POST, endpoint);
         
-        let response = self.client.post(url)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled_once(self.key_pool.as_ref(), |key| {
+            let request = self.deadline(self.connection.authorize(client.post(url.clone()), key), endpoint).multipart(form);
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::POST, &url, &[]);
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_response_with_usage(response, self.lenient_parsing).await
+            }
+        }).await;
         
-        response_processor::process_response(response).await
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        self.observers.finish(observed, result)
     }
     
     /// Send a multipart POST request to the API and get a binary response
+    ///
+    /// Parts created with [`UploadOptions::part`](crate::UploadOptions::part) are streamed
+    /// from their reader instead of being buffered in memory.
     pub async fn post_multipart_binary(
         &self,
         endpoint: &str,
         form: reqwest::multipart::Form,
     ) -> VeniceResult<(Vec<u8>, String, RateLimitInfo)> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        let (client, _in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let observed = self.observers.start(Method::POST, endpoint);
         
-        let response = self.client.post(url)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled_once(self.key_pool.as_ref(), |key| {
+            let request = self.deadline(self.connection.authorize(client.post(url.clone()), key), endpoint).multipart(form);
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::POST, &url, &[]);
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_binary_response(response).await
+            }
+        }).await;
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok((_, _, rate_limit_info)) => self.observers.response(&observed, None, rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
         
-        response_processor::process_binary_response(response).await
+        result
+    }
+    
+    /// Send a multipart POST request to the API and stream the binary response
+    ///
+    /// The body is not buffered; see [`BinaryStream`](crate::BinaryStream).
+    pub async fn post_multipart_binary_stream(
+        &self,
+        endpoint: &str,
+        form: reqwest::multipart::Form,
+    ) -> VeniceResult<response_processor::BinaryStream> {
+        let (client, in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let observed = self.observers.start(Method::POST, endpoint);
+        
+        let result = key_pool::send_pooled_once(self.key_pool.as_ref(), |key| {
+            let request = self.deadline(self.connection.authorize(client.post(url.clone()), key), endpoint).multipart(form);
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::POST, &url, &[]);
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_binary_stream(response).await
+            }
+        }).await;
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok(body) => self.observers.response(&observed, None, &body.rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
+        
+        // Keep the request in flight until the body is dropped
+        result.map(|body| body.attach(in_flight))
     }
     
     /// Send a POST request to the API and get a streaming response
@@ -175,15 +644,45 @@ impl HttpClient {
         endpoint: &str,
         body: &S,
     ) -> VeniceResult<(crate::traits::chat::ChatCompletionStream, RateLimitInfo)> {
-        let url = url::build_url(&self.config.base_url, endpoint)?;
+        let (events, rate_limit_info) = self.post_event_stream(endpoint, body, None).await?;
+        Ok((response_processor::parse_events(events), rate_limit_info))
+    }
+    
+    /// Send a POST request to the API and get its server-sent events
+    ///
+    /// With `last_event_id`, the request carries a `Last-Event-ID` header asking the
+    /// server to resume a stream after that event.
+    pub(crate) async fn post_event_stream<S: Serialize>(
+        &self,
+        endpoint: &str,
+        body: &S,
+        last_event_id: Option<&str>,
+    ) -> VeniceResult<(SseStream, RateLimitInfo)> {
+        let (client, in_flight) = self.connection.checkout()?;
+        let url = self.config.endpoint_url(endpoint)?;
+        let body = JsonBody::new(body, self.compression.as_deref())?.within_limit(self.max_request_bytes)?;
+        let observed = self.observers.start_with_payload(Method::POST, endpoint, Some(body.bytes()));
         
-        let response = self.client.post(url)
-            .json(body)
-            .send()
-            .await
-            .map_err(VeniceError::HttpError)?;
+        let result = key_pool::send_pooled(self.key_pool.as_ref(), |key| {
+            let mut request = body.attach(self.deadline(self.connection.authorize(client.post(url.clone()), key), endpoint));
+            if let Some(last_event_id) = last_event_id {
+                request = request.header("Last-Event-ID", last_event_id);
+            }
+            let request = signer::sign(request, self.request_signer.as_ref(), Method::POST, &url, body.bytes());
+            async move {
+                let response = request?.send().await.map_err(VeniceError::HttpError)?;
+                response_processor::process_event_stream(response).await
+            }
+        }).await;
+        
+        let result = result.map_err(|e| e.with_context(ErrorContext::request("POST", endpoint)));
+        match &result {
+            Ok((_, rate_limit_info)) => self.observers.response(&observed, None, rate_limit_info),
+            Err(err) => self.observers.error(&observed, err),
+        }
         
-        response_processor::process_streaming_response(response).await
+        // Keep the request in flight until the stream is dropped
+        result.map(|(events, rate_limit_info)| (in_flight.attach(events), rate_limit_info))
     }
 }
 
@@ -201,12 +700,7 @@ mod tests {
     
     #[test]
     fn test_http_client_creation() {
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").build().unwrap();
         
         let client = HttpClient::new(config.clone()).unwrap();
         
@@ -216,37 +710,196 @@ mod tests {
     
     #[test]
     fn test_http_client_with_custom_headers() {
-        let mut custom_headers = reqwest::header::HeaderMap::new();
-        custom_headers.insert(
-            reqwest::header::HeaderName::from_static("x-custom-header"),
-            reqwest::header::HeaderValue::from_static("custom-value"),
-        );
-        
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers,
-            timeout_secs: None,
-        };
+        let config = HttpClientConfig::builder()
+            .api_key("test_api_key")
+            .base_url("https://api.venice.ai")
+            .header("x-custom-header", "custom-value")
+            .build()
+            .unwrap();
         
         let client = HttpClient::new(config).unwrap();
         
         // We can't easily test that the headers are actually sent,
         // but we can at least verify that the client was created successfully
         assert_eq!(client.config().api_key, "test_api_key");
+        assert_eq!(client.config().custom_headers["x-custom-header"], "custom-value");
+    }
+    
+    #[test]
+    fn test_http_client_config_builder_validation() {
+        let config = HttpClientConfig::builder()
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout_secs(60)
+            .tcp_keepalive_secs(30)
+            .http1_only(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.base_url, crate::config::DEFAULT_BASE_URL);
+        assert_eq!(config.pool_max_idle_per_host, Some(4));
+        assert!(HttpClient::new(config).is_ok());
+        
+        for builder in [
+            HttpClientConfig::builder().base_url("api.venice.ai"),
+            HttpClientConfig::builder().base_url("ftp://api.venice.ai"),
+            HttpClientConfig::builder().header("bad header", "value"),
+            HttpClientConfig::builder().header("x-ok", "bad\nvalue"),
+            HttpClientConfig::builder().http1_only(true).http2_prior_knowledge(true),
+        ] {
+            assert!(matches!(builder.build(), Err(VeniceError::InvalidInput(_))));
+        }
     }
     
     #[test]
     fn test_http_client_with_timeout() {
-        let config = HttpClientConfig {
-            api_key: "test_api_key".to_string(),
-            base_url: "https://api.venice.ai".to_string(),
-            custom_headers: reqwest::header::HeaderMap::new(),
-            timeout_secs: Some(30),
-        };
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url("https://api.venice.ai").timeout_secs(30).build().unwrap();
         
         let client = HttpClient::new(config).unwrap();
         
         assert_eq!(client.config().timeout_secs, Some(30));
     }
-}
\ No newline at end of file
+    
+    async fn concurrent_gets(coalesce: bool, expected_requests: usize) {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models?type=text")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(expected_requests)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let client = HttpClient::new(config).unwrap().with_request_coalescing(coalesce);
+        let query = [("type", "text")];
+        
+        let (first, second, third) = tokio::join!(
+            client.get_with_query::<_, serde_json::Value>("models", &query),
+            client.get_with_query::<_, serde_json::Value>("models", &query),
+            client.get::<serde_json::Value>("models?type=text"),
+        );
+        assert_eq!(first.unwrap().0["object"], "list");
+        assert_eq!(second.unwrap().0["object"], "list");
+        assert_eq!(third.unwrap().0["object"], "list");
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_identical_gets_share_one_request() {
+        concurrent_gets(true, 1).await;
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_gets_without_coalescing() {
+        concurrent_gets(false, 3).await;
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_gets_with_different_feature_flags_are_not_shared() {
+        use crate::RequestOptions;
+        
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for value in ["on", "off"] {
+            let mock = server
+                .mock("GET", "/models")
+                .match_header("x-venice-feature-reasoning", value)
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(format!(r#"{{"object": "list", "data": [], "reasoning": "{}"}}"#, value))
+                .expect(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let with_flag = |value| RequestOptions::new().feature_flag("reasoning", value).unwrap();
+        
+        let (on, off) = tokio::join!(
+            with_flag("on").scope(client.get::<serde_json::Value>("models")),
+            with_flag("off").scope(client.get::<serde_json::Value>("models")),
+        );
+        assert_eq!(on.unwrap().0["reasoning"], "on");
+        assert_eq!(off.unwrap().0["reasoning"], "off");
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+    
+    #[derive(Debug)]
+    struct EchoSigner;
+    
+    impl RequestSigner for EchoSigner {
+        fn sign(&self, request: &signer::SignableRequest<'_>) -> VeniceResult<reqwest::header::HeaderMap> {
+            let signature = format!(
+                "{} {} {}",
+                request.method,
+                request.url.path(),
+                String::from_utf8_lossy(request.body)
+            );
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("x-signature", signature.parse().unwrap());
+            Ok(headers)
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_request_signer_signs_body_without_bearer_key() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_header("x-signature", r#"POST /chat/completions {"model":"test"}"#)
+            .match_header("authorization", mockito::Matcher::Missing)
+            .match_header("content-type", "application/json")
+            .match_body(r#"{"model":"test"}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"ok": true}"#)
+            .create_async()
+            .await;
+        
+        let config = HttpClientConfig::builder().base_url(server.url()).build().unwrap();
+        let client = HttpClient::new(config).unwrap().with_request_signer(Arc::new(EchoSigner));
+        
+        let (response, _) = client
+            .post::<_, serde_json::Value>("chat/completions", &serde_json::json!({"model": "test"}))
+            .await
+            .unwrap();
+        assert_eq!(response["ok"], true);
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_put_patch_and_delete_with_body() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for method in ["PUT", "PATCH", "DELETE"] {
+            mocks.push(
+                server
+                    .mock(method, "/api_keys/key-1")
+                    .match_body(r#"{"description":"renamed"}"#)
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(format!(r#"{{"method": "{}"}}"#, method))
+                    .create_async()
+                    .await,
+            );
+        }
+        
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let body = serde_json::json!({"description": "renamed"});
+        
+        let (put, _) = client.put::<_, serde_json::Value>("api_keys/key-1", &body).await.unwrap();
+        let (patch, _) = client.patch::<_, serde_json::Value>("api_keys/key-1", &body).await.unwrap();
+        let (delete, _) = client.delete_with_body::<_, serde_json::Value>("api_keys/key-1", &body).await.unwrap();
+        assert_eq!(put["method"], "PUT");
+        assert_eq!(patch["method"], "PATCH");
+        assert_eq!(delete["method"], "DELETE");
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+}
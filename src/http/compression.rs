@@ -0,0 +1,183 @@
+//! Compressing request bodies
+//!
+//! Responses are decompressed transparently: every request advertises gzip and brotli
+//! in `Accept-Encoding`. Request bodies are only compressed when a client opts in with
+//! [`ClientConfig::with_request_compression`](crate::ClientConfig::with_request_compression),
+//! which pays off for long chat histories and other multi-megabyte payloads.
+
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{VeniceError, VeniceResult};
+
+/// Smallest body compressed by default, in bytes
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 64 * 1024;
+
+/// Bandwidth saved by compressing request bodies, from
+/// [`Client::compression_stats`](crate::Client::compression_stats)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompressionStats {
+    /// Number of request bodies sent compressed
+    pub requests_compressed: u64,
+    /// Total size of those bodies before compression, in bytes
+    pub bytes_before: u64,
+    /// Total size of those bodies as sent, in bytes
+    pub bytes_after: u64,
+}
+
+impl CompressionStats {
+    /// Get the number of bytes compression kept off the wire
+    pub fn bytes_saved(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    /// Get the size of the compressed bodies relative to their original size
+    ///
+    /// Returns 1.0 when nothing has been compressed yet.
+    pub fn ratio(&self) -> f64 {
+        if self.bytes_before == 0 {
+            1.0
+        } else {
+            self.bytes_after as f64 / self.bytes_before as f64
+        }
+    }
+}
+
+/// Gzips request bodies above a size threshold and counts the bytes saved
+#[derive(Debug)]
+pub(crate) struct RequestCompression {
+    threshold: usize,
+    requests_compressed: AtomicU64,
+    bytes_before: AtomicU64,
+    bytes_after: AtomicU64,
+}
+
+impl RequestCompression {
+    /// Compress bodies of at least `threshold` bytes
+    pub(crate) fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            requests_compressed: AtomicU64::new(0),
+            bytes_before: AtomicU64::new(0),
+            bytes_after: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the bandwidth saved so far
+    pub(crate) fn stats(&self) -> CompressionStats {
+        CompressionStats {
+            requests_compressed: self.requests_compressed.load(Ordering::Acquire),
+            bytes_before: self.bytes_before.load(Ordering::Acquire),
+            bytes_after: self.bytes_after.load(Ordering::Acquire),
+        }
+    }
+
+    /// Gzip a body if it is large enough and compression makes it smaller
+    fn compress(&self, body: Bytes) -> VeniceResult<(Bytes, bool)> {
+        if body.len() < self.threshold {
+            return Ok((body, false));
+        }
+
+        let mut encoder = GzEncoder::new(Vec::with_capacity(body.len() / 4), Compression::default());
+        let compressed = encoder
+            .write_all(&body)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| VeniceError::InvalidInput(format!("Failed to compress request body: {}", e)))?;
+        if compressed.len() >= body.len() {
+            return Ok((body, false));
+        }
+
+        log::debug!("Compressed request body from {} to {} bytes", body.len(), compressed.len());
+        self.requests_compressed.fetch_add(1, Ordering::AcqRel);
+        self.bytes_before.fetch_add(body.len() as u64, Ordering::AcqRel);
+        self.bytes_after.fetch_add(compressed.len() as u64, Ordering::AcqRel);
+        Ok((Bytes::from(compressed), true))
+    }
+}
+
+/// A JSON request body, serialized once so the bytes that are signed are the bytes that are sent
+#[derive(Debug, Clone)]
+pub(crate) struct JsonBody {
+    bytes: Bytes,
+    gzipped: bool,
+}
+
+impl JsonBody {
+    /// Serialize a body, compressing it when the client asks for compression
+    pub(crate) fn new<S: Serialize>(body: &S, compression: Option<&RequestCompression>) -> VeniceResult<Self> {
+        let bytes = serde_json::to_vec(body)
+            .map(Bytes::from)
+            .map_err(|e| VeniceError::InvalidInput(format!("Failed to serialize request body: {}", e)))?;
+        let (bytes, gzipped) = match compression {
+            Some(compression) => compression.compress(bytes)?,
+            None => (bytes, false),
+        };
+        Ok(Self { bytes, gzipped })
+    }
+
+    /// Fail with [`VeniceError::PayloadTooLarge`] if the body as sent is larger than `limit` bytes
+    pub(crate) fn within_limit(self, limit: Option<usize>) -> VeniceResult<Self> {
+        check_size(self.bytes.len(), limit)?;
+        Ok(self)
+    }
+
+    /// Get the bytes sent on the wire
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Attach the body and its content headers to a request
+    pub(crate) fn attach(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = request.header(CONTENT_TYPE, "application/json");
+        let request = if self.gzipped {
+            request.header(CONTENT_ENCODING, "gzip")
+        } else {
+            request
+        };
+        request.body(self.bytes.clone())
+    }
+}
+
+/// Fail with [`VeniceError::PayloadTooLarge`] if `size` bytes are more than `limit`
+pub(crate) fn check_size(size: usize, limit: Option<usize>) -> VeniceResult<()> {
+    match limit {
+        Some(limit) if size > limit => Err(VeniceError::PayloadTooLarge { size, limit }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_compresses_large_bodies_only() {
+        let compression = RequestCompression::new(1024);
+
+        let small = JsonBody::new(&serde_json::json!({"content": "hello"}), Some(&compression)).unwrap();
+        assert!(!small.gzipped);
+
+        let history = "The quick brown fox jumps over the lazy dog. ".repeat(1000);
+        let large = JsonBody::new(&serde_json::json!({"content": history}), Some(&compression)).unwrap();
+        assert!(large.gzipped);
+
+        let mut decoded = String::new();
+        GzDecoder::new(large.bytes()).read_to_string(&mut decoded).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&decoded).unwrap()["content"], history.as_str());
+
+        let stats = compression.stats();
+        assert_eq!(stats.requests_compressed, 1);
+        assert_eq!(stats.bytes_before as usize, decoded.len());
+        assert_eq!(stats.bytes_after as usize, large.bytes().len());
+        assert!(stats.bytes_saved() > 0);
+        assert!(stats.ratio() < 0.1);
+    }
+}
@@ -2,10 +2,25 @@
 
 mod client;
 mod client_factory;
+mod compression;
 mod response_processor;
+mod signer;
+mod single_flight;
+mod sse;
+mod upload;
 mod url;
 
-pub use client::{HttpClient, HttpClientConfig, HttpResult, SharedHttpClient, new_shared_http_client};
+pub use client::{HttpClient, HttpClientConfig, HttpClientConfigBuilder, HttpResult, SharedHttpClient, new_shared_http_client};
 pub use client_factory::create_client;
-pub use response_processor::{process_response, process_binary_response, process_streaming_response};
+pub use compression::{CompressionStats, DEFAULT_COMPRESSION_THRESHOLD};
+pub(crate) use compression::{JsonBody, RequestCompression};
+pub use response_processor::{
+    process_response_envelope, process_response_with_usage, ResponseEnvelope, ResponseMetadata, process_binary_response,
+    process_binary_stream, BinaryStream, ParseWarning,
+    process_streaming_response,
+};
+pub(crate) use response_processor::{parse_event, rate_limit_error, RawResponse};
+pub(crate) use sse::SseStream;
+pub use signer::{RequestSigner, SignableRequest};
+pub use upload::{UploadOptions, UploadProgressCallback, DEFAULT_UPLOAD_CHUNK_SIZE};
 pub use url::build_url;
\ No newline at end of file
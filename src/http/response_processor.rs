@@ -1,22 +1,131 @@
 use futures::{Stream, StreamExt, TryStreamExt};
-use reqwest::Response;
-use serde::de::DeserializeOwned;
+use reqwest::header::HeaderMap;
+use reqwest::{Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
-use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use super::sse::{self, SseEvent, SseStream};
+use crate::error::{ErrorContext, RateLimitInfo, VeniceError, VeniceResult};
+use crate::retry::RetryReport;
+use crate::traits::chat::ChatCompletionUsage;
 
-/// Process a response from the API
-pub async fn process_response<T: DeserializeOwned>(
+/// Accounting fields of a response body, reported to usage observers
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResponseMetadata {
+    /// The model that served the request
+    #[serde(default)]
+    pub model: Option<String>,
+    /// The token usage reported by the API
+    #[serde(default)]
+    pub usage: Option<ChatCompletionUsage>,
+    /// The HTTP status of the response
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+    /// Fields dropped by lenient parsing
+    #[serde(skip)]
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// A response field that failed to deserialize and was dropped by lenient parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Path of the dropped field, such as `data[3].model_spec`
+    pub path: String,
+    /// Why the field could not be deserialized
+    pub message: String,
+}
+
+/// Maximum number of fields lenient parsing drops before giving up
+const MAX_LENIENT_REPAIRS: usize = 32;
+
+/// A typed response body together with the raw HTTP response it was parsed from
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+    /// The HTTP status code
+    pub status: StatusCode,
+    /// All response headers
+    pub headers: HeaderMap,
+    /// Time from sending the request until the body was received
+    pub latency: Duration,
+    /// The unparsed response body
+    pub raw_body: String,
+    /// Rate limit information from the response headers
+    pub rate_limit_info: RateLimitInfo,
+    /// The attempts made before this response, for clients with retries enabled
+    pub retry: RetryReport,
+    /// The parsed response body
+    pub body: T,
+}
+
+impl<T> ResponseEnvelope<T> {
+    /// Get a response header as a string, if present and valid UTF-8
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Get the request ID assigned by the API, if present
+    pub fn request_id(&self) -> Option<&str> {
+        crate::error::request_id_from_headers(&self.headers)
+    }
+
+    /// Split the envelope into the typed body and rate limit information
+    pub fn into_parts(self) -> (T, RateLimitInfo) {
+        (self.body, self.rate_limit_info)
+    }
+}
+
+/// Process a response from the API, also extracting the model and token usage reported in the body
+pub async fn process_response_with_usage<T: DeserializeOwned>(
     response: Response,
-) -> VeniceResult<(T, RateLimitInfo)> {
+    lenient: bool,
+) -> VeniceResult<(T, RateLimitInfo, ResponseMetadata)> {
+    process_response_envelope(response, Instant::now(), lenient)
+        .await
+        .map(|(envelope, rate_limit_info, metadata)| (envelope.body, rate_limit_info, metadata))
+}
+
+/// Process a response from the API, keeping the status, headers and raw body
+///
+/// `started` is when the request was sent and is used to measure latency. With
+/// `lenient` set, fields that fail to deserialize are dropped and reported as
+/// [`ParseWarning`]s in the metadata instead of failing the call.
+pub async fn process_response_envelope<T: DeserializeOwned>(
+    response: Response,
+    started: Instant,
+    lenient: bool,
+) -> VeniceResult<(ResponseEnvelope<T>, RateLimitInfo, ResponseMetadata)> {
+    let raw = read_response(response).await?;
+    parse_raw_response(raw, started, lenient)
+}
+
+/// The error for a 429 response, carrying the rate limit and request ID from its headers
+pub(crate) fn rate_limit_error(headers: &HeaderMap) -> VeniceError {
+    VeniceError::RateLimitExceeded(format!(
+        "Rate limit exceeded: {}",
+        RateLimitInfo::from_headers(headers)
+    ))
+    .with_context(ErrorContext::from_headers(headers))
+}
+
+/// A successful response whose body has been read but not yet deserialized
+#[derive(Debug, Clone)]
+pub(crate) struct RawResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) rate_limit_info: RateLimitInfo,
+    pub(crate) body: bytes::Bytes,
+}
+
+/// Read the body of a response, turning error statuses into errors
+pub(crate) async fn read_response(response: Response) -> VeniceResult<RawResponse> {
     let rate_limit_info = RateLimitInfo::from_headers(response.headers());
     let status = response.status();
+    let context = ErrorContext::from_headers(response.headers());
+    let headers = response.headers().clone();
 
     if status.as_u16() == 429 {
-        return Err(VeniceError::RateLimitExceeded(format!(
-            "Rate limit exceeded: {}",
-            rate_limit_info
-        )));
+        return Err(rate_limit_error(response.headers()));
     }
 
     if !status.is_success() {
@@ -57,15 +166,124 @@ pub async fn process_response<T: DeserializeOwned>(
             status,
             code,
             message,
+        }.with_context(context));
+    }
+
+    let body = response.bytes().await.map_err(|err| {
+        VeniceError::parse_error(format!("Failed to read response body: {}", err))
+    })?;
+
+    Ok(RawResponse {
+        status,
+        headers,
+        rate_limit_info,
+        body,
+    })
+}
+
+/// Deserialize a response whose body has been read
+pub(crate) fn parse_raw_response<T: DeserializeOwned>(
+    raw: RawResponse,
+    started: Instant,
+    lenient: bool,
+) -> VeniceResult<(ResponseEnvelope<T>, RateLimitInfo, ResponseMetadata)> {
+    let RawResponse { status, headers, rate_limit_info, body } = raw;
+    let context = ErrorContext::from_headers(&headers);
+
+    let (data, warnings) = deserialize_body::<T>(&body, lenient).map_err(|err| err.with_context(context))?;
+
+    let mut metadata = serde_json::from_slice::<ResponseMetadata>(&body).unwrap_or_default();
+    metadata.status = Some(status);
+    metadata.warnings = warnings;
+
+    let envelope = ResponseEnvelope {
+        status,
+        headers,
+        latency: started.elapsed(),
+        raw_body: String::from_utf8_lossy(&body).into_owned(),
+        rate_limit_info: rate_limit_info.clone(),
+        retry: RetryReport::default(),
+        body: data,
+    };
+
+    Ok((envelope, rate_limit_info, metadata))
+}
+
+/// Deserialize a response body, keeping the failing path and raw body on error
+///
+/// With `lenient` set, a body that fails to deserialize is repaired by removing the
+/// failing field and retrying, so optional fields fall back to their defaults. Each
+/// removed field is returned as a warning. The original error is returned if the
+/// body cannot be repaired.
+pub(crate) fn deserialize_body<T: DeserializeOwned>(
+    body: &[u8],
+    lenient: bool,
+) -> VeniceResult<(T, Vec<ParseWarning>)> {
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let err = match serde_path_to_error::deserialize::<_, T>(&mut deserializer) {
+        Ok(data) => return Ok((data, Vec::new())),
+        Err(err) => err,
+    };
+    let original = VeniceError::parse_body_error(err, body);
+    if !lenient {
+        return Err(original);
+    }
+
+    let mut value = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => value,
+        Err(_) => return Err(original),
+    };
+    let mut warnings = Vec::new();
+    for _ in 0..MAX_LENIENT_REPAIRS {
+        let err = match serde_path_to_error::deserialize::<_, T>(&value) {
+            Ok(data) => {
+                for warning in &warnings {
+                    log::warn!("Dropped response field that failed to parse: {:?}", warning);
+                }
+                return Ok((data, warnings));
+            }
+            Err(err) => err,
+        };
+        if !remove_at_path(&mut value, err.path()) {
+            break;
+        }
+        warnings.push(ParseWarning {
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
         });
     }
+    Err(original)
+}
 
-    match response.json::<T>().await {
-        Ok(data) => Ok((data, rate_limit_info)),
-        Err(err) => Err(VeniceError::ParseError(format!(
-            "Failed to parse response: {}",
-            err
-        ))),
+/// Remove the value at a deserialization path, returning whether anything was removed
+fn remove_at_path(value: &mut serde_json::Value, path: &serde_path_to_error::Path) -> bool {
+    use serde_path_to_error::Segment;
+
+    let segments: Vec<&Segment> = path.iter().collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return false;
+    };
+    let mut current = value;
+    for segment in parents {
+        current = match (segment, current) {
+            (Segment::Seq { index }, serde_json::Value::Array(items)) => match items.get_mut(*index) {
+                Some(item) => item,
+                None => return false,
+            },
+            (Segment::Map { key }, serde_json::Value::Object(fields)) => match fields.get_mut(key) {
+                Some(field) => field,
+                None => return false,
+            },
+            _ => return false,
+        };
+    }
+    match (last, current) {
+        (Segment::Seq { index }, serde_json::Value::Array(items)) if *index < items.len() => {
+            items.remove(*index);
+            true
+        }
+        (Segment::Map { key }, serde_json::Value::Object(fields)) => fields.remove(key).is_some(),
+        _ => false,
     }
 }
 
@@ -73,14 +291,42 @@ pub async fn process_response<T: DeserializeOwned>(
 pub async fn process_binary_response(
     response: Response,
 ) -> VeniceResult<(Vec<u8>, String, RateLimitInfo)> {
+    let (response, mime_type, rate_limit_info) = binary_response_head(response).await?;
+
+    // Get the binary response data
+    let binary_data = response
+        .bytes()
+        .await
+        .map_err(|e| VeniceError::parse_error(format!("Failed to read response bytes: {}", e)))?
+        .to_vec();
+
+    Ok((binary_data, mime_type, rate_limit_info))
+}
+
+/// Process a binary response from the API without buffering its body
+pub async fn process_binary_stream(response: Response) -> VeniceResult<BinaryStream> {
+    let (response, mime_type, rate_limit_info) = binary_response_head(response).await?;
+    let content_length = response.content_length();
+    let stream = response
+        .bytes_stream()
+        .map_err(|e| VeniceError::parse_error(format!("Failed to read response bytes: {}", e)));
+
+    Ok(BinaryStream {
+        mime_type,
+        content_length,
+        rate_limit_info,
+        stream: Box::pin(stream),
+    })
+}
+
+/// Check the status of a binary response and read its content type
+async fn binary_response_head(response: Response) -> VeniceResult<(Response, String, RateLimitInfo)> {
     let rate_limit_info = RateLimitInfo::from_headers(response.headers());
     let status = response.status();
+    let context = ErrorContext::from_headers(response.headers());
 
     if status.as_u16() == 429 {
-        return Err(VeniceError::RateLimitExceeded(format!(
-            "Rate limit exceeded: {}",
-            rate_limit_info
-        )));
+        return Err(rate_limit_error(response.headers()));
     }
 
     if !status.is_success() {
@@ -121,7 +367,7 @@ pub async fn process_binary_response(
             status,
             code,
             message,
-        });
+        }.with_context(context));
     }
 
     // Get the content type
@@ -132,28 +378,119 @@ pub async fn process_binary_response(
         .unwrap_or("application/octet-stream")
         .to_string();
 
-    // Get the binary response data
-    let binary_data = response
-        .bytes()
-        .await
-        .map_err(|e| VeniceError::ParseError(format!("Failed to read response bytes: {}", e)))?
-        .to_vec();
+    Ok((response, mime_type, rate_limit_info))
+}
 
-    Ok((binary_data, mime_type, rate_limit_info))
+/// A binary response body delivered in chunks as it arrives
+///
+/// Use [`write_to`](BinaryStream::write_to) to pipe the body into a file or any other
+/// writer with bounded memory, or poll it as a [`Stream`] of [`Bytes`](bytes::Bytes).
+pub struct BinaryStream {
+    /// MIME type of the body
+    pub mime_type: String,
+    /// Size of the body in bytes, if the server sent it
+    pub content_length: Option<u64>,
+    /// Rate limit information from the response headers
+    pub rate_limit_info: RateLimitInfo,
+    stream: Pin<Box<dyn Stream<Item = VeniceResult<bytes::Bytes>> + Send>>,
+}
+
+impl std::fmt::Debug for BinaryStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinaryStream")
+            .field("mime_type", &self.mime_type)
+            .field("content_length", &self.content_length)
+            .field("rate_limit_info", &self.rate_limit_info)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BinaryStream {
+    /// Call `callback` after every chunk with the bytes received so far and the content length
+    pub fn on_download_progress(
+        mut self,
+        callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static,
+    ) -> Self {
+        let total = self.content_length;
+        let mut received = 0u64;
+        self.stream = Box::pin(self.stream.inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                received += chunk.len() as u64;
+                callback(received, total);
+            }
+        }));
+        self
+    }
+
+    /// Write the whole body to a writer, returning the number of bytes written
+    pub async fn write_to<W>(mut self, writer: &mut W) -> VeniceResult<u64>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let mut written = 0u64;
+        while let Some(chunk) = self.stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Keep the request registered with the client until the body is dropped
+    pub(crate) fn attach(mut self, in_flight: crate::lifecycle::InFlight) -> Self {
+        self.stream = in_flight.attach(self.stream);
+        self
+    }
+}
+
+impl Stream for BinaryStream {
+    type Item = VeniceResult<bytes::Bytes>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
 }
 
 /// Process a streaming response from the API
 pub async fn process_streaming_response<T: DeserializeOwned + 'static + Send>(
     response: Response,
 ) -> VeniceResult<(Pin<Box<dyn Stream<Item = VeniceResult<T>> + Send>>, RateLimitInfo)> {
+    let (events, rate_limit_info) = process_event_stream(response).await?;
+    Ok((parse_events(events), rate_limit_info))
+}
+
+/// Parse the data of server-sent events as JSON, ending at the `[DONE]` marker
+pub(crate) fn parse_events<T: DeserializeOwned + 'static + Send>(
+    events: SseStream,
+) -> Pin<Box<dyn Stream<Item = VeniceResult<T>> + Send>> {
+    Box::pin(
+        events
+            .try_take_while(|event| futures::future::ready(Ok(event.data != "[DONE]")))
+            .and_then(|event| async move { parse_event(&event) }),
+    )
+}
+
+/// Parse the data of a server-sent event as JSON
+pub(crate) fn parse_event<T: DeserializeOwned>(event: &SseEvent) -> VeniceResult<T> {
+    let mut deserializer = serde_json::Deserializer::from_str(&event.data);
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|e| VeniceError::parse_body_error(e, event.data.as_bytes()))
+}
+
+/// Check the status of a streaming response and decode its body into server-sent events
+pub(crate) async fn process_event_stream(response: Response) -> VeniceResult<(SseStream, RateLimitInfo)> {
     let rate_limit_info = RateLimitInfo::from_headers(response.headers());
     let status = response.status();
+    let context = ErrorContext::from_headers(response.headers());
 
     if status.as_u16() == 429 {
-        return Err(VeniceError::RateLimitExceeded(format!(
-            "Rate limit exceeded: {}",
-            rate_limit_info
-        )));
+        return Err(rate_limit_error(response.headers()));
     }
 
     if !status.is_success() {
@@ -194,55 +531,62 @@ pub async fn process_streaming_response<T: DeserializeOwned + 'static + Send>(
             status,
             code,
             message,
-        });
+        }.with_context(context));
     }
 
-    // Create a stream from the response body
-    let stream = response
-        .bytes_stream()
-        .map_err(|e| VeniceError::HttpError(e))
-        .and_then(|chunk| async move {
-            // Each chunk is a SSE message in the format:
-            // data: {...}\n\n
-            let chunk_str = String::from_utf8(chunk.to_vec())
-                .map_err(|e| VeniceError::ParseError(format!("Invalid UTF-8: {}", e)))?;
-            
-            // Process each line in the chunk
-            let mut result = None;
-            for line in chunk_str.lines() {
-                if line.starts_with("data: ") {
-                    let data = line.trim_start_matches("data: ");
-                    if data == "[DONE]" {
-                        // End of stream marker
-                        continue;
-                    }
-                    
-                    // Parse the JSON data
-                    result = Some(serde_json::from_str::<T>(data)
-                        .map_err(|e| VeniceError::ParseError(format!("Failed to parse JSON: {}", e)))?);
-                }
-            }
-            
-            // Return the parsed data if found
-            match result {
-                Some(data) => Ok(data),
-                None => Err(VeniceError::ParseError("No data found in chunk".to_string())),
+    Ok((sse::events(response), rate_limit_info))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Listing {
+        data: Vec<Entry>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Entry {
+        id: String,
+        #[serde(default)]
+        context_size: Option<u32>,
+    }
+
+    const BODY: &[u8] = br#"{"data": [{"id": "a", "context_size": 8192}, {"id": "b", "context_size": "large"}, {"context_size": 1}]}"#;
+
+    #[test]
+    fn test_parse_error_keeps_path_and_body() {
+        let err = deserialize_body::<Listing>(BODY, false).unwrap_err();
+        match &err {
+            VeniceError::ParseError { path, body, .. } => {
+                assert_eq!(path.as_deref(), Some("data[1].context_size"));
+                assert_eq!(body.as_deref().map(str::as_bytes), Some(BODY));
             }
-        })
-        .filter_map(|result| async move {
-            match result {
-                Ok(data) => Some(Ok(data)),
-                Err(e) => {
-                    // Filter out the "No data found in chunk" errors
-                    if let VeniceError::ParseError(msg) = &e {
-                        if msg == "No data found in chunk" {
-                            return None;
-                        }
-                    }
-                    Some(Err(e))
-                }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(err.to_string().ends_with("(at data[1].context_size)"));
+
+        let long = format!(r#"{{"data": "{}"}}"#, "x".repeat(2 * crate::error::MAX_PARSE_ERROR_BODY_LEN));
+        match deserialize_body::<Listing>(long.as_bytes(), false).unwrap_err() {
+            VeniceError::ParseError { body: Some(body), .. } => {
+                assert_eq!(body.len(), crate::error::MAX_PARSE_ERROR_BODY_LEN + 3);
             }
-        });
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
 
-    Ok((Box::pin(stream), rate_limit_info))
-}
\ No newline at end of file
+    #[test]
+    fn test_lenient_parsing_drops_failing_fields() {
+        let (listing, warnings) = deserialize_body::<Listing>(BODY, true).unwrap();
+        let ids: Vec<_> = listing.data.iter().map(|e| (e.id.as_str(), e.context_size)).collect();
+        assert_eq!(ids, vec![("a", Some(8192)), ("b", None)]);
+        assert_eq!(
+            warnings.iter().map(|w| w.path.as_str()).collect::<Vec<_>>(),
+            vec!["data[1].context_size", "data[2]"]
+        );
+
+        // Bodies that are not JSON, or fail at the root, still fail
+        assert!(deserialize_body::<Listing>(b"not json", true).is_err());
+        assert!(deserialize_body::<Listing>(br#"{"items": []}"#, true).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Signing outgoing requests
+//!
+//! A [`RequestSigner`] computes headers from the method, URL and body of each request.
+//! It lets wallet-authenticated users prove who they are with a signature instead of
+//! a bearer API key.
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::VeniceResult;
+
+/// The parts of a request covered by a signature
+#[derive(Debug, Clone, Copy)]
+pub struct SignableRequest<'a> {
+    /// The HTTP method
+    pub method: &'a Method,
+    /// The full URL, including any query string
+    pub url: &'a ::url::Url,
+    /// The exact bytes of the body; empty for requests without a body and for multipart uploads
+    pub body: &'a [u8],
+}
+
+/// Computes signature headers for outgoing requests
+///
+/// The signer is called for every attempt, so signatures that include a timestamp or
+/// nonce stay fresh when a request is sent again.
+///
+/// # Examples
+///
+/// ```
+/// use reqwest::header::{HeaderMap, HeaderValue};
+/// use venice_ai_api_sdk_rust::{RequestSigner, SignableRequest, VeniceError, VeniceResult};
+///
+/// #[derive(Debug)]
+/// struct WalletSigner {
+///     address: String,
+/// }
+///
+/// impl RequestSigner for WalletSigner {
+///     fn sign(&self, request: &SignableRequest<'_>) -> VeniceResult<HeaderMap> {
+///         // Sign the body with the wallet key here
+///         let signature = format!("{}:{}", request.method, request.body.len());
+///
+///         let mut headers = HeaderMap::new();
+///         let value = |s: &str| HeaderValue::from_str(s).map_err(|e| VeniceError::InvalidInput(e.to_string()));
+///         headers.insert("x-venice-address", value(&self.address)?);
+///         headers.insert("x-venice-signature", value(&signature)?);
+///         Ok(headers)
+///     }
+/// }
+/// ```
+pub trait RequestSigner: fmt::Debug + Send + Sync {
+    /// Compute the headers to add to a request
+    fn sign(&self, request: &SignableRequest<'_>) -> VeniceResult<HeaderMap>;
+}
+
+/// Add the signature headers of an optional signer to a request
+pub(crate) fn sign(
+    request: RequestBuilder,
+    signer: Option<&Arc<dyn RequestSigner>>,
+    method: Method,
+    url: &::url::Url,
+    body: &[u8],
+) -> VeniceResult<RequestBuilder> {
+    match signer {
+        Some(signer) => {
+            let headers = signer.sign(&SignableRequest { method: &method, url, body })?;
+            Ok(request.headers(headers))
+        }
+        None => Ok(request),
+    }
+}
@@ -0,0 +1,63 @@
+//! Coalescing of identical concurrent requests
+//!
+//! Callers that ask for the same request while it is in flight wait for its response
+//! instead of sending their own. The response is forgotten once it arrives, so later
+//! calls always send a fresh request.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::http::response_processor::RawResponse;
+
+type SharedResult = Result<RawResponse, Arc<VeniceError>>;
+
+/// In-flight requests keyed by their URL
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    calls: Mutex<HashMap<String, Arc<OnceCell<SharedResult>>>>,
+}
+
+impl SingleFlight {
+    /// Run `fetch` unless an identical request is already in flight, in which case wait for it
+    ///
+    /// The caller whose `fetch` ran gets its error back unchanged; other callers get a copy.
+    /// If that caller is cancelled, a waiting caller sends the request instead.
+    pub(crate) async fn run<F, Fut>(&self, key: &str, fetch: F) -> VeniceResult<RawResponse>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = VeniceResult<RawResponse>>,
+    {
+        let cell = self.lock().entry(key.to_string()).or_default().clone();
+
+        let mut own_error = None;
+        let shared = cell
+            .get_or_init(|| async {
+                fetch().await.map_err(|err| {
+                    let shared = Arc::new(err.duplicate());
+                    own_error = Some(err);
+                    shared
+                })
+            })
+            .await
+            .clone();
+
+        let mut calls = self.lock();
+        if calls.get(key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            calls.remove(key);
+        }
+        drop(calls);
+
+        match own_error {
+            Some(err) => Err(err),
+            None => shared.map_err(|err| err.duplicate()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceCell<SharedResult>>>> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
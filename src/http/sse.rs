@@ -0,0 +1,178 @@
+//! Server-sent events decoding
+//!
+//! Streaming responses are server-sent events. An event is a group of `field: value`
+//! lines ended by a blank line, and network chunks do not line up with events: one chunk
+//! can hold several events, and one event can be split across chunks. [`SseDecoder`]
+//! buffers the body until events are complete, skips comment lines such as the
+//! `: keep-alive` pings some proxies send, and keeps the `id` and `retry` fields needed
+//! to resume a dropped stream.
+
+use futures::{stream, Stream, StreamExt};
+use reqwest::Response;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::error::{VeniceError, VeniceResult};
+
+/// A stream of decoded server-sent events
+pub(crate) type SseStream = Pin<Box<dyn Stream<Item = VeniceResult<SseEvent>> + Send>>;
+
+/// A complete server-sent event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// Type of the event, if the server named one
+    pub event: Option<String>,
+    /// The data lines of the event, joined by newlines
+    pub data: String,
+    /// The last event ID the server sent, up to and including this event
+    pub last_event_id: Option<String>,
+    /// The reconnection delay the server asked for, up to and including this event
+    pub retry: Option<Duration>,
+}
+
+/// Decodes server-sent events from the chunks of a response body
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes of the line not yet ended
+    buffer: Vec<u8>,
+    /// Type of the event being read
+    event: Option<String>,
+    /// Data lines of the event being read
+    data: Vec<String>,
+    /// The last event ID the server sent
+    last_event_id: Option<String>,
+    /// The reconnection delay the server asked for
+    retry: Option<Duration>,
+}
+
+impl SseDecoder {
+    /// Decode a chunk of the body, returning the events it completes
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            events.extend(self.line(line.trim_end_matches(['\n', '\r'])));
+        }
+        events
+    }
+
+    /// Decode the rest of the body once it has ended
+    ///
+    /// An event the server did not end with a blank line is still returned.
+    pub(crate) fn finish(&mut self) -> Vec<SseEvent> {
+        let rest = std::mem::take(&mut self.buffer);
+        let mut events = Vec::new();
+        if !rest.is_empty() {
+            let line = String::from_utf8_lossy(&rest);
+            events.extend(self.line(line.trim_end_matches('\r')));
+        }
+        events.extend(self.line(""));
+        events
+    }
+
+    /// Process one line, returning the event it completes, if any
+    fn line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // A comment, such as a keep-alive ping
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => self.data.push(value.to_string()),
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(millis) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// End the event being read, returning it if it has data
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            event,
+            data: std::mem::take(&mut self.data).join("\n"),
+            last_event_id: self.last_event_id.clone(),
+            retry: self.retry,
+        })
+    }
+}
+
+/// Decode the body of a successful streaming response into events
+pub(crate) fn events(response: Response) -> SseStream {
+    let state = (response.bytes_stream(), SseDecoder::default(), VecDeque::new(), false);
+    Box::pin(stream::unfold(state, |(mut body, mut decoder, mut pending, mut ended)| async move {
+        loop {
+            if let Some(event) = pending.pop_front() {
+                return Some((Ok(event), (body, decoder, pending, ended)));
+            }
+            if ended {
+                return None;
+            }
+            match body.next().await {
+                Some(Ok(chunk)) => pending.extend(decoder.feed(&chunk)),
+                Some(Err(err)) => return Some((Err(VeniceError::HttpError(err)), (body, decoder, pending, true))),
+                None => {
+                    ended = true;
+                    pending.extend(decoder.finish());
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_buffers_events_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        let mut events = decoder.feed(b": keep-alive\n\nretry: 2500\nid: 1\ndata: {\"a\":");
+        assert!(events.is_empty());
+        events.extend(decoder.feed(b"1}\r\n\r\ndata: first\ndata: second\n\nevent: ping\n"));
+        events.extend(decoder.feed(b"data: [DONE]"));
+        events.extend(decoder.finish());
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: None,
+                    data: "{\"a\":1}".to_string(),
+                    last_event_id: Some("1".to_string()),
+                    retry: Some(Duration::from_millis(2500)),
+                },
+                SseEvent {
+                    event: None,
+                    data: "first\nsecond".to_string(),
+                    last_event_id: Some("1".to_string()),
+                    retry: Some(Duration::from_millis(2500)),
+                },
+                SseEvent {
+                    event: Some("ping".to_string()),
+                    data: "[DONE]".to_string(),
+                    last_event_id: Some("1".to_string()),
+                    retry: Some(Duration::from_millis(2500)),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Streaming multipart uploads
+//!
+//! Multipart parts built with [`UploadOptions::part`] read their content from an
+//! `AsyncRead` in fixed-size chunks while the request is sent, so large files are
+//! never held in memory as a whole.
+
+use bytes::Bytes;
+use reqwest::multipart::Part;
+use std::fmt;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Default number of bytes read from the source per chunk
+pub const DEFAULT_UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Callback receiving the bytes sent so far and the total size, if known
+pub type UploadProgressCallback = Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+/// Options for streaming a multipart part from a reader
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, UploadOptions};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let file = tokio::fs::File::open("large.png").await?;
+/// let length = file.metadata().await?.len();
+///
+/// let part = UploadOptions::new()
+///     .chunk_size(256 * 1024)
+///     .on_upload_progress(|sent, total| println!("{} / {:?} bytes", sent, total))
+///     .part(file, Some(length))
+///     .file_name("large.png");
+/// let form = reqwest::multipart::Form::new()
+///     .text("model", "upscaler")
+///     .part("image", part);
+///
+/// let (image, mime_type, _) = client.post_multipart_binary("image/upscale", form).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct UploadOptions {
+    chunk_size: usize,
+    on_upload_progress: Option<UploadProgressCallback>,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_UPLOAD_CHUNK_SIZE,
+            on_upload_progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for UploadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("chunk_size", &self.chunk_size)
+            .field("on_upload_progress", &self.on_upload_progress.is_some())
+            .finish()
+    }
+}
+
+impl UploadOptions {
+    /// Create upload options with the default chunk size and no progress callback
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of bytes read from the source per chunk (at least 1)
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set a callback invoked after every chunk with the bytes sent so far and the total size
+    pub fn on_upload_progress(mut self, callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        self.on_upload_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Create a multipart part that streams its content from a reader
+    ///
+    /// Pass the length of the content when it is known; it is sent as the part's
+    /// length and reported to the progress callback as the total.
+    pub fn part<R>(&self, reader: R, length: Option<u64>) -> Part
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        let body = reqwest::Body::wrap_stream(self.stream(reader, length));
+        match length {
+            Some(length) => Part::stream_with_length(body, length),
+            None => Part::stream(body),
+        }
+    }
+
+    /// Read the source in chunks, reporting progress as each chunk is handed to the request
+    fn stream<R>(
+        &self,
+        reader: R,
+        length: Option<u64>,
+    ) -> impl futures::Stream<Item = std::io::Result<Bytes>> + Send + Sync + 'static
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        let chunk_size = self.chunk_size;
+        let on_progress = self.on_upload_progress.clone();
+        futures::stream::try_unfold((reader, 0u64), move |(mut reader, sent)| {
+            let on_progress = on_progress.clone();
+            async move {
+                let mut chunk = vec![0; chunk_size];
+                let mut filled = 0;
+                while filled < chunk_size {
+                    let read = reader.read(&mut chunk[filled..]).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    filled += read;
+                }
+                if filled == 0 {
+                    return Ok(None);
+                }
+                chunk.truncate(filled);
+
+                let sent = sent + filled as u64;
+                if let Some(on_progress) = &on_progress {
+                    on_progress(sent, length);
+                }
+                Ok(Some((Bytes::from(chunk), (reader, sent))))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{HttpClient, HttpClientConfig};
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_streamed_part_is_uploaded_with_progress() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/image/upscale")
+            .match_body(mockito::Matcher::Regex("abcdefghij".to_string()))
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_body("png")
+            .create_async()
+            .await;
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorded = progress.clone();
+        let part = UploadOptions::new()
+            .chunk_size(4)
+            .on_upload_progress(move |sent, total| recorded.lock().unwrap().push((sent, total)))
+            .part(std::io::Cursor::new(b"abcdefghij".to_vec()), Some(10))
+            .file_name("image.png");
+        let form = reqwest::multipart::Form::new().part("image", part);
+
+        let client = HttpClient::new(HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap())
+        .unwrap();
+        let (body, mime_type, _) = client.post_multipart_binary("image/upscale", form).await.unwrap();
+
+        assert_eq!(body, b"png");
+        assert_eq!(mime_type, "image/png");
+        assert_eq!(*progress.lock().unwrap(), vec![(4, Some(10)), (8, Some(10)), (10, Some(10))]);
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,303 @@
+//! Client-side validation of image generation requests
+//!
+//! Image models publish their limits (dimension divisor, step count, prompt length)
+//! in the `model_spec.constraints` object of the models list. [`ImageConstraints`]
+//! holds those limits, falling back to conservative defaults for anything a model
+//! does not report, and checks requests against them before they are sent.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::utils::validation::validate_number_range;
+
+/// Limits that an image model places on generation requests
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageConstraints {
+    /// Width and height must be multiples of this value
+    pub width_height_divisor: u32,
+    /// Maximum image width in pixels
+    pub max_width: u32,
+    /// Maximum image height in pixels
+    pub max_height: u32,
+    /// Minimum number of diffusion steps
+    pub min_steps: u32,
+    /// Maximum number of diffusion steps
+    pub max_steps: u32,
+    /// Minimum guidance scale
+    pub min_cfg_scale: f32,
+    /// Maximum guidance scale
+    pub max_cfg_scale: f32,
+    /// Maximum prompt length in characters, if the model reports one
+    pub prompt_character_limit: Option<u32>,
+}
+
+impl Default for ImageConstraints {
+    fn default() -> Self {
+        Self {
+            width_height_divisor: 8,
+            max_width: 1280,
+            max_height: 1280,
+            min_steps: 1,
+            max_steps: 50,
+            min_cfg_scale: 0.0,
+            max_cfg_scale: 20.0,
+            prompt_character_limit: None,
+        }
+    }
+}
+
+/// The fields of an image generation request that are checked before sending
+#[derive(Debug, Default)]
+pub(crate) struct ImageRequestFields<'a> {
+    /// ID of the target model
+    pub model: &'a str,
+    /// The prompt
+    pub prompt: &'a str,
+    /// Image width in pixels
+    pub width: Option<u32>,
+    /// Image height in pixels
+    pub height: Option<u32>,
+    /// Number of diffusion steps
+    pub steps: Option<u32>,
+    /// Guidance scale
+    pub cfg_scale: Option<f32>,
+}
+
+impl ImageConstraints {
+    /// Check request fields, collecting every problem into a single `InvalidInput` error
+    pub(crate) fn check(&self, fields: &ImageRequestFields<'_>) -> VeniceResult<()> {
+        let mut errors = Vec::new();
+
+        for (value, max, field_name) in [
+            (fields.width, self.max_width, "width"),
+            (fields.height, self.max_height, "height"),
+        ] {
+            if let Some(value) = value {
+                if self.width_height_divisor > 0 && value % self.width_height_divisor != 0 {
+                    errors.push(format!(
+                        "{} must be a multiple of {}, got {}",
+                        field_name, self.width_height_divisor, value
+                    ));
+                }
+                if let Err(e) = validate_number_range(value, self.width_height_divisor.max(1), max, field_name) {
+                    errors.push(e);
+                }
+            }
+        }
+
+        if let Some(steps) = fields.steps {
+            if let Err(e) = validate_number_range(steps, self.min_steps, self.max_steps, "steps") {
+                errors.push(e);
+            }
+        }
+
+        if let Some(cfg_scale) = fields.cfg_scale {
+            if let Err(e) = validate_number_range(cfg_scale, self.min_cfg_scale, self.max_cfg_scale, "cfg_scale") {
+                errors.push(e);
+            }
+        }
+
+        if let Some(limit) = self.prompt_character_limit {
+            let length = fields.prompt.chars().count();
+            if length > limit as usize {
+                errors.push(format!(
+                    "prompt must be at most {} characters, got {}",
+                    limit, length
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(VeniceError::InvalidInput(format!(
+                "Invalid image generation request for model {}: {}",
+                fields.model,
+                errors.join("; ")
+            )))
+        }
+    }
+}
+
+/// The models list, reduced to the image constraints of each model
+#[derive(Debug, Deserialize)]
+pub(crate) struct ImageModelsResponse {
+    data: Vec<ImageModelEntry>,
+}
+
+/// A model entry of the models list
+#[derive(Debug, Deserialize)]
+struct ImageModelEntry {
+    id: String,
+    #[serde(default)]
+    model_spec: Option<ImageModelSpec>,
+}
+
+/// The `model_spec` object of a model entry
+#[derive(Debug, Deserialize)]
+struct ImageModelSpec {
+    #[serde(default)]
+    constraints: Option<RawImageConstraints>,
+}
+
+/// The `constraints` object as reported by the API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawImageConstraints {
+    #[serde(default)]
+    width_height_divisor: Option<u32>,
+    #[serde(default)]
+    max_width: Option<u32>,
+    #[serde(default)]
+    max_height: Option<u32>,
+    #[serde(default)]
+    steps: Option<RawRange<u32>>,
+    #[serde(default)]
+    cfg_scale: Option<RawRange<f32>>,
+    #[serde(default)]
+    prompt_character_limit: Option<u32>,
+}
+
+/// A range reported by the API, where either bound may be missing
+#[derive(Debug, Deserialize)]
+struct RawRange<T> {
+    #[serde(default)]
+    min: Option<T>,
+    #[serde(default)]
+    max: Option<T>,
+}
+
+impl From<RawImageConstraints> for ImageConstraints {
+    fn from(raw: RawImageConstraints) -> Self {
+        let defaults = ImageConstraints::default();
+        let (min_steps, max_steps) = match raw.steps {
+            Some(range) => (range.min.unwrap_or(defaults.min_steps), range.max.unwrap_or(defaults.max_steps)),
+            None => (defaults.min_steps, defaults.max_steps),
+        };
+        let (min_cfg_scale, max_cfg_scale) = match raw.cfg_scale {
+            Some(range) => (
+                range.min.unwrap_or(defaults.min_cfg_scale),
+                range.max.unwrap_or(defaults.max_cfg_scale),
+            ),
+            None => (defaults.min_cfg_scale, defaults.max_cfg_scale),
+        };
+
+        Self {
+            width_height_divisor: raw.width_height_divisor.unwrap_or(defaults.width_height_divisor),
+            max_width: raw.max_width.unwrap_or(defaults.max_width),
+            max_height: raw.max_height.unwrap_or(defaults.max_height),
+            min_steps,
+            max_steps,
+            min_cfg_scale,
+            max_cfg_scale,
+            prompt_character_limit: raw.prompt_character_limit,
+        }
+    }
+}
+
+impl ImageModelsResponse {
+    /// Convert the models list into constraints keyed by model ID
+    pub(crate) fn into_constraints(self) -> HashMap<String, ImageConstraints> {
+        self.data
+            .into_iter()
+            .map(|entry| {
+                let constraints = entry
+                    .model_spec
+                    .and_then(|spec| spec.constraints)
+                    .map(ImageConstraints::from)
+                    .unwrap_or_default();
+                (entry.id, constraints)
+            })
+            .collect()
+    }
+}
+
+/// A lazily filled cache of image constraints, shared by clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImageConstraintsCache {
+    constraints: Arc<RwLock<Option<HashMap<String, ImageConstraints>>>>,
+}
+
+impl ImageConstraintsCache {
+    /// Check whether the cache has been filled
+    pub(crate) fn is_loaded(&self) -> bool {
+        self.constraints.read().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    /// Fill the cache
+    pub(crate) fn store(&self, constraints: HashMap<String, ImageConstraints>) {
+        *self.constraints.write().unwrap_or_else(|e| e.into_inner()) = Some(constraints);
+    }
+
+    /// Get the constraints of a model, if the model is known
+    pub(crate) fn get(&self, model: &str) -> Option<ImageConstraints> {
+        self.constraints
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .and_then(|constraints| constraints.get(model).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints_from_models_list() {
+        let response: ImageModelsResponse = serde_json::from_value(serde_json::json!({
+            "data": [
+                {
+                    "id": "fluently-xl",
+                    "model_spec": {
+                        "constraints": {
+                            "promptCharacterLimit": 1500,
+                            "steps": {"default": 25, "max": 30},
+                            "widthHeightDivisor": 16
+                        }
+                    }
+                },
+                {"id": "flux-dev"}
+            ]
+        }))
+        .unwrap();
+
+        let constraints = response.into_constraints();
+        let fluently = &constraints["fluently-xl"];
+        assert_eq!(fluently.width_height_divisor, 16);
+        assert_eq!(fluently.max_steps, 30);
+        assert_eq!(fluently.prompt_character_limit, Some(1500));
+        assert_eq!(constraints["flux-dev"], ImageConstraints::default());
+    }
+
+    #[test]
+    fn test_check_reports_all_problems() {
+        let constraints = ImageConstraints::default();
+        let fields = ImageRequestFields {
+            model: "fluently-xl",
+            prompt: "A lighthouse",
+            width: Some(1023),
+            height: Some(2048),
+            steps: Some(80),
+            cfg_scale: Some(7.5),
+        };
+
+        let message = constraints.check(&fields).unwrap_err().to_string();
+        assert!(message.contains("width must be a multiple of 8, got 1023"));
+        assert!(message.contains("height must be between 8 and 1280, got 2048"));
+        assert!(message.contains("steps must be between 1 and 50, got 80"));
+        assert!(!message.contains("cfg_scale"));
+
+        let fields = ImageRequestFields {
+            model: "fluently-xl",
+            prompt: "A lighthouse",
+            width: Some(1024),
+            height: Some(768),
+            steps: Some(30),
+            cfg_scale: Some(7.5),
+        };
+        assert!(constraints.check(&fields).is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+//! Improving image prompts with a chat model
+//!
+//! [`Client::enhance_prompt`](crate::Client::enhance_prompt) asks a chat model to rewrite
+//! a short idea into a detailed image prompt and a negative prompt, the usual step
+//! before generating an image. The model replies in JSON schema mode, and
+//! [`EnhancedPrompt::builder`] turns the reply into an image generation request.
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::schema::{self, VeniceSchema};
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder, ResponseFormat};
+use crate::traits::image::ImageGenerateBuilder;
+
+/// Instructions sent to the chat model unless the options replace them
+pub const DEFAULT_ENHANCE_TEMPLATE: &str = "You write prompts for image generation models. \
+Rewrite the user's idea as one detailed prompt describing the subject, composition, \
+lighting, style and medium, keeping everything the user asked for. Also write a negative \
+prompt listing what the image should not contain, such as artifacts and unwanted elements. \
+Reply with JSON only.";
+
+/// Options for [`Client::enhance_prompt`](crate::Client::enhance_prompt)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnhanceOptions {
+    /// Chat model rewriting the prompt, the client's default chat model if `None`
+    pub model: Option<String>,
+    /// Instructions sent as the system message
+    pub template: String,
+    /// Style the prompt should aim for, such as "watercolor" or "35mm photo"
+    pub style: Option<String>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Maximum number of tokens of the reply
+    pub max_tokens: Option<u32>,
+}
+
+impl Default for EnhanceOptions {
+    fn default() -> Self {
+        Self {
+            model: None,
+            template: DEFAULT_ENHANCE_TEMPLATE.to_string(),
+            style: None,
+            temperature: None,
+            max_tokens: Some(512),
+        }
+    }
+}
+
+impl EnhanceOptions {
+    /// Create options with default values: the default template and at most 512 tokens
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the chat model rewriting the prompt
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Replace the instructions sent as the system message
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Set the style the prompt should aim for
+    pub fn style(mut self, style: impl Into<String>) -> Self {
+        self.style = Some(style.into());
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the maximum number of tokens of the reply
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+}
+
+/// An image prompt rewritten by [`Client::enhance_prompt`](crate::Client::enhance_prompt)
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct EnhancedPrompt {
+    /// The detailed prompt
+    pub prompt: String,
+    /// What the image should not contain
+    pub negative_prompt: String,
+}
+
+impl VeniceSchema for EnhancedPrompt {
+    fn schema_name() -> String {
+        "EnhancedPrompt".to_string()
+    }
+
+    fn schema() -> schema::Value {
+        schema::object(vec![
+            ("prompt", schema::describe(String::schema(), Some("Detailed image prompt"))),
+            ("negative_prompt", schema::describe(String::schema(), Some("What the image should not contain"))),
+        ])
+    }
+}
+
+impl EnhancedPrompt {
+    /// Start an image generation request with both prompts
+    pub fn builder(&self, model: impl Into<String>) -> ImageGenerateBuilder {
+        let builder = ImageGenerateBuilder::new(model, self.prompt.clone());
+        if self.negative_prompt.is_empty() {
+            builder
+        } else {
+            builder.negative_prompt(self.negative_prompt.clone())
+        }
+    }
+}
+
+/// Ask `model` to rewrite `prompt` for image generation
+pub(crate) async fn enhance_prompt<A: ChatApi + Sync + ?Sized>(
+    api: &A,
+    model: &str,
+    prompt: &str,
+    options: &EnhanceOptions,
+) -> VeniceResult<EnhancedPrompt> {
+    if prompt.trim().is_empty() {
+        return Err(VeniceError::InvalidInput("Cannot enhance an empty prompt".to_string()));
+    }
+    let mut builder = ChatCompletionBuilder::new(model)
+        .add_system(options.template.clone())
+        .response_format(ResponseFormat::json_schema(EnhancedPrompt::schema_name(), EnhancedPrompt::schema()));
+    builder = match &options.style {
+        Some(style) => builder.add_user(format!("{}\n\nStyle: {}", prompt, style)),
+        None => builder.add_user(prompt),
+    };
+    if let Some(temperature) = options.temperature {
+        builder = builder.temperature(temperature);
+    }
+    if let Some(max_tokens) = options.max_tokens {
+        builder = builder.max_tokens(max_tokens);
+    }
+
+    let (response, _) = api.create_chat_completion(builder.build()).await?;
+    let content = response
+        .choices
+        .first()
+        .map(|choice| choice.message.content.as_str())
+        .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+    let enhanced: EnhancedPrompt = serde_json::from_str(schema::strip_code_fence(content))
+        .map_err(|e| VeniceError::parse_error(format!("Reply is not an enhanced prompt: {}", e)))?;
+    if enhanced.prompt.trim().is_empty() {
+        return Err(VeniceError::parse_error("Reply has an empty prompt"));
+    }
+    Ok(enhanced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Client;
+
+    #[tokio::test]
+    async fn test_enhance_prompt() {
+        let mut server = mockito::Server::new_async().await;
+        let reply = serde_json::json!({
+            "prompt": "A lighthouse on a cliff at dusk, crashing waves, warm light, watercolor",
+            "negative_prompt": "blurry, text, watermark"
+        });
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "model": "llama-3.3-70b",
+                "response_format": {"type": "json_schema"},
+                "messages": [
+                    {"role": "system", "content": DEFAULT_ENHANCE_TEMPLATE},
+                    {"role": "user", "content": "a lighthouse\n\nStyle: watercolor"}
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": format!("```json\n{}\n```", reply)},
+                    "finish_reason": "stop"
+                }]
+            }).to_string())
+            .create_async()
+            .await;
+        let client = Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .default_chat_model("llama-3.3-70b")
+            .build()
+            .unwrap();
+
+        let enhanced = client.enhance_prompt("a lighthouse", EnhanceOptions::new().style("watercolor")).await.unwrap();
+        assert!(enhanced.prompt.starts_with("A lighthouse on a cliff"));
+        let request = enhanced.builder("fluently-xl").build();
+        assert_eq!(request.negative_prompt.as_deref(), Some("blurry, text, watermark"));
+        mock.assert_async().await;
+
+        let err = client.enhance_prompt("  ", EnhanceOptions::new()).await.unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(_)));
+    }
+}
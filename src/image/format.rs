@@ -0,0 +1,141 @@
+//! Output format and quality of generated images
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::error::{VeniceError, VeniceResult};
+
+/// File format of a generated or upscaled image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    /// Lossless PNG
+    Png,
+    /// WebP
+    Webp,
+    /// JPEG
+    Jpeg,
+}
+
+impl ImageFormat {
+    /// Formats accepted by the image generation endpoint
+    pub const GENERATE: &'static [ImageFormat] = &[ImageFormat::Png, ImageFormat::Webp, ImageFormat::Jpeg];
+
+    /// Formats accepted by the image upscaling endpoint
+    pub const UPSCALE: &'static [ImageFormat] = &[ImageFormat::Png, ImageFormat::Jpeg];
+
+    /// The name the API uses for the format
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Jpeg => "jpeg",
+        }
+    }
+
+    /// The MIME type of images in this format
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// Get the format of a MIME type, ignoring parameters such as `; charset=binary`
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+        [ImageFormat::Png, ImageFormat::Webp, ImageFormat::Jpeg]
+            .into_iter()
+            .find(|format| essence.eq_ignore_ascii_case(format.mime_type()) || (*format == ImageFormat::Jpeg && essence.eq_ignore_ascii_case("image/jpg")))
+    }
+
+    /// The usual file extension of the format, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
+
+    /// Whether the format takes a quality setting
+    pub fn is_lossy(&self) -> bool {
+        !matches!(self, ImageFormat::Png)
+    }
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Check the output format and quality of a request against what an endpoint accepts
+pub(crate) fn check_output(
+    endpoint: &str,
+    supported: &[ImageFormat],
+    format: Option<ImageFormat>,
+    quality: Option<u8>,
+) -> VeniceResult<()> {
+    if let Some(format) = format {
+        if !supported.contains(&format) {
+            let supported: Vec<&str> = supported.iter().map(ImageFormat::as_str).collect();
+            return Err(VeniceError::InvalidInput(format!(
+                "{} does not support the {} format; expected one of: {}",
+                endpoint,
+                format,
+                supported.join(", ")
+            )));
+        }
+    }
+
+    if let Some(quality) = quality {
+        if !(1..=100).contains(&quality) {
+            return Err(VeniceError::InvalidInput(format!(
+                "quality must be between 1 and 100, got {}",
+                quality
+            )));
+        }
+        if format == Some(ImageFormat::Png) {
+            return Err(VeniceError::InvalidInput(
+                "quality cannot be set for the lossless png format".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_output() {
+        assert!(check_output("image/generate", ImageFormat::GENERATE, Some(ImageFormat::Webp), Some(80)).is_ok());
+        assert!(check_output("image/generate", ImageFormat::GENERATE, None, Some(80)).is_ok());
+
+        let message = check_output("image/upscale", ImageFormat::UPSCALE, Some(ImageFormat::Webp), None)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("image/upscale does not support the webp format; expected one of: png, jpeg"));
+
+        assert!(check_output("image/generate", ImageFormat::GENERATE, Some(ImageFormat::Jpeg), Some(0)).is_err());
+        assert!(check_output("image/generate", ImageFormat::GENERATE, Some(ImageFormat::Png), Some(90)).is_err());
+    }
+
+    #[test]
+    fn test_format_serialization() {
+        assert_eq!(serde_json::to_string(&ImageFormat::Jpeg).unwrap(), r#""jpeg""#);
+        assert_eq!(serde_json::from_str::<ImageFormat>(r#""webp""#).unwrap(), ImageFormat::Webp);
+    }
+
+    #[test]
+    fn test_from_mime_type() {
+        assert_eq!(ImageFormat::from_mime_type("image/PNG"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_mime_type("image/jpg; charset=binary"), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::from_mime_type("application/octet-stream"), None);
+        assert_eq!(ImageFormat::Jpeg.extension(), "jpg");
+    }
+}
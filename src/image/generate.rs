@@ -3,12 +3,13 @@ use std::collections::HashMap;
 
 use crate::{
     client::Client,
-    error::{RateLimitInfo, VeniceResult},
+    endpoints,
+    error::{RateLimitInfo, VeniceError, VeniceResult},
+    image::{check_output, ImageConstraints, ImageFormat, ImageRequestFields, Seed},
+    observer::RequestLabels,
+    telemetry::ApiSpan,
 };
 
-/// The endpoint for image generation
-const IMAGE_GENERATE_ENDPOINT: &str = "image/generate";
-
 /// Request for image generation
 #[derive(Debug, Clone, Serialize)]
 pub struct ImageGenerateRequest {
@@ -36,7 +37,7 @@ pub struct ImageGenerateRequest {
     pub cfg_scale: Option<f32>,
     /// Random seed for reproducible results
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub seed: Option<u64>,
+    pub seed: Option<Seed>,
     /// Strength of LoRA adaptation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lora_strength: Option<u32>,
@@ -49,6 +50,18 @@ pub struct ImageGenerateRequest {
     /// Remove the watermark from the generated image
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hide_watermark: Option<bool>,
+    /// File format of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ImageFormat>,
+    /// Quality of lossy formats, from 1 to 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
+    /// ID of the end user, forwarded to the API for abuse tracking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Tags for grouping requests in usage analytics
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub request_tags: Vec<String>,
     /// Additional custom parameters
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -93,7 +106,7 @@ pub struct ImageGenerateRequestDetails {
     pub steps: Option<u32>,
     /// Seed used for generation
     #[serde(default)]
-    pub seed: Option<u64>,
+    pub seed: Option<Seed>,
     /// Additional fields (captured as a map)
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -124,7 +137,7 @@ pub struct ImageData {
     pub revised_prompt: Option<String>,
     /// Seed that was used for generation
     #[serde(default)]
-    pub seed: Option<u64>,
+    pub seed: Option<Seed>,
 }
 
 impl Default for ImageGenerateRequest {
@@ -143,11 +156,97 @@ impl Default for ImageGenerateRequest {
             safe_mode: None,
             return_binary: None,
             hide_watermark: None,
+            format: None,
+            quality: None,
+            user: None,
+            request_tags: Vec::new(),
             extra: HashMap::new(),
         }
     }
 }
 
+impl ImageGenerateRequest {
+    /// Check the request against the constraints of an image model
+    ///
+    /// Requests are checked automatically before they are sent; use
+    /// [`Client::image_constraints`] to fetch the constraints for a model.
+    pub fn validate_against(&self, constraints: &ImageConstraints) -> VeniceResult<()> {
+        constraints.check(&self.fields())
+    }
+
+    /// The fields checked by image request validation
+    pub(crate) fn fields(&self) -> ImageRequestFields<'_> {
+        ImageRequestFields {
+            model: &self.model,
+            prompt: &self.prompt,
+            width: self.width,
+            height: self.height,
+            steps: self.steps,
+            cfg_scale: self.cfg_scale,
+        }
+    }
+
+    /// Create a request that reproduces a previous generation
+    ///
+    /// The model, prompt, dimensions, steps and seed are copied from the request details
+    /// returned by the API. Any other parameters echoed back by the API (such as
+    /// `negative_prompt`, `style_preset` or `cfg_scale`) are restored as well, so the
+    /// resulting request can be sent as-is or tweaked before sending.
+    pub fn from_details(details: &ImageGenerateRequestDetails) -> Self {
+        let mut extra = details.extra.clone();
+        
+        let negative_prompt = take_string(&mut extra, "negative_prompt");
+        let style_preset = take_string(&mut extra, "style_preset");
+        let cfg_scale = extra
+            .remove("cfg_scale")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32);
+        let lora_strength = extra
+            .remove("lora_strength")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+        let safe_mode = extra.remove("safe_mode").and_then(|v| v.as_bool());
+        let return_binary = extra.remove("return_binary").and_then(|v| v.as_bool());
+        let hide_watermark = extra.remove("hide_watermark").and_then(|v| v.as_bool());
+        let format = extra
+            .remove("format")
+            .and_then(|v| serde_json::from_value(v).ok());
+        let quality = extra
+            .remove("quality")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u8::try_from(v).ok());
+        
+        Self {
+            model: details.model.clone(),
+            prompt: details.prompt.clone(),
+            negative_prompt,
+            style_preset,
+            height: details.height,
+            width: details.width,
+            steps: details.steps,
+            cfg_scale,
+            seed: details.seed,
+            lora_strength,
+            safe_mode,
+            return_binary,
+            hide_watermark,
+            format,
+            quality,
+            user: None,
+            request_tags: Vec::new(),
+            extra,
+        }
+    }
+}
+
+/// Remove a string value from a map of extra fields
+fn take_string(extra: &mut HashMap<String, serde_json::Value>, key: &str) -> Option<String> {
+    match extra.remove(key) {
+        Some(serde_json::Value::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
 /// Builder for image generation requests
 #[derive(Debug, Clone)]
 pub struct ImageGenerateRequestBuilder {
@@ -202,9 +301,9 @@ impl ImageGenerateRequestBuilder {
         self
     }
 
-    /// Set the random seed
-    pub fn with_seed(mut self, seed: u64) -> Self {
-        self.request.seed = Some(seed);
+    /// Set the random seed, such as [`Seed::random`]
+    pub fn with_seed(mut self, seed: impl Into<Seed>) -> Self {
+        self.request.seed = Some(seed.into());
         self
     }
 
@@ -232,6 +331,33 @@ impl ImageGenerateRequestBuilder {
         self
     }
 
+    /// Set the file format of the generated image
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.request.format = Some(format);
+        self
+    }
+    
+    /// Set the quality of lossy formats, from 1 to 100
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.request.quality = Some(quality);
+        self
+    }
+    
+    /// Set the ID of the end user, forwarded to the API for abuse tracking
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.request.user = Some(user_id.into());
+        self
+    }
+
+    /// Add a tag for grouping requests in usage analytics
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.request.request_tags.contains(&tag) {
+            self.request.request_tags.push(tag);
+        }
+        self
+    }
+
     /// Add a custom parameter to the request
     pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
         self.request.extra.insert(key.into(), value.into());
@@ -249,7 +375,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{
     ///     Client,
     ///     image::ImageGenerateRequestBuilder,
@@ -281,13 +407,21 @@ impl Client {
         &self,
         request: ImageGenerateRequest,
     ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
-        let (mut response, rate_limit_info): (ImageGenerateResponse, RateLimitInfo) = self.post(IMAGE_GENERATE_ENDPOINT, &request).await?;
+        let mut request = request;
+        self.apply_content_policy(&mut request);
+        check_output(endpoints::IMAGE_GENERATE, ImageFormat::GENERATE, request.format, request.quality)?;
+        self.check_image_request(&request.fields()).await?;
+        
+        let span = ApiSpan::image_generate(&request.model);
+        let labels = RequestLabels::new(request.user.as_deref(), &request.request_tags);
+        let result = labels.scope(span.instrument(self.post(endpoints::IMAGE_GENERATE, &request))).await;
+        let (mut response, rate_limit_info): (ImageGenerateResponse, RateLimitInfo) = span.finish(result, |_| None)?;
         
         // For backward compatibility, populate the old fields from the new response format
         response.created = chrono::Utc::now().timestamp() as u64;
         
         // Convert images array to the old data format
-        response.data = response.images.iter().enumerate().map(|(_i, img_data)| {
+        response.data = response.images.iter().map(|img_data| {
             ImageData {
                 // We don't have URLs in the new format, just base64 data
                 url: None,
@@ -299,13 +433,59 @@ impl Client {
         
         Ok((response, rate_limit_info))
     }
+    
+    /// Generate images again using the request details of a previous response
+    ///
+    /// Returns an error if the response does not include request details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{
+    ///     Client,
+    ///     image::{ImageGenerateRequest, ImageGenerateRequestBuilder},
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your-api-key")?;
+    ///
+    ///     let request = ImageGenerateRequestBuilder::new("fluently-xl", "A lighthouse at dusk").build();
+    ///     let (first, _) = client.generate_image(request).await?;
+    ///
+    ///     // Reproduce the same image
+    ///     let (_again, _) = client.regenerate(&first).await?;
+    ///
+    ///     // Or tweak the previous request before sending it
+    ///     if let Some(details) = &first.request {
+    ///         let mut request = ImageGenerateRequest::from_details(details);
+    ///         request.steps = Some(40);
+    ///         let (_tweaked, _) = client.generate_image(request).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn regenerate(
+        &self,
+        previous: &ImageGenerateResponse,
+    ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
+        let details = previous.request.as_ref().ok_or_else(|| {
+            VeniceError::InvalidInput(format!(
+                "Image generation response {} does not include request details",
+                previous.id
+            ))
+        })?;
+        
+        self.generate_image(ImageGenerateRequest::from_details(details)).await
+    }
 }
 
 /// Helper function to generate images
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::image::{
 ///     generate_image,
 ///     ImageGenerateRequestBuilder,
@@ -337,4 +517,40 @@ pub async fn generate_image(
 ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
     let client = Client::new(api_key)?;
     client.generate_image(request).await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_request_from_details() {
+        let details: ImageGenerateRequestDetails = serde_json::from_value(serde_json::json!({
+            "model": "fluently-xl",
+            "prompt": "A lighthouse at dusk",
+            "width": 1024,
+            "height": 768,
+            "steps": 30,
+            "seed": 12345,
+            "negative_prompt": "people",
+            "cfg_scale": 7.5,
+            "safe_mode": false,
+            "embed_exif_metadata": true
+        }))
+        .unwrap();
+        
+        let request = ImageGenerateRequest::from_details(&details);
+        
+        assert_eq!(request.model, "fluently-xl");
+        assert_eq!(request.prompt, "A lighthouse at dusk");
+        assert_eq!(request.width, Some(1024));
+        assert_eq!(request.height, Some(768));
+        assert_eq!(request.steps, Some(30));
+        assert_eq!(request.seed, Some(Seed::new(12345)));
+        assert_eq!(request.negative_prompt.as_deref(), Some("people"));
+        assert_eq!(request.cfg_scale, Some(7.5));
+        assert_eq!(request.safe_mode, Some(false));
+        assert_eq!(request.extra.len(), 1);
+        assert_eq!(request.extra["embed_exif_metadata"], serde_json::json!(true));
+    }
+}
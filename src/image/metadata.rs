@@ -0,0 +1,370 @@
+//! Recording how generated images were made
+//!
+//! [`ImageMetadataWriter`] saves an image together with the parameters that produced it,
+//! either as text chunks inside the PNG or as a JSON file next to the image, so the
+//! image can be generated again later.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::image::Seed;
+
+/// The signature at the start of every PNG file
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// The parameters an image was generated with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    /// The model used for generation
+    pub model: String,
+    /// The prompt used for generation
+    pub prompt: String,
+    /// The negative prompt, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_prompt: Option<String>,
+    /// The style preset, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_preset: Option<String>,
+    /// The random seed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<Seed>,
+    /// The number of diffusion steps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<u32>,
+    /// The guidance scale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_scale: Option<f32>,
+    /// Width of the image in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Height of the image in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+impl ImageMetadata {
+    /// Set the seed, such as the one the API reports when the request did not set one
+    pub fn with_seed(mut self, seed: impl Into<Seed>) -> Self {
+        self.seed = Some(seed.into());
+        self
+    }
+
+    /// Read metadata written by [`ImageMetadataWriter`] from the text chunks of a PNG
+    ///
+    /// Returns `None` if the data is not a PNG or has no prompt recorded.
+    pub fn from_png(png: &[u8]) -> Option<Self> {
+        let mut metadata = Self::default();
+        let mut found = false;
+        for (keyword, text) in png_chunks(png)?.filter_map(|(kind, data)| text_chunk(kind, data)) {
+            found |= metadata.set(&keyword, text);
+        }
+        found.then_some(metadata)
+    }
+
+    /// The fields as PNG text entries, in a fixed order
+    fn entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![("prompt", self.prompt.clone()), ("model", self.model.clone())];
+        let optional = [
+            ("negative_prompt", self.negative_prompt.clone()),
+            ("style_preset", self.style_preset.clone()),
+            ("seed", self.seed.map(|seed| seed.to_string())),
+            ("steps", self.steps.map(|steps| steps.to_string())),
+            ("cfg_scale", self.cfg_scale.map(|cfg_scale| cfg_scale.to_string())),
+            ("width", self.width.map(|width| width.to_string())),
+            ("height", self.height.map(|height| height.to_string())),
+        ];
+        entries.extend(optional.into_iter().filter_map(|(key, value)| value.map(|value| (key, value))));
+        entries
+    }
+
+    /// Set a field from a PNG text entry, returning whether the keyword is the prompt
+    fn set(&mut self, keyword: &str, text: String) -> bool {
+        match keyword {
+            "prompt" => {
+                self.prompt = text;
+                return true;
+            }
+            "model" => self.model = text,
+            "negative_prompt" => self.negative_prompt = Some(text),
+            "style_preset" => self.style_preset = Some(text),
+            "seed" => self.seed = text.parse().ok(),
+            "steps" => self.steps = text.parse().ok(),
+            "cfg_scale" => self.cfg_scale = text.parse().ok(),
+            "width" => self.width = text.parse().ok(),
+            "height" => self.height = text.parse().ok(),
+            _ => {}
+        }
+        false
+    }
+}
+
+impl From<&crate::traits::image::ImageGenerateRequest> for ImageMetadata {
+    fn from(request: &crate::traits::image::ImageGenerateRequest) -> Self {
+        Self {
+            model: request.model.clone(),
+            prompt: request.prompt.clone(),
+            negative_prompt: request.negative_prompt.clone(),
+            style_preset: request.style_preset.clone(),
+            seed: request.seed,
+            steps: request.steps,
+            cfg_scale: request.cfg_scale,
+            width: request.width,
+            height: request.height,
+        }
+    }
+}
+
+impl From<&crate::image::ImageGenerateRequest> for ImageMetadata {
+    fn from(request: &crate::image::ImageGenerateRequest) -> Self {
+        Self {
+            model: request.model.clone(),
+            prompt: request.prompt.clone(),
+            negative_prompt: request.negative_prompt.clone(),
+            style_preset: request.style_preset.clone(),
+            seed: request.seed,
+            steps: request.steps,
+            cfg_scale: request.cfg_scale,
+            width: request.width,
+            height: request.height,
+        }
+    }
+}
+
+/// Where [`ImageMetadataWriter`] records the metadata of an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataFormat {
+    /// Text chunks inside the PNG; other image formats get a JSON sidecar instead
+    PngText,
+    /// A JSON file next to the image, named after it with `.json` appended
+    Sidecar,
+}
+
+/// Saves generated images together with the parameters that produced them
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     image::ImageMetadataWriter,
+///     traits::image::{ImageApi, ImageGenerateBuilder},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let request = ImageGenerateBuilder::new("fluently-xl", "A lighthouse at dusk")
+///     .negative_prompt("people")
+///     .steps(30)
+///     .build();
+/// let (response, _) = ImageApi::generate_image(&client, request.clone()).await?;
+///
+/// let writer = ImageMetadataWriter::png_text();
+/// response.save_image_with_metadata(0, "lighthouse.png", &writer, &request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageMetadataWriter {
+    format: MetadataFormat,
+}
+
+impl ImageMetadataWriter {
+    /// Create a writer that records metadata in the given format
+    pub fn new(format: MetadataFormat) -> Self {
+        Self { format }
+    }
+
+    /// Create a writer that records metadata in PNG text chunks
+    pub fn png_text() -> Self {
+        Self::new(MetadataFormat::PngText)
+    }
+
+    /// Create a writer that records metadata in a JSON sidecar file
+    pub fn sidecar() -> Self {
+        Self::new(MetadataFormat::Sidecar)
+    }
+
+    /// Get the format metadata is recorded in
+    pub fn format(&self) -> MetadataFormat {
+        self.format
+    }
+
+    /// Get the path of the sidecar file for an image
+    pub fn sidecar_path(path: impl AsRef<Path>) -> PathBuf {
+        let mut sidecar = path.as_ref().as_os_str().to_owned();
+        sidecar.push(".json");
+        PathBuf::from(sidecar)
+    }
+
+    /// Write an image and its metadata
+    pub async fn save(
+        &self,
+        path: impl AsRef<Path>,
+        image: &[u8],
+        metadata: &ImageMetadata,
+    ) -> VeniceResult<()> {
+        let path = path.as_ref();
+        if self.format == MetadataFormat::PngText {
+            if let Some(png) = embed_png_text(image, &metadata.entries()) {
+                tokio::fs::write(path, png).await?;
+                return Ok(());
+            }
+        }
+
+        let sidecar = serde_json::to_vec_pretty(metadata)
+            .map_err(|e| VeniceError::InvalidInput(format!("Failed to serialize image metadata: {}", e)))?;
+        tokio::fs::write(path, image).await?;
+        tokio::fs::write(Self::sidecar_path(path), sidecar).await?;
+        Ok(())
+    }
+}
+
+/// Iterate over the chunks of a PNG as (type, data) pairs, or `None` if it is not a PNG
+fn png_chunks(png: &[u8]) -> Option<impl Iterator<Item = (&[u8], &[u8])>> {
+    let mut rest = png.strip_prefix(PNG_SIGNATURE)?;
+    Some(std::iter::from_fn(move || {
+        if rest.len() < 12 {
+            return None;
+        }
+        let length = u32::from_be_bytes(rest[..4].try_into().ok()?) as usize;
+        let end = 8usize.checked_add(length)?.checked_add(4)?;
+        if rest.len() < end {
+            return None;
+        }
+        let chunk = (&rest[4..8], &rest[8..8 + length]);
+        rest = &rest[end..];
+        Some(chunk)
+    }))
+}
+
+/// Decode a `tEXt` or uncompressed `iTXt` chunk into its keyword and text
+fn text_chunk(kind: &[u8], data: &[u8]) -> Option<(String, String)> {
+    let separator = data.iter().position(|&byte| byte == 0)?;
+    let keyword = String::from_utf8_lossy(&data[..separator]).into_owned();
+    let rest = &data[separator + 1..];
+    match kind {
+        b"tEXt" => Some((keyword, rest.iter().map(|&byte| byte as char).collect())),
+        b"iTXt" => {
+            // Compression flag and method, then the language tag and translated keyword
+            let [0, _, rest @ ..] = rest else {
+                return None;
+            };
+            let language_end = rest.iter().position(|&byte| byte == 0)?;
+            let rest = &rest[language_end + 1..];
+            let translated_end = rest.iter().position(|&byte| byte == 0)?;
+            let text = std::str::from_utf8(&rest[translated_end + 1..]).ok()?;
+            Some((keyword, text.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Insert text chunks after the header of a PNG, or return `None` if it is not a PNG
+///
+/// ASCII text is written as `tEXt`; anything else as `iTXt`, which holds UTF-8.
+fn embed_png_text(png: &[u8], entries: &[(&str, String)]) -> Option<Vec<u8>> {
+    let (kind, header) = png_chunks(png)?.next()?;
+    if kind != b"IHDR" {
+        return None;
+    }
+    let header_end = PNG_SIGNATURE.len() + 12 + header.len();
+
+    let mut output = Vec::with_capacity(png.len() + 64 * entries.len());
+    output.extend_from_slice(&png[..header_end]);
+    for (keyword, text) in entries {
+        let mut data = keyword.as_bytes().to_vec();
+        data.push(0);
+        if text.is_ascii() {
+            data.extend_from_slice(text.as_bytes());
+            write_chunk(&mut output, b"tEXt", &data);
+        } else {
+            // Uncompressed, with empty language tag and translated keyword
+            data.extend_from_slice(&[0, 0, 0, 0]);
+            data.extend_from_slice(text.as_bytes());
+            write_chunk(&mut output, b"iTXt", &data);
+        }
+    }
+    output.extend_from_slice(&png[header_end..]);
+    Some(output)
+}
+
+/// Append a chunk with its length and checksum
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(kind);
+    output.extend_from_slice(data);
+    output.extend_from_slice(&crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+/// The CRC-32 used by PNG chunks
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 1x1 transparent PNG
+    const PIXEL: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+    fn metadata() -> ImageMetadata {
+        ImageMetadata {
+            model: "fluently-xl".to_string(),
+            prompt: "Un phare au crépuscule".to_string(),
+            negative_prompt: Some("people".to_string()),
+            seed: Some(Seed::new(-42)),
+            steps: Some(30),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND".iter()), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_png_text_round_trip() {
+        let png = base64::decode(PIXEL).unwrap();
+        let written = embed_png_text(&png, &metadata().entries()).unwrap();
+
+        assert_eq!(ImageMetadata::from_png(&written), Some(metadata()));
+        assert_eq!(ImageMetadata::from_png(&png), None);
+        assert!(embed_png_text(b"RIFF....WEBP", &metadata().entries()).is_none());
+
+        // Every chunk of the original image is still there, in order
+        let kinds: Vec<&[u8]> = png_chunks(&written).unwrap().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds.first(), Some(&&b"IHDR"[..]));
+        assert_eq!(kinds.last(), Some(&&b"IEND"[..]));
+    }
+
+    #[tokio::test]
+    async fn test_save_falls_back_to_sidecar() {
+        let directory = std::env::temp_dir().join(format!("venice-metadata-{}", std::process::id()));
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+
+        let png_path = directory.join("image.png");
+        let png = base64::decode(PIXEL).unwrap();
+        ImageMetadataWriter::png_text().save(&png_path, &png, &metadata()).await.unwrap();
+        let saved = tokio::fs::read(&png_path).await.unwrap();
+        assert_eq!(ImageMetadata::from_png(&saved), Some(metadata()));
+        assert!(!ImageMetadataWriter::sidecar_path(&png_path).exists());
+
+        let webp_path = directory.join("image.webp");
+        ImageMetadataWriter::png_text().save(&webp_path, b"RIFF....WEBP", &metadata()).await.unwrap();
+        let sidecar = tokio::fs::read(directory.join("image.webp.json")).await.unwrap();
+        assert_eq!(serde_json::from_slice::<ImageMetadata>(&sidecar).unwrap(), metadata());
+
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
@@ -2,10 +2,25 @@
 //!
 //! This module contains types and functions for working with Venice.ai's image API.
 
+mod constraints;
+mod enhance;
+mod format;
 mod generate;
+mod metadata;
+mod queue;
+mod seed;
 mod styles;
 mod upscale;
 
+pub use constraints::ImageConstraints;
+pub use enhance::{EnhanceOptions, EnhancedPrompt, DEFAULT_ENHANCE_TEMPLATE};
+pub use format::ImageFormat;
+pub(crate) use format::check_output;
+pub(crate) use enhance::enhance_prompt;
+pub(crate) use constraints::{ImageConstraintsCache, ImageModelsResponse, ImageRequestFields};
 pub use generate::*;
+pub use metadata::{ImageMetadata, ImageMetadataWriter, MetadataFormat};
+pub use seed::Seed;
+pub use queue::{ImageJob, ImageJobResult, InMemoryJobStore, JobQueue, JobQueueReport, JobStatus, JobStore, JsonFileJobStore};
 pub use styles::*;
 pub use upscale::*;
\ No newline at end of file
@@ -0,0 +1,521 @@
+//! Persistent queue of image generation jobs
+//!
+//! A [`JobQueue`] stores image generation requests in a [`JobStore`] and runs them
+//! with a fixed number of workers. Every change of a job is saved before work moves
+//! on, so a batch interrupted by a crash or restart picks up where it stopped: jobs
+//! that were running are sent again and finished jobs keep their images.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::rate_limit::RateLimiter;
+use crate::retry::is_retryable_error;
+use crate::traits::image::{ImageApi, ImageGenerateRequest};
+
+/// State of an image generation job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for a worker
+    Pending,
+    /// Sent to the API
+    Running,
+    /// Finished with images
+    Completed,
+    /// Given up on
+    Failed,
+}
+
+/// Images produced by a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageJobResult {
+    /// The ID the API gave the generation
+    pub id: String,
+    /// The generated images, base64 encoded
+    pub images: Vec<String>,
+}
+
+impl ImageJobResult {
+    /// Decode a generated image
+    pub fn image_bytes(&self, index: usize) -> VeniceResult<Vec<u8>> {
+        let image = self.images.get(index).ok_or_else(|| {
+            VeniceError::InvalidInput(format!("No image at index {} of {}", index, self.images.len()))
+        })?;
+        base64::decode(image).map_err(|e| VeniceError::InvalidInput(format!("Invalid base64 image data: {}", e)))
+    }
+}
+
+/// An image generation request and its progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageJob {
+    /// Unique ID of the job
+    pub id: String,
+    /// The request to send
+    pub request: ImageGenerateRequest,
+    /// Where the job is in its lifecycle
+    pub status: JobStatus,
+    /// Number of times the request was sent
+    pub attempts: u32,
+    /// The images, once the job completed
+    pub result: Option<ImageJobResult>,
+    /// The error of the last failed attempt
+    pub error: Option<String>,
+    /// Unix timestamp in milliseconds of when the job was enqueued
+    pub created_at: u64,
+    /// Unix timestamp in milliseconds of when the job last changed
+    pub updated_at: u64,
+}
+
+impl ImageJob {
+    /// Create a pending job
+    pub fn new(id: impl Into<String>, request: ImageGenerateRequest) -> Self {
+        let now = now_millis();
+        Self {
+            id: id.into(),
+            request,
+            status: JobStatus::Pending,
+            attempts: 0,
+            result: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn set_status(&mut self, status: JobStatus) {
+        self.status = status;
+        self.updated_at = now_millis();
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+/// Storage for image generation jobs
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// Load a job, returning `None` if it does not exist
+    async fn load(&self, id: &str) -> VeniceResult<Option<ImageJob>>;
+
+    /// Save a job, replacing any previous version
+    async fn save(&self, job: &ImageJob) -> VeniceResult<()>;
+
+    /// List all stored jobs
+    async fn list(&self) -> VeniceResult<Vec<ImageJob>>;
+
+    /// Delete a job, returning whether it existed
+    async fn delete(&self, id: &str) -> VeniceResult<bool>;
+}
+
+/// A job store that keeps jobs in memory, shared by clones
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryJobStore {
+    jobs: Arc<RwLock<HashMap<String, ImageJob>>>,
+}
+
+impl InMemoryJobStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn load(&self, id: &str) -> VeniceResult<Option<ImageJob>> {
+        Ok(self.jobs.read().unwrap_or_else(|e| e.into_inner()).get(id).cloned())
+    }
+
+    async fn save(&self, job: &ImageJob) -> VeniceResult<()> {
+        self.jobs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(job.id.clone(), job.clone());
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<ImageJob>> {
+        Ok(self.jobs.read().unwrap_or_else(|e| e.into_inner()).values().cloned().collect())
+    }
+
+    async fn delete(&self, id: &str) -> VeniceResult<bool> {
+        Ok(self.jobs.write().unwrap_or_else(|e| e.into_inner()).remove(id).is_some())
+    }
+}
+
+/// A job store that keeps each job in a JSON file, surviving process restarts
+///
+/// Job `id` is stored as `<directory>/<id>.json`. IDs may only contain ASCII
+/// letters, digits, `-` and `_`.
+#[derive(Debug, Clone)]
+pub struct JsonFileJobStore {
+    directory: PathBuf,
+}
+
+impl JsonFileJobStore {
+    /// Create a store in the given directory, which is created on first save
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the directory jobs are stored in
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the path of a job file
+    fn path(&self, id: &str) -> VeniceResult<PathBuf> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(VeniceError::InvalidInput(format!(
+                "Invalid job ID '{}': only ASCII letters, digits, '-' and '_' are allowed",
+                id
+            )));
+        }
+        Ok(self.directory.join(format!("{}.json", id)))
+    }
+
+    async fn read(&self, path: &Path) -> VeniceResult<Option<ImageJob>> {
+        let contents = match tokio::fs::read(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| VeniceError::parse_error(format!("Invalid job file {}: {}", path.display(), e)))
+    }
+}
+
+#[async_trait]
+impl JobStore for JsonFileJobStore {
+    async fn load(&self, id: &str) -> VeniceResult<Option<ImageJob>> {
+        self.read(&self.path(id)?).await
+    }
+
+    async fn save(&self, job: &ImageJob) -> VeniceResult<()> {
+        let path = self.path(&job.id)?;
+        let contents = serde_json::to_vec_pretty(job)
+            .map_err(|e| VeniceError::parse_error(format!("Failed to serialize job: {}", e)))?;
+
+        // Write to a temporary file first so a crash never leaves a truncated job
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let temporary = path.with_extension("json.tmp");
+        tokio::fs::write(&temporary, contents).await?;
+        tokio::fs::rename(&temporary, &path).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> VeniceResult<Vec<ImageJob>> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut jobs = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                if let Some(job) = self.read(&path).await? {
+                    jobs.push(job);
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    async fn delete(&self, id: &str) -> VeniceResult<bool> {
+        match tokio::fs::remove_file(self.path(id)?).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Outcome of a [`JobQueue::run`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobQueueReport {
+    /// Jobs completed by this run
+    pub completed: usize,
+    /// Jobs that failed for good in this run
+    pub failed: usize,
+    /// Running jobs left over from an interrupted run and sent again
+    pub resumed: usize,
+}
+
+/// A persistent queue of image generation jobs
+///
+/// Failed attempts with retryable errors, such as rate limits and server errors,
+/// put the job back in the queue until it has been tried `max_attempts` times.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::Client;
+/// use venice_ai_api_sdk_rust::image::{JobQueue, JsonFileJobStore};
+/// use venice_ai_api_sdk_rust::traits::image::ImageGenerateBuilder;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-api-key")?;
+/// let queue = JobQueue::new(Arc::new(JsonFileJobStore::new("image-jobs"))).workers(4);
+///
+/// for prompt in ["A lighthouse", "A forest", "A city at night"] {
+///     queue.enqueue(ImageGenerateBuilder::new("fluently-xl", prompt).build()).await?;
+/// }
+///
+/// // Safe to call again after a restart: only unfinished jobs are sent
+/// let report = queue.run(&client).await?;
+/// println!("{} completed, {} failed", report.completed, report.failed);
+/// # Ok(())
+/// # }
+/// ```
+pub struct JobQueue {
+    store: Arc<dyn JobStore>,
+    workers: usize,
+    max_attempts: u32,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl std::fmt::Debug for JobQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobQueue")
+            .field("workers", &self.workers)
+            .field("max_attempts", &self.max_attempts)
+            .field("rate_limiter", &self.rate_limiter.is_some())
+            .finish()
+    }
+}
+
+impl JobQueue {
+    /// Create a queue over a store, with 2 workers and 3 attempts per job
+    pub fn new(store: Arc<dyn JobStore>) -> Self {
+        Self {
+            store,
+            workers: 2,
+            max_attempts: 3,
+            rate_limiter: None,
+        }
+    }
+
+    /// Set the number of jobs run at the same time
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Set how many times a job is sent before it fails for good
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Take a permit from a rate limiter before every request
+    ///
+    /// Clients with their own rate limiter already wait for it; use this to share a
+    /// limit across queues or processes that use different clients.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Add a request to the queue, returning the ID of its job
+    pub async fn enqueue(&self, request: ImageGenerateRequest) -> VeniceResult<String> {
+        let id = format!("job-{}-{:08x}", now_millis(), rand::random::<u32>());
+        self.store.save(&ImageJob::new(id.clone(), request)).await?;
+        Ok(id)
+    }
+
+    /// Get a job
+    pub async fn job(&self, id: &str) -> VeniceResult<Option<ImageJob>> {
+        self.store.load(id).await
+    }
+
+    /// Get all jobs, oldest first
+    pub async fn jobs(&self) -> VeniceResult<Vec<ImageJob>> {
+        let mut jobs = self.store.list().await?;
+        jobs.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(jobs)
+    }
+
+    /// Put failed jobs back in the queue with a fresh set of attempts
+    pub async fn retry_failed(&self) -> VeniceResult<usize> {
+        let mut count = 0;
+        for mut job in self.store.list().await? {
+            if job.status == JobStatus::Failed {
+                job.attempts = 0;
+                job.set_status(JobStatus::Pending);
+                self.store.save(&job).await?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Run every unfinished job, returning once none is left
+    ///
+    /// Jobs left running by an interrupted run are sent again. Jobs enqueued while
+    /// the queue runs are picked up by the next run.
+    pub async fn run<A: ImageApi + Sync + ?Sized>(&self, api: &A) -> VeniceResult<JobQueueReport> {
+        let mut report = JobQueueReport::default();
+        let mut pending = Vec::new();
+        for mut job in self.jobs().await? {
+            match job.status {
+                JobStatus::Running => {
+                    job.set_status(JobStatus::Pending);
+                    self.store.save(&job).await?;
+                    report.resumed += 1;
+                    pending.push(job);
+                }
+                JobStatus::Pending => pending.push(job),
+                JobStatus::Completed | JobStatus::Failed => {}
+            }
+        }
+
+        let queue = Mutex::new(VecDeque::from(pending));
+        let report = Mutex::new(report);
+        let workers = (0..self.workers).map(|_| self.work(api, &queue, &report));
+        futures::future::try_join_all(workers).await?;
+        Ok(report.into_inner().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Take jobs from the queue until it is empty
+    async fn work<A: ImageApi + Sync + ?Sized>(
+        &self,
+        api: &A,
+        queue: &Mutex<VecDeque<ImageJob>>,
+        report: &Mutex<JobQueueReport>,
+    ) -> VeniceResult<()> {
+        loop {
+            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+            let Some(mut job) = next else {
+                return Ok(());
+            };
+
+            job.attempts += 1;
+            job.set_status(JobStatus::Running);
+            self.store.save(&job).await?;
+
+            let result = match &self.rate_limiter {
+                Some(rate_limiter) => match rate_limiter.acquire().await {
+                    Ok(()) => api.generate_image(job.request.clone()).await,
+                    Err(err) => Err(err),
+                },
+                None => api.generate_image(job.request.clone()).await,
+            };
+
+            match result {
+                Ok((response, _)) => {
+                    job.result = Some(ImageJobResult {
+                        id: response.id,
+                        images: response.images,
+                    });
+                    job.error = None;
+                    job.set_status(JobStatus::Completed);
+                    self.store.save(&job).await?;
+                    report.lock().unwrap_or_else(|e| e.into_inner()).completed += 1;
+                }
+                Err(err) => {
+                    let retry = is_retryable_error(&err) && job.attempts < self.max_attempts;
+                    log::warn!("Image job {} failed on attempt {}: {}", job.id, job.attempts, err);
+                    job.error = Some(err.to_string());
+                    if retry {
+                        job.set_status(JobStatus::Pending);
+                        self.store.save(&job).await?;
+                        queue.lock().unwrap_or_else(|e| e.into_inner()).push_back(job);
+                    } else {
+                        job.set_status(JobStatus::Failed);
+                        self.store.save(&job).await?;
+                        report.lock().unwrap_or_else(|e| e.into_inner()).failed += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RateLimitInfo;
+    use crate::testing::{Fault, FaultInjector};
+    use crate::traits::image::{
+        ImageGenerateBuilder, ImageGenerateResponse, ImageUpscaleRequest, ImageUpscaleResponse, ListImageStylesResponse,
+    };
+
+    /// Answers every request with one image holding the prompt
+    struct EchoImageApi;
+
+    #[async_trait]
+    impl ImageApi for EchoImageApi {
+        async fn generate_image(
+            &self,
+            request: ImageGenerateRequest,
+        ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
+            if request.prompt == "forbidden" {
+                return Err(VeniceError::InvalidInput("Prompt rejected".to_string()));
+            }
+            let response = ImageGenerateResponse {
+                id: format!("img-{}", request.prompt),
+                images: vec![base64::encode(&request.prompt)],
+                request: None,
+                timing: None,
+                created: None,
+                data: Vec::new(),
+            };
+            Ok((response, RateLimitInfo::from_headers(&Default::default())))
+        }
+
+        async fn list_styles(&self) -> VeniceResult<(ListImageStylesResponse, RateLimitInfo)> {
+            unimplemented!()
+        }
+
+        async fn upscale_image(&self, _request: ImageUpscaleRequest) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_resumes_after_restart() {
+        let directory = std::env::temp_dir().join(format!("venice-image-jobs-{}", std::process::id()));
+        let request = |prompt: &str| ImageGenerateBuilder::new("fluently-xl", prompt).build();
+
+        // A first process enqueues jobs and dies while one of them is running
+        let queue = JobQueue::new(Arc::new(JsonFileJobStore::new(&directory)));
+        let lighthouse = queue.enqueue(request("lighthouse")).await.unwrap();
+        let forest = queue.enqueue(request("forest")).await.unwrap();
+        let forbidden = queue.enqueue(request("forbidden")).await.unwrap();
+        let mut running = queue.job(&forest).await.unwrap().unwrap();
+        running.set_status(JobStatus::Running);
+        JsonFileJobStore::new(&directory).save(&running).await.unwrap();
+        drop(queue);
+
+        // The next process runs what is left, retrying the rate limited request
+        let api = FaultInjector::new(EchoImageApi).then_fail(Fault::rate_limited(std::time::Duration::ZERO));
+        let queue = JobQueue::new(Arc::new(JsonFileJobStore::new(&directory))).workers(2);
+        let report = queue.run(&api).await.unwrap();
+        assert_eq!(report, JobQueueReport { completed: 2, failed: 1, resumed: 1 });
+        assert_eq!(api.requests(), 4);
+
+        let job = queue.job(&lighthouse).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Completed);
+        assert_eq!(job.result.unwrap().image_bytes(0).unwrap(), b"lighthouse");
+        let job = queue.job(&forbidden).await.unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.error.unwrap().contains("Prompt rejected"));
+
+        // Finished jobs are not sent again
+        assert_eq!(queue.run(&api).await.unwrap(), JobQueueReport::default());
+        assert_eq!(queue.retry_failed().await.unwrap(), 1);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
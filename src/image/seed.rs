@@ -0,0 +1,112 @@
+//! Image generation seeds
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The seed of an image generation
+///
+/// Seeds may be negative. The same type is used in requests, in the request details and
+/// image data of responses, and in [`ImageMetadata`](crate::image::ImageMetadata), so a
+/// seed read from a response can be sent again as-is. It serializes as a plain integer.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::image::Seed;
+///
+/// let seed = Seed::random();
+/// assert!((Seed::MIN..=Seed::MAX).contains(&seed));
+/// assert_eq!(Seed::new(-42).value(), -42);
+/// assert_eq!("-42".parse::<Seed>().unwrap(), Seed::from(-42));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Seed(i64);
+
+impl Seed {
+    /// The smallest seed the API accepts
+    pub const MIN: Seed = Seed(-999_999_999);
+    /// The largest seed the API accepts
+    pub const MAX: Seed = Seed(999_999_999);
+
+    /// Create a seed
+    pub const fn new(value: i64) -> Self {
+        Self(value)
+    }
+
+    /// Pick a random seed between [`Seed::MIN`] and [`Seed::MAX`]
+    ///
+    /// Setting a random seed on a request, instead of leaving it unset, means the seed of
+    /// every image is known before it is generated.
+    pub fn random() -> Self {
+        Self(rand::thread_rng().gen_range(Self::MIN.0..=Self::MAX.0))
+    }
+
+    /// Get the value of the seed
+    pub const fn value(self) -> i64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Seed {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse().map(Self)
+    }
+}
+
+impl From<i64> for Seed {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<i32> for Seed {
+    fn from(value: i32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u32> for Seed {
+    fn from(value: u32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl TryFrom<u64> for Seed {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        i64::try_from(value).map(Self)
+    }
+}
+
+impl From<Seed> for i64 {
+    fn from(seed: Seed) -> Self {
+        seed.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_negative_seeds() {
+        let seed: Seed = serde_json::from_str("-12345").unwrap();
+        assert_eq!(seed, Seed::new(-12345));
+        assert_eq!(serde_json::to_string(&seed).unwrap(), "-12345");
+        assert_eq!(seed.to_string().parse::<Seed>().unwrap(), seed);
+        assert!(Seed::try_from(u64::MAX).is_err());
+    }
+}
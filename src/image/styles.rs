@@ -2,12 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
 };
 
-/// The endpoint for image styles
-const IMAGE_STYLES_ENDPOINT: &str = "image/styles";
-
 /// Request parameters for listing image styles
 #[derive(Debug, Serialize, Default)]
 pub struct ListImageStylesRequest {
@@ -47,7 +45,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::Client;
     /// 
     /// #[tokio::main]
@@ -63,7 +61,7 @@ impl Client {
     /// }
     /// ```
     pub async fn list_image_styles(&self) -> VeniceResult<(ListImageStylesResponse, RateLimitInfo)> {
-        self.get(IMAGE_STYLES_ENDPOINT).await
+        self.get(endpoints::IMAGE_STYLES).await
     }
 }
 
@@ -71,7 +69,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::image::list_image_styles;
 /// 
 /// #[tokio::main]
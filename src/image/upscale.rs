@@ -3,12 +3,11 @@ use std::collections::HashMap;
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
+    image::{check_output, ImageFormat},
 };
 
-/// The endpoint for image upscaling
-const IMAGE_UPSCALE_ENDPOINT: &str = "image/upscale";
-
 /// Request for image upscaling
 #[derive(Debug, Clone, Serialize)]
 pub struct ImageUpscaleRequest {
@@ -26,6 +25,12 @@ pub struct ImageUpscaleRequest {
     /// Return the image as binary data instead of URL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_binary: Option<bool>,
+    /// File format of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ImageFormat>,
+    /// Quality of lossy formats, from 1 to 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
     /// Additional custom parameters
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -59,6 +64,8 @@ impl Default for ImageUpscaleRequest {
             image_data: None,
             scale: None,
             return_binary: None,
+            format: None,
+            quality: None,
             extra: HashMap::new(),
         }
     }
@@ -105,6 +112,18 @@ impl ImageUpscaleRequestBuilder {
         self
     }
 
+    /// Set the file format of the upscaled image
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.request.format = Some(format);
+        self
+    }
+
+    /// Set the quality of lossy formats, from 1 to 100
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.request.quality = Some(quality);
+        self
+    }
+
     /// Add a custom parameter to the request
     pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
         self.request.extra.insert(key.into(), value.into());
@@ -122,7 +141,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{
     ///     Client,
     ///     image::ImageUpscaleRequestBuilder,
@@ -152,7 +171,8 @@ impl Client {
         &self,
         request: ImageUpscaleRequest,
     ) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)> {
-        self.post(IMAGE_UPSCALE_ENDPOINT, &request).await
+        check_output(endpoints::IMAGE_UPSCALE, ImageFormat::UPSCALE, request.format, request.quality)?;
+        self.post(endpoints::IMAGE_UPSCALE, &request).await
     }
 }
 
@@ -160,7 +180,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::image::{
 ///     upscale_image,
 ///     ImageUpscaleRequestBuilder,
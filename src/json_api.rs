@@ -0,0 +1,231 @@
+//! JSON façade for language bindings
+//!
+//! [`JsonApi`] exposes the SDK through a single string-in, string-out entry point, so
+//! wrappers for other languages (through uniffi, napi or a C ABI) only need to pass
+//! strings across the boundary instead of re-modelling every request and response type.
+//!
+//! Payloads are checked against the SDK's request types and the client's content
+//! policy is applied before they are sent, so bindings get the same validation as Rust
+//! callers. Requests go through the client's retries, rate limiter and observers.
+//! Results are returned as the API sent them.
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::client::Client;
+use crate::endpoints;
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+
+/// The methods understood by [`JsonApi::handle`], with their payloads
+pub const METHODS: &[&str] = &[
+    // A chat completion request; streaming is not supported
+    "chat.create_completion",
+    // Optional query parameters, such as {"type": "image"}
+    "models.list",
+    // Optional query parameters, such as {"type": "text"}
+    "models.traits",
+    "models.compatibility_mapping",
+    // An image generation request
+    "image.generate",
+    "image.styles",
+    // Optional pagination parameters
+    "api_keys.list",
+    // An API key creation request
+    "api_keys.create",
+    // {"id": "<key id>"}
+    "api_keys.delete",
+    // A web3 key generation request
+    "api_keys.generate_web3_key",
+];
+
+/// Dispatches JSON-encoded calls to a [`Client`]
+///
+/// Every call returns a JSON object. Successful calls return
+/// `{"ok": true, "result": ..., "rate_limit": {...}}`; failed calls return
+/// `{"ok": false, "error": {"kind": ..., "message": ...}}`, with the HTTP `status` and
+/// `request_id` when the API answered.
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, json_api::JsonApi};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let api = JsonApi::new(Client::new("your-api-key")?);
+/// let reply = api
+///     .handle(
+///         "chat.create_completion",
+///         r#"{"model": "llama-3.3-70b", "messages": [{"role": "user", "content": "Hi"}]}"#,
+///     )
+///     .await;
+/// println!("{}", reply);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonApi {
+    client: Client,
+}
+
+impl JsonApi {
+    /// Create a façade over a client
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Get the client calls are sent with
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Run a method with a JSON payload and return the JSON-encoded outcome
+    ///
+    /// An empty payload is treated as `{}`. See [`METHODS`] for the methods.
+    pub async fn handle(&self, method: &str, json_payload: &str) -> String {
+        let outcome = match self.dispatch(method, json_payload).await {
+            Ok((result, rate_limit_info)) => json!({
+                "ok": true,
+                "result": result,
+                "rate_limit": rate_limit_json(&rate_limit_info),
+            }),
+            Err(err) => json!({"ok": false, "error": error_json(&err)}),
+        };
+        outcome.to_string()
+    }
+
+    async fn dispatch(&self, method: &str, json_payload: &str) -> VeniceResult<(Value, RateLimitInfo)> {
+        let payload = if json_payload.trim().is_empty() { "{}" } else { json_payload };
+        let client = &self.client;
+        match method {
+            "chat.create_completion" => {
+                let mut request: crate::models::chat::ChatCompletionRequest = parse(method, payload)?;
+                if request.stream == Some(true) {
+                    return Err(VeniceError::InvalidInput(format!("{} does not support streaming", method)));
+                }
+                client.apply_content_policy(&mut request);
+                client.post(endpoints::CHAT_COMPLETIONS, &request).await
+            }
+            "models.list" => client.get_with_query(endpoints::MODELS, &parse::<Value>(method, payload)?).await,
+            "models.traits" => client.get_with_query(endpoints::MODELS_TRAITS, &parse::<Value>(method, payload)?).await,
+            "models.compatibility_mapping" => {
+                client.get_with_query(endpoints::MODELS_COMPATIBILITY_MAPPING, &parse::<Value>(method, payload)?).await
+            }
+            "image.generate" => {
+                let mut request: crate::traits::image::ImageGenerateRequest = parse(method, payload)?;
+                client.apply_content_policy(&mut request);
+                client.post(endpoints::IMAGE_GENERATE, &request).await
+            }
+            "image.styles" => client.get(endpoints::IMAGE_STYLES).await,
+            "api_keys.list" => client.get_with_query(endpoints::API_KEYS, &parse::<Value>(method, payload)?).await,
+            "api_keys.create" => {
+                let request: Value = parse(method, payload)?;
+                client.post(endpoints::API_KEYS, &request).await
+            }
+            "api_keys.delete" => {
+                let id = parse::<Value>(method, payload)?
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| VeniceError::InvalidInput(format!("{} requires a string \"id\"", method)))?;
+                client.delete(&format!("{}/{}", endpoints::API_KEYS, id)).await
+            }
+            "api_keys.generate_web3_key" => {
+                let request: Value = parse(method, payload)?;
+                client.post(endpoints::API_KEYS_GENERATE_WEB3_KEY, &request).await
+            }
+            _ => Err(VeniceError::InvalidInput(format!(
+                "Unknown method {}; expected one of: {}",
+                method,
+                METHODS.join(", ")
+            ))),
+        }
+    }
+}
+
+/// Parse the payload of a method
+fn parse<T: DeserializeOwned>(method: &str, payload: &str) -> VeniceResult<T> {
+    serde_json::from_str(payload).map_err(|e| VeniceError::InvalidInput(format!("Invalid payload for {}: {}", method, e)))
+}
+
+/// A short name for the kind of an error, stable across versions
+fn error_kind(err: &VeniceError) -> &'static str {
+    match err.root() {
+        VeniceError::ApiError { .. } => "api_error",
+        VeniceError::HttpError(_) => "http_error",
+        VeniceError::IoError(_) => "io_error",
+        VeniceError::ParseError { .. } => "parse_error",
+        VeniceError::InvalidInput(_) => "invalid_input",
+        VeniceError::RateLimitExceeded(_) => "rate_limit_exceeded",
+        VeniceError::AuthenticationFailed(_) => "authentication_failed",
+        VeniceError::ClientShutdown => "client_shutdown",
+        VeniceError::BudgetExceeded(_) => "budget_exceeded",
+        VeniceError::PayloadTooLarge { .. } => "payload_too_large",
+        _ => "unknown",
+    }
+}
+
+fn error_json(err: &VeniceError) -> Value {
+    let mut error = json!({"kind": error_kind(err), "message": err.to_string()});
+    if let VeniceError::ApiError { status, .. } = err.root() {
+        error["status"] = json!(status.as_u16());
+    }
+    if let Some(request_id) = err.request_id() {
+        error["request_id"] = json!(request_id);
+    }
+    error
+}
+
+fn rate_limit_json(info: &RateLimitInfo) -> Value {
+    json!({
+        "limit_requests": info.limit_requests,
+        "remaining_requests": info.remaining_requests,
+        "reset_requests": info.reset_requests,
+        "limit_tokens": info.limit_tokens,
+        "remaining_tokens": info.remaining_tokens,
+        "reset_tokens": info.reset_tokens,
+        "balance_vcu": info.balance_vcu,
+        "balance_usd": info.balance_usd,
+        "raw": info.raw,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_dispatches_and_reports_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(r#"{"model": "llama-3.3-70b"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("x-ratelimit-remaining-requests", "9")
+            .with_body(r#"{"id": "chatcmpl-1", "choices": [{"message": {"role": "assistant", "content": "Hello"}}]}"#)
+            .create_async()
+            .await;
+        let client = Client::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        let api = JsonApi::new(client);
+
+        let reply: Value = serde_json::from_str(
+            &api.handle(
+                "chat.create_completion",
+                r#"{"model": "llama-3.3-70b", "messages": [{"role": "user", "content": "Hi"}]}"#,
+            )
+            .await,
+        )
+        .unwrap();
+        mock.assert_async().await;
+        assert_eq!(reply["ok"], true);
+        assert_eq!(reply["result"]["choices"][0]["message"]["content"], "Hello");
+        assert_eq!(reply["rate_limit"]["remaining_requests"], 9);
+
+        let reply: Value = serde_json::from_str(&api.handle("chat.create_completion", r#"{"model": 1}"#).await).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert_eq!(reply["error"]["kind"], "invalid_input");
+
+        let reply: Value = serde_json::from_str(&api.handle("chat.teleport", "").await).unwrap();
+        assert!(reply["error"]["message"].as_str().unwrap().contains("Unknown method chat.teleport"));
+    }
+}
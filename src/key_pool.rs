@@ -0,0 +1,412 @@
+//! Pools of API keys
+//!
+//! A [`KeyPool`] spreads requests over several API keys. Keys are picked per request,
+//! either in turn or by the most remaining requests reported in the rate limit headers.
+//! A key that is rate limited is rested until its limit resets, a key that is rejected
+//! as unauthorized is retired, and the request is sent again with the next key.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::RequestBuilder;
+
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::http::{BinaryStream, RawResponse, ResponseMetadata};
+
+/// How long a rate limited key rests when the API does not say when its limit resets
+pub const DEFAULT_KEY_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How a [`KeyPool`] picks the key for the next request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeySelection {
+    /// Use the keys in turn
+    #[default]
+    RoundRobin,
+    /// Use the key with the most remaining requests; keys not used yet go first
+    LeastLoaded,
+}
+
+/// State of one key in the pool
+#[derive(Debug, Clone, Default)]
+struct KeyState {
+    remaining_requests: Option<u32>,
+    cooldown_until: Option<Instant>,
+    revoked: bool,
+}
+
+impl KeyState {
+    fn is_available(&self, now: Instant) -> bool {
+        !self.revoked && self.cooldown_until.is_none_or(|until| until <= now)
+    }
+}
+
+/// Status of a key in a [`KeyPool`], without the key itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStatus {
+    /// Position of the key in the pool
+    pub index: usize,
+    /// Remaining requests reported by the last response sent with the key
+    pub remaining_requests: Option<u32>,
+    /// Whether the key is resting after hitting its rate limit
+    pub cooling_down: bool,
+    /// Whether the key was rejected as unauthorized and is no longer used
+    pub revoked: bool,
+}
+
+/// A set of API keys shared by the requests of a client
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, KeyPool, KeySelection};
+///
+/// let pool = KeyPool::new(["key-one", "key-two", "key-three"])
+///     .unwrap()
+///     .with_selection(KeySelection::LeastLoaded);
+/// let client = Client::builder().key_pool(pool).build().unwrap();
+/// ```
+pub struct KeyPool {
+    keys: Vec<String>,
+    states: Mutex<Vec<KeyState>>,
+    selection: KeySelection,
+    cooldown: Duration,
+    next: AtomicUsize,
+}
+
+impl fmt::Debug for KeyPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPool")
+            .field("keys", &self.keys.len())
+            .field("selection", &self.selection)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl KeyPool {
+    /// Create a pool from a list of API keys
+    ///
+    /// Returns an error if the list is empty or contains an empty key.
+    pub fn new<K: Into<String>>(keys: impl IntoIterator<Item = K>) -> VeniceResult<Self> {
+        let keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+        if keys.is_empty() {
+            return Err(VeniceError::InvalidInput("A key pool needs at least one API key".to_string()));
+        }
+        if keys.iter().any(|key| key.trim().is_empty()) {
+            return Err(VeniceError::InvalidInput("A key pool cannot contain empty API keys".to_string()));
+        }
+        Ok(Self {
+            states: Mutex::new(vec![KeyState::default(); keys.len()]),
+            keys,
+            selection: KeySelection::default(),
+            cooldown: DEFAULT_KEY_COOLDOWN,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Set how the key for the next request is picked
+    pub fn with_selection(mut self, selection: KeySelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set how long a rate limited key rests when the API does not say when its limit resets
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Get the number of keys in the pool
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Check whether the pool has no keys; always false for a constructed pool
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Get the number of keys that can currently be used
+    pub fn available(&self) -> usize {
+        let now = Instant::now();
+        self.lock().iter().filter(|state| state.is_available(now)).count()
+    }
+
+    /// Get the status of every key in the pool
+    pub fn status(&self) -> Vec<KeyStatus> {
+        let now = Instant::now();
+        self.lock()
+            .iter()
+            .enumerate()
+            .map(|(index, state)| KeyStatus {
+                index,
+                remaining_requests: state.remaining_requests,
+                cooling_down: state.cooldown_until.is_some_and(|until| until > now),
+                revoked: state.revoked,
+            })
+            .collect()
+    }
+
+    /// The first key of the pool, used for the client's default credentials
+    pub(crate) fn primary_key(&self) -> &str {
+        &self.keys[0]
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<KeyState>> {
+        self.states.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Pick the key for the next request
+    fn checkout(&self) -> VeniceResult<usize> {
+        let now = Instant::now();
+        let states = self.lock();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut order = (0..states.len()).map(|offset| (start + offset) % states.len());
+        let index = match self.selection {
+            KeySelection::RoundRobin => order.find(|&i| states[i].is_available(now)),
+            KeySelection::LeastLoaded => order
+                .filter(|&i| states[i].is_available(now))
+                .fold(None, |best: Option<usize>, i| {
+                    let remaining = |i: usize| states[i].remaining_requests.unwrap_or(u32::MAX);
+                    match best {
+                        Some(best) if remaining(best) >= remaining(i) => Some(best),
+                        _ => Some(i),
+                    }
+                }),
+        };
+        index.ok_or_else(|| {
+            if states.iter().all(|state| state.revoked) {
+                VeniceError::AuthenticationFailed("Every API key in the pool was rejected".to_string())
+            } else {
+                VeniceError::RateLimitExceeded("Every API key in the pool is rate limited".to_string())
+            }
+        })
+    }
+
+    /// Record the outcome of a request sent with a key
+    fn report(&self, index: usize, outcome: Result<&RateLimitInfo, &VeniceError>) {
+        let mut states = self.lock();
+        let state = &mut states[index];
+        match outcome {
+            Ok(rate_limit_info) => {
+                state.remaining_requests = rate_limit_info.remaining_requests;
+                state.cooldown_until = rate_limit_info
+                    .is_rate_limited()
+                    .then(|| Instant::now() + self.reset_delay(rate_limit_info));
+            }
+            Err(err) => match failover_reason(err) {
+                Some(Failover::Revoked) => state.revoked = true,
+                Some(Failover::RateLimited) => {
+                    state.remaining_requests = Some(0);
+                    state.cooldown_until = Some(Instant::now() + self.cooldown);
+                }
+                None => {}
+            },
+        }
+    }
+
+    /// Time until the request limit resets, falling back to the configured cooldown
+    fn reset_delay(&self, rate_limit_info: &RateLimitInfo) -> Duration {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match rate_limit_info.reset_requests {
+            Some(reset) if reset > now => Duration::from_secs(reset - now),
+            _ => self.cooldown,
+        }
+    }
+
+    /// Send a request with a pooled key, sending it again with the next key if the
+    /// key is rate limited or rejected
+    pub(crate) async fn send<T, F, Fut>(&self, mut attempt: F) -> VeniceResult<T>
+    where
+        T: PooledResponse,
+        F: FnMut(&str) -> Fut,
+        Fut: Future<Output = VeniceResult<T>>,
+    {
+        let mut remaining = self.keys.len();
+        loop {
+            let index = self.checkout()?;
+            let result = attempt(&self.keys[index]).await;
+            self.report(index, result.as_ref().map(PooledResponse::rate_limit_info));
+            remaining -= 1;
+            match &result {
+                Err(err) if remaining > 0 && failover_reason(err).is_some() => continue,
+                _ => return result,
+            }
+        }
+    }
+
+    /// Send a request that cannot be repeated, such as a multipart upload, with a pooled key
+    pub(crate) async fn send_once<T, Fut>(&self, attempt: impl FnOnce(&str) -> Fut) -> VeniceResult<T>
+    where
+        T: PooledResponse,
+        Fut: Future<Output = VeniceResult<T>>,
+    {
+        let index = self.checkout()?;
+        let result = attempt(&self.keys[index]).await;
+        self.report(index, result.as_ref().map(PooledResponse::rate_limit_info));
+        result
+    }
+}
+
+/// Why a request should be sent again with another key
+enum Failover {
+    Revoked,
+    RateLimited,
+}
+
+fn failover_reason(err: &VeniceError) -> Option<Failover> {
+    match err.root() {
+        VeniceError::AuthenticationFailed(_) => Some(Failover::Revoked),
+        VeniceError::ApiError { status, .. } if status.as_u16() == 401 => Some(Failover::Revoked),
+        VeniceError::RateLimitExceeded(_) => Some(Failover::RateLimited),
+        VeniceError::ApiError { status, .. } if status.as_u16() == 429 => Some(Failover::RateLimited),
+        _ => None,
+    }
+}
+
+/// Set the credentials of a request to a pooled key, if there is one
+pub(crate) fn authorize(request: RequestBuilder, key: Option<&str>) -> RequestBuilder {
+    match key {
+        Some(key) => request.bearer_auth(key),
+        None => request,
+    }
+}
+
+/// Send a request through an optional key pool
+pub(crate) async fn send_pooled<T, F, Fut>(pool: Option<&Arc<KeyPool>>, mut attempt: F) -> VeniceResult<T>
+where
+    T: PooledResponse,
+    F: FnMut(Option<&str>) -> Fut,
+    Fut: Future<Output = VeniceResult<T>>,
+{
+    match pool {
+        Some(pool) => pool.send(|key| attempt(Some(key))).await,
+        None => attempt(None).await,
+    }
+}
+
+/// Send a request that cannot be repeated through an optional key pool
+pub(crate) async fn send_pooled_once<T, Fut>(
+    pool: Option<&Arc<KeyPool>>,
+    attempt: impl FnOnce(Option<&str>) -> Fut,
+) -> VeniceResult<T>
+where
+    T: PooledResponse,
+    Fut: Future<Output = VeniceResult<T>>,
+{
+    match pool {
+        Some(pool) => pool.send_once(|key| attempt(Some(key))).await,
+        None => attempt(None).await,
+    }
+}
+
+/// A successful response whose rate limit headers are fed back to the key pool
+pub(crate) trait PooledResponse {
+    fn rate_limit_info(&self) -> &RateLimitInfo;
+}
+
+impl<T> PooledResponse for (T, RateLimitInfo, ResponseMetadata) {
+    fn rate_limit_info(&self) -> &RateLimitInfo {
+        &self.1
+    }
+}
+
+impl<T> PooledResponse for (T, RateLimitInfo) {
+    fn rate_limit_info(&self) -> &RateLimitInfo {
+        &self.1
+    }
+}
+
+impl PooledResponse for (Vec<u8>, String, RateLimitInfo) {
+    fn rate_limit_info(&self) -> &RateLimitInfo {
+        &self.2
+    }
+}
+
+impl PooledResponse for RawResponse {
+    fn rate_limit_info(&self) -> &RateLimitInfo {
+        &self.rate_limit_info
+    }
+}
+
+impl PooledResponse for BinaryStream {
+    fn rate_limit_info(&self) -> &RateLimitInfo {
+        &self.rate_limit_info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    #[test]
+    fn test_round_robin_skips_unavailable_keys() {
+        let pool = KeyPool::new(["key-a", "key-b", "key-c"]).unwrap();
+        assert_eq!(pool.checkout().unwrap(), 0);
+        assert_eq!(pool.checkout().unwrap(), 1);
+
+        pool.report(2, Err(&VeniceError::RateLimitExceeded("slow down".to_string())));
+        assert_eq!(pool.checkout().unwrap(), 0);
+        assert_eq!(pool.available(), 2);
+        assert!(pool.status()[2].cooling_down);
+
+        pool.report(0, Err(&VeniceError::AuthenticationFailed("revoked".to_string())));
+        pool.report(1, Err(&VeniceError::AuthenticationFailed("revoked".to_string())));
+        assert!(matches!(pool.checkout(), Err(VeniceError::RateLimitExceeded(_))));
+    }
+
+    #[test]
+    fn test_least_loaded_prefers_most_remaining_requests() {
+        let pool = KeyPool::new(["key-a", "key-b"]).unwrap().with_selection(KeySelection::LeastLoaded);
+        let info = |remaining| RateLimitInfo {
+            remaining_requests: Some(remaining),
+            ..RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new())
+        };
+        pool.report(0, Ok(&info(5)));
+        pool.report(1, Ok(&info(50)));
+        assert_eq!(pool.checkout().unwrap(), 1);
+        assert_eq!(pool.checkout().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_fails_over_to_next_key() {
+        let mut server = mockito::Server::new_async().await;
+        let rejected = server
+            .mock("GET", "/models")
+            .match_header("authorization", "Bearer key-a")
+            .with_status(401)
+            .with_body(r#"{"error": "Invalid API key"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("GET", "/models")
+            .match_header("authorization", "Bearer key-b")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"object": "list", "data": []}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = Client::builder()
+            .key_pool(KeyPool::new(["key-a", "key-b"]).unwrap())
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let (body, _) = client.get::<serde_json::Value>("models").await.unwrap();
+        assert_eq!(body["object"], "list");
+        // The revoked key is not tried again
+        client.get::<serde_json::Value>("models").await.unwrap();
+
+        rejected.assert_async().await;
+        accepted.assert_async().await;
+        let status = client.config().key_pool.as_ref().unwrap().status();
+        assert!(status[0].revoked);
+        assert!(!status[1].revoked);
+    }
+}
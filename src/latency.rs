@@ -0,0 +1,311 @@
+//! Per-endpoint latency tracking and adaptive timeouts
+//!
+//! [`LatencyTracker`] keeps an exponentially weighted moving average and a window of
+//! recent latencies for every endpoint a client calls, from which it reports
+//! percentiles as [`LatencyStats`]. Enable it with
+//! [`ClientConfig::with_latency_tracking`](crate::ClientConfig::with_latency_tracking)
+//! and read it with [`Client::latency_stats`](crate::Client::latency_stats).
+//!
+//! With adaptive timeouts, a client that has a timeout extends the deadline of an
+//! endpoint while its recent latencies approach the timeout, so requests wait out a
+//! slow API instead of failing. Deadlines are never shortened below the configured
+//! timeout.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::error::{RateLimitInfo, VeniceError};
+use crate::observer::{RequestInfo, UsageObserver};
+use crate::traits::chat::ChatCompletionUsage;
+
+/// Latencies an endpoint needs before its deadline is adapted
+const MIN_SAMPLES: u64 = 5;
+
+/// Settings of a [`LatencyTracker`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyConfig {
+    /// Weight of the newest latency in the moving average, between 0 and 1
+    pub smoothing: f64,
+    /// Number of recent latencies kept per endpoint for percentiles
+    pub window: usize,
+    /// Whether to extend the timeout of slow endpoints
+    pub adaptive_timeouts: bool,
+    /// Deadline of an endpoint as a multiple of its 95th percentile latency
+    pub timeout_multiplier: f64,
+    /// Longest deadline an endpoint can be extended to
+    pub max_timeout: Duration,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            smoothing: 0.2,
+            window: 100,
+            adaptive_timeouts: true,
+            timeout_multiplier: 3.0,
+            max_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl LatencyConfig {
+    /// Create a configuration with default values: adaptive timeouts of up to 10 minutes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the weight of the newest latency in the moving average, between 0 and 1
+    pub fn smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the number of recent latencies kept per endpoint
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Set whether to extend the timeout of slow endpoints
+    pub fn adaptive_timeouts(mut self, enabled: bool) -> Self {
+        self.adaptive_timeouts = enabled;
+        self
+    }
+
+    /// Set the deadline of an endpoint as a multiple of its 95th percentile latency
+    pub fn timeout_multiplier(mut self, multiplier: f64) -> Self {
+        self.timeout_multiplier = multiplier;
+        self
+    }
+
+    /// Set the longest deadline an endpoint can be extended to
+    pub fn max_timeout(mut self, max_timeout: Duration) -> Self {
+        self.max_timeout = max_timeout;
+        self
+    }
+}
+
+/// Latency summary of an endpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    /// Number of latencies recorded since the tracker was created or reset
+    pub samples: u64,
+    /// Exponentially weighted moving average
+    pub ewma: Duration,
+    /// Median of the recent latencies
+    pub p50: Duration,
+    /// 95th percentile of the recent latencies
+    pub p95: Duration,
+    /// 99th percentile of the recent latencies
+    pub p99: Duration,
+    /// Highest of the recent latencies
+    pub max: Duration,
+}
+
+/// Latencies recorded for one endpoint
+#[derive(Debug, Default)]
+struct EndpointLatency {
+    samples: u64,
+    ewma: f64,
+    recent: VecDeque<Duration>,
+}
+
+impl EndpointLatency {
+    fn stats(&self) -> LatencyStats {
+        let mut recent: Vec<Duration> = self.recent.iter().copied().collect();
+        recent.sort();
+        let percentile = |percentile: f64| {
+            let rank = (percentile / 100.0 * recent.len().saturating_sub(1) as f64).round() as usize;
+            recent.get(rank).copied().unwrap_or_default()
+        };
+        LatencyStats {
+            samples: self.samples,
+            ewma: Duration::from_secs_f64(self.ewma),
+            p50: percentile(50.0),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+            max: recent.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Rolling latency statistics of every endpoint a client calls
+///
+/// Registered as a usage observer, so latencies are recorded for requests sent through
+/// the API traits. Failed requests count only when the API answered, since the latency
+/// of a timeout or a dropped connection says little about how fast the API is.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::{LatencyConfig, LatencyTracker};
+///
+/// let tracker = LatencyTracker::new(LatencyConfig::new());
+/// for millis in [800, 900, 1200, 950, 4000] {
+///     tracker.record("chat/completions", Duration::from_millis(millis));
+/// }
+///
+/// let stats = tracker.endpoint("chat/completions").unwrap();
+/// assert_eq!(stats.samples, 5);
+/// assert_eq!(stats.max, Duration::from_secs(4));
+/// // A 5 second timeout is extended to three times the 95th percentile
+/// assert_eq!(tracker.timeout_for("chat/completions", Duration::from_secs(5)), Duration::from_secs(12));
+/// ```
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    config: LatencyConfig,
+    endpoints: RwLock<HashMap<String, EndpointLatency>>,
+}
+
+impl LatencyTracker {
+    /// Create a tracker
+    pub fn new(config: LatencyConfig) -> Self {
+        Self {
+            config,
+            endpoints: RwLock::default(),
+        }
+    }
+
+    /// Get the settings of the tracker
+    pub fn config(&self) -> &LatencyConfig {
+        &self.config
+    }
+
+    /// Record the latency of a request to an endpoint
+    pub fn record(&self, endpoint: &str, latency: Duration) {
+        let mut endpoints = self.endpoints.write().unwrap_or_else(|e| e.into_inner());
+        let entry = endpoints.entry(endpoint.to_string()).or_default();
+        let seconds = latency.as_secs_f64();
+        entry.ewma = if entry.samples == 0 {
+            seconds
+        } else {
+            self.config.smoothing * seconds + (1.0 - self.config.smoothing) * entry.ewma
+        };
+        entry.samples += 1;
+        if entry.recent.len() >= self.config.window.max(1) {
+            entry.recent.pop_front();
+        }
+        entry.recent.push_back(latency);
+    }
+
+    /// Get the latency summary of an endpoint, if any request to it was recorded
+    pub fn endpoint(&self, endpoint: &str) -> Option<LatencyStats> {
+        let endpoints = self.endpoints.read().unwrap_or_else(|e| e.into_inner());
+        endpoints.get(endpoint).map(EndpointLatency::stats)
+    }
+
+    /// Get the latency summary of every endpoint, by endpoint
+    pub fn stats(&self) -> HashMap<String, LatencyStats> {
+        let endpoints = self.endpoints.read().unwrap_or_else(|e| e.into_inner());
+        endpoints
+            .iter()
+            .map(|(endpoint, latency)| (endpoint.clone(), latency.stats()))
+            .collect()
+    }
+
+    /// Forget every recorded latency
+    pub fn reset(&self) {
+        self.endpoints.write().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Get the deadline for a request to an endpoint, given the configured timeout
+    ///
+    /// Returns `timeout` unless adaptive timeouts are enabled and the endpoint has enough
+    /// recent latencies, in which case the deadline is the 95th percentile latency times
+    /// the multiplier, but no less than `timeout` and no more than the maximum.
+    pub fn timeout_for(&self, endpoint: &str, timeout: Duration) -> Duration {
+        if !self.config.adaptive_timeouts {
+            return timeout;
+        }
+        match self.endpoint(endpoint) {
+            Some(stats) if stats.samples >= MIN_SAMPLES => {
+                let adapted = stats.p95.mul_f64(self.config.timeout_multiplier.max(0.0));
+                adapted.min(self.config.max_timeout).max(timeout)
+            }
+            _ => timeout,
+        }
+    }
+}
+
+impl UsageObserver for LatencyTracker {
+    fn on_response(
+        &self,
+        request: &RequestInfo,
+        _usage: Option<&ChatCompletionUsage>,
+        _rate_limit: &RateLimitInfo,
+        latency: Duration,
+    ) {
+        self.record(&request.endpoint, latency);
+    }
+
+    fn on_error(&self, request: &RequestInfo, error: &VeniceError, latency: Duration) {
+        if matches!(error.root(), VeniceError::ApiError { .. }) {
+            self.record(&request.endpoint, latency);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    fn request(endpoint: &str) -> RequestInfo {
+        RequestInfo {
+            method: Method::GET,
+            endpoint: endpoint.to_string(),
+            model: None,
+            user: None,
+            tags: Vec::new(),
+            payload_sha256: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_stats_and_adaptive_timeouts() {
+        let tracker = LatencyTracker::new(LatencyConfig::new().window(4).smoothing(0.5).max_timeout(Duration::from_secs(20)));
+        let timeout = Duration::from_secs(5);
+        for secs in [2, 4, 6, 8] {
+            tracker.record("chat/completions", Duration::from_secs(secs));
+        }
+        // Too few latencies to adapt the deadline
+        assert_eq!(tracker.timeout_for("chat/completions", timeout), timeout);
+
+        tracker.record("chat/completions", Duration::from_secs(10));
+        let stats = tracker.endpoint("chat/completions").unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.ewma, Duration::from_secs_f64(8.125));
+        assert_eq!(stats.p50, Duration::from_secs(8));
+        assert_eq!(stats.max, Duration::from_secs(10));
+        // Three times the 95th percentile is capped at the maximum
+        assert_eq!(tracker.timeout_for("chat/completions", timeout), Duration::from_secs(20));
+        assert_eq!(tracker.timeout_for("models", timeout), timeout);
+
+        let tracker = LatencyTracker::new(LatencyConfig::new().adaptive_timeouts(false));
+        for _ in 0..10 {
+            tracker.record("models", Duration::from_secs(4));
+        }
+        assert_eq!(tracker.timeout_for("models", timeout), timeout);
+        tracker.reset();
+        assert!(tracker.stats().is_empty());
+    }
+
+    #[test]
+    fn test_errors_count_only_when_the_api_answered() {
+        let tracker = LatencyTracker::new(LatencyConfig::new());
+        let api_error = VeniceError::ApiError {
+            status: reqwest::StatusCode::BAD_GATEWAY,
+            code: "bad_gateway".to_string(),
+            message: "Upstream failed".to_string(),
+        };
+        tracker.on_error(&request("models"), &api_error, Duration::from_millis(300));
+        tracker.on_error(&request("models"), &VeniceError::InvalidInput("bad".to_string()), Duration::from_secs(30));
+
+        let stats = tracker.endpoint("models").unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.max, Duration::from_millis(300));
+    }
+}
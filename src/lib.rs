@@ -10,10 +10,22 @@
 //! - Chat completions API for text generation
 //! - Image generation API
 //! - Image styles API
-//! - Image upscaling API
+//! - Image upscaling API, reading the dimensions of upscaled images (`image` feature)
 //! - Models listing API
 //! - API key management
 //! - Simple, type-safe interface
+//! - Cost estimation, spend tracking and spend caps
+//! - Benchmarking latency, speed and cost of models on a prompt (`benchmark` module)
+//! - Splitting documents into token-bounded chunks for map-reduce pipelines (`util::text` module)
+//! - Per-endpoint latency percentiles and adaptive timeouts for slow endpoints
+//! - Discovering the capabilities of the API and gating requests on them (`capabilities` module)
+//! - Optional `tracing` spans for chat and image calls (`otel` feature)
+//! - Structured extraction into Rust types, with `#[derive(VeniceSchema)]` (`derive` feature)
+//! - Test doubles that simulate rate limits and server errors (`testing` module)
+//! - A JSON string façade for building bindings to other languages (`json_api` module)
+//! - Kotlin and Swift bindings through uniffi (`uniffi` feature, `ffi` module)
+//! - A process-wide client for small tools and tests (`global` feature)
+//! - HTTP status hints for SDK errors, convertible to `http::StatusCode` (`http` feature)
 //!
 //! ## Example
 //!
@@ -47,7 +59,7 @@
 //!         .build();
 //!     
 //!     // Send the request
-//!     let (response, _) = client.create_chat_completion(request).await?;
+//!     let (response, _) = ChatApi::create_chat_completion(&client, request).await?;
 //!     
 //!     // Print the response
 //!     println!("AI response: {}", response.choices[0].message.content);
@@ -56,6 +68,9 @@
 //! }
 //! ```
 
+// Lets code generated by the derive macros name this crate from inside it
+extern crate self as venice_ai_api_sdk_rust;
+
 // Internal modules
 mod error;
 mod config;
@@ -69,33 +84,77 @@ mod macros;
 mod pagination;
 mod retry;
 mod rate_limit;
+mod key_pool;
+mod observer;
+mod content_policy;
+mod cost;
+mod budget;
+mod latency;
+mod audit;
+mod lifecycle;
+mod telemetry;
+mod request_options;
+mod profile;
+mod secret;
 mod api;
 mod services;
 
 // Public modules
+pub mod endpoints;
 pub mod traits;
 pub mod models;
 pub mod chat;
 pub mod image;
 pub mod api_keys;
 pub mod webhooks;
+pub mod schema;
+pub mod benchmark;
+pub mod capabilities;
+pub mod json_api;
+pub mod testing;
+#[cfg(feature = "uniffi")]
+pub mod ffi;
+#[cfg(feature = "global")]
+pub mod global;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 // Public exports
-pub use error::{VeniceError, VeniceResult, RateLimitInfo};
-pub use config::{ClientConfig, DEFAULT_BASE_URL};
-pub use client::{Client, SharedClient, new_shared_client};
-pub use http::{HttpClient, HttpClientConfig, HttpResult, SharedHttpClient, new_shared_http_client};
+pub use error::{ErrorContext, VeniceError, VeniceResult, RateLimitInfo, MAX_PARSE_ERROR_BODY_LEN};
+pub use config::{ClientConfig, DEFAULT_BASE_URL, FEATURE_FLAG_HEADER_PREFIX};
+pub use request_options::RequestOptions;
+pub use profile::Profile;
+pub use secret::{reveal_secrets, SecretString};
+pub use client::{Client, DerivedClientBuilder, PingReport, SharedClient, new_shared_client};
+pub use http::{
+    BinaryStream, CompressionStats, HttpClient, HttpClientConfig, HttpClientConfigBuilder, HttpResult, ParseWarning, RequestSigner, ResponseEnvelope, SharedHttpClient, SignableRequest,
+    new_shared_http_client,
+    UploadOptions, UploadProgressCallback, DEFAULT_COMPRESSION_THRESHOLD, DEFAULT_UPLOAD_CHUNK_SIZE,
+};
 // TODO: Fix middleware module
 // pub use middleware::{
 //     Middleware, MiddlewareChain, Request, Method, Next,
 //     RateLimiterMiddleware, RetryMiddleware,
 // };
 pub use pagination::{
-    PaginatedResponse, PaginationParams, Paginator,
+    BoxPaginator, PaginatedResponse, PaginationParams, Paginator,
     PaginationInfo, create_paginator, create_async_paginator,
 };
-pub use retry::{RetryConfig, with_retry};
-pub use rate_limit::{RateLimiter, RateLimiterConfig, new_shared_rate_limiter, new_shared_rate_limiter_with_config};
+pub use retry::{
+    Clock, ErrorClass, JitterSource, RetryAttempt, RetryConfig, RetryPolicy, RetryReport, SeededJitter, ThreadRngJitter,
+    TokioClock, VirtualClock,
+    with_retry, with_retry_report,
+};
+pub use rate_limit::{LimitClass, RateLimitSnapshot, RateLimiter, RateLimiterConfig, new_shared_rate_limiter, new_shared_rate_limiter_with_config};
+pub use key_pool::{KeyPool, KeySelection, KeyStatus, DEFAULT_KEY_COOLDOWN};
+pub use observer::{UsageObserver, RequestInfo};
+pub use content_policy::ContentPolicy;
+pub use audit::{AuditLog, AuditRecord, AuditSink, JsonLinesSink};
+pub use cost::{Cost, CostCalculator, CostEstimate, CostTracker, ModelSpend};
+pub use budget::SpendGuard;
+pub use latency::{LatencyConfig, LatencyStats, LatencyTracker};
+pub use lifecycle::{ConnectionStats, ShutdownReport};
 pub use api::{ChatApiImpl, ImageApiImpl, ModelsApiImpl, ApiKeysApiImpl};
 pub use services::webhook::WebhookService;
 
@@ -103,6 +162,8 @@ pub use services::webhook::WebhookService;
 pub mod util {
     //! Utility functions for working with the Venice AI API
     
+    pub use crate::utils::schema_drift;
     pub use crate::utils::serialization;
+    pub use crate::utils::text;
     pub use crate::utils::validation;
 }
@@ -0,0 +1,303 @@
+//! Graceful shutdown
+//!
+//! Every request sent by a [`crate::Client`] (and by the HTTP client backing its
+//! API trait implementations) is registered with a shared [`Lifecycle`], so that
+//! [`crate::Client::shutdown`] can refuse new requests, wait for in-flight requests
+//! and streams to finish, and then release the connection pools. The same state
+//! backs [`crate::Client::connection_stats`], for readiness probes in long-running services.
+
+use futures::{Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Client as ReqwestClient, RequestBuilder};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::budget::SpendGuard;
+use crate::error::{VeniceError, VeniceResult};
+use crate::key_pool;
+use crate::request_options;
+
+/// The outcome of [`crate::Client::shutdown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Whether all in-flight requests finished before the deadline
+    pub drained: bool,
+    /// Number of requests or streams still active when the deadline passed
+    pub abandoned: usize,
+}
+
+/// A snapshot of the connections of a client, from [`crate::Client::connection_stats`]
+///
+/// The underlying HTTP client does not report how many sockets it holds open or idle,
+/// so the counts are of the requests and connection pools the SDK manages itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConnectionStats {
+    /// Number of requests and streams currently in flight
+    pub in_flight: usize,
+    /// Highest number of requests and streams in flight at the same time
+    pub peak_in_flight: usize,
+    /// Number of requests started since the client was created
+    pub total_requests: u64,
+    /// Number of connection pools that can still send requests
+    pub open_pools: usize,
+    /// Whether shutdown has started
+    pub shut_down: bool,
+}
+
+impl ConnectionStats {
+    /// Check whether the client can take new requests, for use in readiness probes
+    pub fn is_ready(&self) -> bool {
+        !self.shut_down && self.open_pools > 0
+    }
+}
+
+/// A connection pool that can be released on shutdown
+type Pool = Arc<RwLock<Option<ReqwestClient>>>;
+
+/// Shutdown state shared by a client and its clones
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Lifecycle {
+    inner: Arc<LifecycleInner>,
+}
+
+#[derive(Debug, Default)]
+struct LifecycleInner {
+    /// Set once shutdown has started
+    closed: AtomicBool,
+    /// Number of active requests and streams
+    active: AtomicUsize,
+    /// Highest number of active requests and streams seen
+    peak: AtomicUsize,
+    /// Number of requests and streams started
+    started: AtomicU64,
+    /// Notified when the last active request finishes
+    idle: Notify,
+    /// Connection pools released once the client is drained
+    pools: Mutex<Vec<Pool>>,
+}
+
+impl Lifecycle {
+    /// Check whether shutdown has started
+    pub(crate) fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
+    /// Get the number of active requests and streams
+    pub(crate) fn active(&self) -> usize {
+        self.inner.active.load(Ordering::Acquire)
+    }
+
+    /// Take a snapshot of the connection state
+    pub(crate) fn stats(&self) -> ConnectionStats {
+        let open_pools = self
+            .inner
+            .pools
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|pool| pool.read().unwrap_or_else(|e| e.into_inner()).is_some())
+            .count();
+
+        ConnectionStats {
+            in_flight: self.active(),
+            peak_in_flight: self.inner.peak.load(Ordering::Acquire),
+            total_requests: self.inner.started.load(Ordering::Acquire),
+            open_pools,
+            shut_down: self.is_closed(),
+        }
+    }
+
+    /// Refuse new requests, wait up to `deadline` for active ones, then release the pools
+    pub(crate) async fn shutdown(&self, deadline: Duration) -> ShutdownReport {
+        self.inner.closed.store(true, Ordering::Release);
+
+        let drained = tokio::time::timeout(deadline, async {
+            loop {
+                let idle = self.inner.idle.notified();
+                if self.active() == 0 {
+                    break;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok();
+
+        for pool in self.inner.pools.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            pool.write().unwrap_or_else(|e| e.into_inner()).take();
+        }
+
+        ShutdownReport {
+            drained,
+            abandoned: self.active(),
+        }
+    }
+}
+
+/// An HTTP connection pool registered with a [`Lifecycle`]
+#[derive(Debug, Clone)]
+pub(crate) struct Connection {
+    pool: Pool,
+    lifecycle: Lifecycle,
+    /// Refuses new requests once the client has spent its budget
+    spend_guard: Option<Arc<SpendGuard>>,
+    /// Headers added to every request, on top of the default headers of the pool
+    headers: HeaderMap,
+}
+
+impl Connection {
+    /// Register a connection pool with a lifecycle
+    pub(crate) fn new(client: ReqwestClient, lifecycle: Lifecycle) -> Self {
+        Self {
+            pool: Arc::new(RwLock::new(Some(client))),
+            lifecycle: Lifecycle::default(),
+            spend_guard: None,
+            headers: HeaderMap::new(),
+        }
+        .with_lifecycle(lifecycle)
+    }
+
+    /// Register the connection pool with another lifecycle
+    pub(crate) fn with_lifecycle(mut self, lifecycle: Lifecycle) -> Self {
+        lifecycle
+            .inner
+            .pools
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(self.pool.clone());
+        self.lifecycle = lifecycle;
+        self
+    }
+
+    /// Refuse new requests once the spend of the client reaches a cap
+    pub(crate) fn with_spend_guard(mut self, spend_guard: Arc<SpendGuard>) -> Self {
+        self.spend_guard = Some(spend_guard);
+        self
+    }
+
+    /// Add headers to every request, replacing default headers of the same name
+    pub(crate) fn with_headers(mut self, headers: &HeaderMap) -> Self {
+        for (name, value) in headers {
+            self.headers.insert(name.clone(), value.clone());
+        }
+        self
+    }
+
+    /// Add the headers of this connection, the feature flags of the current call and the
+    /// pooled key, if any, to a request
+    pub(crate) fn authorize(&self, request: RequestBuilder, key: Option<&str>) -> RequestBuilder {
+        let request = if self.headers.is_empty() { request } else { request.headers(self.headers.clone()) };
+        key_pool::authorize(request_options::apply_feature_flags(request), key)
+    }
+
+    /// Get the HTTP client for a new request, keeping the request registered until the guard is dropped
+    pub(crate) fn checkout(&self) -> VeniceResult<(ReqwestClient, InFlight)> {
+        if self.lifecycle.is_closed() {
+            return Err(VeniceError::ClientShutdown);
+        }
+        if let Some(spend_guard) = &self.spend_guard {
+            spend_guard.check()?;
+        }
+        let guard = InFlight::new(&self.lifecycle);
+        let client = self
+            .pool
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or(VeniceError::ClientShutdown)?;
+        Ok((client, guard))
+    }
+}
+
+/// Keeps a request registered as active until dropped
+#[derive(Debug)]
+pub(crate) struct InFlight {
+    lifecycle: Lifecycle,
+}
+
+impl InFlight {
+    fn new(lifecycle: &Lifecycle) -> Self {
+        let active = lifecycle.inner.active.fetch_add(1, Ordering::AcqRel) + 1;
+        lifecycle.inner.peak.fetch_max(active, Ordering::AcqRel);
+        lifecycle.inner.started.fetch_add(1, Ordering::AcqRel);
+        Self {
+            lifecycle: lifecycle.clone(),
+        }
+    }
+
+    /// Keep the request registered until the stream is dropped
+    pub(crate) fn attach<T: Send + 'static>(
+        self,
+        stream: Pin<Box<dyn Stream<Item = T> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = T> + Send>> {
+        Box::pin(stream.map(move |item| {
+            let _ = &self;
+            item
+        }))
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.lifecycle.inner.active.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.lifecycle.inner.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_active_requests() {
+        let lifecycle = Lifecycle::default();
+        let connection = Connection::new(ReqwestClient::new(), lifecycle.clone());
+
+        let (_, in_flight) = connection.checkout().unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            drop(in_flight);
+        });
+
+        let report = lifecycle.shutdown(Duration::from_secs(5)).await;
+        assert_eq!(report, ShutdownReport { drained: true, abandoned: 0 });
+        assert!(matches!(connection.checkout(), Err(VeniceError::ClientShutdown)));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_deadline() {
+        let lifecycle = Lifecycle::default();
+        let connection = Connection::new(ReqwestClient::new(), lifecycle.clone());
+
+        let (_, in_flight) = connection.checkout().unwrap();
+        let stream = in_flight.attach(Box::pin(futures::stream::pending::<u32>()));
+
+        let report = lifecycle.shutdown(Duration::from_millis(20)).await;
+        assert_eq!(report, ShutdownReport { drained: false, abandoned: 1 });
+
+        drop(stream);
+        assert_eq!(lifecycle.active(), 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let lifecycle = Lifecycle::default();
+        let connection = Connection::new(ReqwestClient::new(), lifecycle.clone());
+        assert!(lifecycle.stats().is_ready());
+
+        let (_, first) = connection.checkout().unwrap();
+        let (_, second) = connection.checkout().unwrap();
+        drop(first);
+
+        let stats = lifecycle.stats();
+        assert_eq!(stats.in_flight, 1);
+        assert_eq!(stats.peak_in_flight, 2);
+        assert_eq!(stats.total_requests, 2);
+        assert_eq!(stats.open_pools, 1);
+        drop(second);
+    }
+}
@@ -62,21 +62,21 @@ macro_rules! define_builder_add_method {
 macro_rules! define_endpoint {
     ($trait_name:ident, $method_name:ident, $endpoint:expr, $request_type:ty, $response_type:ty) => {
         #[async_trait::async_trait]
-        impl $trait_name for crate::client::Client {
+        impl $trait_name for $crate::client::Client {
             async fn $method_name(
                 &self,
                 request: $request_type,
-            ) -> crate::error::VeniceResult<($response_type, crate::error::RateLimitInfo)> {
+            ) -> $crate::error::VeniceResult<($response_type, $crate::error::RateLimitInfo)> {
                 self.post($endpoint, &request).await
             }
         }
     };
     ($trait_name:ident, $method_name:ident, $endpoint:expr, $response_type:ty) => {
         #[async_trait::async_trait]
-        impl $trait_name for crate::client::Client {
+        impl $trait_name for $crate::client::Client {
             async fn $method_name(
                 &self,
-            ) -> crate::error::VeniceResult<($response_type, crate::error::RateLimitInfo)> {
+            ) -> $crate::error::VeniceResult<($response_type, $crate::error::RateLimitInfo)> {
                 self.get($endpoint).await
             }
         }
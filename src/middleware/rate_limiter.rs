@@ -53,6 +53,7 @@ mod tests {
         let config = RateLimiterConfig {
             auto_wait: false,
             max_wait_time: 60,
+            ..Default::default()
         };
         let rate_limiter = Arc::new(RateLimiter::with_config(config));
         
@@ -99,6 +100,7 @@ mod tests {
         let config = RateLimiterConfig {
             auto_wait: false,
             max_wait_time: 60,
+            ..Default::default()
         };
         let rate_limiter = Arc::new(RateLimiter::with_config(config));
         
@@ -42,7 +42,7 @@ impl RetryMiddleware {
     
     /// Check if an error is retryable
     fn is_retryable(&self, error: &VeniceError) -> bool {
-        match error {
+        match error.root() {
             // Network errors are retryable
             VeniceError::HttpError(_) => true,
             
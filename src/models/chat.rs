@@ -5,35 +5,25 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// A role for a chat message
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ChatRole {
-    /// The system role, used for setting the behavior of the assistant
-    System,
-    /// The user role, used for user messages
-    User,
-    /// The assistant role, used for assistant responses
-    Assistant,
-}
-
-impl std::fmt::Display for ChatRole {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ChatRole::System => write!(f, "system"),
-            ChatRole::User => write!(f, "user"),
-            ChatRole::Assistant => write!(f, "assistant"),
-        }
-    }
-}
+pub use crate::traits::chat::{
+    ChatRole, FunctionCall, FunctionDefinition, StreamOptions, ToolCall, ToolDefinition, VeniceParameters,
+    VeniceResponseParameters, WebSearchCitation,
+};
 
 /// A chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     /// The role of the message sender
     pub role: ChatRole,
-    /// The content of the message
+    /// The content of the message, empty for assistant messages that only call tools
+    #[serde(default, deserialize_with = "crate::traits::chat::deserialize_nullable_string")]
     pub content: String,
+    /// Tools the assistant asked to call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// ID of the tool call a tool message answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
 }
 
 impl ChatMessage {
@@ -42,6 +32,8 @@ impl ChatMessage {
         Self {
             role,
             content: content.into(),
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
     
@@ -94,6 +86,18 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence_penalty: Option<f32>,
     
+    /// The number of most likely tokens to sample from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    
+    /// The repetition penalty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition_penalty: Option<f32>,
+    
+    /// The minimum probability of a token, relative to the most likely one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f32>,
+    
     /// The number of completions to generate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
@@ -102,6 +106,10 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     
+    /// Options for streamed responses, such as reporting token usage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    
     /// The random seed to use for sampling
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<u64>,
@@ -112,7 +120,26 @@ pub struct ChatCompletionRequest {
     
     /// Venice-specific parameters
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub venice_parameters: Option<HashMap<String, serde_json::Value>>,
+    pub venice_parameters: Option<VeniceParameters>,
+    
+    /// Tools the model may call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+    
+    /// Controls which tool is called: "none", "auto", "required" or a specific function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<serde_json::Value>,
+    /// Format the model must reply in, such as JSON matching a schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<crate::traits::chat::ResponseFormat>,
+    
+    /// ID of the end user, forwarded to the API for abuse tracking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    
+    /// Tags for grouping requests in usage analytics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_tags: Vec<String>,
     
     /// Extra parameters to include in the request
     #[serde(flatten)]
@@ -120,6 +147,26 @@ pub struct ChatCompletionRequest {
 }
 
 impl ChatCompletionRequest {
+    /// Check the request for problems the API would reject
+    ///
+    /// See [`crate::chat::ChatCompletionRequest::validate`] for the checks performed.
+    pub fn validate(&self) -> crate::error::VeniceResult<()> {
+        crate::chat::validation::ChatRequestFields {
+            model: &self.model,
+            roles: self.messages.iter().map(|m| m.role.as_str()).collect(),
+            max_tokens: self.max_tokens,
+            max_completion_tokens: self.max_completion_tokens,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            frequency_penalty: self.frequency_penalty,
+            presence_penalty: self.presence_penalty,
+            repetition_penalty: self.repetition_penalty,
+            min_p: self.min_p,
+            n: self.n,
+        }
+        .validate()
+    }
+
     /// Create a new chat completion request
     pub fn new(model: impl Into<String>, messages: Vec<ChatMessage>) -> Self {
         Self {
@@ -131,11 +178,20 @@ impl ChatCompletionRequest {
             top_p: None,
             frequency_penalty: None,
             presence_penalty: None,
+            top_k: None,
+            repetition_penalty: None,
+            min_p: None,
             n: None,
             stream: None,
+            stream_options: None,
             seed: None,
             stop: None,
             venice_parameters: None,
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            user: None,
+            request_tags: Vec::new(),
             extra: HashMap::new(),
         }
     }
@@ -176,6 +232,24 @@ impl ChatCompletionRequest {
         self
     }
     
+    /// Set the number of most likely tokens to sample from
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+    
+    /// Set the repetition penalty
+    pub fn repetition_penalty(mut self, repetition_penalty: f32) -> Self {
+        self.repetition_penalty = Some(repetition_penalty);
+        self
+    }
+    
+    /// Set the minimum probability of a token, relative to the most likely one
+    pub fn min_p(mut self, min_p: f32) -> Self {
+        self.min_p = Some(min_p);
+        self
+    }
+    
     /// Set the number of completions to generate
     pub fn n(mut self, n: u32) -> Self {
         self.n = Some(n);
@@ -188,6 +262,12 @@ impl ChatCompletionRequest {
         self
     }
     
+    /// Ask for a final streamed chunk reporting the token usage of the completion
+    pub fn include_usage(mut self, include_usage: bool) -> Self {
+        self.stream_options = Some(StreamOptions { include_usage });
+        self
+    }
+    
     /// Set the random seed to use for sampling
     pub fn seed(mut self, seed: u64) -> Self {
         self.seed = Some(seed);
@@ -214,19 +294,31 @@ impl ChatCompletionRequest {
         self
     }
     
-    /// Set a Venice-specific parameter
+    /// Set a Venice-specific parameter by name
     pub fn venice_parameter(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
-        let key = key.into();
-        let value = value.into();
-        match &mut self.venice_parameters {
-            Some(params) => {
-                params.insert(key, value);
-            }
-            None => {
-                let mut params = HashMap::new();
-                params.insert(key, value);
-                self.venice_parameters = Some(params);
-            }
+        self.venice_parameters
+            .get_or_insert_with(VeniceParameters::default)
+            .set(key, value);
+        self
+    }
+    
+    /// Set the Venice-specific parameters, replacing any set before
+    pub fn venice_parameters(mut self, venice_parameters: VeniceParameters) -> Self {
+        self.venice_parameters = Some(venice_parameters);
+        self
+    }
+    
+    /// Set the ID of the end user, forwarded to the API for abuse tracking
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user = Some(user_id.into());
+        self
+    }
+    
+    /// Add a tag for grouping requests in usage analytics
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.request_tags.contains(&tag) {
+            self.request_tags.push(tag);
         }
         self
     }
@@ -271,6 +363,22 @@ pub struct ChatCompletionResponse {
     
     /// The usage statistics
     pub usage: Option<ChatCompletionUsage>,
+    
+    /// Venice-specific response fields, such as web search citations
+    #[serde(default)]
+    pub venice_parameters: Option<VeniceResponseParameters>,
+}
+
+impl ChatCompletionResponse {
+    /// Get the web search results cited in the response
+    ///
+    /// Empty unless the request enabled web search.
+    pub fn web_search_citations(&self) -> &[WebSearchCitation] {
+        self.venice_parameters
+            .as_ref()
+            .map(|params| params.web_search_citations.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 /// Usage statistics for a chat completion
@@ -303,6 +411,26 @@ pub struct ChatCompletionChunk {
     
     /// The choices
     pub choices: Vec<ChatCompletionChunkChoice>,
+    
+    /// Venice-specific fields; web search citations arrive in the first chunk
+    #[serde(default)]
+    pub venice_parameters: Option<VeniceResponseParameters>,
+    
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl ChatCompletionChunk {
+    /// Get the web search results cited in the response
+    ///
+    /// Empty unless the request enabled web search.
+    pub fn web_search_citations(&self) -> &[WebSearchCitation] {
+        self.venice_parameters
+            .as_ref()
+            .map(|params| params.web_search_citations.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 /// A streaming chat completion choice
@@ -316,6 +444,10 @@ pub struct ChatCompletionChunkChoice {
     
     /// The reason the completion stopped
     pub finish_reason: Option<String>,
+    
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// A streaming chat completion delta
@@ -326,6 +458,10 @@ pub struct ChatCompletionChunkDelta {
     
     /// The content of the message
     pub content: Option<String>,
+    
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -358,6 +494,7 @@ mod tests {
         .stream(true)
         .add_stop("\n")
         .venice_parameter("some_param", "value")
+        .venice_parameter("character_slug", "alan-watts")
         .extra("custom_param", 42);
         
         assert_eq!(request.model, "gpt-4");
@@ -368,8 +505,79 @@ mod tests {
         assert_eq!(request.stop, Some(vec!["\n".to_string()]));
         
         let venice_params = request.venice_parameters.unwrap();
-        assert_eq!(venice_params.get("some_param").unwrap().as_str().unwrap(), "value");
+        assert_eq!(venice_params.extra.get("some_param").unwrap().as_str().unwrap(), "value");
+        assert_eq!(venice_params.character_slug.as_deref(), Some("alan-watts"));
         
         assert_eq!(request.extra.get("custom_param").unwrap().as_i64().unwrap(), 42);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_chat_completion_request_sampling_parameters() {
+        let request = ChatCompletionRequest::new("llama-3.3-70b", vec![ChatMessage::user("Hello!")]);
+        let json = serde_json::to_value(&request).unwrap();
+        assert!(json.get("top_k").is_none());
+        assert!(json.get("repetition_penalty").is_none());
+        assert!(json.get("min_p").is_none());
+        
+        let request = request.top_k(40).repetition_penalty(1.1).min_p(0.05);
+        assert!(request.validate().is_ok());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["top_k"], 40);
+        assert_eq!(json["repetition_penalty"], 1.1f32 as f64);
+        assert_eq!(json["min_p"], 0.05f32 as f64);
+        
+        let request = request.repetition_penalty(0.0).min_p(1.5);
+        let err = request.validate().unwrap_err();
+        assert!(matches!(&err, crate::error::VeniceError::InvalidInput(msg)
+            if msg.contains("repetition_penalty must be greater than 0") && msg.contains("min_p must be between 0 and 1, got 1.5")));
+    }
+    
+    #[test]
+    fn test_chunk_captures_unknown_fields() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "system_fingerprint": "fp_1",
+            "choices": [{
+                "index": 0,
+                "delta": {"content": "Hi", "reasoning_content": "thinking"},
+                "finish_reason": null,
+                "logprobs": null
+            }]
+        }"#;
+        
+        let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+        assert_eq!(chunk.extra.get("system_fingerprint").unwrap(), "fp_1");
+        assert!(chunk.choices[0].extra.contains_key("logprobs"));
+        assert_eq!(chunk.choices[0].delta.content.as_deref(), Some("Hi"));
+        assert_eq!(chunk.choices[0].delta.extra.get("reasoning_content").unwrap(), "thinking");
+    }
+    
+    #[test]
+    #[cfg(not(feature = "strict-schema"))]
+    fn test_response_web_search_citations() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "llama-3.3-70b",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+            "usage": null,
+            "venice_parameters": {
+                "web_search_citations": [
+                    {"title": "Venice", "url": "https://venice.ai", "content": "Private AI", "date": "2025-01-01"}
+                ],
+                "include_venice_system_prompt": true
+            }
+        }"#;
+        
+        let response: ChatCompletionResponse = serde_json::from_str(json).unwrap();
+        let citations = response.web_search_citations();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].url, "https://venice.ai");
+        assert_eq!(citations[0].content.as_deref(), Some("Private AI"));
+        assert!(response.venice_parameters.unwrap().extra.contains_key("include_venice_system_prompt"));
+    }
+}
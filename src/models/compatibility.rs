@@ -0,0 +1,218 @@
+//! Cached lookup table of which models support which traits
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::endpoints;
+use crate::error::VeniceResult;
+use crate::http::SharedHttpClient;
+use crate::models::traits::ModelTraitsResponse;
+
+/// How long a fetched index is used before it is refreshed
+pub const DEFAULT_COMPATIBILITY_TTL: Duration = Duration::from_secs(300);
+
+/// Model/trait lookup tables from a single traits fetch
+#[derive(Debug)]
+struct Snapshot {
+    traits_by_model: HashMap<String, HashSet<String>>,
+    models_by_trait: HashMap<String, Vec<String>>,
+    fetched_at: Instant,
+}
+
+impl Snapshot {
+    fn from_response(response: ModelTraitsResponse) -> Self {
+        let mut traits_by_model: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut models_by_trait = HashMap::new();
+        for model_trait in response.data {
+            for model in &model_trait.models {
+                traits_by_model
+                    .entry(model.clone())
+                    .or_default()
+                    .insert(model_trait.id.clone());
+            }
+            models_by_trait.insert(model_trait.id, model_trait.models);
+        }
+        Self {
+            traits_by_model,
+            models_by_trait,
+            fetched_at: Instant::now(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct IndexState {
+    snapshot: Option<Snapshot>,
+    ttl: Duration,
+}
+
+/// Cached index of model traits, built from one `models/traits` fetch
+///
+/// The index is loaded on first use and reloaded once it is older than its TTL.
+/// Lookups against a fresh index are hash map lookups and make no requests.
+/// Obtain one with [`Client::compatibility`](crate::Client::compatibility).
+#[derive(Debug, Clone)]
+pub struct CompatibilityIndex {
+    http_client: SharedHttpClient,
+    state: Arc<RwLock<IndexState>>,
+}
+
+impl CompatibilityIndex {
+    /// Create an empty index that fetches through the given HTTP client
+    pub(crate) fn new(http_client: SharedHttpClient) -> Self {
+        Self {
+            http_client,
+            state: Arc::new(RwLock::new(IndexState {
+                snapshot: None,
+                ttl: DEFAULT_COMPATIBILITY_TTL,
+            })),
+        }
+    }
+
+    /// How long a fetched index is used before it is refreshed
+    pub fn ttl(&self) -> Duration {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).ttl
+    }
+
+    /// Change how long a fetched index is used before it is refreshed
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.state.write().unwrap_or_else(|e| e.into_inner()).ttl = ttl;
+    }
+
+    /// Check whether the index is loaded and younger than its TTL
+    pub fn is_fresh(&self) -> bool {
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        state
+            .snapshot
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.fetched_at.elapsed() < state.ttl)
+    }
+
+    /// Drop the cached index so the next lookup fetches it again
+    pub fn invalidate(&self) {
+        self.state.write().unwrap_or_else(|e| e.into_inner()).snapshot = None;
+    }
+
+    /// Fetch the model traits and rebuild the index
+    pub async fn refresh(&self) -> VeniceResult<()> {
+        let (response, _) = self.http_client.get::<ModelTraitsResponse>(endpoints::MODELS_TRAITS).await?;
+        let snapshot = Snapshot::from_response(response);
+        self.state.write().unwrap_or_else(|e| e.into_inner()).snapshot = Some(snapshot);
+        Ok(())
+    }
+
+    /// Check whether a model has a trait
+    pub async fn supports(&self, model: &str, feature: &str) -> VeniceResult<bool> {
+        self.lookup(|snapshot| {
+            snapshot
+                .traits_by_model
+                .get(model)
+                .is_some_and(|traits| traits.contains(feature))
+        })
+        .await
+    }
+
+    /// Get the traits of a model, sorted by identifier
+    pub async fn traits_of(&self, model: &str) -> VeniceResult<Vec<String>> {
+        self.lookup(|snapshot| {
+            let mut traits: Vec<String> = snapshot
+                .traits_by_model
+                .get(model)
+                .map(|traits| traits.iter().cloned().collect())
+                .unwrap_or_default();
+            traits.sort();
+            traits
+        })
+        .await
+    }
+
+    /// Get the models that have a trait
+    pub async fn models_with(&self, feature: &str) -> VeniceResult<Vec<String>> {
+        self.lookup(|snapshot| snapshot.models_by_trait.get(feature).cloned().unwrap_or_default())
+            .await
+    }
+
+    /// Run a lookup against the index, refreshing it first if it is stale
+    async fn lookup<R>(&self, f: impl Fn(&Snapshot) -> R) -> VeniceResult<R> {
+        if !self.is_fresh() {
+            self.refresh().await?;
+        }
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        Ok(state.snapshot.as_ref().map(f).expect("index was just refreshed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{new_shared_http_client, HttpClientConfig};
+
+    const TRAITS_BODY: &str = r#"{
+        "object": "list",
+        "data": [
+            {"id": "function_calling", "name": "Function calling", "description": "", "models": ["llama-3.3-70b", "qwen-2.5-vl"]},
+            {"id": "vision", "name": "Vision", "description": "", "models": ["qwen-2.5-vl"]}
+        ]
+    }"#;
+
+    fn index_for(server: &mockito::Server) -> CompatibilityIndex {
+        let config = HttpClientConfig::builder().api_key("test_api_key").base_url(server.url()).build().unwrap();
+        CompatibilityIndex::new(new_shared_http_client(config).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_lookups_share_one_fetch() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models/traits")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TRAITS_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+        let index = index_for(&server);
+
+        assert!(index.supports("qwen-2.5-vl", "vision").await.unwrap());
+        assert!(!index.supports("llama-3.3-70b", "vision").await.unwrap());
+        assert!(!index.supports("unknown", "vision").await.unwrap());
+        assert_eq!(
+            index.traits_of("qwen-2.5-vl").await.unwrap(),
+            vec!["function_calling".to_string(), "vision".to_string()]
+        );
+        assert_eq!(index.models_with("vision").await.unwrap(), vec!["qwen-2.5-vl".to_string()]);
+        assert!(index.is_fresh());
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_refresh_and_ttl_refetch() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/models/traits")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TRAITS_BODY)
+            .expect(3)
+            .create_async()
+            .await;
+        let index = index_for(&server);
+
+        index.refresh().await.unwrap();
+        assert!(index.supports("llama-3.3-70b", "function_calling").await.unwrap());
+
+        // A zero TTL makes every lookup refetch
+        index.set_ttl(Duration::ZERO);
+        assert!(!index.is_fresh());
+        assert!(index.supports("llama-3.3-70b", "function_calling").await.unwrap());
+
+        index.set_ttl(DEFAULT_COMPATIBILITY_TTL);
+        index.invalidate();
+        assert!(!index.is_fresh());
+        assert!(index.supports("qwen-2.5-vl", "vision").await.unwrap());
+
+        mock.assert_async().await;
+    }
+}
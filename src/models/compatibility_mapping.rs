@@ -3,12 +3,10 @@ use std::collections::HashMap;
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
 };
 
-/// The endpoint for retrieving model compatibility mapping
-const COMPATIBILITY_ENDPOINT: &str = "models/compatibility_mapping";
-
 /// Request parameters for retrieving model compatibility mapping
 #[derive(Debug, Serialize, Default)]
 pub struct CompatibilityMappingRequest {
@@ -43,7 +41,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{Client, models::CompatibilityMappingRequest};
     ///
     /// #[tokio::main]
@@ -91,17 +89,17 @@ impl Client {
                 
                 // Append query parameters to endpoint
                 if query_params.is_empty() {
-                    self.get(COMPATIBILITY_ENDPOINT).await
+                    self.get(endpoints::MODELS_COMPATIBILITY_MAPPING).await
                 } else {
                     let endpoint = format!(
                         "{}?{}",
-                        COMPATIBILITY_ENDPOINT,
+                        endpoints::MODELS_COMPATIBILITY_MAPPING,
                         serde_urlencoded::to_string(query_params).unwrap_or_default()
                     );
                     self.get(&endpoint).await
                 }
             }
-            None => self.get(COMPATIBILITY_ENDPOINT).await,
+            None => self.get(endpoints::MODELS_COMPATIBILITY_MAPPING).await,
         }
     }
 }
@@ -110,7 +108,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::models::{get_compatibility_mapping, CompatibilityMappingRequest};
 ///
 /// #[tokio::main]
@@ -1,20 +1,65 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
     pagination::{PaginationInfo, PaginationParams, Paginator},
 };
 
-/// The endpoint for listing models
-const MODELS_ENDPOINT: &str = "models";
+/// Kind of model, used to filter the models list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelType {
+    /// Text generation models
+    Text,
+    /// Image generation models
+    Image,
+    /// Text-to-speech models
+    Tts,
+    /// Embedding models
+    Embedding,
+    /// Image upscaling models
+    Upscale,
+    /// Models of every type
+    All,
+}
+
+/// Capability a listed model must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModelCapability {
+    /// Streaming responses
+    Streaming,
+    /// Image generation
+    ImageGeneration,
+    /// Chat completions
+    ChatCompletions,
+    /// Function calling
+    FunctionCalling,
+}
 
 /// Request parameters for listing models
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Default)]
 pub struct ListModelsRequest {
     /// Pagination parameters
     #[serde(flatten)]
     pub pagination: PaginationParams,
+    /// Only list models of this type
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub model_type: Option<ModelType>,
+    /// Only list models that support streaming
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_streaming: Option<bool>,
+    /// Only list models that support image generation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_image_generation: Option<bool>,
+    /// Only list models that support chat completions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_chat_completions: Option<bool>,
+    /// Only list models that support function calling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_function_calling: Option<bool>,
 }
 
 impl ListModelsRequest {
@@ -34,10 +79,31 @@ impl ListModelsRequest {
         self.pagination = self.pagination.cursor(cursor);
         self
     }
+    
+    /// Only list models of the given type
+    pub fn model_type(mut self, model_type: ModelType) -> Self {
+        self.model_type = Some(model_type);
+        self
+    }
+    
+    /// Only list models that have the given capability
+    ///
+    /// Can be called several times; listed models have every requested capability.
+    pub fn capability(mut self, capability: ModelCapability) -> Self {
+        let flag = match capability {
+            ModelCapability::Streaming => &mut self.supports_streaming,
+            ModelCapability::ImageGeneration => &mut self.supports_image_generation,
+            ModelCapability::ChatCompletions => &mut self.supports_chat_completions,
+            ModelCapability::FunctionCalling => &mut self.supports_function_calling,
+        };
+        *flag = Some(true);
+        self
+    }
 }
 
 /// Response from the models API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ListModelsResponse {
     /// Array of model information
     pub data: Vec<Model>,
@@ -66,7 +132,8 @@ impl PaginationInfo<Model> for ListModelsResponse {
 }
 
 /// Information about a model
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Model {
     /// The model identifier
     pub id: String,
@@ -99,7 +166,8 @@ pub struct Model {
 }
 
 /// Model permission information
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ModelPermission {
     /// The type of object
     pub object: String,
@@ -126,7 +194,8 @@ pub struct ModelPermission {
 }
 
 /// Model pricing information
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ModelPricing {
     /// Cost per 1K tokens for input/prompt
     pub prompt: Option<f64>,
@@ -134,12 +203,142 @@ pub struct ModelPricing {
     pub completion: Option<f64>,
 }
 
+impl ModelCapability {
+    /// Every capability, in declaration order
+    pub const ALL: [ModelCapability; 4] = [
+        ModelCapability::Streaming,
+        ModelCapability::ImageGeneration,
+        ModelCapability::ChatCompletions,
+        ModelCapability::FunctionCalling,
+    ];
+}
+
+impl Model {
+    /// Check whether the model has a capability
+    pub fn has_capability(&self, capability: ModelCapability) -> bool {
+        match capability {
+            ModelCapability::Streaming => self.supports_streaming,
+            ModelCapability::ImageGeneration => self.supports_image_generation,
+            ModelCapability::ChatCompletions => self.supports_chat_completions,
+            ModelCapability::FunctionCalling => self.supports_function_calling,
+        }
+    }
+
+    /// Get the capabilities of the model
+    pub fn capabilities(&self) -> Vec<ModelCapability> {
+        ModelCapability::ALL.into_iter().filter(|capability| self.has_capability(*capability)).collect()
+    }
+}
+
+impl ListModelsResponse {
+    /// Compare this list with an earlier one
+    ///
+    /// Models are matched by ID. A model counts as changed when its pricing, its
+    /// capabilities, its context size or its maximum number of tokens differ.
+    pub fn diff(&self, previous: &ListModelsResponse) -> ModelsDiff {
+        let previous_by_id: HashMap<&str, &Model> =
+            previous.data.iter().map(|model| (model.id.as_str(), model)).collect();
+        let current_ids: HashSet<&str> = self.data.iter().map(|model| model.id.as_str()).collect();
+
+        let mut diff = ModelsDiff::default();
+        for model in &self.data {
+            match previous_by_id.get(model.id.as_str()) {
+                None => diff.added.push(model.clone()),
+                Some(previous) => {
+                    let change = ModelChange {
+                        previous: (*previous).clone(),
+                        current: model.clone(),
+                    };
+                    if change.pricing_changed() || change.capabilities_changed() || change.limits_changed() {
+                        diff.changed.push(change);
+                    }
+                }
+            }
+        }
+        diff.removed = previous
+            .data
+            .iter()
+            .filter(|model| !current_ids.contains(model.id.as_str()))
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+/// Differences between two model lists, from [`ListModelsResponse::diff`]
+#[derive(Debug, Clone, Default)]
+pub struct ModelsDiff {
+    /// Models that were not listed before
+    pub added: Vec<Model>,
+    /// Models that are no longer listed
+    pub removed: Vec<Model>,
+    /// Models whose pricing, capabilities or limits changed
+    pub changed: Vec<ModelChange>,
+}
+
+impl ModelsDiff {
+    /// Check whether the lists are the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A model listed in both lists whose details differ
+#[derive(Debug, Clone)]
+pub struct ModelChange {
+    /// The model as previously listed
+    pub previous: Model,
+    /// The model as currently listed
+    pub current: Model,
+}
+
+impl ModelChange {
+    /// The model identifier
+    pub fn id(&self) -> &str {
+        &self.current.id
+    }
+
+    /// Check whether the prompt or completion price changed
+    pub fn pricing_changed(&self) -> bool {
+        self.previous.pricing != self.current.pricing
+    }
+
+    /// Check whether any capability was gained or lost
+    pub fn capabilities_changed(&self) -> bool {
+        self.previous.capabilities() != self.current.capabilities()
+    }
+
+    /// Capabilities the model gained
+    pub fn added_capabilities(&self) -> Vec<ModelCapability> {
+        self.current
+            .capabilities()
+            .into_iter()
+            .filter(|capability| !self.previous.has_capability(*capability))
+            .collect()
+    }
+
+    /// Capabilities the model lost
+    pub fn removed_capabilities(&self) -> Vec<ModelCapability> {
+        self.previous
+            .capabilities()
+            .into_iter()
+            .filter(|capability| !self.current.has_capability(*capability))
+            .collect()
+    }
+
+    /// Check whether the context size or the maximum number of tokens changed
+    pub fn limits_changed(&self) -> bool {
+        self.previous.context_size != self.current.context_size
+            || self.previous.max_tokens != self.current.max_tokens
+    }
+}
+
 impl Client {
     /// List available models
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::Client;
     ///
     /// #[tokio::main]
@@ -162,7 +361,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{Client, models::ListModelsRequest};
     ///
     /// #[tokio::main]
@@ -196,15 +395,15 @@ impl Client {
         &self,
         request: ListModelsRequest,
     ) -> VeniceResult<(ListModelsResponse, RateLimitInfo)> {
-        self.get_with_query(MODELS_ENDPOINT, &request).await
+        self.get_with_query(endpoints::MODELS, &request).await
     }
     
     /// Create a paginator for listing models
     ///
     /// # Examples
     ///
-    /// ```
-    /// use venice_ai_api_sdk_rust::{Client, pagination::{PaginationParams, Paginator}};
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, PaginationParams, Paginator};
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -212,7 +411,7 @@ impl Client {
     ///
     ///     // Create a paginator with 10 models per page
     ///     let params = PaginationParams::new().limit(10);
-    ///     let mut paginator = client.list_models_paginator(params);
+    ///     let mut paginator = client.list_models_paginator(params.clone());
     ///
     ///     // Get all models
     ///     let all_models = paginator.all_pages().await?;
@@ -238,7 +437,7 @@ impl Client {
         let fetch_page = move |params: PaginationParams| {
             let client = client.clone();
             async move {
-                let request = ListModelsRequest { pagination: params };
+                let request = ListModelsRequest { pagination: params, ..Default::default() };
                 client.list_models_with_params(request).await
             }
         };
@@ -251,7 +450,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::models::list_models;
 ///
 /// #[tokio::main]
@@ -288,4 +487,49 @@ pub fn list_models_paginator(
 ) -> VeniceResult<impl Paginator<Model>> {
     let client = Client::new(api_key)?;
     Ok(client.list_models_paginator(params))
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, prompt: f64, function_calling: bool) -> Model {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "object": "model",
+            "owned_by": "venice",
+            "context_size": 32768,
+            "supports_chat_completions": true,
+            "supports_function_calling": function_calling,
+            "pricing": {"prompt": prompt, "completion": 0.002}
+        }))
+        .unwrap()
+    }
+
+    fn list(data: Vec<Model>) -> ListModelsResponse {
+        ListModelsResponse {
+            data,
+            object: "list".to_string(),
+            has_more: false,
+            next_cursor: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_models() {
+        let previous = list(vec![model("kept", 0.001, false), model("repriced", 0.001, false), model("retired", 0.001, false)]);
+        let current = list(vec![model("kept", 0.001, false), model("repriced", 0.0015, true), model("new", 0.001, false)]);
+
+        let diff = current.diff(&previous);
+        assert_eq!(diff.added.iter().map(|model| model.id.as_str()).collect::<Vec<_>>(), vec!["new"]);
+        assert_eq!(diff.removed.iter().map(|model| model.id.as_str()).collect::<Vec<_>>(), vec!["retired"]);
+        assert_eq!(diff.changed.len(), 1);
+        let change = &diff.changed[0];
+        assert_eq!(change.id(), "repriced");
+        assert!(change.pricing_changed());
+        assert!(!change.limits_changed());
+        assert_eq!(change.added_capabilities(), vec![ModelCapability::FunctionCalling]);
+        assert!(change.removed_capabilities().is_empty());
+
+        assert!(current.diff(&current).is_empty());
+    }
+}
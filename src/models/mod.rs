@@ -6,6 +6,7 @@
 pub mod list;
 pub mod traits;
 mod compatibility_mapping;
+mod compatibility;
 
 // Shared data models
 pub mod chat;
@@ -14,4 +15,5 @@ pub mod chat;
 pub use list::*;
 pub use traits::*;
 pub use compatibility_mapping::*;
+pub use compatibility::*;
 pub use chat::*;
\ No newline at end of file
@@ -2,12 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     client::Client,
+    endpoints,
     error::{RateLimitInfo, VeniceResult},
 };
 
-/// The endpoint for retrieving model traits
-const TRAITS_ENDPOINT: &str = "models/traits";
-
 /// Request parameters for retrieving model traits
 #[derive(Debug, Serialize, Default)]
 pub struct ModelTraitsRequest {
@@ -47,7 +45,7 @@ impl Client {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use venice_ai_api_sdk_rust::{Client, models::ModelTraitsRequest};
     ///
     /// #[tokio::main]
@@ -90,17 +88,17 @@ impl Client {
                 
                 // Append query parameters to endpoint
                 if query_params.is_empty() {
-                    self.get(TRAITS_ENDPOINT).await
+                    self.get(endpoints::MODELS_TRAITS).await
                 } else {
                     let endpoint = format!(
                         "{}?{}",
-                        TRAITS_ENDPOINT,
+                        endpoints::MODELS_TRAITS,
                         serde_urlencoded::to_string(query_params).unwrap_or_default()
                     );
                     self.get(&endpoint).await
                 }
             }
-            None => self.get(TRAITS_ENDPOINT).await,
+            None => self.get(endpoints::MODELS_TRAITS).await,
         }
     }
 }
@@ -109,7 +107,7 @@ impl Client {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use venice_ai_api_sdk_rust::models::{get_model_traits, ModelTraitsRequest};
 ///
 /// #[tokio::main]
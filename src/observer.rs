@@ -0,0 +1,382 @@
+//! Usage observers
+//!
+//! This module provides a hook for applications that want to export request
+//! metrics (token usage, rate limits, balances, latency) without wrapping every
+//! call site. Observers registered on a [`crate::Client`] are notified for every
+//! request the client sends.
+
+use reqwest::Method;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::http::{ParseWarning, ResponseMetadata};
+use crate::retry::RetryReport;
+use crate::traits::chat::ChatCompletionUsage;
+
+/// Information about a request passed to usage observers
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    /// The HTTP method of the request
+    pub method: Method,
+    /// The API endpoint, relative to the base URL
+    pub endpoint: String,
+    /// The model that served the request, as reported in the response body
+    ///
+    /// Only set in [`UsageObserver::on_response`], and only for responses that name a model.
+    pub model: Option<String>,
+    /// The end-user ID sent with the request, for chat and image requests that set one
+    pub user: Option<String>,
+    /// The tags sent with the request, for chat and image requests that set them
+    pub tags: Vec<String>,
+    /// Hex-encoded SHA-256 of the JSON body as sent, for requests with a JSON body
+    ///
+    /// Lets audit logs tell requests apart without storing prompts.
+    pub payload_sha256: Option<String>,
+    /// The HTTP status of the response
+    ///
+    /// Set in [`UsageObserver::on_response`] for JSON responses, and in
+    /// [`UsageObserver::on_error`] when the API answered with an error status.
+    pub status: Option<reqwest::StatusCode>,
+}
+
+/// The `user` and `request_tags` of the request being sent
+///
+/// The API methods set these for the duration of a call, so that the transport can
+/// report them to observers without knowing the request type.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestLabels {
+    user: Option<String>,
+    tags: Vec<String>,
+}
+
+tokio::task_local! {
+    static REQUEST_LABELS: RequestLabels;
+}
+
+impl RequestLabels {
+    /// Create labels from the fields of a request
+    pub(crate) fn new(user: Option<&str>, tags: &[String]) -> Self {
+        Self {
+            user: user.map(str::to_string),
+            tags: tags.to_vec(),
+        }
+    }
+
+    /// Run a future with these labels attached to every request it observes
+    pub(crate) async fn scope<F: Future>(self, future: F) -> F::Output {
+        REQUEST_LABELS.scope(self, future).await
+    }
+
+    /// Get the labels of the current call, if any
+    fn current() -> Self {
+        REQUEST_LABELS.try_with(Clone::clone).unwrap_or_default()
+    }
+}
+
+/// Observer for request metrics
+///
+/// All methods have empty default implementations, so an observer only needs to
+/// implement the callbacks it cares about. Callbacks are invoked synchronously on
+/// the request path and should return quickly.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::{Client, RateLimitInfo, RequestInfo, UsageObserver};
+/// use venice_ai_api_sdk_rust::traits::chat::ChatCompletionUsage;
+///
+/// #[derive(Default)]
+/// struct TokenCounter {
+///     total_tokens: AtomicU64,
+/// }
+///
+/// impl UsageObserver for TokenCounter {
+///     fn on_response(
+///         &self,
+///         _request: &RequestInfo,
+///         usage: Option<&ChatCompletionUsage>,
+///         _rate_limit: &RateLimitInfo,
+///         _latency: Duration,
+///     ) {
+///         if let Some(usage) = usage {
+///             self.total_tokens.fetch_add(usage.total_tokens as u64, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let counter = Arc::new(TokenCounter::default());
+/// let client = Client::new("your-api-key")
+///     .unwrap()
+///     .with_usage_observer(counter.clone());
+/// ```
+pub trait UsageObserver: Send + Sync {
+    /// Called right before a request is sent
+    fn on_request(&self, _request: &RequestInfo) {}
+
+    /// Called when a request completes successfully
+    ///
+    /// `usage` is present for responses that report token usage, such as chat completions.
+    fn on_response(
+        &self,
+        _request: &RequestInfo,
+        _usage: Option<&ChatCompletionUsage>,
+        _rate_limit: &RateLimitInfo,
+        _latency: Duration,
+    ) {
+    }
+
+    /// Called when a request fails
+    fn on_error(&self, _request: &RequestInfo, _error: &VeniceError, _latency: Duration) {}
+
+    /// Called when a request had to be sent more than once
+    ///
+    /// Only called for clients with retries enabled, before `on_response` or `on_error`.
+    fn on_retry(&self, _request: &RequestInfo, _report: &RetryReport) {}
+
+    /// Called for every response field dropped by lenient parsing
+    ///
+    /// Only called for clients with lenient parsing enabled, before `on_response`.
+    fn on_parse_warning(&self, _request: &RequestInfo, _warning: &ParseWarning) {}
+}
+
+/// A request that is being observed
+pub(crate) struct ObservedRequest {
+    /// The request information passed to observers
+    info: RequestInfo,
+    /// When the request was started
+    started: Instant,
+}
+
+/// The set of observers registered on a client
+///
+/// Clones share the same underlying list, so observers added through one handle
+/// are seen by every HTTP client built from the same `Client`.
+#[derive(Clone, Default)]
+pub(crate) struct UsageObservers {
+    observers: Arc<RwLock<Vec<Arc<dyn UsageObserver>>>>,
+}
+
+impl fmt::Debug for UsageObservers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageObservers")
+            .field("count", &self.len())
+            .finish()
+    }
+}
+
+impl UsageObservers {
+    /// Register an observer
+    pub(crate) fn add(&self, observer: Arc<dyn UsageObserver>) {
+        self.observers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(observer);
+    }
+
+    /// Get a snapshot of the registered observers
+    pub(crate) fn all(&self) -> Vec<Arc<dyn UsageObserver>> {
+        self.observers.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Get the number of registered observers
+    pub(crate) fn len(&self) -> usize {
+        self.observers.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Run a callback for every registered observer
+    fn notify(&self, f: impl Fn(&dyn UsageObserver)) {
+        let observers = self.observers.read().unwrap_or_else(|e| e.into_inner());
+        for observer in observers.iter() {
+            f(observer.as_ref());
+        }
+    }
+
+    /// Start observing a request and notify `on_request`
+    pub(crate) fn start(&self, method: Method, endpoint: &str) -> ObservedRequest {
+        self.start_with_payload(method, endpoint, None)
+    }
+
+    /// Start observing a request with a JSON body and notify `on_request`
+    ///
+    /// The body is only hashed when observers are registered.
+    pub(crate) fn start_with_payload(&self, method: Method, endpoint: &str, payload: Option<&[u8]>) -> ObservedRequest {
+        let labels = RequestLabels::current();
+        let payload_sha256 = payload
+            .filter(|_| self.len() > 0)
+            .map(|payload| hex::encode(Sha256::digest(payload)));
+        let request = ObservedRequest {
+            info: RequestInfo {
+                method,
+                endpoint: endpoint.to_string(),
+                model: None,
+                user: labels.user,
+                tags: labels.tags,
+                payload_sha256,
+                status: None,
+            },
+            started: Instant::now(),
+        };
+        self.notify(|o| o.on_request(&request.info));
+        request
+    }
+
+    /// Notify `on_response` for a completed request
+    pub(crate) fn response(
+        &self,
+        request: &ObservedRequest,
+        usage: Option<&ChatCompletionUsage>,
+        rate_limit: &RateLimitInfo,
+    ) {
+        let latency = request.started.elapsed();
+        self.notify(|o| o.on_response(&request.info, usage, rate_limit, latency));
+    }
+
+    /// Notify `on_retry` if a request was sent more than once
+    pub(crate) fn retried(&self, request: &ObservedRequest, report: &RetryReport) {
+        if report.retried() {
+            self.notify(|o| o.on_retry(&request.info, report));
+        }
+    }
+
+    /// Notify `on_error` for a failed request
+    pub(crate) fn error(&self, request: &ObservedRequest, error: &VeniceError) {
+        let latency = request.started.elapsed();
+        let mut info = request.info.clone();
+        if let VeniceError::ApiError { status, .. } = error.root() {
+            info.status = Some(*status);
+        }
+        self.notify(|o| o.on_error(&info, error, latency));
+    }
+
+    /// Notify observers of the outcome of a JSON request and drop the response metadata
+    pub(crate) fn finish<T>(
+        &self,
+        mut request: ObservedRequest,
+        result: VeniceResult<(T, RateLimitInfo, ResponseMetadata)>,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        match result {
+            Ok((data, rate_limit_info, metadata)) => {
+                request.info.model = metadata.model;
+                request.info.status = metadata.status;
+                for warning in &metadata.warnings {
+                    self.notify(|o| o.on_parse_warning(&request.info, warning));
+                }
+                self.response(&request, metadata.usage.as_ref(), &rate_limit_info);
+                Ok((data, rate_limit_info))
+            }
+            Err(err) => {
+                self.error(&request, &err);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl UsageObserver for RecordingObserver {
+        fn on_request(&self, request: &RequestInfo) {
+            self.events.lock().unwrap().push(format!("request {} {}", request.method, request.endpoint));
+        }
+
+        fn on_response(
+            &self,
+            request: &RequestInfo,
+            usage: Option<&ChatCompletionUsage>,
+            _rate_limit: &RateLimitInfo,
+            _latency: Duration,
+        ) {
+            let tokens = usage.map(|u| u.total_tokens).unwrap_or(0);
+            let model = request.model.as_deref().unwrap_or("-");
+            self.events.lock().unwrap().push(format!("response {} {}", model, tokens));
+        }
+
+        fn on_error(&self, _request: &RequestInfo, error: &VeniceError, _latency: Duration) {
+            self.events.lock().unwrap().push(format!("error {}", error));
+        }
+    }
+
+    fn rate_limit_info() -> RateLimitInfo {
+        RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new())
+    }
+
+    #[test]
+    fn test_observers_are_notified() {
+        let observer = Arc::new(RecordingObserver::default());
+        let observers = UsageObservers::default();
+        observers.add(observer.clone());
+
+        let request = observers.start(Method::POST, "chat/completions");
+        let usage = ChatCompletionUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+        };
+        let metadata = ResponseMetadata {
+            model: Some("llama-3.3-70b".to_string()),
+            usage: Some(usage),
+            status: Some(reqwest::StatusCode::OK),
+            warnings: Vec::new(),
+        };
+        let result = observers.finish(request, Ok(((), rate_limit_info(), metadata)));
+        assert!(result.is_ok());
+
+        let request = observers.start(Method::GET, "models");
+        let result: VeniceResult<((), RateLimitInfo)> = observers.finish(
+            request,
+            Err(VeniceError::InvalidInput("bad".to_string())),
+        );
+        assert!(result.is_err());
+
+        let events = observer.events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                "request POST chat/completions".to_string(),
+                "response llama-3.3-70b 15".to_string(),
+                "request GET models".to_string(),
+                "error Invalid input: bad".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_labels_reach_observers() {
+        let observer = Arc::new(RecordingObserver::default());
+        let observers = UsageObservers::default();
+        observers.add(observer.clone());
+
+        let labels = RequestLabels::new(Some("user-42"), &["batch".to_string()]);
+        let request = labels.scope(async { observers.start(Method::POST, "chat/completions") }).await;
+        assert_eq!(request.info.user.as_deref(), Some("user-42"));
+        assert_eq!(request.info.tags, ["batch"]);
+
+        let request = observers.start(Method::POST, "chat/completions");
+        assert!(request.info.user.is_none());
+        assert!(request.info.tags.is_empty());
+    }
+
+    #[test]
+    fn test_observers_shared_between_clones() {
+        let observers = UsageObservers::default();
+        let clone = observers.clone();
+        clone.add(Arc::new(RecordingObserver::default()));
+        assert_eq!(observers.len(), 1);
+    }
+}
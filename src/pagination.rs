@@ -21,7 +21,7 @@ pub struct PaginatedResponse<T> {
 }
 
 /// Parameters for paginated requests
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct PaginationParams {
     /// Maximum number of items to return
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,15 +31,6 @@ pub struct PaginationParams {
     pub cursor: Option<String>,
 }
 
-impl Default for PaginationParams {
-    fn default() -> Self {
-        Self {
-            limit: None,
-            cursor: None,
-        }
-    }
-}
-
 impl PaginationParams {
     /// Create a new set of pagination parameters
     pub fn new() -> Self {
@@ -69,6 +60,30 @@ pub trait Paginator<T> {
     async fn all_pages(&mut self) -> VeniceResult<Vec<T>>;
 }
 
+/// A paginator behind a trait object, as returned by the API traits
+///
+/// Returning a box instead of `impl Paginator` keeps [`ModelsApi`] and [`ApiKeysApi`]
+/// object safe, so they can be used as `Arc<dyn ModelsApi + Send + Sync>`.
+///
+/// [`ModelsApi`]: crate::traits::models::ModelsApi
+/// [`ApiKeysApi`]: crate::traits::api_keys::ApiKeysApi
+pub type BoxPaginator<T> = Box<dyn Paginator<T> + Send>;
+
+#[async_trait]
+impl<T, P> Paginator<T> for Box<P>
+where
+    T: Send,
+    P: Paginator<T> + Send + ?Sized,
+{
+    async fn next_page(&mut self) -> VeniceResult<Option<PaginatedResponse<T>>> {
+        (**self).next_page().await
+    }
+
+    async fn all_pages(&mut self) -> VeniceResult<Vec<T>> {
+        (**self).all_pages().await
+    }
+}
+
 /// A generic paginator implementation
 pub struct GenericPaginator<T, R, F>
 where
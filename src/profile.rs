@@ -0,0 +1,75 @@
+//! Preset client configurations
+//!
+//! A [`Profile`] bundles a request timeout, a retry policy and a rate limiter
+//! configuration suited to one kind of program, so clients can be set up with
+//! [`ClientBuilder::profile`](crate::client::ClientBuilder::profile) instead of tuning
+//! each value.
+
+use crate::rate_limit::RateLimiterConfig;
+use crate::retry::RetryConfig;
+
+/// Preset timeout, retry and rate limit settings
+///
+/// Settings made on the builder after the profile override the profile's values.
+///
+/// | Profile | Timeout | Retries | Backoff | Waits for rate limits up to |
+/// |---|---|---|---|---|
+/// | [`Interactive`](Profile::Interactive) | 30 s | 1 | 250 ms to 2 s | 5 s |
+/// | [`Server`](Profile::Server) | 60 s | 3 | 500 ms to 8 s | 10 s |
+/// | [`Batch`](Profile::Batch) | 300 s | 6 | 1 s to 60 s | 300 s |
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::{Client, Profile};
+///
+/// let client = Client::builder()
+///     .api_key("your-api-key")
+///     .profile(Profile::Batch)
+///     .build()
+///     .unwrap();
+/// assert_eq!(client.config().timeout_secs, Some(300));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// A person is waiting for the answer, as in a chat UI or a CLI: fail fast
+    Interactive,
+    /// Requests made on behalf of incoming requests, as in a web backend
+    Server,
+    /// Offline jobs that should finish rather than finish fast, such as data labeling
+    Batch,
+}
+
+impl Profile {
+    /// Get the request timeout in seconds
+    pub fn timeout_secs(&self) -> u64 {
+        match self {
+            Profile::Interactive => 30,
+            Profile::Server => 60,
+            Profile::Batch => 300,
+        }
+    }
+
+    /// Get the retry policy
+    pub fn retry_config(&self) -> RetryConfig {
+        let (max_retries, initial_delay_ms, max_delay_ms) = match self {
+            Profile::Interactive => (1, 250, 2_000),
+            Profile::Server => (3, 500, 8_000),
+            Profile::Batch => (6, 1_000, 60_000),
+        };
+        RetryConfig::new()
+            .max_retries(max_retries)
+            .initial_delay_ms(initial_delay_ms)
+            .max_delay_ms(max_delay_ms)
+    }
+
+    /// Get the rate limiter configuration
+    pub fn rate_limiter_config(&self) -> RateLimiterConfig {
+        let max_wait_time = match self {
+            Profile::Interactive => 5,
+            Profile::Server => 10,
+            Profile::Batch => 300,
+        };
+        RateLimiterConfig::new().auto_wait(true).max_wait_time(max_wait_time)
+    }
+}
@@ -2,10 +2,18 @@
 //!
 //! This module provides functionality for handling rate limits when making requests to the Venice.ai API.
 //! It includes a rate limiter that can track rate limit information and automatically wait when limits are reached.
+//! Chat, image, models and API key endpoints have separate quotas, so limits can also be tracked per
+//! [`LimitClass`].
+//!
+//! Besides reacting to the limits reported by the server, the limiter can shape traffic
+//! proactively with token buckets configured through [`RateLimiterConfig::requests_per_minute`]
+//! and [`RateLimiterConfig::tokens_per_minute`].
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, AtomicI64, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{watch, Semaphore};
 use tokio::time::sleep;
 
 use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
@@ -18,6 +26,18 @@ pub struct RateLimiterConfig {
     
     /// Maximum time to wait for rate limits to reset (in seconds)
     pub max_wait_time: u64,
+    
+    /// Client-side limit on requests per minute, enforced with a token bucket
+    pub requests_per_minute: Option<u32>,
+    
+    /// Client-side limit on tokens per minute
+    ///
+    /// Token usage is only known once a response arrives, so the usage reported by each
+    /// response is charged against the bucket and later requests wait until it refills.
+    /// Chat completions sent through [`ChatApi`](crate::traits::chat::ChatApi) on a
+    /// [`Client`](crate::Client) are charged too, streamed ones once their last chunk
+    /// reports usage.
+    pub tokens_per_minute: Option<u32>,
 }
 
 impl Default for RateLimiterConfig {
@@ -25,20 +45,201 @@ impl Default for RateLimiterConfig {
         Self {
             auto_wait: true,
             max_wait_time: 60, // Default to waiting up to 60 seconds
+            requests_per_minute: None,
+            tokens_per_minute: None,
         }
     }
 }
 
+impl RateLimiterConfig {
+    /// Create a new configuration with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Set whether to automatically wait when rate limits are reached
+    pub fn auto_wait(mut self, auto_wait: bool) -> Self {
+        self.auto_wait = auto_wait;
+        self
+    }
+    
+    /// Set the maximum time to wait for rate limits to reset (in seconds)
+    pub fn max_wait_time(mut self, max_wait_time: u64) -> Self {
+        self.max_wait_time = max_wait_time;
+        self
+    }
+    
+    /// Limit the number of requests sent per minute
+    pub fn requests_per_minute(mut self, requests_per_minute: u32) -> Self {
+        self.requests_per_minute = Some(requests_per_minute);
+        self
+    }
+    
+    /// Limit the number of tokens used per minute
+    pub fn tokens_per_minute(mut self, tokens_per_minute: u32) -> Self {
+        self.tokens_per_minute = Some(tokens_per_minute);
+        self
+    }
+}
+
+/// A token bucket that refills continuously up to its capacity
+#[derive(Debug)]
+struct TokenBucket {
+    /// Maximum number of tokens in the bucket
+    capacity: f64,
+    /// Tokens added per second
+    refill_per_sec: f64,
+    /// Available tokens and the time they were last refilled
+    state: Mutex<(f64, Instant)>,
+}
+
+impl Clone for TokenBucket {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            refill_per_sec: self.refill_per_sec,
+            state: Mutex::new(*self.state.lock().unwrap_or_else(|e| e.into_inner())),
+        }
+    }
+}
+
+impl TokenBucket {
+    /// Create a full bucket that allows `limit` tokens per minute
+    fn per_minute(limit: u32) -> Self {
+        let capacity = limit.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+    
+    /// Refill the bucket and run `f` on the available tokens
+    fn with_available<R>(&self, f: impl FnOnce(&mut f64) -> R) -> R {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.1).as_secs_f64();
+        state.0 = (state.0 + elapsed * self.refill_per_sec).min(self.capacity);
+        state.1 = now;
+        f(&mut state.0)
+    }
+    
+    /// Time needed to refill `missing` tokens
+    fn refill_time(&self, missing: f64) -> Duration {
+        Duration::from_secs_f64(missing / self.refill_per_sec)
+    }
+    
+    /// Take `amount` tokens, or return how long to wait until they are available
+    fn try_take(&self, amount: f64) -> Result<(), Duration> {
+        self.with_available(|available| {
+            if *available >= amount {
+                *available -= amount;
+                Ok(())
+            } else {
+                Err(self.refill_time(amount - *available))
+            }
+        })
+    }
+    
+    /// Check that the bucket is not in debt, or return how long to wait until it is not
+    fn check_not_in_debt(&self) -> Result<(), Duration> {
+        self.with_available(|available| {
+            if *available >= 0.0 {
+                Ok(())
+            } else {
+                Err(self.refill_time(-*available))
+            }
+        })
+    }
+    
+    /// Remove `amount` tokens, allowing the bucket to go into debt
+    fn charge(&self, amount: f64) {
+        self.with_available(|available| *available -= amount);
+    }
+    
+    /// Give back `amount` tokens that were taken for a request that was never sent
+    fn refund(&self, amount: f64) {
+        self.with_available(|available| *available = (*available + amount).min(self.capacity));
+    }
+}
+
+/// A group of endpoints that share a rate limit quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitClass {
+    /// Chat completion endpoints
+    Chat,
+    /// Image generation, upscaling and style endpoints
+    Image,
+    /// Model listing endpoints
+    Models,
+    /// API key management endpoints
+    ApiKeys,
+    /// Any other endpoint
+    Other,
+}
+
+impl LimitClass {
+    /// Determine the limit class of an API endpoint, relative to the base URL
+    pub fn from_endpoint(endpoint: &str) -> Self {
+        let endpoint = endpoint.trim_start_matches('/');
+        let prefix = endpoint.split(['/', '?']).next().unwrap_or_default();
+        match prefix {
+            "chat" => LimitClass::Chat,
+            "image" => LimitClass::Image,
+            "models" => LimitClass::Models,
+            "api_keys" => LimitClass::ApiKeys,
+            _ => LimitClass::Other,
+        }
+    }
+}
+
+/// A point-in-time view of the limits tracked by a [`RateLimiter`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitSnapshot {
+    /// The endpoint class the limits apply to, or `None` for a limiter's own limits
+    pub class: Option<LimitClass>,
+    /// Maximum requests per minute
+    pub limit_requests: Option<u32>,
+    /// Remaining requests
+    pub remaining_requests: Option<u32>,
+    /// When the request limit resets
+    pub requests_reset_at: Option<SystemTime>,
+    /// Maximum tokens per minute
+    pub limit_tokens: Option<u32>,
+    /// Remaining tokens
+    pub remaining_tokens: Option<u32>,
+    /// When the token limit resets
+    pub tokens_reset_at: Option<SystemTime>,
+    /// Last reported VCU balance
+    pub balance_vcu: Option<f64>,
+    /// Last reported USD balance
+    pub balance_usd: Option<f64>,
+}
+
 /// Rate limiter for managing API rate limits
 ///
 /// The rate limiter tracks the current rate limit status and can automatically
 /// wait until rate limits reset if configured to do so.
+///
+/// [`RateLimiter::acquire`] and [`RateLimiter::update_from_response`] track a single
+/// set of limits. The `*_for_endpoint` variants used by [`crate::Client`] keep a
+/// separate set per [`LimitClass`], so exhausting the image quota does not block
+/// unrelated calls such as listing models.
+///
+/// Each successful [`RateLimiter::acquire`] takes one request permit from
+/// `remaining_requests`, so concurrent tasks cannot all pass on the same remaining
+/// request. The count is corrected by the value reported in the next response.
+/// Tasks waiting for a reset are served in FIFO order.
+///
+/// [`RateLimiter::snapshot`] returns the current limits and balances, and
+/// [`RateLimiter::subscribe`] delivers a new snapshot whenever a response reports
+/// different values, including responses tracked by the per-class limiters.
 #[derive(Debug)]
 pub struct RateLimiter {
     /// Maximum requests per minute
     pub max_requests: AtomicU32,
     
-    /// Current remaining requests
+    /// Current remaining requests, or [`RateLimiter::UNKNOWN`] until the server reports a value
     pub remaining_requests: AtomicU32,
     
     /// Unix timestamp when the request limit will reset
@@ -47,7 +248,7 @@ pub struct RateLimiter {
     /// Maximum tokens per minute
     pub max_tokens: AtomicU32,
     
-    /// Current remaining tokens
+    /// Current remaining tokens, or [`RateLimiter::UNKNOWN`] until the server reports a value
     pub remaining_tokens: AtomicU32,
     
     /// Unix timestamp when the token limit will reset
@@ -55,6 +256,24 @@ pub struct RateLimiter {
     
     /// Configuration for the rate limiter
     pub config: RateLimiterConfig,
+    
+    /// Limits tracked separately for each endpoint class
+    classes: RwLock<HashMap<LimitClass, Arc<RateLimiter>>>,
+    
+    /// Client-side request budget
+    request_bucket: Option<TokenBucket>,
+    
+    /// Client-side token budget
+    token_bucket: Option<TokenBucket>,
+    
+    /// Queue of tasks waiting for permission, served in FIFO order
+    gate: Semaphore,
+    
+    /// The endpoint class tracked by this limiter, if it is a per-class limiter
+    class: Option<LimitClass>,
+    
+    /// The latest reported limits, published to subscribers
+    events: watch::Sender<RateLimitSnapshot>,
 }
 
 impl Clone for RateLimiter {
@@ -67,11 +286,33 @@ impl Clone for RateLimiter {
             remaining_tokens: AtomicU32::new(self.remaining_tokens.load(Ordering::Relaxed)),
             reset_time_tokens: AtomicI64::new(self.reset_time_tokens.load(Ordering::Relaxed)),
             config: self.config.clone(),
+            classes: RwLock::new(
+                self.classes
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .iter()
+                    .map(|(class, limiter)| (*class, Arc::new(limiter.as_ref().clone())))
+                    .collect(),
+            ),
+            request_bucket: self.request_bucket.clone(),
+            token_bucket: self.token_bucket.clone(),
+            gate: Semaphore::new(1),
+            class: self.class,
+            events: watch::channel(self.events.borrow().clone()).0,
         }
     }
 }
 
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RateLimiter {
+    /// Value of `remaining_requests` and `remaining_tokens` while the limit is unknown
+    pub const UNKNOWN: u32 = u32::MAX;
+    
     /// Creates a new rate limiter with default configuration
     pub fn new() -> Self {
         Self::with_config(RateLimiterConfig::default())
@@ -81,23 +322,187 @@ impl RateLimiter {
     pub fn with_config(config: RateLimiterConfig) -> Self {
         Self {
             max_requests: AtomicU32::new(0),
-            remaining_requests: AtomicU32::new(1), // Initialize to 1 to avoid being rate limited initially
+            remaining_requests: AtomicU32::new(Self::UNKNOWN),
             reset_time_requests: AtomicI64::new(0),
             max_tokens: AtomicU32::new(0),
-            remaining_tokens: AtomicU32::new(1), // Initialize to 1 to avoid being rate limited initially
+            remaining_tokens: AtomicU32::new(Self::UNKNOWN),
             reset_time_tokens: AtomicI64::new(0),
+            request_bucket: config.requests_per_minute.map(TokenBucket::per_minute),
+            token_bucket: config.tokens_per_minute.map(TokenBucket::per_minute),
             config,
+            classes: RwLock::new(HashMap::new()),
+            gate: Semaphore::new(1),
+            class: None,
+            events: watch::channel(RateLimitSnapshot::default()).0,
+        }
+    }
+    
+    /// Gets the limiter tracking the limits of an endpoint class, creating it if needed
+    pub fn for_class(&self, class: LimitClass) -> Arc<RateLimiter> {
+        if let Some(limiter) = self.classes.read().unwrap_or_else(|e| e.into_inner()).get(&class) {
+            return limiter.clone();
+        }
+        self.classes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(class)
+            .or_insert_with(|| {
+                // Client-side budgets are enforced once, by this limiter
+                let mut limiter = RateLimiter::with_config(RateLimiterConfig {
+                    requests_per_minute: None,
+                    tokens_per_minute: None,
+                    ..self.config.clone()
+                });
+                limiter.class = Some(class);
+                Arc::new(limiter)
+            })
+            .clone()
+    }
+    
+    /// Updates the limits of the endpoint's class with information from a response
+    pub fn update_from_response_for_endpoint(&self, endpoint: &str, rate_limit_info: &RateLimitInfo) {
+        let limiter = self.for_class(LimitClass::from_endpoint(endpoint));
+        limiter.update_from_response(rate_limit_info);
+        self.publish(limiter.snapshot());
+    }
+    
+    /// Gets the current limits and the last reported balances
+    ///
+    /// The per-class limits updated by [`crate::Client`] are available from
+    /// [`RateLimiter::for_class`].
+    pub fn snapshot(&self) -> RateLimitSnapshot {
+        fn known(value: u32) -> Option<u32> {
+            (value != RateLimiter::UNKNOWN).then_some(value)
+        }
+        fn limit(value: u32) -> Option<u32> {
+            (value > 0).then_some(value)
+        }
+        fn at(timestamp: i64) -> Option<SystemTime> {
+            (timestamp > 0).then(|| UNIX_EPOCH + Duration::from_secs(timestamp as u64))
+        }
+        
+        let latest = self.events.borrow();
+        RateLimitSnapshot {
+            class: self.class,
+            limit_requests: limit(self.max_requests.load(Ordering::Relaxed)),
+            remaining_requests: known(self.remaining_requests.load(Ordering::Acquire)),
+            requests_reset_at: at(self.reset_time_requests.load(Ordering::Relaxed)),
+            limit_tokens: limit(self.max_tokens.load(Ordering::Relaxed)),
+            remaining_tokens: known(self.remaining_tokens.load(Ordering::Acquire)),
+            tokens_reset_at: at(self.reset_time_tokens.load(Ordering::Relaxed)),
+            balance_vcu: latest.balance_vcu,
+            balance_usd: latest.balance_usd,
+        }
+    }
+    
+    /// Subscribes to limit updates
+    ///
+    /// The receiver is notified whenever a response reports limits or balances that
+    /// differ from the previous update.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use venice_ai_api_sdk_rust::RateLimiter;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rate_limiter = RateLimiter::new();
+    ///     let mut updates = rate_limiter.subscribe();
+    ///
+    ///     while updates.changed().await.is_ok() {
+    ///         let snapshot = updates.borrow().clone();
+    ///         println!("{:?}: {:?} requests left", snapshot.class, snapshot.remaining_requests);
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe(&self) -> watch::Receiver<RateLimitSnapshot> {
+        self.events.subscribe()
+    }
+    
+    /// Publishes a snapshot to subscribers if it differs from the previous one
+    fn publish(&self, snapshot: RateLimitSnapshot) {
+        self.events.send_if_modified(|current| {
+            if *current == snapshot {
+                false
+            } else {
+                *current = snapshot;
+                true
+            }
+        });
+    }
+    
+    /// Acquires permission to call an endpoint, waiting only on the limits of its class
+    pub async fn acquire_for_endpoint(&self, endpoint: &str) -> VeniceResult<()> {
+        self.acquire_budget().await?;
+        let result = self.for_class(LimitClass::from_endpoint(endpoint)).acquire_permit().await;
+        if result.is_err() {
+            self.refund_budget();
+        }
+        result
+    }
+    
+    /// Gives back what [`RateLimiter::acquire_for_endpoint`] took for a request that was never sent
+    pub(crate) fn release_for_endpoint(&self, endpoint: &str) {
+        self.refund_budget();
+        self.for_class(LimitClass::from_endpoint(endpoint)).return_permit();
+    }
+    
+    /// Charges tokens used by a request against the client-side token budget
+    pub fn record_tokens(&self, tokens: u32) {
+        if let Some(bucket) = &self.token_bucket {
+            bucket.charge(tokens as f64);
         }
     }
     
+    /// Waits until the client-side budgets allow another request
+    async fn acquire_budget(&self) -> VeniceResult<()> {
+        loop {
+            let wait = match self.budget_wait() {
+                Some(wait) => wait,
+                None => return Ok(()),
+            };
+            
+            if !self.config.auto_wait || wait > Duration::from_secs(self.config.max_wait_time) {
+                return Err(VeniceError::RateLimitExceeded(format!(
+                    "Client-side rate limit reached. Next request allowed in {:.1} seconds.",
+                    wait.as_secs_f64()
+                )));
+            }
+            
+            log::debug!("Client-side rate limit reached. Waiting for {:?}...", wait);
+            sleep(wait).await;
+        }
+    }
+    
+    /// Gives back the request taken by [`RateLimiter::acquire_budget`] when no permit was granted
+    fn refund_budget(&self) {
+        if let Some(bucket) = &self.request_bucket {
+            bucket.refund(1.0);
+        }
+    }
+    
+    /// Takes a request from the client-side budgets, or returns how long to wait
+    fn budget_wait(&self) -> Option<Duration> {
+        if let Some(Err(wait)) = self.token_bucket.as_ref().map(TokenBucket::check_not_in_debt) {
+            return Some(wait);
+        }
+        if let Some(Err(wait)) = self.request_bucket.as_ref().map(|bucket| bucket.try_take(1.0)) {
+            return Some(wait);
+        }
+        None
+    }
+    
     /// Updates the rate limiter with information from a response
     pub fn update_from_response(&self, rate_limit_info: &RateLimitInfo) {
         if let Some(limit) = rate_limit_info.limit_requests {
             self.max_requests.store(limit, Ordering::Relaxed);
         }
         
+        // Reported values replace the local count, which only tracks requests since the last response.
+        // They are capped below UNKNOWN so a huge limit is not mistaken for a missing one.
         if let Some(remaining) = rate_limit_info.remaining_requests {
-            self.remaining_requests.store(remaining, Ordering::Relaxed);
+            self.remaining_requests.store(remaining.min(Self::UNKNOWN - 1), Ordering::Release);
         }
         
         if let Some(reset) = rate_limit_info.reset_requests {
@@ -109,7 +514,7 @@ impl RateLimiter {
         }
         
         if let Some(remaining) = rate_limit_info.remaining_tokens {
-            self.remaining_tokens.store(remaining, Ordering::Relaxed);
+            self.remaining_tokens.store(remaining.min(Self::UNKNOWN - 1), Ordering::Release);
         }
         
         if let Some(reset) = rate_limit_info.reset_tokens {
@@ -119,6 +524,46 @@ impl RateLimiter {
                 self.reset_time_tokens.store(reset_time as i64, Ordering::Relaxed);
             }
         }
+        
+        let mut snapshot = self.snapshot();
+        if rate_limit_info.balance_vcu.is_some() {
+            snapshot.balance_vcu = rate_limit_info.balance_vcu;
+        }
+        if rate_limit_info.balance_usd.is_some() {
+            snapshot.balance_usd = rate_limit_info.balance_usd;
+        }
+        self.publish(snapshot);
+    }
+    
+    /// Returns a request permit taken for a request that was never sent
+    fn return_permit(&self) {
+        let _ = self.remaining_requests.fetch_update(Ordering::AcqRel, Ordering::Acquire, |remaining| {
+            match remaining {
+                Self::UNKNOWN => None,
+                remaining => Some(remaining.saturating_add(1).min(Self::UNKNOWN - 1)),
+            }
+        });
+    }
+    
+    /// Takes a request permit, returning `false` if none are left
+    fn try_take_permit(&self) -> bool {
+        if self.remaining_tokens.load(Ordering::Acquire) == 0 {
+            return false;
+        }
+        self.remaining_requests
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |remaining| match remaining {
+                0 => None,
+                Self::UNKNOWN => Some(Self::UNKNOWN),
+                remaining => Some(remaining - 1),
+            })
+            .is_ok()
+    }
+    
+    /// Restores the limits after a reset, to the reported maximums if known
+    fn replenish(&self) {
+        let limit_or_unknown = |max: u32| if max > 0 { max.min(Self::UNKNOWN - 1) } else { Self::UNKNOWN };
+        self.remaining_requests.store(limit_or_unknown(self.max_requests.load(Ordering::Relaxed)), Ordering::Release);
+        self.remaining_tokens.store(limit_or_unknown(self.max_tokens.load(Ordering::Relaxed)), Ordering::Release);
     }
     
     /// Checks if the rate limit is currently exceeded
@@ -168,7 +613,7 @@ impl RateLimiter {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use venice_ai_api_sdk_rust::rate_limit::RateLimiter;
+    /// use venice_ai_api_sdk_rust::RateLimiter;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -184,7 +629,24 @@ impl RateLimiter {
     /// }
     /// ```
     pub async fn acquire(&self) -> VeniceResult<()> {
-        if !self.is_rate_limited() {
+        self.acquire_budget().await?;
+        let result = self.acquire_permit().await;
+        if result.is_err() {
+            self.refund_budget();
+        }
+        result
+    }
+    
+    /// Takes a request permit, waiting for the limits to reset if necessary
+    async fn acquire_permit(&self) -> VeniceResult<()> {
+        // Queue behind other waiting tasks, so a task arriving later cannot take a
+        // permit ahead of them; the semaphore is never closed
+        let _gate = self.gate.acquire().await.map_err(|_| {
+            VeniceError::RateLimitExceeded("Rate limiter is shut down.".to_string())
+        })?;
+        
+        // A task ahead in the queue may have replenished the permits
+        if self.try_take_permit() {
             return Ok(());
         }
         
@@ -194,18 +656,26 @@ impl RateLimiter {
             ));
         }
         
-        if let Some(wait_time) = self.time_until_reset() {
-            let wait_time = wait_time.min(self.config.max_wait_time);
-            
-            if wait_time > 0 {
-                log::info!("Rate limit exceeded. Waiting for {} seconds...", wait_time);
-                sleep(Duration::from_secs(wait_time)).await;
-            }
-            
+        let reset_known = self.reset_time_requests.load(Ordering::Relaxed) > 0
+            || self.reset_time_tokens.load(Ordering::Relaxed) > 0;
+        if !reset_known {
+            return Err(VeniceError::RateLimitExceeded(
+                "Rate limit exceeded and reset time is unknown.".to_string()
+            ));
+        }
+        
+        let wait_time = self.time_until_reset().unwrap_or(0).min(self.config.max_wait_time);
+        if wait_time > 0 {
+            log::info!("Rate limit exceeded. Waiting for {} seconds...", wait_time);
+            sleep(Duration::from_secs(wait_time)).await;
+        }
+        
+        self.replenish();
+        if self.try_take_permit() {
             Ok(())
         } else {
             Err(VeniceError::RateLimitExceeded(
-                "Rate limit exceeded and reset time is unknown.".to_string()
+                "Rate limit exceeded after waiting for reset.".to_string()
             ))
         }
     }
@@ -238,6 +708,7 @@ mod tests {
             reset_tokens: Some(60),
             balance_vcu: None,
             balance_usd: None,
+            ..Default::default()
         };
         
         rate_limiter.update_from_response(&rate_limit_info);
@@ -274,4 +745,203 @@ mod tests {
         rate_limiter.remaining_tokens.store(10, Ordering::Relaxed);
         assert!(!rate_limiter.is_rate_limited());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_limit_class_from_endpoint() {
+        assert_eq!(LimitClass::from_endpoint("chat/completions"), LimitClass::Chat);
+        assert_eq!(LimitClass::from_endpoint("/image/generate"), LimitClass::Image);
+        assert_eq!(LimitClass::from_endpoint("models?type=image"), LimitClass::Models);
+        assert_eq!(LimitClass::from_endpoint("api_keys/rate_limits"), LimitClass::ApiKeys);
+        assert_eq!(LimitClass::from_endpoint("characters"), LimitClass::Other);
+    }
+    
+    #[tokio::test]
+    async fn test_limits_are_tracked_per_class() {
+        let rate_limiter = RateLimiter::with_config(RateLimiterConfig::new().auto_wait(false).max_wait_time(0));
+        
+        let exhausted = RateLimitInfo {
+            limit_requests: Some(20),
+            remaining_requests: Some(0),
+            reset_requests: None,
+            limit_tokens: None,
+            remaining_tokens: None,
+            reset_tokens: None,
+            balance_vcu: None,
+            balance_usd: None,
+            ..Default::default()
+        };
+        rate_limiter.update_from_response_for_endpoint("image/generate", &exhausted);
+        
+        assert!(rate_limiter.for_class(LimitClass::Image).is_rate_limited());
+        assert!(rate_limiter.acquire_for_endpoint("image/upscale").await.is_err());
+        assert!(rate_limiter.acquire_for_endpoint("models").await.is_ok());
+        assert!(!rate_limiter.is_rate_limited());
+        
+        let clone = rate_limiter.clone();
+        assert!(clone.for_class(LimitClass::Image).is_rate_limited());
+    }
+    
+    #[tokio::test]
+    async fn test_request_budget() {
+        let rate_limiter = RateLimiter::with_config(
+            RateLimiterConfig::new().auto_wait(false).requests_per_minute(2),
+        );
+        
+        assert!(rate_limiter.acquire().await.is_ok());
+        assert!(rate_limiter.acquire_for_endpoint("models").await.is_ok());
+        let err = rate_limiter.acquire_for_endpoint("chat/completions").await.unwrap_err();
+        assert!(matches!(err, VeniceError::RateLimitExceeded(msg) if msg.contains("Client-side")));
+    }
+    
+    #[tokio::test]
+    async fn test_token_budget() {
+        let rate_limiter = RateLimiter::with_config(
+            RateLimiterConfig::new().auto_wait(false).tokens_per_minute(600),
+        );
+        
+        rate_limiter.record_tokens(500);
+        assert!(rate_limiter.acquire().await.is_ok());
+        rate_limiter.record_tokens(500);
+        assert!(rate_limiter.acquire().await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_budget_waits_for_refill() {
+        let rate_limiter = RateLimiter::with_config(RateLimiterConfig::new().requests_per_minute(600));
+        for _ in 0..600 {
+            rate_limiter.acquire().await.unwrap();
+        }
+        
+        // 600 requests per minute refill one request every 100ms
+        let started = Instant::now();
+        rate_limiter.acquire().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+    
+    #[tokio::test]
+    async fn test_acquire_takes_permits_atomically() {
+        let rate_limiter = Arc::new(RateLimiter::with_config(RateLimiterConfig::new().auto_wait(false)));
+        rate_limiter.remaining_requests.store(3, Ordering::Relaxed);
+        
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let rate_limiter = rate_limiter.clone();
+                tokio::spawn(async move { rate_limiter.acquire().await.is_ok() })
+            })
+            .collect();
+        let mut granted = 0;
+        for task in tasks {
+            if task.await.unwrap() {
+                granted += 1;
+            }
+        }
+        
+        assert_eq!(granted, 3);
+        assert_eq!(rate_limiter.remaining_requests.load(Ordering::Relaxed), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_acquire_does_not_jump_the_queue() {
+        let rate_limiter = Arc::new(RateLimiter::with_config(RateLimiterConfig::new().max_wait_time(1)));
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        rate_limiter.max_requests.store(1, Ordering::Relaxed);
+        rate_limiter.remaining_requests.store(0, Ordering::Relaxed);
+        rate_limiter.reset_time_requests.store(now as i64 + 2, Ordering::Relaxed);
+        
+        let waiting = tokio::spawn({
+            let rate_limiter = rate_limiter.clone();
+            async move { rate_limiter.acquire().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        
+        // A permit reported while a task is queued is not taken by a later caller
+        let mut rate_limit_info = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+        rate_limit_info.remaining_requests = Some(1);
+        rate_limiter.update_from_response(&rate_limit_info);
+        let late = tokio::time::timeout(Duration::from_millis(100), rate_limiter.acquire()).await;
+        assert!(late.is_err());
+        
+        waiting.await.unwrap().unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_refused_permits_refund_the_request_budget() {
+        let rate_limiter = RateLimiter::with_config(
+            RateLimiterConfig::new().auto_wait(false).requests_per_minute(2),
+        );
+        rate_limiter.for_class(LimitClass::Image).remaining_requests.store(0, Ordering::Relaxed);
+        rate_limiter.remaining_requests.store(0, Ordering::Relaxed);
+        
+        assert!(rate_limiter.acquire_for_endpoint("image/generate").await.is_err());
+        assert!(rate_limiter.acquire().await.is_err());
+        rate_limiter.remaining_requests.store(RateLimiter::UNKNOWN, Ordering::Relaxed);
+        assert!(rate_limiter.acquire_for_endpoint("models").await.is_ok());
+        assert!(rate_limiter.acquire_for_endpoint("chat/completions").await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_acquire_replenishes_after_reset() {
+        let rate_limiter = RateLimiter::new();
+        rate_limiter.max_requests.store(5, Ordering::Relaxed);
+        rate_limiter.remaining_requests.store(0, Ordering::Relaxed);
+        
+        // Reset time unknown
+        assert!(rate_limiter.acquire().await.is_err());
+        
+        // Reset time in the past
+        rate_limiter.reset_time_requests.store(1, Ordering::Relaxed);
+        assert!(rate_limiter.acquire().await.is_ok());
+        assert_eq!(rate_limiter.remaining_requests.load(Ordering::Relaxed), 4);
+    }
+    
+    #[test]
+    fn test_unknown_limits_are_not_decremented() {
+        let rate_limiter = RateLimiter::new();
+        assert!(rate_limiter.try_take_permit());
+        assert_eq!(rate_limiter.remaining_requests.load(Ordering::Relaxed), RateLimiter::UNKNOWN);
+        
+        let mut rate_limit_info = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+        rate_limit_info.remaining_requests = Some(u32::MAX);
+        rate_limiter.update_from_response(&rate_limit_info);
+        assert_eq!(rate_limiter.remaining_requests.load(Ordering::Relaxed), u32::MAX - 1);
+    }
+    
+    #[test]
+    fn test_snapshot() {
+        let rate_limiter = RateLimiter::new();
+        assert_eq!(rate_limiter.snapshot(), RateLimitSnapshot::default());
+        
+        let mut rate_limit_info = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+        rate_limit_info.limit_requests = Some(100);
+        rate_limit_info.remaining_requests = Some(42);
+        rate_limit_info.reset_requests = Some(1614556800);
+        rate_limit_info.balance_usd = Some(12.5);
+        rate_limiter.update_from_response(&rate_limit_info);
+        
+        let snapshot = rate_limiter.snapshot();
+        assert_eq!(snapshot.limit_requests, Some(100));
+        assert_eq!(snapshot.remaining_requests, Some(42));
+        assert_eq!(snapshot.requests_reset_at, Some(UNIX_EPOCH + Duration::from_secs(1614556800)));
+        assert_eq!(snapshot.remaining_tokens, None);
+        assert_eq!(snapshot.balance_usd, Some(12.5));
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_receives_changes() {
+        let rate_limiter = RateLimiter::new();
+        let mut updates = rate_limiter.subscribe();
+        
+        let mut rate_limit_info = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+        rate_limit_info.remaining_requests = Some(10);
+        rate_limiter.update_from_response_for_endpoint("image/generate", &rate_limit_info);
+        
+        updates.changed().await.unwrap();
+        let snapshot = updates.borrow_and_update().clone();
+        assert_eq!(snapshot.class, Some(LimitClass::Image));
+        assert_eq!(snapshot.remaining_requests, Some(10));
+        
+        // Unchanged headers do not notify
+        rate_limiter.update_from_response_for_endpoint("image/generate", &rate_limit_info);
+        assert!(!updates.has_changed().unwrap());
+    }
+}
@@ -0,0 +1,149 @@
+//! Options for individual requests
+//!
+//! [`RequestOptions`] change how the requests made inside a future are sent, without
+//! building another client. They follow the future across `.await` points on the same
+//! task, like the `user` and `request_tags` labels of chat and image requests.
+
+use std::future::Future;
+
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+
+use crate::config::parse_feature_flag;
+use crate::error::{VeniceError, VeniceResult};
+
+tokio::task_local! {
+    static REQUEST_OPTIONS: RequestOptions;
+}
+
+/// Options applied to every request sent while a future runs
+///
+/// # Examples
+///
+/// ```no_run
+/// use venice_ai_api_sdk_rust::{Client, RequestOptions};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::builder()
+///     .api_key("your-api-key")
+///     .default_chat_model("llama-3.3-70b")
+///     .allowed_base_url("https://staging.example.com/api/v1")
+///     .build()?;
+///
+/// // Send this one question to staging
+/// let answer = RequestOptions::new()
+///     .base_url_override("https://staging.example.com/api/v1")
+///     .scope(client.ask("Hello"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    base_url_override: Option<String>,
+    feature_flags: HeaderMap,
+}
+
+impl RequestOptions {
+    /// Create options that change nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send requests to another base URL
+    ///
+    /// The URL must be allowed by the client with
+    /// [`ClientConfig::with_allowed_base_url`](crate::ClientConfig::with_allowed_base_url),
+    /// since requests carry the client's API key. Requests to a URL that is not allowed
+    /// fail with [`VeniceError::InvalidInput`] before anything is sent.
+    pub fn base_url_override(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url_override = Some(base_url.into());
+        self
+    }
+
+    /// Set a feature flag for these requests, replacing the client's value of the flag
+    ///
+    /// The flag is sent as an `X-Venice-Feature-<name>` header, like the flags of
+    /// [`ClientConfig::with_feature_flag`](crate::ClientConfig::with_feature_flag). Fails
+    /// with [`VeniceError::InvalidInput`] if the name or value is not a valid header.
+    pub fn feature_flag(mut self, name: &str, value: &str) -> VeniceResult<Self> {
+        let (name, value) = parse_feature_flag(name, value)?;
+        self.feature_flags.insert(name, value);
+        Ok(self)
+    }
+
+    /// Run a future with these options applied to every request it sends
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        REQUEST_OPTIONS.scope(self, future).await
+    }
+
+    /// Get the base URL override of the current call, if any
+    fn current_base_url() -> Option<String> {
+        REQUEST_OPTIONS
+            .try_with(|options| options.base_url_override.clone())
+            .ok()
+            .flatten()
+    }
+}
+
+/// Add the feature flags of the current call to a request
+pub(crate) fn apply_feature_flags(request: RequestBuilder) -> RequestBuilder {
+    match REQUEST_OPTIONS.try_with(|options| options.feature_flags.clone()) {
+        Ok(flags) if !flags.is_empty() => request.headers(flags),
+        _ => request,
+    }
+}
+
+/// Describe the feature flags of the current call, so calls with different flags are not shared
+pub(crate) fn feature_flag_key() -> Option<String> {
+    let mut flags = REQUEST_OPTIONS
+        .try_with(|options| {
+            options
+                .feature_flags
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, String::from_utf8_lossy(value.as_bytes())))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if flags.is_empty() {
+        return None;
+    }
+    flags.sort();
+    Some(flags.join("&"))
+}
+
+/// Get the base URL for a request, applying an allowed override of the current call
+pub(crate) fn resolve_base_url(base_url: &str, allowed: &[String]) -> VeniceResult<String> {
+    let Some(requested) = RequestOptions::current_base_url() else {
+        return Ok(base_url.to_string());
+    };
+    let normalized = requested.trim_end_matches('/');
+    let is_allowed = normalized == base_url.trim_end_matches('/')
+        || allowed.iter().any(|url| url.trim_end_matches('/') == normalized);
+    if !is_allowed {
+        return Err(VeniceError::InvalidInput(format!(
+            "Base URL override {} is not in the allowed base URLs of the client",
+            requested
+        )));
+    }
+    Ok(requested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_override_must_be_allowed() {
+        let allowed = vec!["https://staging.example.com/api/v1/".to_string()];
+        assert_eq!(resolve_base_url("https://api.venice.ai/api/v1", &allowed).unwrap(), "https://api.venice.ai/api/v1");
+
+        let staging = RequestOptions::new().base_url_override("https://staging.example.com/api/v1");
+        let resolved = staging.scope(async { resolve_base_url("https://api.venice.ai/api/v1", &allowed) }).await;
+        assert_eq!(resolved.unwrap(), "https://staging.example.com/api/v1");
+
+        let other = RequestOptions::new().base_url_override("https://attacker.example.com");
+        let err = other.scope(async { resolve_base_url("https://api.venice.ai/api/v1", &allowed) }).await.unwrap_err();
+        assert!(matches!(err, VeniceError::InvalidInput(msg) if msg.contains("attacker.example.com")));
+    }
+}
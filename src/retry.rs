@@ -1,9 +1,221 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::time::sleep;
 
-use crate::error::{VeniceError, VeniceResult};
+use crate::error::{ErrorContext, VeniceError, VeniceResult};
+
+/// Waits between retry attempts
+///
+/// The default [`TokioClock`] sleeps on the tokio timer. Tests can use a
+/// [`VirtualClock`] to run retry loops without waiting.
+#[async_trait::async_trait]
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Wait for `duration`
+    async fn sleep(&self, duration: Duration);
+}
+
+/// A clock that sleeps on the tokio timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+#[async_trait::async_trait]
+impl Clock for TokioClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// A clock that returns immediately and records how long it was asked to sleep
+///
+/// # Examples
+///
+/// ```
+/// # async fn example() {
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::{with_retry, RetryConfig, VeniceError, VirtualClock};
+///
+/// let clock = Arc::new(VirtualClock::new());
+/// let config = RetryConfig::new().add_jitter(false).clock(clock.clone());
+///
+/// let result = with_retry(|| async { Err::<(), _>(VeniceError::RateLimitExceeded("slow down".to_string())) }, &config).await;
+/// assert!(result.is_err());
+/// assert_eq!(clock.sleeps().len(), 3);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct VirtualClock {
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl VirtualClock {
+    /// Create a clock that has not slept yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get every duration the clock was asked to sleep, in order
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Get the total virtual time slept
+    pub fn elapsed(&self) -> Duration {
+        self.sleeps().iter().sum()
+    }
+}
+
+#[async_trait::async_trait]
+impl Clock for VirtualClock {
+    async fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap_or_else(|e| e.into_inner()).push(duration);
+    }
+}
+
+/// Source of the random factors used to jitter retry delays
+pub trait JitterSource: fmt::Debug + Send + Sync {
+    /// Get a random value in `[0, 1)`
+    fn next_f64(&self) -> f64;
+}
+
+/// Jitter drawn from the thread-local random number generator
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadRngJitter;
+
+impl JitterSource for ThreadRngJitter {
+    fn next_f64(&self) -> f64 {
+        rand::random::<f64>()
+    }
+}
+
+/// Jitter from a seeded generator, so the same seed always gives the same delays
+#[derive(Debug)]
+pub struct SeededJitter {
+    rng: Mutex<StdRng>,
+}
+
+impl SeededJitter {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl JitterSource for SeededJitter {
+    fn next_f64(&self) -> f64 {
+        self.rng.lock().unwrap_or_else(|e| e.into_inner()).gen::<f64>()
+    }
+}
+
+/// Class of a retryable error, each of which can have its own [`RetryPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// The API answered 429 Too Many Requests
+    RateLimit,
+    /// The API answered with a 5xx status
+    ServerError,
+    /// The request failed before the API answered, such as on a timeout or a reset connection
+    Network,
+}
+
+impl ErrorClass {
+    /// Classify an error, `None` for errors that are never retried
+    pub fn of(error: &VeniceError) -> Option<Self> {
+        match error.root() {
+            VeniceError::HttpError(_) => Some(ErrorClass::Network),
+            VeniceError::RateLimitExceeded(_) => Some(ErrorClass::RateLimit),
+            VeniceError::ApiError { status, .. } if status.is_server_error() => Some(ErrorClass::ServerError),
+            _ => None,
+        }
+    }
+}
+
+/// Retry behavior for one [`ErrorClass`]
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::{RetryConfig, RetryPolicy};
+///
+/// // Wait out rate limits patiently, but give up quickly on a failing server
+/// let config = RetryConfig::new()
+///     .rate_limit_policy(RetryPolicy::new().max_retries(8).max_delay_ms(60_000).honor_reset_header(true))
+///     .server_error_policy(RetryPolicy::new().max_retries(1).initial_delay_ms(200));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retry attempts for errors of the class
+    pub max_retries: u32,
+    /// Initial delay between retries in milliseconds
+    pub initial_delay_ms: u64,
+    /// Maximum delay between retries in milliseconds
+    pub max_delay_ms: u64,
+    /// Multiplier for exponential backoff
+    pub backoff_factor: f64,
+    /// Whether to wait as long as the API asks, up to `max_delay_ms`, instead of backing off
+    pub honor_reset_header: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 10000,
+            backoff_factor: 2.0,
+            honor_reset_header: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of retry attempts
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the initial delay between retries in milliseconds
+    pub fn initial_delay_ms(mut self, initial_delay_ms: u64) -> Self {
+        self.initial_delay_ms = initial_delay_ms;
+        self
+    }
+
+    /// Set the maximum delay between retries in milliseconds
+    pub fn max_delay_ms(mut self, max_delay_ms: u64) -> Self {
+        self.max_delay_ms = max_delay_ms;
+        self
+    }
+
+    /// Set the backoff factor for exponential backoff
+    pub fn backoff_factor(mut self, backoff_factor: f64) -> Self {
+        self.backoff_factor = backoff_factor;
+        self
+    }
+
+    /// Set whether to wait as long as the API asks through `Retry-After` or the rate limit reset
+    pub fn honor_reset_header(mut self, honor_reset_header: bool) -> Self {
+        self.honor_reset_header = honor_reset_header;
+        self
+    }
+}
 
 /// Configuration for retry behavior
+///
+/// The top-level fields apply to every error class without its own policy, and those
+/// classes share one retry budget. A class given a policy with
+/// [`rate_limit_policy`](Self::rate_limit_policy),
+/// [`server_error_policy`](Self::server_error_policy) or
+/// [`network_policy`](Self::network_policy) is retried on its own budget and schedule.
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts
@@ -16,6 +228,18 @@ pub struct RetryConfig {
     pub backoff_factor: f64,
     /// Whether to add jitter to the delay
     pub add_jitter: bool,
+    /// Whether to wait as long as the API asks, up to `max_delay_ms`, instead of backing off
+    pub honor_reset_header: bool,
+    /// Policy for rate limit errors, if they are not retried like other errors
+    pub rate_limit: Option<RetryPolicy>,
+    /// Policy for server errors, if they are not retried like other errors
+    pub server_error: Option<RetryPolicy>,
+    /// Policy for network errors, if they are not retried like other errors
+    pub network: Option<RetryPolicy>,
+    /// Clock used to wait between attempts
+    pub clock: Arc<dyn Clock>,
+    /// Source of the jitter factors
+    pub jitter: Arc<dyn JitterSource>,
 }
 
 impl Default for RetryConfig {
@@ -26,6 +250,12 @@ impl Default for RetryConfig {
             max_delay_ms: 10000,
             backoff_factor: 2.0,
             add_jitter: true,
+            honor_reset_header: false,
+            rate_limit: None,
+            server_error: None,
+            network: None,
+            clock: Arc::new(TokioClock),
+            jitter: Arc::new(ThreadRngJitter),
         }
     }
 }
@@ -66,15 +296,99 @@ impl RetryConfig {
         self
     }
 
+    /// Set whether to wait as long as the API asks through `Retry-After` or the rate limit reset
+    pub fn honor_reset_header(mut self, honor_reset_header: bool) -> Self {
+        self.honor_reset_header = honor_reset_header;
+        self
+    }
+
+    /// Retry rate limit errors with their own policy
+    pub fn rate_limit_policy(mut self, policy: RetryPolicy) -> Self {
+        self.rate_limit = Some(policy);
+        self
+    }
+
+    /// Retry server errors with their own policy
+    pub fn server_error_policy(mut self, policy: RetryPolicy) -> Self {
+        self.server_error = Some(policy);
+        self
+    }
+
+    /// Retry network errors with their own policy
+    pub fn network_policy(mut self, policy: RetryPolicy) -> Self {
+        self.network = Some(policy);
+        self
+    }
+
+    /// Set the clock used to wait between attempts
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the source of the jitter factors
+    pub fn jitter_source(mut self, jitter: Arc<dyn JitterSource>) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
     /// Calculate the delay for a given retry attempt
+    ///
+    /// With jitter, the delay is scaled by a random factor between 0.5 and 1.5. The
+    /// result never exceeds `max_delay_ms`.
     pub fn calculate_delay(&self, attempt: u32) -> Duration {
-        let base_delay = (self.initial_delay_ms as f64 * self.backoff_factor.powi(attempt as i32)) as u64;
-        let delay = base_delay.min(self.max_delay_ms);
+        self.backoff(&self.default_policy(), attempt)
+    }
+
+    /// Get the policy for a class of errors
+    pub fn policy(&self, class: ErrorClass) -> RetryPolicy {
+        self.override_for(class).cloned().unwrap_or_else(|| self.default_policy())
+    }
+
+    /// Calculate the delay before retrying `error` for the `attempt`th time
+    ///
+    /// Uses the delay the API asked for when the policy of the error honors it, and
+    /// the policy's backoff otherwise.
+    pub fn delay_for(&self, error: &VeniceError, attempt: u32) -> Duration {
+        let policy = ErrorClass::of(error).map_or_else(|| self.default_policy(), |class| self.policy(class));
+        let retry_after = error.context().and_then(|context| context.retry_after);
+        match retry_after {
+            Some(retry_after) if policy.honor_reset_header => {
+                retry_after.min(Duration::from_millis(policy.max_delay_ms))
+            }
+            _ => self.backoff(&policy, attempt),
+        }
+    }
+
+    /// The policy set for a class, if it does not use the top-level fields
+    fn override_for(&self, class: ErrorClass) -> Option<&RetryPolicy> {
+        match class {
+            ErrorClass::RateLimit => self.rate_limit.as_ref(),
+            ErrorClass::ServerError => self.server_error.as_ref(),
+            ErrorClass::Network => self.network.as_ref(),
+        }
+    }
+
+    /// The policy made of the top-level fields
+    fn default_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            initial_delay_ms: self.initial_delay_ms,
+            max_delay_ms: self.max_delay_ms,
+            backoff_factor: self.backoff_factor,
+            honor_reset_header: self.honor_reset_header,
+        }
+    }
+
+    /// Exponential backoff of a policy, jittered if enabled
+    fn backoff(&self, policy: &RetryPolicy, attempt: u32) -> Duration {
+        let base_delay = (policy.initial_delay_ms as f64 * policy.backoff_factor.powi(attempt as i32)) as u64;
+        let delay = base_delay.min(policy.max_delay_ms);
         
         if self.add_jitter {
             // Add jitter by multiplying by a random value between 0.5 and 1.5
-            let jitter = 0.5 + rand::random::<f64>();
-            Duration::from_millis((delay as f64 * jitter) as u64)
+            let jitter = 0.5 + self.jitter.next_f64();
+            Duration::from_millis(((delay as f64 * jitter) as u64).min(policy.max_delay_ms))
         } else {
             Duration::from_millis(delay)
         }
@@ -82,19 +396,51 @@ impl RetryConfig {
 }
 
 /// Determines if an error is retryable
+///
+/// Network errors, rate limit errors and server errors (5xx) are retryable.
 pub fn is_retryable_error(error: &VeniceError) -> bool {
-    match error {
-        // Network errors are generally retryable
-        VeniceError::HttpError(_) => true,
-        
-        // Rate limit errors are retryable
-        VeniceError::RateLimitExceeded(_) => true,
-        
-        // Server errors (5xx) are retryable
-        VeniceError::ApiError { status, .. } => status.as_u16() >= 500 && status.as_u16() < 600,
-        
-        // Other errors are not retryable
-        _ => false,
+    ErrorClass::of(error).is_some()
+}
+
+/// A failed attempt of a request that was sent again
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryAttempt {
+    /// The error of the attempt
+    pub error: String,
+    /// The HTTP status of the attempt, if the API answered
+    pub status: Option<reqwest::StatusCode>,
+    /// How long the client waited before the next attempt
+    pub backoff: Duration,
+}
+
+/// What happened while retrying a request
+///
+/// Explains latency spikes that retries would otherwise hide. A request that succeeded
+/// on its first attempt has one attempt and no failures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryReport {
+    /// Number of attempts made, including the first
+    pub attempts: u32,
+    /// The attempts that failed and were retried, in order
+    pub failures: Vec<RetryAttempt>,
+    /// Total time spent waiting between attempts
+    pub total_backoff: Duration,
+}
+
+impl Default for RetryReport {
+    fn default() -> Self {
+        Self {
+            attempts: 1,
+            failures: Vec::new(),
+            total_backoff: Duration::ZERO,
+        }
+    }
+}
+
+impl RetryReport {
+    /// Check whether the request was sent more than once
+    pub fn retried(&self) -> bool {
+        self.attempts > 1
     }
 }
 
@@ -107,29 +453,208 @@ where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = VeniceResult<T>>,
 {
-    let mut attempt = 0;
+    with_retry_report(f, config).await.0
+}
+
+/// Execute a function with retry logic, reporting the attempts that were made
+///
+/// # Examples
+///
+/// ```
+/// # async fn example() {
+/// use venice_ai_api_sdk_rust::{RetryConfig, VeniceError, with_retry_report};
+///
+/// let config = RetryConfig::new().initial_delay_ms(1);
+/// let (result, report) = with_retry_report(
+///     || async { Err::<(), _>(VeniceError::RateLimitExceeded("slow down".to_string())) },
+///     &config,
+/// )
+/// .await;
+///
+/// assert!(result.is_err());
+/// assert_eq!(report.attempts, 4);
+/// assert_eq!(report.failures.len(), 3);
+/// # }
+/// ```
+pub async fn with_retry_report<T, F, Fut>(
+    f: F,
+    config: &RetryConfig,
+) -> (VeniceResult<T>, RetryReport)
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = VeniceResult<T>>,
+{
+    let mut report = RetryReport { attempts: 0, ..RetryReport::default() };
+    // Retries made per class with its own policy, and under `None` for the other classes
+    let mut retries: HashMap<Option<ErrorClass>, u32> = HashMap::new();
     
     loop {
+        report.attempts += 1;
+        let attempt = report.attempts;
         match f().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => return (Ok(result), report),
             Err(error) => {
-                attempt += 1;
-                
-                if attempt > config.max_retries || !is_retryable_error(&error) {
-                    return Err(error);
+                let class = ErrorClass::of(&error);
+                let budget = class.filter(|class| config.override_for(*class).is_some());
+                let max_retries = class.map_or(0, |class| config.policy(class).max_retries);
+                let retry = retries.entry(budget).or_insert(0);
+                if class.is_none() || *retry >= max_retries {
+                    if attempt > 1 {
+                        return (Err(error.with_context(ErrorContext::new().attempts(attempt))), report);
+                    }
+                    return (Err(error), report);
                 }
+                *retry += 1;
                 
-                let delay = config.calculate_delay(attempt);
+                let delay = config.delay_for(&error, *retry);
                 log::debug!(
                     "Request failed with error: {}. Retrying in {:?} (attempt {}/{})",
                     error,
                     delay,
-                    attempt,
-                    config.max_retries
+                    *retry,
+                    max_retries
+                );
+                #[cfg(feature = "otel")]
+                tracing::warn!(
+                    venice.retry.attempt = attempt,
+                    venice.retry.backoff_ms = delay.as_millis() as u64,
+                    error.message = %error,
+                    "retrying request"
                 );
                 
-                sleep(delay).await;
+                let status = match error.root() {
+                    VeniceError::ApiError { status, .. } => Some(*status),
+                    _ => None,
+                };
+                report.failures.push(RetryAttempt {
+                    error: error.to_string(),
+                    status,
+                    backoff: delay,
+                });
+                report.total_backoff += delay;
+                
+                config.clock.sleep(delay).await;
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn rate_limited() -> VeniceError {
+        VeniceError::RateLimitExceeded("slow down".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_backoff_with_virtual_clock() {
+        let clock = Arc::new(VirtualClock::new());
+        let config = RetryConfig::new()
+            .max_retries(4)
+            .initial_delay_ms(100)
+            .max_delay_ms(1000)
+            .add_jitter(false)
+            .clock(clock.clone());
+
+        let (result, report) = with_retry_report(|| async { Err::<(), _>(rate_limited()) }, &config).await;
+        assert!(result.is_err());
+        assert_eq!(report.attempts, 5);
+
+        let millis: Vec<u64> = clock.sleeps().iter().map(|d| d.as_millis() as u64).collect();
+        assert_eq!(millis, [200, 400, 800, 1000]);
+        assert_eq!(clock.elapsed(), report.total_backoff);
+    }
+
+    #[tokio::test]
+    async fn test_policies_per_error_class() {
+        let clock = Arc::new(VirtualClock::new());
+        let config = RetryConfig::new()
+            .max_retries(1)
+            .initial_delay_ms(100)
+            .add_jitter(false)
+            .rate_limit_policy(RetryPolicy::new().max_retries(3).max_delay_ms(30_000).honor_reset_header(true))
+            .clock(clock.clone());
+        let errors = std::sync::Mutex::new(vec![
+            VeniceError::ApiError {
+                status: reqwest::StatusCode::BAD_GATEWAY,
+                code: "bad_gateway".to_string(),
+                message: "Bad gateway".to_string(),
+            },
+            rate_limited().with_context(ErrorContext {
+                retry_after: Some(Duration::from_secs(60)),
+                ..ErrorContext::default()
+            }),
+            rate_limited(),
+            rate_limited(),
+        ]);
+
+        let (result, report) = with_retry_report(
+            || {
+                let error = errors.lock().unwrap().pop();
+                async move { error.map_or(Ok(()), Err) }
+            },
+            &config,
+        )
+        .await;
+        // Rate limits are retried on their own budget, leaving the server error its one retry
+        assert!(result.is_ok());
+        assert_eq!(report.attempts, 5);
+        let millis: Vec<u64> = clock.sleeps().iter().map(|d| d.as_millis() as u64).collect();
+        assert_eq!(millis, [1000, 2000, 30_000, 200]);
+
+        assert_eq!(config.policy(ErrorClass::ServerError).max_retries, 1);
+        assert_eq!(ErrorClass::of(&rate_limited()), Some(ErrorClass::RateLimit));
+        assert_eq!(ErrorClass::of(&VeniceError::InvalidInput("bad".to_string())), None);
+    }
+
+    #[test]
+    fn test_seeded_jitter_is_deterministic() {
+        let config = |seed| RetryConfig::new().jitter_source(Arc::new(SeededJitter::new(seed)));
+        let (a, b) = (config(7), config(7));
+        for attempt in 1..6 {
+            assert_eq!(a.calculate_delay(attempt), b.calculate_delay(attempt));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_delays_stay_within_bounds(
+            seed in any::<u64>(),
+            initial_delay_ms in 1u64..5_000,
+            max_delay_ms in 1u64..60_000,
+            backoff_factor in 1.0f64..4.0,
+            attempt in 1u32..20,
+        ) {
+            let config = RetryConfig::new()
+                .initial_delay_ms(initial_delay_ms)
+                .max_delay_ms(max_delay_ms)
+                .backoff_factor(backoff_factor)
+                .jitter_source(Arc::new(SeededJitter::new(seed)));
+
+            let base = ((initial_delay_ms as f64 * backoff_factor.powi(attempt as i32)) as u64).min(max_delay_ms);
+            let delay = config.calculate_delay(attempt).as_millis() as u64;
+            prop_assert!(delay <= max_delay_ms);
+            prop_assert!(delay >= base / 2);
+            prop_assert!(delay <= base + base / 2);
+        }
+
+        #[test]
+        fn prop_delays_without_jitter_never_decrease(
+            initial_delay_ms in 1u64..5_000,
+            max_delay_ms in 1u64..60_000,
+            backoff_factor in 1.0f64..4.0,
+        ) {
+            let config = RetryConfig::new()
+                .initial_delay_ms(initial_delay_ms)
+                .max_delay_ms(max_delay_ms)
+                .backoff_factor(backoff_factor)
+                .add_jitter(false);
+
+            let delays: Vec<Duration> = (1..20).map(|attempt| config.calculate_delay(attempt)).collect();
+            prop_assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+            prop_assert!(delays.iter().all(|delay| delay.as_millis() as u64 <= max_delay_ms));
+        }
+    }
+}
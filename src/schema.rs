@@ -0,0 +1,327 @@
+//! JSON schemas for structured extraction
+//!
+//! A type implementing [`VeniceSchema`] describes its JSON shape, so the model can be
+//! asked to reply with JSON that deserializes into it. With the `derive` feature,
+//! `#[derive(VeniceSchema)]` generates the schema from a struct's fields, doc comments
+//! and serde attributes; [`extract`] then sends a prompt in JSON schema mode and
+//! returns the parsed value, and [`extract_with_repair`] shows the model its own
+//! malformed reply with the parse error and asks again before giving up.
+
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::{VeniceError, VeniceResult};
+use crate::traits::chat::{ChatApi, ChatCompletionBuilder, ChatMessage, ResponseFormat};
+
+pub use serde_json::Value;
+#[cfg(feature = "derive")]
+pub use venice_ai_derive::VeniceSchema;
+
+/// A type with a JSON schema
+///
+/// Schemas are written for strict JSON schema mode: objects list every property as
+/// required and reject others, and optional values are nullable instead.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::schema::{self, Value, VeniceSchema};
+///
+/// struct City {
+///     name: String,
+///     population: Option<u64>,
+/// }
+///
+/// impl VeniceSchema for City {
+///     fn schema_name() -> String {
+///         "City".to_string()
+///     }
+///
+///     fn schema() -> Value {
+///         schema::object(vec![
+///             ("name", String::schema()),
+///             ("population", Option::<u64>::schema()),
+///         ])
+///     }
+/// }
+///
+/// assert_eq!(City::schema()["required"], serde_json::json!(["name", "population"]));
+/// ```
+pub trait VeniceSchema {
+    /// Name of the schema, sent with it to the API
+    fn schema_name() -> String {
+        "response".to_string()
+    }
+
+    /// The JSON schema of the type
+    fn schema() -> Value;
+}
+
+/// Build the schema of an object with the given properties, all required
+pub fn object(properties: Vec<(&str, Value)>) -> Value {
+    let required: Vec<&str> = properties.iter().map(|(name, _)| *name).collect();
+    let properties: serde_json::Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Build the schema of a string taking one of the given values
+pub fn string_enum(values: &[&str]) -> Value {
+    serde_json::json!({"type": "string", "enum": values})
+}
+
+/// Add a description to a schema
+pub fn describe(mut schema: Value, description: Option<&str>) -> Value {
+    if let (Some(description), Some(object)) = (description, schema.as_object_mut()) {
+        object.insert("description".to_string(), Value::from(description));
+    }
+    schema
+}
+
+/// Implement [`VeniceSchema`] for types with a fixed schema
+macro_rules! impl_schema {
+    ($schema:tt => $($ty:ty),*) => {$(
+        impl VeniceSchema for $ty {
+            fn schema() -> Value {
+                serde_json::json!($schema)
+            }
+        }
+    )*};
+}
+
+impl_schema!({"type": "string"} => String, str, char);
+impl_schema!({"type": "boolean"} => bool);
+impl_schema!({"type": "integer"} => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_schema!({"type": "number"} => f32, f64);
+impl_schema!({} => Value);
+
+impl<T: VeniceSchema + ?Sized> VeniceSchema for &T {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: VeniceSchema> VeniceSchema for Option<T> {
+    fn schema() -> Value {
+        serde_json::json!({"anyOf": [T::schema(), {"type": "null"}]})
+    }
+}
+
+impl<T: VeniceSchema> VeniceSchema for Vec<T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "array", "items": T::schema()})
+    }
+}
+
+impl<T: VeniceSchema> VeniceSchema for HashMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "object", "additionalProperties": T::schema()})
+    }
+}
+
+impl<T: VeniceSchema> VeniceSchema for BTreeMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({"type": "object", "additionalProperties": T::schema()})
+    }
+}
+
+/// Message sent after a reply that does not parse, followed by the parse error
+pub const REPAIR_PROMPT: &str = "Your reply does not match the JSON schema. \
+Reply again with only the corrected JSON. The error was:";
+
+/// Ask a model to answer a prompt with JSON matching `T`'s schema, and parse it
+///
+/// Code fences around the reply are ignored, for models that add them despite the schema.
+pub async fn extract<T, A>(api: &A, model: &str, prompt: impl Into<String>) -> VeniceResult<T>
+where
+    T: VeniceSchema + DeserializeOwned,
+    A: ChatApi + Sync + ?Sized,
+{
+    extract_with_repair(api, model, prompt, 0).await
+}
+
+/// Like [`extract`], but ask the model to fix a reply that does not parse, up to `max_repairs` times
+///
+/// Each repair continues the conversation with the malformed reply and a
+/// [`REPAIR_PROMPT`] carrying the parse error, so the model sees what to fix. The parse
+/// error of the last reply is returned once the repairs run out.
+pub async fn extract_with_repair<T, A>(api: &A, model: &str, prompt: impl Into<String>, max_repairs: u32) -> VeniceResult<T>
+where
+    T: VeniceSchema + DeserializeOwned,
+    A: ChatApi + Sync + ?Sized,
+{
+    let mut messages = vec![ChatMessage::user(prompt)];
+    let mut repairs = 0;
+    loop {
+        let mut builder = ChatCompletionBuilder::new(model)
+            .response_format(ResponseFormat::json_schema(T::schema_name(), T::schema()));
+        for message in &messages {
+            builder = builder.add_message(message.clone());
+        }
+        let (response, _) = api.create_chat_completion(builder.build()).await?;
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| VeniceError::parse_error("Chat completion response has no choices"))?;
+
+        let error = match serde_json::from_str(strip_code_fence(&content)) {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if repairs == max_repairs {
+            let attempts = if repairs > 0 { format!(" after {} repairs", repairs) } else { String::new() };
+            return Err(VeniceError::parse_error(format!(
+                "Reply does not match the {} schema{}: {}",
+                T::schema_name(),
+                attempts,
+                error
+            )));
+        }
+        repairs += 1;
+        messages.push(ChatMessage::assistant(content));
+        messages.push(ChatMessage::user(format!("{} {}", REPAIR_PROMPT, error)));
+    }
+}
+
+/// Remove a Markdown code fence around a reply
+pub(crate) fn strip_code_fence(content: &str) -> &str {
+    let content = content.trim();
+    match content.strip_prefix("```").and_then(|rest| rest.strip_suffix("```")) {
+        Some(fenced) => fenced.trim_start_matches("json").trim(),
+        None => content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_schemas() {
+        assert_eq!(Vec::<Option<u32>>::schema(), serde_json::json!({
+            "type": "array",
+            "items": {"anyOf": [{"type": "integer"}, {"type": "null"}]}
+        }));
+        assert_eq!(strip_code_fence("```json\n{\"a\": 1}\n```"), "{\"a\": 1}");
+        assert_eq!(strip_code_fence(" {\"a\": 1} "), "{\"a\": 1}");
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_repair() {
+        let completion = |content: &str| {
+            serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": content}, "finish_reason": "stop"}]
+            })
+            .to_string()
+        };
+        let mut server = mockito::Server::new_async().await;
+        // The repair request is matched first, since it also contains the original prompt
+        let repair = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "messages": [
+                    {"role": "user", "content": "List three primes."},
+                    {"role": "assistant", "content": "[2, 3, 5"}
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion("[2, 3, 5]"))
+            .create_async()
+            .await;
+        let first = server
+            .mock("POST", "/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(completion("[2, 3, 5"))
+            .expect(2)
+            .create_async()
+            .await;
+        let client = crate::Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let primes: Vec<u32> = client.extract_with_repair("llama-3.3-70b", "List three primes.", 2).await.unwrap();
+        assert_eq!(primes, [2, 3, 5]);
+        repair.assert_async().await;
+
+        let err = client.extract::<Vec<u32>>("llama-3.3-70b", "List three primes.").await.unwrap_err();
+        assert!(err.to_string().contains("does not match the"));
+        first.assert_async().await;
+    }
+
+    #[cfg(feature = "derive")]
+    #[tokio::test]
+    async fn test_derive_and_extract() {
+        use serde::Deserialize;
+
+        /// A city mentioned in the text
+        #[derive(Debug, Deserialize, VeniceSchema)]
+        #[serde(rename_all = "camelCase")]
+        #[allow(dead_code)]
+        struct City {
+            /// Name of the city
+            city_name: String,
+            population: Option<u64>,
+            size: Size,
+            #[serde(skip)]
+            note: String,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, VeniceSchema)]
+        #[serde(rename_all = "snake_case")]
+        enum Size {
+            SmallTown,
+            Metropolis,
+        }
+
+        let schema = City::schema();
+        assert_eq!(schema["description"], "A city mentioned in the text");
+        assert_eq!(schema["required"], serde_json::json!(["cityName", "population", "size"]));
+        assert_eq!(schema["properties"]["cityName"]["description"], "Name of the city");
+        assert_eq!(schema["properties"]["size"]["enum"], serde_json::json!(["small_town", "metropolis"]));
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"response_format": {"type": "json_schema", "json_schema": {"name": "City", "strict": true}}}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "llama-3.3-70b",
+                "choices": [{"index": 0, "message": {"role": "assistant", "content": "{\"cityName\": \"Paris\", \"population\": null, \"size\": \"metropolis\"}"}, "finish_reason": "stop"}]
+            }"#)
+            .create_async()
+            .await;
+        let client = crate::Client::builder()
+            .api_key("test_api_key")
+            .base_url(server.url())
+            .build()
+            .unwrap();
+
+        let city: City = client.extract("llama-3.3-70b", "I live in Paris.").await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(city.city_name, "Paris");
+        assert_eq!(city.size, Size::Metropolis);
+    }
+}
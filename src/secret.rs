@@ -0,0 +1,145 @@
+//! Secret values
+//!
+//! API keys are held in a [`SecretString`], whose `Debug` output shows only enough of
+//! the value to tell keys apart, so configs and responses can be logged with `{:?}`.
+//! Tests that need the full values in their output can wrap it in [`reveal_secrets`].
+
+use std::cell::Cell;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+thread_local! {
+    static REVEAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A string that is redacted in `Debug` output
+///
+/// A key such as `sk-1234567890abcd` is shown as `"sk-****abcd"`; short values are shown
+/// as `"****"`. The value itself is read with [`SecretString::expose_secret`].
+/// It serializes as a plain string.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::SecretString;
+///
+/// let key = SecretString::new("sk-1234567890abcd");
+/// assert_eq!(format!("{:?}", key), r#""sk-****abcd""#);
+/// assert_eq!(key.expose_secret(), "sk-1234567890abcd");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a secret value
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Get the secret value
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Check whether the value is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the redacted form shown by `Debug`
+    pub fn redacted(&self) -> String {
+        let chars: Vec<char> = self.0.chars().collect();
+        if chars.is_empty() {
+            return String::new();
+        }
+        if chars.len() <= 8 {
+            return "****".to_string();
+        }
+        // Keep a short prefix such as `sk-`, which names the kind of key
+        let prefix = match self.0.find('-') {
+            Some(end) if end <= 4 => &self.0[..=end],
+            _ => "",
+        };
+        let last4: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}****{}", prefix, last4)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if REVEAL.with(Cell::get) {
+            fmt::Debug::fmt(&self.0, f)
+        } else {
+            fmt::Debug::fmt(&self.redacted(), f)
+        }
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Run `f` with secrets shown in full in `Debug` output
+///
+/// Only output produced on the current thread while `f` runs is affected. Meant for tests
+/// and local debugging; never wrap code whose output reaches logs.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::{reveal_secrets, ClientConfig};
+///
+/// let config = ClientConfig::new("sk-1234567890abcd");
+/// assert!(!format!("{:?}", config).contains("1234567890"));
+/// assert!(reveal_secrets(|| format!("{:?}", config)).contains("sk-1234567890abcd"));
+/// ```
+pub fn reveal_secrets<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            REVEAL.with(|reveal| reveal.set(self.0));
+        }
+    }
+
+    let _restore = Restore(REVEAL.with(|reveal| reveal.replace(true)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        assert_eq!(SecretString::new("sk-1234567890abcd").redacted(), "sk-****abcd");
+        assert_eq!(SecretString::new("VENICE1234567890abcd").redacted(), "****abcd");
+        assert_eq!(SecretString::new("short").redacted(), "****");
+        assert_eq!(SecretString::new("").redacted(), "");
+
+        let key = SecretString::new("sk-1234567890abcd");
+        assert_eq!(reveal_secrets(|| format!("{:?}", key)), r#""sk-1234567890abcd""#);
+        assert_eq!(format!("{:?}", key), r#""sk-****abcd""#);
+    }
+}
@@ -4,17 +4,37 @@
 
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::error::VeniceError;
 
+/// How far a webhook timestamp may be from the current time by default
+pub const DEFAULT_WEBHOOK_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
 /// Service for verifying webhook signatures
-#[derive(Debug, Clone)]
-pub struct WebhookService;
+#[derive(Debug, Clone, Default)]
+pub struct WebhookService {
+    tolerance: Option<Duration>,
+}
 
 impl WebhookService {
     /// Create a new webhook service
+    ///
+    /// The service does not check timestamps; use [`with_tolerance`](Self::with_tolerance)
+    /// to reject replayed webhooks.
     pub fn new() -> Self {
-        Self
+        Self { tolerance: None }
+    }
+
+    /// Reject webhooks whose timestamp is further than `tolerance` from the current time
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Get the timestamp tolerance, if timestamps are checked
+    pub fn tolerance(&self) -> Option<Duration> {
+        self.tolerance
     }
     
     /// Verify a webhook signature
@@ -29,7 +49,7 @@ impl WebhookService {
     /// # Returns
     ///
     /// * `Ok(())` if the signature is valid
-    /// * `Err(VeniceError)` if the signature is invalid
+    /// * `Err(VeniceError)` if the signature is invalid or the timestamp is outside the tolerance
     pub fn verify_signature(
         &self,
         signature: &str,
@@ -37,24 +57,62 @@ impl WebhookService {
         body: &[u8],
         secret: &str,
     ) -> Result<(), VeniceError> {
+        self.verify_signature_with_secrets(signature, timestamp, body, &[secret])
+    }
+
+    /// Verify a webhook signature against several secrets
+    ///
+    /// The signature is valid if it matches any of the secrets, which lets a new secret be
+    /// rolled out while webhooks signed with the old one are still arriving.
+    pub fn verify_signature_with_secrets<S: AsRef<str>>(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+        secrets: &[S],
+    ) -> Result<(), VeniceError> {
+        self.verify_at(signature, timestamp, body, secrets, SystemTime::now())
+    }
+
+    /// Verify a webhook signature as of the given time
+    fn verify_at<S: AsRef<str>>(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+        secrets: &[S],
+        now: SystemTime,
+    ) -> Result<(), VeniceError> {
+        if secrets.is_empty() {
+            return Err(VeniceError::InvalidInput(
+                "At least one webhook secret is required".to_string(),
+            ));
+        }
+
+        if let Some(tolerance) = self.tolerance {
+            self.check_timestamp(timestamp, tolerance, now)?;
+        }
+
+        // A signature that is not valid hex cannot match any secret
+        let signature = hex::decode(signature.trim()).map_err(|_| {
+            VeniceError::InvalidWebhookSignature("Signature mismatch".to_string())
+        })?;
+
         // Create the message to verify
         let message = format!("{}:{}", timestamp, String::from_utf8_lossy(body));
-        
-        // Create the HMAC
-        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
-            .map_err(|_| VeniceError::InvalidWebhookSignature("Invalid secret".to_string()))?;
-        
-        // Update the HMAC with the message
-        mac.update(message.as_bytes());
-        
-        // Get the HMAC result
-        let result = mac.finalize().into_bytes();
-        
-        // Convert the result to a hex string
-        let computed_signature = hex::encode(result);
-        
-        // Compare the signatures using constant-time comparison
-        if self.constant_time_compare(signature, &computed_signature) {
+
+        // Check every secret so the time taken does not reveal which one matched
+        let mut matched = false;
+        for secret in secrets {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_ref().as_bytes())
+                .map_err(|_| VeniceError::InvalidWebhookSignature("Invalid secret".to_string()))?;
+            mac.update(message.as_bytes());
+
+            // `verify_slice` compares the tags in constant time
+            matched |= mac.verify_slice(&signature).is_ok();
+        }
+
+        if matched {
             Ok(())
         } else {
             Err(VeniceError::InvalidWebhookSignature(
@@ -62,25 +120,31 @@ impl WebhookService {
             ))
         }
     }
-    
-    /// Compare two strings in constant time
-    ///
-    /// This function compares two strings in constant time to prevent timing attacks.
-    /// It returns true if the strings are equal, false otherwise.
-    fn constant_time_compare(&self, a: &str, b: &str) -> bool {
-        if a.len() != b.len() {
-            return false;
-        }
-        
-        let a_bytes = a.as_bytes();
-        let b_bytes = b.as_bytes();
-        
-        let mut result = 0;
-        for i in 0..a.len() {
-            result |= a_bytes[i] ^ b_bytes[i];
+
+    /// Check that a timestamp, in seconds since the Unix epoch, is within the tolerance of `now`
+    fn check_timestamp(
+        &self,
+        timestamp: &str,
+        tolerance: Duration,
+        now: SystemTime,
+    ) -> Result<(), VeniceError> {
+        let sent = timestamp.trim().parse::<u64>().map_err(|_| {
+            VeniceError::InvalidWebhookSignature(format!("Invalid timestamp: {}", timestamp))
+        })?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        if now.abs_diff(sent) > tolerance.as_secs() {
+            return Err(VeniceError::InvalidWebhookSignature(format!(
+                "Timestamp {} is outside the tolerance of {} seconds",
+                timestamp,
+                tolerance.as_secs()
+            )));
         }
-        
-        result == 0
+
+        Ok(())
     }
 }
 
@@ -88,20 +152,6 @@ impl WebhookService {
 mod tests {
     use super::*;
     
-    #[test]
-    fn test_constant_time_compare() {
-        let service = WebhookService::new();
-        
-        // Equal strings
-        assert!(service.constant_time_compare("hello", "hello"));
-        
-        // Different strings of same length
-        assert!(!service.constant_time_compare("hello", "world"));
-        
-        // Different strings of different length
-        assert!(!service.constant_time_compare("hello", "hello world"));
-    }
-    
     #[test]
     fn test_verify_signature_valid() {
         let service = WebhookService::new();
@@ -137,4 +187,64 @@ mod tests {
         let result = service.verify_signature(signature, timestamp, body, secret);
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+        let message = format!("{}:{}", timestamp, String::from_utf8_lossy(body));
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_verify_signature_truncated() {
+        let service = WebhookService::new();
+        let signature = sign("test_secret", "1234567890", b"body");
+
+        let result = service.verify_signature(&signature[..32], "1234567890", b"body", "test_secret");
+        assert!(matches!(result, Err(VeniceError::InvalidWebhookSignature(_))));
+    }
+
+    #[test]
+    fn test_verify_signature_tolerance() {
+        let service = WebhookService::new().with_tolerance(DEFAULT_WEBHOOK_TOLERANCE);
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let secrets = ["test_secret"];
+
+        let fresh = "1699999900";
+        let signature = sign("test_secret", fresh, b"body");
+        assert!(service.verify_at(&signature, fresh, b"body", &secrets, now).is_ok());
+
+        let replayed = "1699999000";
+        let signature = sign("test_secret", replayed, b"body");
+        assert!(matches!(
+            service.verify_at(&signature, replayed, b"body", &secrets, now),
+            Err(VeniceError::InvalidWebhookSignature(_))
+        ));
+
+        let future = "1700001000";
+        let signature = sign("test_secret", future, b"body");
+        assert!(service.verify_at(&signature, future, b"body", &secrets, now).is_err());
+
+        let signature = sign("test_secret", "yesterday", b"body");
+        assert!(service.verify_at(&signature, "yesterday", b"body", &secrets, now).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_with_secrets() {
+        let service = WebhookService::new();
+        let signature = sign("old_secret", "1234567890", b"body");
+
+        assert!(service
+            .verify_signature_with_secrets(&signature, "1234567890", b"body", &["new_secret", "old_secret"])
+            .is_ok());
+        assert!(service
+            .verify_signature_with_secrets(&signature, "1234567890", b"body", &["new_secret"])
+            .is_err());
+
+        let none: [&str; 0] = [];
+        assert!(matches!(
+            service.verify_signature_with_secrets(&signature, "1234567890", b"body", &none),
+            Err(VeniceError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+//! Tracing spans for API calls
+//!
+//! With the `otel` feature enabled, chat and image calls are wrapped in `tracing`
+//! spans that can be exported to OpenTelemetry with `tracing-opentelemetry`.
+//! Without the feature every function in this module is a no-op.
+
+use std::future::Future;
+
+use crate::error::{RateLimitInfo, VeniceResult};
+
+/// Create a span for an API call with the fields recorded by [`ApiSpan`]
+#[cfg(feature = "otel")]
+macro_rules! api_span {
+    ($name:literal, $model:expr) => {
+        tracing::info_span!(
+            $name,
+            otel.kind = "client",
+            otel.status_code = tracing::field::Empty,
+            venice.model = %$model,
+            venice.usage.prompt_tokens = tracing::field::Empty,
+            venice.usage.completion_tokens = tracing::field::Empty,
+            venice.usage.total_tokens = tracing::field::Empty,
+            venice.rate_limit.remaining_requests = tracing::field::Empty,
+            venice.rate_limit.remaining_tokens = tracing::field::Empty,
+            http.status_code = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        )
+    };
+}
+
+/// A span covering a single API call
+pub(crate) struct ApiSpan {
+    #[cfg(feature = "otel")]
+    span: tracing::Span,
+}
+
+impl ApiSpan {
+    /// Create a `venice.chat.completions` span
+    pub(crate) fn chat_completions(model: &str) -> Self {
+        #[cfg(feature = "otel")]
+        {
+            Self {
+                span: api_span!("venice.chat.completions", model),
+            }
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            let _ = model;
+            Self {}
+        }
+    }
+
+    /// Create a `venice.image.generate` span
+    pub(crate) fn image_generate(model: &str) -> Self {
+        #[cfg(feature = "otel")]
+        {
+            Self {
+                span: api_span!("venice.image.generate", model),
+            }
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            let _ = model;
+            Self {}
+        }
+    }
+
+    /// Run a future inside the span
+    pub(crate) async fn instrument<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "otel")]
+        {
+            use tracing::Instrument;
+            future.instrument(self.span.clone()).await
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            future.await
+        }
+    }
+
+    /// Record the outcome of the call on the span
+    ///
+    /// `usage` extracts `(prompt_tokens, completion_tokens, total_tokens)` from the response.
+    pub(crate) fn finish<T>(
+        &self,
+        result: VeniceResult<(T, RateLimitInfo)>,
+        usage: impl FnOnce(&T) -> Option<(u32, u32, u32)>,
+    ) -> VeniceResult<(T, RateLimitInfo)> {
+        #[cfg(feature = "otel")]
+        match &result {
+            Ok((response, rate_limit_info)) => {
+                self.span.record("otel.status_code", "OK");
+                if let Some((prompt, completion, total)) = usage(response) {
+                    self.span.record("venice.usage.prompt_tokens", prompt);
+                    self.span.record("venice.usage.completion_tokens", completion);
+                    self.span.record("venice.usage.total_tokens", total);
+                }
+                if let Some(remaining) = rate_limit_info.remaining_requests {
+                    self.span.record("venice.rate_limit.remaining_requests", remaining);
+                }
+                if let Some(remaining) = rate_limit_info.remaining_tokens {
+                    self.span.record("venice.rate_limit.remaining_tokens", remaining);
+                }
+            }
+            Err(err) => {
+                self.span.record("otel.status_code", "ERROR");
+                self.span.record("error.message", tracing::field::display(err));
+                if let crate::error::VeniceError::ApiError { status, .. } = err.root() {
+                    self.span.record("http.status_code", status.as_u16());
+                }
+            }
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = usage;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VeniceError;
+
+    #[tokio::test]
+    async fn test_span_passes_result_through() {
+        let span = ApiSpan::chat_completions("llama-3.3-70b");
+        let result: VeniceResult<(u32, RateLimitInfo)> = span
+            .instrument(async { Err(VeniceError::InvalidInput("bad".to_string())) })
+            .await;
+        let result = span.finish(result, |_| None);
+        assert!(matches!(result, Err(VeniceError::InvalidInput(_))));
+
+        let span = ApiSpan::image_generate("fluently-xl");
+        let rate_limit_info = RateLimitInfo::from_headers(&reqwest::header::HeaderMap::new());
+        let result = span.finish(Ok((7u32, rate_limit_info)), |n| Some((*n, 0, *n)));
+        assert_eq!(result.unwrap().0, 7);
+    }
+}
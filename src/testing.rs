@@ -0,0 +1,285 @@
+//! Test doubles for applications built on the SDK
+//!
+//! [`TestChatClient`] answers chat requests with canned responses. [`FaultInjector`]
+//! wraps it, or any other API implementation, and fails a planned number of requests
+//! with the same errors the client returns for real 429 and 5xx responses, so backoff
+//! and retry handling can be tested without touching the network.
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{ErrorContext, RateLimitInfo, VeniceError, VeniceResult};
+use crate::http::rate_limit_error;
+use crate::traits::chat::{ChatApi, ChatCompletionRequest, ChatCompletionResponse, ChatCompletionStream};
+use crate::traits::image::{
+    ImageApi, ImageGenerateRequest, ImageGenerateResponse, ImageUpscaleRequest, ImageUpscaleResponse,
+    ListImageStylesResponse,
+};
+
+pub use crate::chat::test_client::{ErrorConfig, TestChatClient};
+
+/// A failure injected in place of a response
+#[derive(Debug)]
+pub enum Fault {
+    /// A 429 response whose rate limit headers report no remaining requests
+    RateLimited {
+        /// Requests allowed per window, sent as `x-ratelimit-limit-requests`
+        limit: u32,
+        /// Time until the window resets, sent as `x-ratelimit-reset-requests`
+        reset_after: Duration,
+    },
+    /// An error response with a status code, such as 500 or 503
+    ServerError(StatusCode),
+    /// Any other error
+    Error(VeniceError),
+}
+
+impl Fault {
+    /// A 429 response with a limit of 20 requests that resets after `reset_after`
+    pub fn rate_limited(reset_after: Duration) -> Self {
+        Fault::RateLimited { limit: 20, reset_after }
+    }
+
+    /// A 503 Service Unavailable response
+    pub fn service_unavailable() -> Self {
+        Fault::ServerError(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    /// The headers of the response this fault simulates
+    ///
+    /// Rate limit faults carry `x-ratelimit-*` headers with the reset time as a Unix
+    /// timestamp, as the API sends them. Every fault carries an `x-request-id`.
+    pub fn headers(&self, request_id: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = HeaderValue::from_str(request_id) {
+            headers.insert("x-request-id", value);
+        }
+        if let Fault::RateLimited { limit, reset_after } = self {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+            let reset = (now + *reset_after).as_secs();
+            headers.insert("x-ratelimit-limit-requests", HeaderValue::from(*limit));
+            headers.insert("x-ratelimit-remaining-requests", HeaderValue::from(0));
+            headers.insert("x-ratelimit-reset-requests", HeaderValue::from(reset));
+        }
+        headers
+    }
+
+    /// The error the client returns for this fault
+    pub fn to_error(&self, request_id: &str) -> VeniceError {
+        match self {
+            Fault::RateLimited { .. } => rate_limit_error(&self.headers(request_id)),
+            Fault::ServerError(status) => VeniceError::ApiError {
+                status: *status,
+                code: "server_error".to_string(),
+                message: status.canonical_reason().unwrap_or("Server error").to_string(),
+            }
+            .with_context(ErrorContext::from_headers(&self.headers(request_id))),
+            Fault::Error(error) => error.duplicate(),
+        }
+    }
+}
+
+/// Fails planned requests to an API before passing the rest through
+///
+/// Faults are used up in the order they were planned, one per request. Once they are
+/// used up, requests go to the wrapped API.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::testing::{Fault, FaultInjector, TestChatClient};
+/// use venice_ai_api_sdk_rust::traits::chat::{ChatApi, ChatCompletionBuilder};
+/// use venice_ai_api_sdk_rust::{with_retry, RetryConfig, VirtualClock};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let api = FaultInjector::new(TestChatClient::new())
+///     .fail_first(2, || Fault::rate_limited(Duration::from_secs(30)));
+///
+/// let config = RetryConfig::default().clock(Arc::new(VirtualClock::new()));
+/// let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+/// let (response, _) = with_retry(|| api.create_chat_completion(request.clone()), &config)
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(response.choices[0].message.content, "This is a test response");
+/// assert_eq!(api.requests(), 3);
+/// assert_eq!(api.faults_injected(), 2);
+/// # }
+/// ```
+pub struct FaultInjector<A> {
+    inner: A,
+    faults: Mutex<VecDeque<Fault>>,
+    requests: AtomicU32,
+    injected: AtomicU32,
+}
+
+impl<A> FaultInjector<A> {
+    /// Wrap an API without planning any faults
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            faults: Mutex::new(VecDeque::new()),
+            requests: AtomicU32::new(0),
+            injected: AtomicU32::new(0),
+        }
+    }
+
+    /// Fail the next request that is not already planned to fail
+    pub fn then_fail(self, fault: Fault) -> Self {
+        self.faults.lock().unwrap_or_else(|e| e.into_inner()).push_back(fault);
+        self
+    }
+
+    /// Fail the next `count` requests that are not already planned to fail
+    pub fn fail_first(mut self, count: usize, fault: impl Fn() -> Fault) -> Self {
+        for _ in 0..count {
+            self = self.then_fail(fault());
+        }
+        self
+    }
+
+    /// Plan another fault while the injector is in use
+    pub fn inject(&self, fault: Fault) {
+        self.faults.lock().unwrap_or_else(|e| e.into_inner()).push_back(fault);
+    }
+
+    /// Get the number of requests made, failed or not
+    pub fn requests(&self) -> u32 {
+        self.requests.load(Ordering::Acquire)
+    }
+
+    /// Get the number of requests failed so far
+    pub fn faults_injected(&self) -> u32 {
+        self.injected.load(Ordering::Acquire)
+    }
+
+    /// Get the number of planned faults not used yet
+    pub fn faults_remaining(&self) -> usize {
+        self.faults.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Get the wrapped API
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Count a request and fail it if a fault is planned
+    fn check(&self) -> VeniceResult<()> {
+        let request = self.requests.fetch_add(1, Ordering::AcqRel) + 1;
+        let fault = self.faults.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        match fault {
+            Some(fault) => {
+                self.injected.fetch_add(1, Ordering::AcqRel);
+                Err(fault.to_error(&format!("fault-{}", request)))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<A> std::fmt::Debug for FaultInjector<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultInjector")
+            .field("requests", &self.requests())
+            .field("faults_injected", &self.faults_injected())
+            .field("faults_remaining", &self.faults_remaining())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<A: ChatApi + Send + Sync> ChatApi for FaultInjector<A> {
+    async fn create_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> VeniceResult<(ChatCompletionResponse, RateLimitInfo)> {
+        self.check()?;
+        self.inner.create_chat_completion(request).await
+    }
+
+    async fn create_streaming_chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> VeniceResult<(ChatCompletionStream, RateLimitInfo)> {
+        self.check()?;
+        self.inner.create_streaming_chat_completion(request).await
+    }
+}
+
+#[async_trait]
+impl<A: ImageApi + Send + Sync> ImageApi for FaultInjector<A> {
+    async fn generate_image(
+        &self,
+        request: ImageGenerateRequest,
+    ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
+        self.check()?;
+        self.inner.generate_image(request).await
+    }
+
+    async fn list_styles(&self) -> VeniceResult<(ListImageStylesResponse, RateLimitInfo)> {
+        self.check()?;
+        self.inner.list_styles().await
+    }
+
+    async fn upscale_image(&self, request: ImageUpscaleRequest) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)> {
+        self.check()?;
+        self.inner.upscale_image(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::{is_retryable_error, with_retry_report, RetryConfig, VirtualClock};
+    use crate::traits::chat::ChatCompletionBuilder;
+
+    #[tokio::test]
+    async fn test_faults_then_success() {
+        let clock = std::sync::Arc::new(VirtualClock::new());
+        let api = FaultInjector::new(TestChatClient::new())
+            .fail_first(2, || Fault::rate_limited(Duration::from_secs(30)))
+            .then_fail(Fault::service_unavailable());
+        let config = RetryConfig::default()
+            .max_retries(3)
+            .initial_delay_ms(100)
+            .clock(clock.clone());
+        let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello").build();
+
+        let (result, report) = with_retry_report(|| api.create_chat_completion(request.clone()), &config).await;
+        assert!(result.is_ok());
+        assert_eq!(report.attempts, 4);
+        assert_eq!(clock.sleeps().len(), 3);
+        assert_eq!(api.requests(), 4);
+        assert_eq!(api.faults_injected(), 3);
+        assert_eq!(api.faults_remaining(), 0);
+    }
+
+    #[test]
+    fn test_fault_errors_match_client_errors() {
+        let fault = Fault::rate_limited(Duration::from_secs(60));
+        let info = RateLimitInfo::from_headers(&fault.headers("req-1"));
+        assert_eq!(info.limit_requests, Some(20));
+        assert_eq!(info.remaining_requests, Some(0));
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert!((now + 59..=now + 61).contains(&info.reset_requests.unwrap()));
+
+        let error = fault.to_error("req-1");
+        assert!(matches!(error.root(), VeniceError::RateLimitExceeded(_)));
+        assert_eq!(error.context().and_then(|c| c.request_id.as_deref()), Some("req-1"));
+        assert!(is_retryable_error(&error));
+
+        let error = Fault::ServerError(StatusCode::INTERNAL_SERVER_ERROR).to_error("req-2");
+        assert!(matches!(error.root(), VeniceError::ApiError { status, .. } if *status == StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_error(&error));
+
+        assert!(!is_retryable_error(&Fault::Error(VeniceError::InvalidInput("bad".to_string())).to_error("req-3")));
+    }
+}
@@ -1,8 +1,8 @@
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
-use crate::error::{RateLimitInfo, VeniceResult};
-use crate::pagination::{PaginationParams, Paginator};
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::pagination::{BoxPaginator, PaginationParams};
 use crate::api_keys::list::{ApiKey, ListApiKeysRequest, ListApiKeysResponse};
 
 /// Request to create a new API key
@@ -12,23 +12,8 @@ pub struct CreateApiKeyRequest {
     pub name: String,
 }
 
-/// Response from creating a new API key
-#[derive(Debug, Clone, Deserialize)]
-pub struct CreateApiKeyResponse {
-    /// The created API key
-    pub key: ApiKey,
-    /// The full API key value (only returned once)
-    pub secret: String,
-}
-
-/// Response from deleting an API key
-#[derive(Debug, Clone, Deserialize)]
-pub struct DeleteApiKeyResponse {
-    /// Whether the deletion was successful
-    pub deleted: bool,
-    /// The ID of the deleted API key
-    pub id: String,
-}
+// The trait returns the same response types as the `Client` methods
+pub use crate::api_keys::{CreateApiKeyResponse, DeleteApiKeyResponse, GenerateWeb3KeyResponse};
 
 /// Request to generate a Web3 key
 #[derive(Debug, Clone, Serialize)]
@@ -39,13 +24,6 @@ pub struct GenerateWeb3KeyRequest {
     pub signature: String,
 }
 
-/// Response from generating a Web3 key
-#[derive(Debug, Clone, Deserialize)]
-pub struct GenerateWeb3KeyResponse {
-    /// The generated API key
-    pub key: String,
-}
-
 /// API Keys API trait
 #[async_trait]
 pub trait ApiKeysApi {
@@ -59,7 +37,7 @@ pub trait ApiKeysApi {
     ) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)>;
     
     /// Create a paginator for listing API keys
-    fn list_api_keys_paginator(&self, params: PaginationParams) -> impl Paginator<ApiKey>;
+    fn list_api_keys_paginator(&self, params: PaginationParams) -> BoxPaginator<ApiKey>;
     
     /// Create a new API key
     async fn create_api_key(
@@ -75,4 +53,108 @@ pub trait ApiKeysApi {
         &self,
         request: GenerateWeb3KeyRequest,
     ) -> VeniceResult<(GenerateWeb3KeyResponse, RateLimitInfo)>;
+    
+    /// Find the API key matching a predicate, going through every page of keys
+    ///
+    /// Returns `None` if no key matches, and [`VeniceError::InvalidInput`] listing the
+    /// candidates if more than one does, so a lookup never silently picks one of them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::api_keys::ApiKeysApi};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-admin-key")?;
+    /// if let Some(key) = client.find_api_key(&|key| key.last_chars == "x9Qa2B").await? {
+    ///     println!("{} is {}", key.last_chars, key.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn find_api_key(&self, predicate: &(dyn for<'k> Fn(&'k ApiKey) -> bool + Send + Sync)) -> VeniceResult<Option<ApiKey>>
+    where
+        Self: Sync,
+    {
+        let mut request = ListApiKeysRequest::new();
+        let mut matches = Vec::new();
+        loop {
+            let (page, _) = self.list_api_keys_with_params(request).await?;
+            matches.extend(page.data.into_iter().filter(|key| predicate(key)));
+            match page.next_cursor {
+                Some(cursor) if page.has_more => request = ListApiKeysRequest::new().cursor(cursor),
+                _ => break,
+            }
+        }
+        if matches.len() > 1 {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|key| format!("{} ({}, ...{})", key.id, key.name.as_deref().unwrap_or("unnamed"), key.last_chars))
+                .collect();
+            return Err(VeniceError::InvalidInput(format!(
+                "{} API keys match; expected one of: {}",
+                matches.len(),
+                candidates.join(", ")
+            )));
+        }
+        Ok(matches.pop())
+    }
+    
+    /// Delete the API key with a name
+    ///
+    /// Revoked keys are ignored. Fails without deleting anything if no key or more than
+    /// one key has the name; see [`ApiKeysApi::find_api_key`].
+    async fn delete_api_key_by_name(&self, name: &str) -> VeniceResult<(DeleteApiKeyResponse, RateLimitInfo)>
+    where
+        Self: Sync,
+    {
+        let key = self
+            .find_api_key(&|key| !key.revoked && key.name.as_deref() == Some(name))
+            .await?
+            .ok_or_else(|| VeniceError::InvalidInput(format!("No API key is named {}", name)))?;
+        self.delete_api_key(&key.id).await
+    }
 }
+
+/// Implement [`ApiKeysApi`] for pointer types by delegating to the pointee
+macro_rules! delegate_api_keys_api {
+    ($($pointer:ty),*) => {$(
+        #[async_trait]
+        impl<T: ApiKeysApi + Send + Sync + ?Sized> ApiKeysApi for $pointer {
+            async fn list_api_keys(&self) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)> {
+                (**self).list_api_keys().await
+            }
+
+            async fn list_api_keys_with_params(
+                &self,
+                request: ListApiKeysRequest,
+            ) -> VeniceResult<(ListApiKeysResponse, RateLimitInfo)> {
+                (**self).list_api_keys_with_params(request).await
+            }
+
+            fn list_api_keys_paginator(&self, params: PaginationParams) -> BoxPaginator<ApiKey> {
+                (**self).list_api_keys_paginator(params)
+            }
+
+            async fn create_api_key(
+                &self,
+                request: CreateApiKeyRequest,
+            ) -> VeniceResult<(CreateApiKeyResponse, RateLimitInfo)> {
+                (**self).create_api_key(request).await
+            }
+
+            async fn delete_api_key(&self, key_id: &str) -> VeniceResult<(DeleteApiKeyResponse, RateLimitInfo)> {
+                (**self).delete_api_key(key_id).await
+            }
+
+            async fn generate_web3_key(
+                &self,
+                request: GenerateWeb3KeyRequest,
+            ) -> VeniceResult<(GenerateWeb3KeyResponse, RateLimitInfo)> {
+                (**self).generate_web3_key(request).await
+            }
+        }
+    )*};
+}
+
+delegate_api_keys_api!(std::sync::Arc<T>, &T);
@@ -1,13 +1,16 @@
 use async_trait::async_trait;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::pin::Pin;
 
 use crate::error::{RateLimitInfo, VeniceResult};
 
 /// Chat message roles
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+///
+/// Roles the SDK does not know are kept as [`ChatRole::Other`], so responses and
+/// streams using a new role still deserialize.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ChatRole {
     /// System message
     System,
@@ -17,22 +20,154 @@ pub enum ChatRole {
     Assistant,
     /// Function message
     Function,
+    /// Tool result message
+    Tool,
+    /// A role the SDK does not know, with its name as sent by the API
+    Other(String),
+}
+
+impl ChatRole {
+    /// Get the role name used by the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChatRole::System => "system",
+            ChatRole::User => "user",
+            ChatRole::Assistant => "assistant",
+            ChatRole::Function => "function",
+            ChatRole::Tool => "tool",
+            ChatRole::Other(role) => role,
+        }
+    }
+}
+
+impl From<&str> for ChatRole {
+    fn from(role: &str) -> Self {
+        match role {
+            "system" => ChatRole::System,
+            "user" => ChatRole::User,
+            "assistant" => ChatRole::Assistant,
+            "function" => ChatRole::Function,
+            "tool" => ChatRole::Tool,
+            other => ChatRole::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ChatRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ChatRole {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatRole {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let role = std::borrow::Cow::<str>::deserialize(deserializer)?;
+        Ok(ChatRole::from(role.as_ref()))
+    }
 }
 
 /// A chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ChatMessage {
     /// The role of the message author
     pub role: ChatRole,
-    /// The content of the message
+    /// The content of the message, empty for assistant messages that only call tools
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub content: String,
     /// Name of the message author
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Tools the assistant asked to call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// ID of the tool call a tool message answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+/// Deserialize a string that the API may send as `null`
+pub(crate) fn deserialize_nullable_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// A tool the model may call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    /// The type of the tool, always "function"
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The function the model may call
+    pub function: FunctionDefinition,
+}
+
+impl ToolDefinition {
+    /// Create a function tool
+    ///
+    /// `parameters` is a JSON schema describing the arguments of the function.
+    pub fn function(name: impl Into<String>, description: impl Into<String>, parameters: serde_json::Value) -> Self {
+        Self {
+            kind: "function".to_string(),
+            function: FunctionDefinition {
+                name: name.into(),
+                description: Some(description.into()),
+                parameters,
+            },
+        }
+    }
+}
+
+/// A function the model may call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionDefinition {
+    /// Name of the function
+    pub name: String,
+    /// Description the model uses to decide when to call the function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON schema of the function arguments
+    pub parameters: serde_json::Value,
+}
+
+/// A tool call requested by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ToolCall {
+    /// ID of the call, echoed in the tool message answering it
+    pub id: String,
+    /// The type of the tool, always "function"
+    #[serde(rename = "type", default = "default_tool_kind")]
+    pub kind: String,
+    /// The function to call
+    pub function: FunctionCall,
+}
+
+/// A function call requested by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct FunctionCall {
+    /// Name of the function
+    pub name: String,
+    /// Arguments of the call, as a JSON string
+    #[serde(default)]
+    pub arguments: String,
+}
+
+fn default_tool_kind() -> String {
+    "function".to_string()
 }
 
 /// Request for chat completions
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ChatCompletionRequest {
     /// ID of the model to use
     pub model: String,
@@ -41,16 +176,221 @@ pub struct ChatCompletionRequest {
     /// Maximum number of tokens to generate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
+    /// Alternative to max_tokens, used by compatible libraries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
     /// Sampling temperature between 0 and 2
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+    /// Nucleus sampling parameter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Penalizes repeated tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Penalizes repeated topics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Only sample from the k most likely tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Penalizes tokens that already appeared; 1 leaves the distribution unchanged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repetition_penalty: Option<f32>,
+    /// Minimum probability of a token, relative to the most likely one, between 0 and 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_p: Option<f32>,
+    /// Generate multiple completion choices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
     /// Whether to stream the results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// Options for streamed responses, such as reporting token usage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    /// Used for deterministic results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// List of stop sequences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// Venice-specific parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub venice_parameters: Option<VeniceParameters>,
+    /// Tools the model may call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+    /// Controls which tool is called: "none", "auto", "required" or a specific function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<serde_json::Value>,
+    /// Format the model must reply in, such as JSON matching a schema
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    /// ID of the end user, forwarded to the API for abuse tracking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Tags for grouping requests in usage analytics
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub request_tags: Vec<String>,
+    /// Additional custom parameters
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Format of the model's reply, set as `response_format`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Plain text
+    Text,
+    /// Any valid JSON object
+    JsonObject,
+    /// JSON matching a schema
+    JsonSchema {
+        /// The schema and its name
+        json_schema: JsonSchemaFormat,
+    },
+}
+
+/// A JSON schema the reply must match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonSchemaFormat {
+    /// Name of the schema
+    pub name: String,
+    /// Whether the reply must match the schema exactly
+    #[serde(default)]
+    pub strict: bool,
+    /// The JSON schema
+    pub schema: serde_json::Value,
+}
+
+impl ResponseFormat {
+    /// Require a reply matching a strict JSON schema
+    pub fn json_schema(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        ResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: name.into(),
+                strict: true,
+                schema,
+            },
+        }
+    }
+}
+
+/// Options for streamed chat completions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamOptions {
+    /// Send a final chunk with the token usage of the whole completion
+    ///
+    /// The chunk has no choices; its usage is in [`ChatCompletionChunk::usage`].
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+/// Venice-specific parameters for chat completion requests
+///
+/// Shared by the request types in `chat`, `models::chat` and `traits::chat`.
+/// Parameters without a dedicated field can be set with [`VeniceParameters::set`].
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::traits::chat::VeniceParameters;
+///
+/// let params = VeniceParameters::new()
+///     .web_search(true)
+///     .include_venice_system_prompt(false)
+///     .character_slug("alan-watts");
+///
+/// let json = serde_json::to_value(&params).unwrap();
+/// assert_eq!(json["enable_web_search"], "on");
+/// assert_eq!(json["character_slug"], "alan-watts");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VeniceParameters {
+    /// Web search mode: "on", "off" or "auto"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_web_search: Option<String>,
+    /// Include Venice's default system prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_venice_system_prompt: Option<bool>,
+    /// Slug of the public character to chat with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_slug: Option<String>,
+    /// Additional Venice parameters
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl VeniceParameters {
+    /// Create an empty set of parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the web search mode ("on", "off" or "auto")
+    pub fn enable_web_search(mut self, mode: impl Into<String>) -> Self {
+        self.enable_web_search = Some(mode.into());
+        self
+    }
+
+    /// Turn web search on or off
+    pub fn web_search(self, enable: bool) -> Self {
+        self.enable_web_search(if enable { "on" } else { "off" })
+    }
+
+    /// Set whether to include Venice's default system prompt
+    pub fn include_venice_system_prompt(mut self, include: bool) -> Self {
+        self.include_venice_system_prompt = Some(include);
+        self
+    }
+
+    /// Set the character to chat with
+    pub fn character_slug(mut self, slug: impl Into<String>) -> Self {
+        self.character_slug = Some(slug.into());
+        self
+    }
+
+    /// Set a parameter by name
+    ///
+    /// Known parameters are stored in their typed field; anything else, including
+    /// known parameters with a value of an unexpected type, goes to `extra`.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) {
+        let key = key.into();
+        let value = value.into();
+        let mut fields = match serde_json::to_value(&*self) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        fields.insert(key.clone(), value.clone());
+        match serde_json::from_value(serde_json::Value::Object(fields)) {
+            Ok(params) => *self = params,
+            Err(_) => {
+                self.extra.insert(key, value);
+            }
+        }
+    }
+
+    /// Set a parameter by name, builder style
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Overlay the parameters set in `other` onto these
+    pub(crate) fn merge(&mut self, other: VeniceParameters) {
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(other) {
+            for (key, value) in fields {
+                self.set(key, value);
+            }
+        }
+    }
 }
 
 /// A chat completion choice
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ChatCompletionChoice {
     /// The completion message
     pub message: ChatMessage,
@@ -61,7 +401,8 @@ pub struct ChatCompletionChoice {
 }
 
 /// Usage information for a chat completion request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ChatCompletionUsage {
     /// The number of prompt tokens used
     pub prompt_tokens: u32,
@@ -72,7 +413,8 @@ pub struct ChatCompletionUsage {
 }
 
 /// Response from the chat completions API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ChatCompletionResponse {
     /// The ID of the chat completion
     pub id: String,
@@ -86,6 +428,54 @@ pub struct ChatCompletionResponse {
     pub choices: Vec<ChatCompletionChoice>,
     /// The usage information for the request
     pub usage: Option<ChatCompletionUsage>,
+    /// Venice-specific response fields, such as web search citations
+    #[serde(default)]
+    pub venice_parameters: Option<VeniceResponseParameters>,
+}
+
+impl ChatCompletionResponse {
+    /// Get the web search results cited in the response
+    ///
+    /// Empty unless the request enabled web search.
+    pub fn web_search_citations(&self) -> &[WebSearchCitation] {
+        self.venice_parameters
+            .as_ref()
+            .map(|params| params.web_search_citations.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// A web search result cited by the model
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebSearchCitation {
+    /// Title of the page
+    #[serde(default)]
+    pub title: String,
+    /// URL of the page
+    #[serde(default)]
+    pub url: String,
+    /// Excerpt of the page content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Publication date, as reported by the search provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    #[cfg_attr(feature = "strict-schema", serde(deserialize_with = "crate::utils::schema_drift::deny_unknown_fields"))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Venice-specific fields of a chat completion response or chunk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VeniceResponseParameters {
+    /// Web search results used to answer the request
+    #[serde(default)]
+    pub web_search_citations: Vec<WebSearchCitation>,
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    #[cfg_attr(feature = "strict-schema", serde(deserialize_with = "crate::utils::schema_drift::deny_unknown_fields"))]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// A streaming chat completion chunk
@@ -101,6 +491,28 @@ pub struct ChatCompletionChunk {
     pub model: String,
     /// The chat completion chunk choices
     pub choices: Vec<ChatCompletionChunkChoice>,
+    /// Venice-specific fields; web search citations arrive in the first chunk
+    #[serde(default)]
+    pub venice_parameters: Option<VeniceResponseParameters>,
+    /// Token usage, reported in the last chunk of a stream
+    #[serde(default)]
+    pub usage: Option<ChatCompletionUsage>,
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    #[cfg_attr(feature = "strict-schema", serde(deserialize_with = "crate::utils::schema_drift::deny_unknown_fields"))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl ChatCompletionChunk {
+    /// Get the web search results cited in the response
+    ///
+    /// Empty unless the request enabled web search.
+    pub fn web_search_citations(&self) -> &[WebSearchCitation] {
+        self.venice_parameters
+            .as_ref()
+            .map(|params| params.web_search_citations.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 /// A streaming chat completion chunk choice
@@ -112,6 +524,10 @@ pub struct ChatCompletionChunkChoice {
     pub delta: ChatCompletionChunkDelta,
     /// The reason the completion stopped, if applicable
     pub finish_reason: Option<String>,
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    #[cfg_attr(feature = "strict-schema", serde(deserialize_with = "crate::utils::schema_drift::deny_unknown_fields"))]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// The delta content for a streaming chat completion chunk
@@ -121,11 +537,56 @@ pub struct ChatCompletionChunkDelta {
     pub role: Option<ChatRole>,
     /// The content of the message, if present in this chunk
     pub content: Option<String>,
+    /// Fragments of the tool calls requested by the model, if present in this chunk
+    #[serde(default)]
+    pub tool_calls: Option<Vec<ChatCompletionChunkToolCall>>,
+    /// Fields not known to this version of the SDK
+    #[serde(flatten)]
+    #[cfg_attr(feature = "strict-schema", serde(deserialize_with = "crate::utils::schema_drift::deny_unknown_fields"))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A fragment of a tool call in a streaming chat completion chunk
+///
+/// The first fragment of a call carries its ID and function name; later fragments
+/// with the same `index` append to its arguments.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ChatCompletionChunkToolCall {
+    /// Position of the call among the calls of the message
+    pub index: u32,
+    /// ID of the call, present in its first fragment
+    #[serde(default)]
+    pub id: Option<String>,
+    /// The type of the tool, present in the first fragment
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    /// The function name and a piece of its arguments
+    #[serde(default)]
+    pub function: Option<FunctionCallDelta>,
+}
+
+/// A fragment of a function call in a streaming chat completion chunk
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct FunctionCallDelta {
+    /// Name of the function, present in the first fragment
+    #[serde(default)]
+    pub name: Option<String>,
+    /// A piece of the JSON arguments of the call
+    #[serde(default)]
+    pub arguments: Option<String>,
 }
 
 /// Type alias for a stream of chat completion chunks
 pub type ChatCompletionStream = Pin<Box<dyn Stream<Item = VeniceResult<ChatCompletionChunk>> + Send>>;
 
+/// Type alias for a stream of untyped chat completion chunks
+///
+/// Each item is the JSON payload of one server-sent event, left as a
+/// `serde_json::Value` so that schema changes never break the stream.
+pub type RawChatCompletionStream = Pin<Box<dyn Stream<Item = VeniceResult<serde_json::Value>> + Send>>;
+
 /// Chat API trait
 #[async_trait]
 pub trait ChatApi {
@@ -146,35 +607,93 @@ pub trait ChatApi {
     ) -> VeniceResult<(ChatCompletionStream, RateLimitInfo)>;
 }
 
+/// Implement [`ChatApi`] for pointer types by delegating to the pointee
+macro_rules! delegate_chat_api {
+    ($($pointer:ty),*) => {$(
+        #[async_trait]
+        impl<T: ChatApi + Send + Sync + ?Sized> ChatApi for $pointer {
+            async fn create_chat_completion(
+                &self,
+                request: ChatCompletionRequest,
+            ) -> VeniceResult<(ChatCompletionResponse, RateLimitInfo)> {
+                (**self).create_chat_completion(request).await
+            }
+
+            async fn create_streaming_chat_completion(
+                &self,
+                request: ChatCompletionRequest,
+            ) -> VeniceResult<(ChatCompletionStream, RateLimitInfo)> {
+                (**self).create_streaming_chat_completion(request).await
+            }
+        }
+    )*};
+}
+
+delegate_chat_api!(std::sync::Arc<T>, &T);
+
+
+impl ChatCompletionRequest {
+    /// Check the request for problems the API would reject
+    ///
+    /// See [`crate::chat::ChatCompletionRequest::validate`] for the checks performed.
+    pub fn validate(&self) -> VeniceResult<()> {
+        crate::chat::validation::ChatRequestFields {
+            model: &self.model,
+            roles: self.messages.iter().map(|m| m.role.as_str()).collect(),
+            max_tokens: self.max_tokens,
+            max_completion_tokens: self.max_completion_tokens,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            frequency_penalty: self.frequency_penalty,
+            presence_penalty: self.presence_penalty,
+            repetition_penalty: self.repetition_penalty,
+            min_p: self.min_p,
+            n: self.n,
+        }
+        .validate()
+    }
+}
 
 /// Helper functions to create chat messages
 impl ChatMessage {
     /// Create a new system message
     pub fn system(content: impl Into<String>) -> Self {
-        Self {
-            role: ChatRole::System,
-            content: content.into(),
-            name: None,
-        }
+        Self::new(ChatRole::System, content)
     }
 
     /// Create a new user message
     pub fn user(content: impl Into<String>) -> Self {
-        Self {
-            role: ChatRole::User,
-            content: content.into(),
-            name: None,
-        }
+        Self::new(ChatRole::User, content)
     }
 
     /// Create a new assistant message
     pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::Assistant, content)
+    }
+
+    /// Create a new tool message answering a tool call
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
         Self {
-            role: ChatRole::Assistant,
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::new(ChatRole::Tool, content)
+        }
+    }
+
+    /// Create a new message with the given role
+    pub fn new(role: ChatRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
             content: content.into(),
             name: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
+
+    /// Get the tool calls requested by an assistant message
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        self.tool_calls.as_deref().unwrap_or_default()
+    }
 }
 
 /// Builder for chat completion requests
@@ -189,14 +708,33 @@ impl ChatCompletionBuilder {
         Self {
             request: ChatCompletionRequest {
                 model: model.into(),
-                messages: Vec::new(),
-                max_tokens: None,
-                temperature: None,
-                stream: None,
+                ..Default::default()
             },
         }
     }
 
+    /// Create a builder for the default chat model of a client
+    ///
+    /// Fails with [`VeniceError::InvalidInput`](crate::VeniceError::InvalidInput) if the
+    /// client has no default chat model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::ChatCompletionBuilder};
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .default_chat_model("llama-3.3-70b")
+    ///     .build()
+    ///     .unwrap();
+    /// let request = ChatCompletionBuilder::default_model(&client).unwrap().add_user("Hello").build();
+    /// assert_eq!(request.model, "llama-3.3-70b");
+    /// ```
+    pub fn default_model(client: &crate::Client) -> VeniceResult<Self> {
+        client.default_chat_model().map(Self::new)
+    }
+
     /// Add a message to the request
     pub fn add_message(mut self, message: ChatMessage) -> Self {
         self.request.messages.push(message);
@@ -219,23 +757,207 @@ impl ChatCompletionBuilder {
         self
     }
 
+    /// Set the alternative maximum number of tokens to generate
+    pub fn max_completion_tokens(mut self, max_completion_tokens: u32) -> Self {
+        self.request.max_completion_tokens = Some(max_completion_tokens);
+        self
+    }
+
     /// Set the sampling temperature
     pub fn temperature(mut self, temperature: f32) -> Self {
         self.request.temperature = Some(temperature);
         self
     }
 
+    /// Set the nucleus sampling parameter
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.request.top_p = Some(top_p);
+        self
+    }
+
+    /// Set the frequency penalty
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.request.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set the presence penalty
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.request.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Only sample from the `top_k` most likely tokens
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.request.top_k = Some(top_k);
+        self
+    }
+
+    /// Set the repetition penalty, greater than 0; values above 1 discourage repetition
+    pub fn repetition_penalty(mut self, repetition_penalty: f32) -> Self {
+        self.request.repetition_penalty = Some(repetition_penalty);
+        self
+    }
+
+    /// Set the minimum probability of a token relative to the most likely one, between 0 and 1
+    pub fn min_p(mut self, min_p: f32) -> Self {
+        self.request.min_p = Some(min_p);
+        self
+    }
+
+    /// Set the number of completion choices to generate
+    pub fn n(mut self, n: u32) -> Self {
+        self.request.n = Some(n);
+        self
+    }
+
+    /// Set the seed for deterministic results
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.request.seed = Some(seed);
+        self
+    }
+
+    /// Set the stop sequences, replacing any added before
+    pub fn stop(mut self, stop: Vec<String>) -> Self {
+        self.request.stop = Some(stop);
+        self
+    }
+
+    /// Add a stop sequence
+    pub fn add_stop(mut self, stop: impl Into<String>) -> Self {
+        self.request.stop.get_or_insert_with(Vec::new).push(stop.into());
+        self
+    }
+
+    /// Set a custom parameter that has no dedicated setter
+    pub fn extra(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.request.extra.insert(key.into(), value.into());
+        self
+    }
+
     /// Enable streaming of results
     pub fn stream(mut self, stream: bool) -> Self {
         self.request.stream = Some(stream);
         self
     }
 
+    /// Set the ID of the end user, forwarded to the API for abuse tracking
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.request.user = Some(user_id.into());
+        self
+    }
+
+    /// Add a tag for grouping requests in usage analytics
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.request.request_tags.contains(&tag) {
+            self.request.request_tags.push(tag);
+        }
+        self
+    }
+
+    /// Ask for a final streamed chunk reporting the token usage of the completion
+    pub fn include_usage(mut self, include_usage: bool) -> Self {
+        self.request.stream_options = Some(StreamOptions { include_usage });
+        self
+    }
+
+    /// Add a tool the model may call
+    pub fn tool(mut self, tool: ToolDefinition) -> Self {
+        self.request.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
+    /// Set the tools the model may call, replacing any added before
+    pub fn tools(mut self, tools: Vec<ToolDefinition>) -> Self {
+        self.request.tools = Some(tools);
+        self
+    }
+
+    /// Set which tool the model calls ("none", "auto", "required" or a specific function)
+    pub fn tool_choice(mut self, tool_choice: impl Into<serde_json::Value>) -> Self {
+        self.request.tool_choice = Some(tool_choice.into());
+        self
+    }
+
+    /// Set the format the model must reply in
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.request.response_format = Some(response_format);
+        self
+    }
+
+    /// Set the Venice-specific parameters
+    pub fn venice_parameters(mut self, venice_parameters: VeniceParameters) -> Self {
+        self.request.venice_parameters = Some(venice_parameters);
+        self
+    }
+
+    /// Turn Venice's web search on or off
+    pub fn web_search(mut self, enable: bool) -> Self {
+        let params = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(params.web_search(enable));
+        self
+    }
+
+    /// Set whether to include Venice's default system prompt
+    pub fn venice_system_prompt(mut self, include: bool) -> Self {
+        let params = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(params.include_venice_system_prompt(include));
+        self
+    }
+
+    /// Set the character to chat with
+    pub fn character_slug(mut self, slug: impl Into<String>) -> Self {
+        let params = self.request.venice_parameters.take().unwrap_or_default();
+        self.request.venice_parameters = Some(params.character_slug(slug));
+        self
+    }
+
+    /// Set the model and Venice parameters from a [`ModelSpec`](crate::chat::ModelSpec)
+    ///
+    /// Parameters in the spec are sent as `venice_parameters` and override any set before.
+    pub fn model_spec(mut self, spec: crate::chat::ModelSpec) -> Self {
+        let (model, spec_params) = spec.into_parts();
+        let mut params = self.request.venice_parameters.take().unwrap_or_default();
+        params.merge(spec_params);
+        self.request.model = model;
+        self.request.venice_parameters = Some(params);
+        self
+    }
+
     /// Build the chat completion request
     pub fn build(self) -> ChatCompletionRequest {
         self.request
     }
     
+    /// Build the request and check it against the context window of its model
+    ///
+    /// Fails if the request is invalid; see [`Client::check_chat_request`](crate::Client::check_chat_request).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::chat::ChatCompletionBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-api-key")?;
+    /// let (request, report) = ChatCompletionBuilder::new("llama-3.3-70b")
+    ///     .add_user("Summarize this book: ...")
+    ///     .max_tokens(4000)
+    ///     .build_checked(&client)
+    ///     .await?;
+    /// for warning in &report.warnings {
+    ///     eprintln!("warning: {}", warning);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_checked(self, client: &crate::Client) -> VeniceResult<(ChatCompletionRequest, crate::chat::ValidationReport)> {
+        let request = self.request;
+        let report = client.check_chat_request(&request).await?;
+        Ok((request, report))
+    }
+    
     /// Build and create a streaming chat completion request
     ///
     /// This is a convenience method that builds the request and calls
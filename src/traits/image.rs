@@ -1,10 +1,14 @@
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 
-use crate::error::{RateLimitInfo, VeniceResult};
+use crate::error::{RateLimitInfo, VeniceError, VeniceResult};
+use crate::image::{ImageConstraints, ImageFormat, ImageMetadata, ImageMetadataWriter, ImageRequestFields, Seed};
 
 /// Request for image generation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageGenerateRequest {
     /// ID of the model to use
     pub model: String,
@@ -30,7 +34,7 @@ pub struct ImageGenerateRequest {
     pub cfg_scale: Option<f32>,
     /// Random seed for reproducible results
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub seed: Option<u64>,
+    pub seed: Option<Seed>,
     /// Strength of LoRA adaptation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lora_strength: Option<u32>,
@@ -43,10 +47,45 @@ pub struct ImageGenerateRequest {
     /// Remove the watermark from the generated image
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hide_watermark: Option<bool>,
+    /// File format of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ImageFormat>,
+    /// Quality of lossy formats, from 1 to 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
+    /// ID of the end user, forwarded to the API for abuse tracking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Tags for grouping requests in usage analytics
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_tags: Vec<String>,
+}
+
+impl ImageGenerateRequest {
+    /// Check the request against the constraints of an image model
+    ///
+    /// Requests are checked automatically before they are sent; use
+    /// [`crate::Client::image_constraints`] to fetch the constraints for a model.
+    pub fn validate_against(&self, constraints: &ImageConstraints) -> VeniceResult<()> {
+        constraints.check(&self.fields())
+    }
+
+    /// The fields checked by image request validation
+    pub(crate) fn fields(&self) -> ImageRequestFields<'_> {
+        ImageRequestFields {
+            model: &self.model,
+            prompt: &self.prompt,
+            width: self.width,
+            height: self.height,
+            steps: self.steps,
+            cfg_scale: self.cfg_scale,
+        }
+    }
 }
 
 /// Response from image generation API
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ImageGenerateResponse {
     /// The ID of the image generation request
     pub id: String,
@@ -66,8 +105,43 @@ pub struct ImageGenerateResponse {
     pub data: Vec<ImageData>,
 }
 
+impl ImageGenerateResponse {
+    /// Decode a generated image
+    pub fn image_bytes(&self, index: usize) -> VeniceResult<Vec<u8>> {
+        let image = self.images.get(index).ok_or_else(|| {
+            VeniceError::InvalidInput(format!("No image at index {} of {}", index, self.images.len()))
+        })?;
+        base64::decode(image)
+            .map_err(|e| VeniceError::InvalidInput(format!("Invalid base64 image data: {}", e)))
+    }
+
+    /// Decode a generated image and write it to a file
+    pub async fn save_image(&self, index: usize, path: impl AsRef<std::path::Path>) -> VeniceResult<()> {
+        tokio::fs::write(path, self.image_bytes(index)?).await?;
+        Ok(())
+    }
+
+    /// Decode a generated image and write it to a file together with the request parameters
+    ///
+    /// If the request did not set a seed, the seed reported in the response is recorded.
+    pub async fn save_image_with_metadata(
+        &self,
+        index: usize,
+        path: impl AsRef<std::path::Path>,
+        writer: &ImageMetadataWriter,
+        request: &ImageGenerateRequest,
+    ) -> VeniceResult<()> {
+        let mut metadata = ImageMetadata::from(request);
+        if metadata.seed.is_none() {
+            metadata.seed = self.request.as_ref().and_then(|details| details.seed);
+        }
+        writer.save(path, &self.image_bytes(index)?, &metadata).await
+    }
+}
+
 /// Request details returned in the response
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ImageGenerateRequestDetails {
     /// The model used for generation
     pub model: String,
@@ -84,11 +158,12 @@ pub struct ImageGenerateRequestDetails {
     pub steps: Option<u32>,
     /// Seed used for generation
     #[serde(default)]
-    pub seed: Option<i64>,
+    pub seed: Option<Seed>,
 }
 
 /// Timing information from the API response
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ImageGenerateTiming {
     /// Total processing time in milliseconds
     #[serde(default)]
@@ -97,6 +172,7 @@ pub struct ImageGenerateTiming {
 
 /// Data for a generated image
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ImageData {
     /// URL to the generated image
     #[serde(default)]
@@ -109,11 +185,12 @@ pub struct ImageData {
     pub revised_prompt: Option<String>,
     /// Seed that was used for generation
     #[serde(default)]
-    pub seed: Option<i64>,
+    pub seed: Option<Seed>,
 }
 
 /// Information about an image style preset
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ImageStyle {
     /// The style preset identifier
     pub id: String,
@@ -135,12 +212,211 @@ pub struct ImageStyle {
 
 /// Response from image styles API
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ListImageStylesResponse {
     /// Array of available style presets or a single style name
     #[serde(rename = "data")]
     pub styles: Vec<String>,
 }
 
+/// Response from image styles API with full style metadata
+///
+/// The API currently returns plain style names; these are turned into an
+/// [`ImageStyle`] whose `id` and `name` are both the style name. When the API
+/// returns style objects, their descriptions and sample prompts are kept.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawImageStylesResponse")]
+pub struct ListImageStylesDetailedResponse {
+    /// The available style presets
+    pub styles: Vec<ImageStyle>,
+}
+
+impl ListImageStylesDetailedResponse {
+    /// Find a style by its identifier or display name
+    pub fn find(&self, preset: &StylePreset) -> Option<&ImageStyle> {
+        self.styles.iter().find(|style| preset.matches(style))
+    }
+
+    /// Check that a style preset is in the list of available styles
+    pub fn validate(&self, preset: &StylePreset) -> VeniceResult<()> {
+        if self.find(preset).is_some() {
+            return Ok(());
+        }
+
+        let available: Vec<&str> = self.styles.iter().map(|style| style.id.as_str()).collect();
+        Err(VeniceError::InvalidInput(format!(
+            "Unknown style preset '{}'. Available styles: {}",
+            preset,
+            available.join(", ")
+        )))
+    }
+
+    /// Find the style a loosely written name refers to
+    ///
+    /// Case, spaces and punctuation are ignored, so `"3d-model"` finds `3D Model`. A query
+    /// that is part of exactly one style name, or that is one typo away from one (a few
+    /// typos for longer names), finds that style as well. Otherwise the error lists the
+    /// closest styles.
+    pub fn search(&self, query: &str) -> VeniceResult<&ImageStyle> {
+        let wanted = normalize_style_name(query);
+        if wanted.is_empty() {
+            return Err(VeniceError::InvalidInput("Style query cannot be empty".to_string()));
+        }
+        let names = |style: &ImageStyle| [normalize_style_name(&style.id), normalize_style_name(&style.name)];
+
+        if let Some(style) = self.styles.iter().find(|style| names(style).contains(&wanted)) {
+            return Ok(style);
+        }
+
+        let containing: Vec<&ImageStyle> = self
+            .styles
+            .iter()
+            .filter(|style| names(style).iter().any(|name| name.contains(&wanted)))
+            .collect();
+        if let [style] = containing[..] {
+            return Ok(style);
+        }
+
+        let mut ranked: Vec<(usize, &ImageStyle)> = self
+            .styles
+            .iter()
+            .map(|style| {
+                let distance = names(style).iter().map(|name| edit_distance(&wanted, name)).min().unwrap_or(usize::MAX);
+                (distance, style)
+            })
+            .collect();
+        ranked.sort_by_key(|(distance, _)| *distance);
+        let max_distance = (wanted.chars().count() / 4).max(1);
+        if let Some(&(best, style)) = ranked.first() {
+            let unique = ranked.get(1).is_none_or(|(distance, _)| *distance > best);
+            if best <= max_distance && unique {
+                return Ok(style);
+            }
+        }
+
+        let suggestions: Vec<&str> = if containing.len() > 1 {
+            containing.iter().map(|style| style.id.as_str()).collect()
+        } else {
+            ranked.iter().take(3).map(|(_, style)| style.id.as_str()).collect()
+        };
+        Err(VeniceError::InvalidInput(format!(
+            "No style preset matches '{}'. Did you mean: {}",
+            query,
+            suggestions.join(", ")
+        )))
+    }
+}
+
+/// Lowercase a style name and drop everything but letters and digits
+fn normalize_style_name(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// The number of single character edits turning `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Raw image styles response, which may contain names or style objects
+#[derive(Deserialize)]
+struct RawImageStylesResponse {
+    data: Vec<RawImageStyle>,
+}
+
+/// A single entry of the raw image styles response
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawImageStyle {
+    Name(String),
+    Detailed(ImageStyle),
+}
+
+impl From<RawImageStylesResponse> for ListImageStylesDetailedResponse {
+    fn from(raw: RawImageStylesResponse) -> Self {
+        let styles = raw
+            .data
+            .into_iter()
+            .map(|entry| match entry {
+                RawImageStyle::Name(name) => ImageStyle {
+                    id: name.clone(),
+                    name,
+                    ..Default::default()
+                },
+                RawImageStyle::Detailed(style) => style,
+            })
+            .collect();
+
+        Self { styles }
+    }
+}
+
+/// An image style preset
+///
+/// Accepts plain strings as well as styles returned by
+/// [`ListImageStylesDetailedResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StylePreset(String);
+
+impl StylePreset {
+    /// Create a new style preset
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Get the style preset name
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check whether this preset refers to the given style
+    ///
+    /// Both the style identifier and display name are compared, ignoring case.
+    pub fn matches(&self, style: &ImageStyle) -> bool {
+        style.id.eq_ignore_ascii_case(&self.0) || style.name.eq_ignore_ascii_case(&self.0)
+    }
+}
+
+impl fmt::Display for StylePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for StylePreset {
+    fn from(name: &str) -> Self {
+        Self(name.to_string())
+    }
+}
+
+impl From<String> for StylePreset {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+impl From<&ImageStyle> for StylePreset {
+    fn from(style: &ImageStyle) -> Self {
+        Self(style.id.clone())
+    }
+}
+
+impl From<StylePreset> for String {
+    fn from(preset: StylePreset) -> Self {
+        preset.0
+    }
+}
+
 /// Request for image upscaling
 #[derive(Debug, Clone, Serialize)]
 pub struct ImageUpscaleRequest {
@@ -158,6 +434,12 @@ pub struct ImageUpscaleRequest {
     /// Return the image as binary data instead of URL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_binary: Option<bool>,
+    /// File format of the image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ImageFormat>,
+    /// Quality of lossy formats, from 1 to 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
 }
 
 /// Response from image upscaling API
@@ -173,8 +455,58 @@ pub struct ImageUpscaleResponse {
     pub data: Vec<UpscaledImageData>,
 }
 
+impl ImageUpscaleResponse {
+    /// Get the format of the image from its MIME type
+    pub fn format(&self) -> Option<ImageFormat> {
+        ImageFormat::from_mime_type(&self.mime_type)
+    }
+
+    /// Get the size of the image in bytes
+    pub fn size(&self) -> usize {
+        self.image_data.len()
+    }
+
+    /// Get the width and height of the image in pixels
+    ///
+    /// Only the header of the image is decoded.
+    #[cfg(feature = "image")]
+    pub fn dimensions(&self) -> VeniceResult<(u32, u32)> {
+        ::image::ImageReader::new(std::io::Cursor::new(&self.image_data))
+            .with_guessed_format()?
+            .into_dimensions()
+            .map_err(|e| VeniceError::parse_error(format!("Cannot read the upscaled image header: {}", e)))
+    }
+
+    /// Get the width of the image in pixels
+    #[cfg(feature = "image")]
+    pub fn width(&self) -> VeniceResult<u32> {
+        self.dimensions().map(|(width, _)| width)
+    }
+
+    /// Get the height of the image in pixels
+    #[cfg(feature = "image")]
+    pub fn height(&self) -> VeniceResult<u32> {
+        self.dimensions().map(|(_, height)| height)
+    }
+
+    /// Write the image to a file, with the extension of its MIME type
+    ///
+    /// The extension of `path` is replaced, so `upscaled` and `upscaled.img` both become
+    /// `upscaled.png` for a PNG image. Paths are kept as given when the MIME type is not
+    /// a known image format. Returns the path written.
+    pub async fn save(&self, path: impl AsRef<std::path::Path>) -> VeniceResult<std::path::PathBuf> {
+        let path = match self.format() {
+            Some(format) => path.as_ref().with_extension(format.extension()),
+            None => path.as_ref().to_path_buf(),
+        };
+        tokio::fs::write(&path, &self.image_data).await?;
+        Ok(path)
+    }
+}
+
 /// Data for an upscaled image (for backward compatibility)
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct UpscaledImageData {
     /// URL to the upscaled image
     #[serde(default)]
@@ -184,6 +516,105 @@ pub struct UpscaledImageData {
     pub b64_json: Option<String>,
 }
 
+/// Progress of a batch image generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Number of requests that have finished
+    pub completed: usize,
+    /// Number of requests that succeeded
+    pub succeeded: usize,
+    /// Number of requests that failed
+    pub failed: usize,
+    /// Total number of requests in the batch
+    pub total: usize,
+}
+
+/// Callback invoked after each request of a batch finishes
+pub type BatchProgressCallback = Arc<dyn Fn(BatchProgress) + Send + Sync>;
+
+/// Options for batch image generation
+#[derive(Clone)]
+pub struct BatchOptions {
+    /// Maximum number of requests in flight at once
+    pub concurrency: usize,
+    /// Callback invoked after each request finishes
+    pub on_progress: Option<BatchProgressCallback>,
+}
+
+impl fmt::Debug for BatchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchOptions")
+            .field("concurrency", &self.concurrency)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            on_progress: None,
+        }
+    }
+}
+
+impl BatchOptions {
+    /// Create batch options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of requests in flight at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set a callback invoked after each request finishes
+    pub fn on_progress(mut self, callback: impl Fn(BatchProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+}
+
+/// The result of one request of a batch
+#[derive(Debug)]
+pub struct ImageBatchItem {
+    /// Position of the request in the batch
+    pub index: usize,
+    /// The outcome of the request
+    pub result: VeniceResult<(ImageGenerateResponse, RateLimitInfo)>,
+}
+
+/// The results of a batch image generation, in request order
+#[derive(Debug)]
+pub struct ImageBatchResponse {
+    /// One item per request
+    pub items: Vec<ImageBatchItem>,
+}
+
+impl ImageBatchResponse {
+    /// Iterate over the successful responses
+    pub fn successes(&self) -> impl Iterator<Item = &ImageGenerateResponse> {
+        self.items
+            .iter()
+            .filter_map(|item| item.result.as_ref().ok().map(|(response, _)| response))
+    }
+
+    /// Iterate over the failed requests, with their position in the batch
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &VeniceError)> {
+        self.items
+            .iter()
+            .filter_map(|item| item.result.as_ref().err().map(|err| (item.index, err)))
+    }
+
+    /// Check whether every request succeeded
+    pub fn all_succeeded(&self) -> bool {
+        self.items.iter().all(|item| item.result.is_ok())
+    }
+}
+
 /// Image API trait
 #[async_trait]
 pub trait ImageApi {
@@ -200,9 +631,129 @@ pub trait ImageApi {
     async fn upscale_image(
         &self,
         request: ImageUpscaleRequest,
-    ) -> VeniceResult<ImageUpscaleResponse>;
+    ) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)>;
+    
+    /// Upscale an image, discarding the rate limit info
+    #[deprecated(since = "0.3.0", note = "use `upscale_image`, which also returns the rate limit info")]
+    async fn upscale_image_response(&self, request: ImageUpscaleRequest) -> VeniceResult<ImageUpscaleResponse>
+    where
+        Self: Sync,
+    {
+        self.upscale_image(request).await.map(|(response, _)| response)
+    }
+    
+    /// Generate images for several requests with bounded concurrency
+    ///
+    /// Requests go through [`ImageApi::generate_image`], so client-side rate limiting
+    /// and retries apply to each of them. A failed request does not stop the batch;
+    /// its error is reported in the corresponding item.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::image::{BatchOptions, ImageApi, ImageGenerateBuilder}};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your-api-key")?;
+    ///     let requests = ["A lighthouse", "A forest", "A city at night"]
+    ///         .iter()
+    ///         .map(|prompt| ImageGenerateBuilder::new("fluently-xl", *prompt).build())
+    ///         .collect();
+    ///
+    ///     let options = BatchOptions::new()
+    ///         .concurrency(2)
+    ///         .on_progress(|p| println!("{}/{} done", p.completed, p.total));
+    ///     let batch = client.generate_images_batch(requests, options).await;
+    ///
+    ///     for (index, err) in batch.failures() {
+    ///         eprintln!("Request {} failed: {}", index, err);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn generate_images_batch(
+        &self,
+        requests: Vec<ImageGenerateRequest>,
+        options: BatchOptions,
+    ) -> ImageBatchResponse
+    where
+        Self: Sync,
+    {
+        let total = requests.len();
+        let mut progress = BatchProgress {
+            completed: 0,
+            succeeded: 0,
+            failed: 0,
+            total,
+        };
+        
+        let mut results = stream::iter(requests.into_iter().enumerate())
+            .map(|(index, request)| async move {
+                ImageBatchItem {
+                    index,
+                    result: self.generate_image(request).await,
+                }
+            })
+            .buffer_unordered(options.concurrency.max(1));
+        
+        let mut items = Vec::with_capacity(total);
+        while let Some(item) = results.next().await {
+            progress.completed += 1;
+            if item.result.is_ok() {
+                progress.succeeded += 1;
+            } else {
+                progress.failed += 1;
+            }
+            if let Some(callback) = &options.on_progress {
+                callback(progress);
+            }
+            items.push(item);
+        }
+        
+        items.sort_by_key(|item| item.index);
+        ImageBatchResponse { items }
+    }
 }
 
+/// Implement [`ImageApi`] for pointer types by delegating to the pointee
+macro_rules! delegate_image_api {
+    ($($pointer:ty),*) => {$(
+        #[async_trait]
+        impl<T: ImageApi + Send + Sync + ?Sized> ImageApi for $pointer {
+            async fn generate_image(
+                &self,
+                request: ImageGenerateRequest,
+            ) -> VeniceResult<(ImageGenerateResponse, RateLimitInfo)> {
+                (**self).generate_image(request).await
+            }
+
+            async fn list_styles(&self) -> VeniceResult<(ListImageStylesResponse, RateLimitInfo)> {
+                (**self).list_styles().await
+            }
+
+            async fn upscale_image(
+                &self,
+                request: ImageUpscaleRequest,
+            ) -> VeniceResult<(ImageUpscaleResponse, RateLimitInfo)> {
+                (**self).upscale_image(request).await
+            }
+
+            async fn generate_images_batch(
+                &self,
+                requests: Vec<ImageGenerateRequest>,
+                options: BatchOptions,
+            ) -> ImageBatchResponse
+            where
+                Self: Sync,
+            {
+                (**self).generate_images_batch(requests, options).await
+            }
+        }
+    )*};
+}
+
+delegate_image_api!(Arc<T>, &T);
 
 /// Builder for image generation requests
 #[derive(Debug, Clone)]
@@ -228,10 +779,21 @@ impl ImageGenerateBuilder {
                 safe_mode: None,
                 return_binary: None,
                 hide_watermark: None,
+                format: None,
+                quality: None,
+                user: None,
+                request_tags: Vec::new(),
             },
         }
     }
 
+    /// Create a builder for the default image model of a client
+    ///
+    /// Fails with [`VeniceError::InvalidInput`] if the client has no default image model.
+    pub fn default_model(client: &crate::Client, prompt: impl Into<String>) -> VeniceResult<Self> {
+        client.default_image_model().map(|model| Self::new(model, prompt))
+    }
+
     /// Set the negative prompt
     pub fn negative_prompt(mut self, value: impl Into<String>) -> Self {
         self.request.negative_prompt = Some(value.into());
@@ -239,8 +801,8 @@ impl ImageGenerateBuilder {
     }
 
     /// Set the style preset
-    pub fn style_preset(mut self, value: impl Into<String>) -> Self {
-        self.request.style_preset = Some(value.into());
+    pub fn style_preset(mut self, value: impl Into<StylePreset>) -> Self {
+        self.request.style_preset = Some(value.into().into());
         self
     }
 
@@ -268,9 +830,9 @@ impl ImageGenerateBuilder {
         self
     }
 
-    /// Set the random seed
-    pub fn seed(mut self, value: u64) -> Self {
-        self.request.seed = Some(value);
+    /// Set the random seed, such as [`Seed::random`]
+    pub fn seed(mut self, value: impl Into<Seed>) -> Self {
+        self.request.seed = Some(value.into());
         self
     }
 
@@ -298,6 +860,33 @@ impl ImageGenerateBuilder {
         self
     }
 
+    /// Set the file format of the generated image
+    pub fn format(mut self, value: ImageFormat) -> Self {
+        self.request.format = Some(value);
+        self
+    }
+
+    /// Set the quality of lossy formats, from 1 to 100
+    pub fn quality(mut self, value: u8) -> Self {
+        self.request.quality = Some(value);
+        self
+    }
+
+    /// Set the ID of the end user, forwarded to the API for abuse tracking
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.request.user = Some(user_id.into());
+        self
+    }
+
+    /// Add a tag for grouping requests in usage analytics
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.request.request_tags.contains(&tag) {
+            self.request.request_tags.push(tag);
+        }
+        self
+    }
+
     /// Build the image generation request
     pub fn build(self) -> ImageGenerateRequest {
         self.request
@@ -320,6 +909,8 @@ impl ImageUpscaleBuilder {
                 image_data: None,
                 scale: None,
                 return_binary: None,
+                format: None,
+                quality: None,
             },
         }
     }
@@ -333,6 +924,8 @@ impl ImageUpscaleBuilder {
                 image_data: Some(image_data.into()),
                 scale: None,
                 return_binary: None,
+                format: None,
+                quality: None,
             },
         }
     }
@@ -349,6 +942,18 @@ impl ImageUpscaleBuilder {
         self
     }
 
+    /// Set the file format of the upscaled image
+    pub fn format(mut self, value: ImageFormat) -> Self {
+        self.request.format = Some(value);
+        self
+    }
+
+    /// Set the quality of lossy formats, from 1 to 100
+    pub fn quality(mut self, value: u8) -> Self {
+        self.request.quality = Some(value);
+        self
+    }
+
     /// Build the image upscaling request
     pub fn build(self) -> ImageUpscaleRequest {
         self.request
@@ -1,4 +1,42 @@
 pub mod models;
 pub mod chat;
 pub mod image;
-pub mod api_keys;
\ No newline at end of file
+pub mod api_keys;
+
+use chat::ChatApi;
+use image::ImageApi;
+
+/// The chat and image APIs behind a single trait object
+///
+/// Every type that implements [`ChatApi`] and [`ImageApi`] implements this trait, so a
+/// client can be handed to code that only knows about `Arc<dyn VeniceApi>`, such as a
+/// dependency injection container or a test double.
+///
+/// [`models::ModelsApi`] and [`api_keys::ApiKeysApi`] are object safe as well, so code
+/// that lists models or manages keys can take `Arc<dyn ModelsApi + Send + Sync>` or
+/// `Arc<dyn ApiKeysApi + Send + Sync>` the same way.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use venice_ai_api_sdk_rust::{
+///     Client,
+///     traits::{VeniceApi, chat::{ChatApi, ChatCompletionBuilder}},
+/// };
+///
+/// async fn greet(api: Arc<dyn VeniceApi>) -> Result<String, Box<dyn std::error::Error>> {
+///     let request = ChatCompletionBuilder::new("llama-3.3-70b").add_user("Hello!").build();
+///     let (response, _) = api.create_chat_completion(request).await?;
+///     Ok(response.choices[0].message.content.clone())
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client: Arc<dyn VeniceApi> = Arc::new(Client::new("your-api-key")?);
+/// println!("{}", greet(client).await?);
+/// # Ok(())
+/// # }
+/// ```
+pub trait VeniceApi: ChatApi + ImageApi + Send + Sync {}
+
+impl<T: ChatApi + ImageApi + Send + Sync + ?Sized> VeniceApi for T {}
@@ -2,11 +2,12 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use crate::error::{RateLimitInfo, VeniceResult};
-use crate::pagination::{PaginationParams, Paginator};
-use crate::models::list::ListModelsRequest;
+use crate::pagination::{BoxPaginator, PaginationParams};
+use crate::models::list::{ListModelsRequest, ModelsDiff};
 
 /// Information about a model
 #[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Model {
     /// The model identifier
     pub id: String,
@@ -30,6 +31,7 @@ pub struct Model {
 
 /// Response from models API
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ListModelsResponse {
     /// Array of model information
     pub data: Vec<Model>,
@@ -37,6 +39,7 @@ pub struct ListModelsResponse {
 
 /// Response from model traits API
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ModelTraitsResponse {
     /// The model identifier
     pub model: String,
@@ -57,7 +60,7 @@ pub trait ModelsApi {
     ) -> VeniceResult<(crate::models::list::ListModelsResponse, RateLimitInfo)>;
     
     /// Create a paginator for listing models
-    fn list_models_paginator(&self, params: PaginationParams) -> impl Paginator<crate::models::list::Model>;
+    fn list_models_paginator(&self, params: PaginationParams) -> BoxPaginator<crate::models::list::Model>;
     
     /// Get the traits supported by a model
     async fn get_model_traits(&self, model_id: &str) -> VeniceResult<(ModelTraitsResponse, RateLimitInfo)>;
@@ -70,4 +73,84 @@ pub trait ModelsApi {
     
     /// Check if a model is compatible with a feature
     async fn is_model_compatible(&self, model_id: &str, feature: &str) -> VeniceResult<bool>;
+
+    /// Compare the current models with an earlier list
+    ///
+    /// Lists the models with default parameters, like [`list_models`](Self::list_models),
+    /// and returns the models added, removed or changed in pricing, capabilities or limits
+    /// since `previous`, along with the current list to compare against next time. To
+    /// compare lists fetched with other parameters, use [`ListModelsResponse::diff`].
+    ///
+    /// [`ListModelsResponse::diff`]: crate::models::list::ListModelsResponse::diff
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use venice_ai_api_sdk_rust::{Client, traits::models::ModelsApi};
+    ///
+    /// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let (mut snapshot, _) = client.list_models_with_params(Default::default()).await?;
+    /// loop {
+    ///     tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+    ///     let (diff, current) = client.diff_models(&snapshot).await?;
+    ///     for model in &diff.added {
+    ///         println!("New model: {}", model.id);
+    ///     }
+    ///     for change in diff.changed.iter().filter(|change| change.pricing_changed()) {
+    ///         println!("Price change: {} {:?} -> {:?}", change.id(), change.previous.pricing, change.current.pricing);
+    ///     }
+    ///     snapshot = current;
+    /// }
+    /// # }
+    /// ```
+    async fn diff_models(
+        &self,
+        previous: &crate::models::list::ListModelsResponse,
+    ) -> VeniceResult<(ModelsDiff, crate::models::list::ListModelsResponse)>
+    where
+        Self: Sync,
+    {
+        let (current, _) = self.list_models_with_params(ListModelsRequest::default()).await?;
+        Ok((current.diff(previous), current))
+    }
+}
+
+/// Implement [`ModelsApi`] for pointer types by delegating to the pointee
+macro_rules! delegate_models_api {
+    ($($pointer:ty),*) => {$(
+        #[async_trait]
+        impl<T: ModelsApi + Send + Sync + ?Sized> ModelsApi for $pointer {
+            async fn list_models(&self) -> VeniceResult<(ListModelsResponse, RateLimitInfo)> {
+                (**self).list_models().await
+            }
+
+            async fn list_models_with_params(
+                &self,
+                request: ListModelsRequest,
+            ) -> VeniceResult<(crate::models::list::ListModelsResponse, RateLimitInfo)> {
+                (**self).list_models_with_params(request).await
+            }
+
+            fn list_models_paginator(&self, params: PaginationParams) -> BoxPaginator<crate::models::list::Model> {
+                (**self).list_models_paginator(params)
+            }
+
+            async fn get_model_traits(&self, model_id: &str) -> VeniceResult<(ModelTraitsResponse, RateLimitInfo)> {
+                (**self).get_model_traits(model_id).await
+            }
+
+            async fn get_model_traits_internal(
+                &self,
+                request: Option<crate::models::traits::ModelTraitsRequest>,
+            ) -> VeniceResult<(crate::models::traits::ModelTraitsResponse, RateLimitInfo)> {
+                (**self).get_model_traits_internal(request).await
+            }
+
+            async fn is_model_compatible(&self, model_id: &str, feature: &str) -> VeniceResult<bool> {
+                (**self).is_model_compatible(model_id, feature).await
+            }
+        }
+    )*};
 }
+
+delegate_models_api!(std::sync::Arc<T>, &T);
@@ -1,4 +1,6 @@
 //! Shared utilities for the Venice AI API SDK
 
+pub mod schema_drift;
 pub mod serialization;
+pub mod text;
 pub mod validation;
\ No newline at end of file
@@ -0,0 +1,283 @@
+//! Detecting drift between API responses and the SDK's response types
+//!
+//! [`check`] deserializes a live response into a response type and reports the fields the
+//! API sent that the type does not declare, and the fields the type declares that the API
+//! did not send. Run it against fresh responses to notice upstream API changes before
+//! they turn into parse errors.
+//!
+//! Fields are matched per struct. Types that keep unknown fields in an `extra` map are
+//! deserialized as maps, so their fields and the fields nested below them are not
+//! checked; build with the `strict-schema` feature to reject unknown fields in those too.
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::error::{VeniceError, VeniceResult};
+
+/// Fields that differ between a response and the type it is deserialized into
+///
+/// Paths use dots between fields and `[]` for array elements, so a field missing from
+/// every element of a list is reported once, such as `data[].pricing`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    /// Fields sent by the API that the type does not declare
+    pub unknown_fields: BTreeSet<String>,
+    /// Fields declared by the type that the API did not send
+    pub missing_fields: BTreeSet<String>,
+}
+
+impl SchemaDrift {
+    /// Check whether the response matched the type exactly
+    pub fn is_empty(&self) -> bool {
+        self.unknown_fields.is_empty() && self.missing_fields.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no schema drift");
+        }
+        let list = |fields: &BTreeSet<String>| fields.iter().map(String::as_str).collect::<Vec<_>>().join(", ");
+        let mut parts = Vec::new();
+        if !self.unknown_fields.is_empty() {
+            parts.push(format!("unknown fields: {}", list(&self.unknown_fields)));
+        }
+        if !self.missing_fields.is_empty() {
+            parts.push(format!("missing fields: {}", list(&self.missing_fields)));
+        }
+        f.write_str(&parts.join("; "))
+    }
+}
+
+/// Compare a response body with the type it is deserialized into
+///
+/// Fails if the body does not deserialize into `T` at all, which in `strict-schema`
+/// builds includes bodies with unknown fields.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::models::list::ListModelsResponse;
+/// use venice_ai_api_sdk_rust::util::schema_drift;
+///
+/// let live = serde_json::json!({
+///     "object": "list",
+///     "data": [],
+///     "has_more": false
+/// });
+///
+/// let drift = schema_drift::check::<ListModelsResponse>(&live).unwrap();
+/// assert!(drift.missing_fields.contains("next_cursor"));
+/// assert!(drift.unknown_fields.is_empty());
+/// ```
+pub fn check<T: DeserializeOwned>(value: &Value) -> VeniceResult<SchemaDrift> {
+    let drift = RefCell::new(SchemaDrift::default());
+    T::deserialize(Tracked { value, path: String::new(), drift: &drift })
+        .map_err(|e| VeniceError::parse_error(e.to_string()))?;
+    Ok(drift.into_inner())
+}
+
+/// Reject the fields collected by an `extra` map, in `strict-schema` builds
+#[cfg(feature = "strict-schema")]
+pub(crate) fn deny_unknown_fields<'de, D>(
+    deserializer: D,
+) -> Result<std::collections::HashMap<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let extra = std::collections::HashMap::<String, Value>::deserialize(deserializer)?;
+    if extra.is_empty() {
+        return Ok(extra);
+    }
+    let mut fields: Vec<&str> = extra.keys().map(String::as_str).collect();
+    fields.sort_unstable();
+    Err(de::Error::custom(format!("unknown fields: {}", fields.join(", "))))
+}
+
+/// A deserializer over a JSON value that records the fields of every struct it visits
+struct Tracked<'de> {
+    value: &'de Value,
+    path: String,
+    drift: &'de RefCell<SchemaDrift>,
+}
+
+impl<'de> Tracked<'de> {
+    fn child(&self, value: &'de Value, segment: &str) -> Self {
+        let path = match (self.path.is_empty(), segment) {
+            (_, "[]") => format!("{}[]", self.path),
+            (true, _) => segment.to_string(),
+            (false, _) => format!("{}.{}", self.path, segment),
+        };
+        Tracked { value, path, drift: self.drift }
+    }
+
+    fn record(&self, object: &Map<String, Value>, fields: &[&str]) {
+        let mut drift = self.drift.borrow_mut();
+        let path = |field: &str| self.child(&Value::Null, field).path;
+        for key in object.keys().filter(|key| !fields.contains(&key.as_str())) {
+            drift.unknown_fields.insert(path(key));
+        }
+        for field in fields.iter().filter(|field| !object.contains_key(**field)) {
+            drift.missing_fields.insert(path(field));
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(object) => visitor.visit_map(TrackedMap::new(self, object)),
+            Value::Array(items) => visitor.visit_seq(TrackedSeq { parent: self, items: items.iter() }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(object) => {
+                self.record(object, fields);
+                visitor.visit_map(TrackedMap::new(self, object))
+            }
+            value => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+/// The entries of a JSON object, with tracked values
+struct TrackedMap<'de> {
+    parent: Tracked<'de>,
+    entries: serde_json::map::Iter<'de>,
+    value: Option<(&'de str, &'de Value)>,
+}
+
+impl<'de> TrackedMap<'de> {
+    fn new(parent: Tracked<'de>, object: &'de Map<String, Value>) -> Self {
+        Self { parent, entries: object.iter(), value: None }
+    }
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                seed.deserialize(BorrowedStrDeserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(self.parent.child(value, key))
+    }
+}
+
+/// The elements of a JSON array, with tracked values
+struct TrackedSeq<'de> {
+    parent: Tracked<'de>,
+    items: std::slice::Iter<'de, Value>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'de> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(self.parent.child(item, "[]")).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::list::ListModelsResponse;
+
+    #[test]
+    #[cfg(not(feature = "strict-schema"))]
+    fn test_reports_unknown_and_missing_fields() {
+        let live = serde_json::json!({
+            "object": "list",
+            "data": [
+                {"id": "a", "object": "model", "owned_by": "venice", "type": "text"},
+                {"id": "b", "object": "model", "owned_by": "venice", "type": "text"}
+            ],
+            "has_more": false
+        });
+
+        let drift = check::<ListModelsResponse>(&live).unwrap();
+        assert!(drift.unknown_fields.contains("data[].type"));
+        assert!(drift.missing_fields.contains("next_cursor"));
+        assert!(drift.missing_fields.contains("data[].pricing"));
+        assert_eq!(drift.unknown_fields.len(), 1);
+        assert!(drift.to_string().starts_with("unknown fields: data[].type; missing fields: "));
+    }
+
+    #[test]
+    fn test_fails_on_incompatible_response() {
+        let live = serde_json::json!({"object": "list", "data": "nope"});
+        assert!(check::<ListModelsResponse>(&live).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+//! Text chunking utilities for the Venice AI API SDK
+//!
+//! [`chunk_text`] splits a document into pieces that fit a token budget, for
+//! map-reduce pipelines that summarize or query each piece separately. Token counts
+//! are estimated at four characters per token, the same estimate the SDK uses for
+//! cost pre-flight checks, so leave some headroom below the context window.
+
+/// Characters per estimated token
+const CHARS_PER_TOKEN: usize = 4;
+
+/// How [`chunk_text`] splits a document
+///
+/// Every strategy packs as many whole pieces as fit in `max_tokens`, and repeats up
+/// to `overlap` tokens of trailing pieces at the start of the next chunk so context is
+/// not lost at the cut. Pieces too long for a chunk on their own are split further:
+/// separated pieces into sentences, sentences into words, and words into characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkingStrategy {
+    /// Split between words
+    Tokens {
+        /// Maximum estimated tokens of a chunk
+        max_tokens: usize,
+        /// Estimated tokens repeated from the end of the previous chunk
+        overlap: usize,
+    },
+    /// Split between sentences and paragraphs
+    Sentences {
+        /// Maximum estimated tokens of a chunk
+        max_tokens: usize,
+        /// Estimated tokens repeated from the end of the previous chunk
+        overlap: usize,
+    },
+    /// Split after a separator, such as `"\n\n"` or a Markdown heading
+    Separator {
+        /// The separator, kept at the end of the piece it ends
+        separator: String,
+        /// Maximum estimated tokens of a chunk
+        max_tokens: usize,
+        /// Estimated tokens repeated from the end of the previous chunk
+        overlap: usize,
+    },
+}
+
+impl ChunkingStrategy {
+    /// Split between words into chunks of at most `max_tokens`, without overlap
+    pub fn tokens(max_tokens: usize) -> Self {
+        Self::Tokens { max_tokens, overlap: 0 }
+    }
+
+    /// Split between sentences into chunks of at most `max_tokens`, without overlap
+    pub fn sentences(max_tokens: usize) -> Self {
+        Self::Sentences { max_tokens, overlap: 0 }
+    }
+
+    /// Split after `separator` into chunks of at most `max_tokens`, without overlap
+    pub fn separator(separator: impl Into<String>, max_tokens: usize) -> Self {
+        Self::Separator {
+            separator: separator.into(),
+            max_tokens,
+            overlap: 0,
+        }
+    }
+
+    /// Repeat up to `overlap` tokens of the previous chunk at the start of the next
+    pub fn overlap(mut self, overlap: usize) -> Self {
+        match &mut self {
+            Self::Tokens { overlap: o, .. } | Self::Sentences { overlap: o, .. } | Self::Separator { overlap: o, .. } => {
+                *o = overlap;
+            }
+        }
+        self
+    }
+
+    /// Get the maximum estimated tokens of a chunk
+    pub fn max_tokens(&self) -> usize {
+        self.limits().0
+    }
+
+    fn limits(&self) -> (usize, usize) {
+        let (max_tokens, overlap) = match self {
+            Self::Tokens { max_tokens, overlap }
+            | Self::Sentences { max_tokens, overlap }
+            | Self::Separator { max_tokens, overlap, .. } => (*max_tokens, *overlap),
+        };
+        let max_tokens = max_tokens.max(1);
+        (max_tokens, overlap.min(max_tokens - 1))
+    }
+}
+
+/// Estimate the number of tokens in a piece of text
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Split a document into chunks of at most the strategy's token budget
+///
+/// Chunks are slices of `text` with surrounding whitespace trimmed; empty chunks are
+/// skipped. A `max_tokens` of 0 is treated as 1, and the overlap is capped below
+/// `max_tokens` so every chunk moves forward.
+///
+/// # Examples
+///
+/// ```
+/// use venice_ai_api_sdk_rust::util::text::{chunk_text, estimate_tokens, ChunkingStrategy};
+///
+/// let document = "The first sentence is here. The second one follows. A third closes the paragraph.";
+/// let chunks = chunk_text(document, &ChunkingStrategy::sentences(10));
+/// assert_eq!(chunks, ["The first sentence is here.", "The second one follows.", "A third closes the paragraph."]);
+/// assert!(chunks.iter().all(|chunk| estimate_tokens(chunk) <= 10));
+/// ```
+pub fn chunk_text<'a>(text: &'a str, strategy: &ChunkingStrategy) -> Vec<&'a str> {
+    let (max_tokens, overlap) = strategy.limits();
+    let max_chars = max_tokens * CHARS_PER_TOKEN;
+    let level = match strategy {
+        ChunkingStrategy::Separator { separator, .. } if !separator.is_empty() => Level::Separator(separator),
+        ChunkingStrategy::Separator { .. } | ChunkingStrategy::Sentences { .. } => Level::Sentences,
+        ChunkingStrategy::Tokens { .. } => Level::Words,
+    };
+    let mut pieces = Vec::new();
+    split(text, 0, level, max_chars, &mut pieces);
+    pack(text, &pieces, max_tokens, overlap)
+}
+
+/// How finely a piece of text is split
+#[derive(Clone, Copy)]
+enum Level<'s> {
+    Separator(&'s str),
+    Sentences,
+    Words,
+    Chars,
+}
+
+impl Level<'_> {
+    fn finer(self) -> Self {
+        match self {
+            Level::Separator(_) => Level::Sentences,
+            Level::Sentences => Level::Words,
+            Level::Words | Level::Chars => Level::Chars,
+        }
+    }
+}
+
+/// A piece of the document: its byte range and number of characters
+#[derive(Clone, Copy)]
+struct Piece {
+    start: usize,
+    end: usize,
+    chars: usize,
+}
+
+/// Split `text`, found at byte `offset` of the document, into pieces of at most `max_chars`
+fn split(text: &str, offset: usize, level: Level<'_>, max_chars: usize, pieces: &mut Vec<Piece>) {
+    let mut start = 0;
+    for end in boundaries(text, level, max_chars) {
+        let piece = &text[start..end];
+        let chars = piece.chars().count();
+        if chars > max_chars && !matches!(level, Level::Chars) {
+            split(piece, offset + start, level.finer(), max_chars, pieces);
+        } else if chars > 0 {
+            pieces.push(Piece {
+                start: offset + start,
+                end: offset + end,
+                chars,
+            });
+        }
+        start = end;
+    }
+}
+
+/// Byte offsets at which `text` is cut, ending with its length
+fn boundaries(text: &str, level: Level<'_>, max_chars: usize) -> Vec<usize> {
+    let mut cuts = Vec::new();
+    match level {
+        Level::Separator(separator) => {
+            cuts.extend(text.match_indices(separator).map(|(index, _)| index + separator.len()));
+        }
+        Level::Sentences => {
+            let mut chars = text.char_indices().peekable();
+            while let Some((index, c)) = chars.next() {
+                let Some(&(next_index, next)) = chars.peek() else { break };
+                let ends_sentence = matches!(c, '.' | '!' | '?') && next.is_whitespace();
+                let ends_paragraph = c == '\n' && next == '\n';
+                if ends_sentence || ends_paragraph {
+                    // Keep the whitespace after the sentence with it
+                    let mut end = next_index;
+                    while let Some(&(i, w)) = chars.peek() {
+                        if !w.is_whitespace() {
+                            break;
+                        }
+                        end = i + w.len_utf8();
+                        chars.next();
+                    }
+                    cuts.push(end.max(index + c.len_utf8()));
+                }
+            }
+        }
+        Level::Words => {
+            let mut in_space = false;
+            for (index, c) in text.char_indices() {
+                if in_space && !c.is_whitespace() {
+                    cuts.push(index);
+                }
+                in_space = c.is_whitespace();
+            }
+        }
+        Level::Chars => {
+            cuts.extend(text.char_indices().map(|(index, _)| index).skip(max_chars).step_by(max_chars));
+        }
+    }
+    cuts.retain(|&cut| cut > 0 && cut < text.len());
+    cuts.push(text.len());
+    cuts
+}
+
+/// Pack consecutive pieces into chunks of at most `max_tokens`, overlapping by up to `overlap`
+fn pack<'a>(text: &'a str, pieces: &[Piece], max_tokens: usize, overlap: usize) -> Vec<&'a str> {
+    let tokens = |from: usize, to: usize| {
+        pieces[from..=to].iter().map(|piece| piece.chars).sum::<usize>().div_ceil(CHARS_PER_TOKEN)
+    };
+    let mut chunks = Vec::new();
+    let mut first = 0;
+    while first < pieces.len() {
+        let mut last = first;
+        while last + 1 < pieces.len() && tokens(first, last + 1) <= max_tokens {
+            last += 1;
+        }
+        let chunk = text[pieces[first].start..pieces[last].end].trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        if last + 1 == pieces.len() {
+            break;
+        }
+        let mut next = last + 1;
+        while next - 1 > first && tokens(next - 1, last) <= overlap {
+            next -= 1;
+        }
+        first = next;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_fit_and_overlap() {
+        let text = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+        let chunks = chunk_text(text, &ChunkingStrategy::tokens(5));
+        assert_eq!(chunks, ["alpha beta gamma", "delta epsilon zeta", "eta theta iota kappa"]);
+
+        let chunks = chunk_text(text, &ChunkingStrategy::tokens(5).overlap(2));
+        assert_eq!(chunks[0], "alpha beta gamma");
+        assert!(chunks[1].starts_with("gamma"));
+        assert!(chunks.iter().all(|chunk| estimate_tokens(chunk) <= 5));
+        assert!(chunks.last().unwrap().ends_with("kappa"));
+    }
+
+    #[test]
+    fn test_separators_fall_back_to_finer_splits() {
+        let text = "# Intro\nShort.\n\n# Body\nA much longer section. It has two sentences.\n\n# End\nDone.";
+        let chunks = chunk_text(text, &ChunkingStrategy::separator("\n\n", 8));
+        assert_eq!(chunks, [
+            "# Intro\nShort.",
+            "# Body\nA much longer section.",
+            "It has two sentences.",
+            "# End\nDone.",
+        ]);
+
+        // A word longer than a chunk is cut between characters
+        let chunks = chunk_text("ééééééééé", &ChunkingStrategy::tokens(1));
+        assert_eq!(chunks, ["éééé", "éééé", "é"]);
+        assert!(chunk_text("   ", &ChunkingStrategy::sentences(10)).is_empty());
+    }
+}
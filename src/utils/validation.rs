@@ -32,6 +32,65 @@ pub fn validate_non_empty_vec<T>(value: &[T], field_name: &str) -> Result<(), St
     Ok(())
 }
 
+/// Validate an Ethereum-style wallet address
+///
+/// The address must be `0x` followed by 40 hex digits. Mixed-case addresses must
+/// carry a valid EIP-55 checksum; all-lowercase or all-uppercase addresses are accepted
+/// without one.
+pub fn validate_wallet_address(value: &str, field_name: &str) -> Result<(), String> {
+    let hex_digits = value
+        .strip_prefix("0x")
+        .filter(|digits| digits.len() == 40 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("{} must be 0x followed by 40 hex digits, got {}", field_name, value))?;
+
+    let has_lower = hex_digits.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_digits.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && checksum_address(hex_digits) != hex_digits {
+        return Err(format!("{} has an invalid EIP-55 checksum: {}", field_name, value));
+    }
+    Ok(())
+}
+
+/// Apply the EIP-55 mixed-case checksum to 40 hex digits
+fn checksum_address(hex_digits: &str) -> String {
+    let lower = hex_digits.to_ascii_lowercase();
+    let hash = keccak256(lower.as_bytes());
+    lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect()
+}
+
+/// Compute the Keccak-256 hash used by Ethereum
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    const RATE: usize = 136;
+    let mut state = [0u64; 25];
+
+    let mut padded = data.to_vec();
+    padded.push(0x01);
+    padded.resize(padded.len().div_ceil(RATE) * RATE, 0);
+    *padded.last_mut().unwrap_or(&mut 0) |= 0x80;
+
+    for block in padded.chunks(RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(bytes);
+            *lane ^= u64::from_le_bytes(word);
+        }
+        keccak::f1600(&mut state);
+    }
+
+    let mut hash = [0u8; 32];
+    for (bytes, lane) in hash.chunks_mut(8).zip(state.iter()) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    hash
+}
+
 /// Validate that a string matches a regex pattern
 #[cfg(feature = "regex")]
 pub fn validate_regex_match(value: &str, pattern: &str, field_name: &str) -> Result<(), String> {
@@ -4,9 +4,13 @@
 //! Webhooks are HTTP callbacks that are triggered when certain events occur in the Venice.ai system.
 //! To ensure that webhook requests are genuinely from Venice.ai, they include a signature that can be verified.
 
+use std::time::Duration;
+
 use crate::error::{VeniceError, VeniceResult};
 use crate::services::webhook::WebhookService;
 
+pub use crate::services::webhook::DEFAULT_WEBHOOK_TOLERANCE;
+
 /// Verifies a webhook signature from Venice.ai
 ///
 /// This function verifies that a webhook request is genuinely from Venice.ai by checking
@@ -62,6 +66,97 @@ pub fn verify_webhook_signature(
     }
 }
 
+/// Options for [`verify_webhook_signature_with_options`]
+///
+/// By default, webhooks whose timestamp is more than [`DEFAULT_WEBHOOK_TOLERANCE`] away
+/// from the current time are rejected as replays.
+#[derive(Debug, Clone)]
+pub struct WebhookVerifyOptions {
+    secrets: Vec<String>,
+    tolerance: Option<Duration>,
+}
+
+impl WebhookVerifyOptions {
+    /// Create options that verify against a single secret
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secrets: vec![secret.into()],
+            tolerance: Some(DEFAULT_WEBHOOK_TOLERANCE),
+        }
+    }
+
+    /// Also accept signatures made with another secret, such as the previous one during rotation
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secrets.push(secret.into());
+        self
+    }
+
+    /// Set how far the webhook timestamp may be from the current time
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Accept webhooks regardless of their timestamp
+    pub fn without_tolerance(mut self) -> Self {
+        self.tolerance = None;
+        self
+    }
+
+    /// Get the accepted secrets
+    pub fn secrets(&self) -> &[String] {
+        &self.secrets
+    }
+
+    /// Get the timestamp tolerance, if timestamps are checked
+    pub fn tolerance(&self) -> Option<Duration> {
+        self.tolerance
+    }
+}
+
+/// Verifies a webhook signature from Venice.ai with replay protection and secret rotation
+///
+/// The signature is accepted if it matches any of the secrets in `options` and the
+/// timestamp is within the tolerance of the current time.
+///
+/// # Returns
+///
+/// * `Ok(true)` if the signature is valid and the timestamp is fresh
+/// * `Ok(false)` if the signature is invalid or the timestamp is stale or malformed
+/// * `Err(VeniceError)` if an error occurs during verification
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use venice_ai_api_sdk_rust::webhooks::{verify_webhook_signature_with_options, WebhookVerifyOptions};
+///
+/// fn is_genuine(body: &[u8], signature: &str, timestamp: &str) -> Result<bool, Box<dyn std::error::Error>> {
+///     let options = WebhookVerifyOptions::new(std::env::var("VENICE_WEBHOOK_SECRET")?)
+///         .with_secret(std::env::var("VENICE_WEBHOOK_SECRET_PREVIOUS")?)
+///         .with_tolerance(Duration::from_secs(120));
+///
+///     Ok(verify_webhook_signature_with_options(body, signature, timestamp, &options)?)
+/// }
+/// ```
+pub fn verify_webhook_signature_with_options(
+    payload: &[u8],
+    signature: &str,
+    timestamp: &str,
+    options: &WebhookVerifyOptions,
+) -> VeniceResult<bool> {
+    let mut webhook_service = WebhookService::new();
+    if let Some(tolerance) = options.tolerance {
+        webhook_service = webhook_service.with_tolerance(tolerance);
+    }
+
+    match webhook_service.verify_signature_with_secrets(signature, timestamp, payload, &options.secrets) {
+        Ok(()) => Ok(true),
+        Err(VeniceError::InvalidWebhookSignature(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 /// Extracts the Venice.ai webhook signature and timestamp from headers
 ///
 /// This function extracts the webhook signature and timestamp from the request headers.
@@ -153,6 +248,33 @@ mod tests {
         assert!(!verify_webhook_signature(payload, invalid_signature, timestamp, secret).unwrap());
     }
     
+    #[test]
+    fn test_verify_webhook_signature_with_options() {
+        let payload = b"test_payload";
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let sign = |secret: &str, timestamp: &str| {
+            let message = format!("{}:{}", timestamp, String::from_utf8_lossy(payload));
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(message.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        };
+
+        let options = WebhookVerifyOptions::new("new_secret").with_secret("old_secret");
+
+        let fresh = now.to_string();
+        assert!(verify_webhook_signature_with_options(payload, &sign("old_secret", &fresh), &fresh, &options).unwrap());
+        assert!(!verify_webhook_signature_with_options(payload, &sign("other_secret", &fresh), &fresh, &options).unwrap());
+
+        let stale = (now - 3600).to_string();
+        let signature = sign("new_secret", &stale);
+        assert!(!verify_webhook_signature_with_options(payload, &signature, &stale, &options).unwrap());
+        assert!(verify_webhook_signature_with_options(payload, &signature, &stale, &options.clone().without_tolerance()).unwrap());
+    }
+
     #[test]
     fn test_get_webhook_headers() {
         let mut headers = std::collections::HashMap::new();
@@ -1,2 +1,4 @@
 // Include streaming tests
-pub mod streaming_tests;
\ No newline at end of file
+pub mod streaming_tests;
+// Compare live responses with the response types
+pub mod schema_drift_tests;
@@ -0,0 +1,35 @@
+use std::env;
+use venice_ai_api_sdk_rust::{
+    Client,
+    api_keys::list::ListApiKeysResponse,
+    models::list::ListModelsResponse,
+    util::schema_drift,
+};
+
+// Compare live responses with the SDK's response types
+//
+// Ignored by default, since the API may add fields at any time. Run it with
+// `cargo test --test integration_tests schema_drift -- --ignored` to check for drift.
+#[tokio::test]
+#[ignore]
+async fn test_schema_drift() {
+    let api_key = match env::var("VENICE_API_KEY") {
+        Ok(api_key) => api_key,
+        Err(_) => {
+            eprintln!("VENICE_API_KEY environment variable not set, skipping schema drift check");
+            return;
+        }
+    };
+    let client = Client::new(api_key).expect("Failed to create client");
+
+    let (models, _) = client.get::<serde_json::Value>("models").await.expect("Failed to list models");
+    let drift = schema_drift::check::<ListModelsResponse>(&models).expect("Models no longer parse");
+    println!("models: {}", drift);
+
+    let (api_keys, _) = client.get::<serde_json::Value>("api-keys").await.expect("Failed to list API keys");
+    let api_keys_drift = schema_drift::check::<ListApiKeysResponse>(&api_keys).expect("API keys no longer parse");
+    println!("api_keys: {}", api_keys_drift);
+
+    assert!(drift.unknown_fields.is_empty(), "models: {}", drift);
+    assert!(api_keys_drift.unknown_fields.is_empty(), "api_keys: {}", api_keys_drift);
+}
@@ -2,7 +2,7 @@ use std::env;
 use futures::StreamExt;
 use venice_ai_api_sdk_rust::{
     Client,
-    traits::chat::{ChatApi, ChatCompletionBuilder, ChatCompletionChunk},
+    traits::chat::{ChatCompletionBuilder, ChatCompletionChunk},
     VeniceError,
 };
 
@@ -42,7 +42,7 @@ async fn test_streaming_chat_completion() {
         .build();
 
     // Send the streaming request
-    let result = client.create_streaming_chat_completion(request).await;
+    let result = client.create_streaming_chat_completion(request.into()).await;
     assert!(result.is_ok(), "Failed to create streaming chat completion");
 
     let (stream, rate_limit) = result.unwrap();
@@ -134,7 +134,7 @@ async fn test_streaming_builder_convenience_method() {
                 if chunk.choices.is_empty() {
                     None
                 } else {
-                    chunk.choices[0].delta.content.as_ref().map(|s| s.as_str())
+                    chunk.choices[0].delta.content.as_deref()
                 }
             })
             .collect::<Vec<&str>>()
@@ -146,7 +146,10 @@ async fn test_streaming_builder_convenience_method() {
     println!("Received {} chunks from streaming API", chunks.len());
 }
 
+// Talks to the live API, so it is ignored by default. Run it with
+// `cargo test --test integration_tests streaming_error -- --ignored`.
 #[tokio::test]
+#[ignore]
 async fn test_streaming_error_handling() {
     // Create a client with an invalid API key
     let client = Client::new("invalid_api_key").unwrap();
@@ -158,12 +161,12 @@ async fn test_streaming_error_handling() {
         .build();
 
     // Send the streaming request and expect an error
-    let result = client.create_streaming_chat_completion(request).await;
+    let result = client.create_streaming_chat_completion(request.into()).await;
     
     // Verify we got the expected error
-    assert!(result.is_err(), "Expected error but got success");
+    let err = result.err().expect("Expected error but got success");
     
-    if let Err(VeniceError::ApiError { code, message, .. }) = result {
+    if let VeniceError::ApiError { code, message, .. } = err.root() {
         assert_eq!(code, "api_error", "Unexpected error code: {}", code);
         assert!(message.contains("Authentication failed") || message.contains("auth"),
                 "Unexpected error message: {}", message);
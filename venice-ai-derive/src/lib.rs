@@ -0,0 +1,216 @@
+//! Derive macros for the Venice.ai API SDK
+//!
+//! Use these through the `derive` feature of `venice-ai-api-sdk-rust`, which re-exports
+//! them next to the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Meta};
+
+/// Derive `VeniceSchema`, generating a JSON schema from a struct or a fieldless enum
+///
+/// Structs with named fields become objects that require every field and allow no
+/// others. Doc comments become descriptions. The serde attributes `rename`,
+/// `rename_all` and `skip` are honored, so the schema matches what serde deserializes.
+#[proc_macro_derive(VeniceSchema, attributes(serde))]
+pub fn derive_venice_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = quote!(::venice_ai_api_sdk_rust::schema);
+    let name = &input.ident;
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    let description = option_tokens(doc_comment(&input.attrs));
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let mut properties = Vec::new();
+                for field in &fields.named {
+                    let attrs = SerdeAttrs::parse(&field.attrs)?;
+                    if attrs.skip {
+                        continue;
+                    }
+                    let ident = field.ident.as_ref().expect("named field");
+                    let key = attrs.rename.unwrap_or_else(|| {
+                        rename(ident.to_string().trim_start_matches("r#"), container.rename_all.as_deref())
+                    });
+                    let ty = &field.ty;
+                    let field_description = option_tokens(doc_comment(&field.attrs));
+                    properties.push(quote! {
+                        (#key, #krate::describe(<#ty as #krate::VeniceSchema>::schema(), #field_description))
+                    });
+                }
+                quote!(#krate::object(::std::vec![#(#properties),*]))
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote!(<#ty as #krate::VeniceSchema>::schema())
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "VeniceSchema can only be derived for structs with named fields and newtype structs",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "VeniceSchema can only be derived for enums without fields",
+                    ));
+                }
+                let attrs = SerdeAttrs::parse(&variant.attrs)?;
+                if attrs.skip {
+                    continue;
+                }
+                variants.push(
+                    attrs
+                        .rename
+                        .unwrap_or_else(|| rename(&variant.ident.to_string(), container.rename_all.as_deref())),
+                );
+            }
+            quote!(#krate::string_enum(&[#(#variants),*]))
+        }
+        Data::Union(_) => return Err(syn::Error::new_spanned(name, "VeniceSchema cannot be derived for unions")),
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#krate::VeniceSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let schema_name = name.to_string();
+
+    Ok(quote! {
+        impl #impl_generics #krate::VeniceSchema for #name #ty_generics #where_clause {
+            fn schema_name() -> ::std::string::String {
+                ::std::string::String::from(#schema_name)
+            }
+
+            fn schema() -> #krate::Value {
+                #krate::describe(#body, #description)
+            }
+        }
+    })
+}
+
+/// The serde attributes that change the shape of the JSON
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("rename_all") && meta.input.peek(syn::Token![=]) {
+                    parsed.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    parsed.skip = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<TokenStream2>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Join the doc comment lines of an item
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(doc), .. }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn option_tokens(value: Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+/// Split an identifier into lowercase words at underscores and case changes
+fn words(ident: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in ident.chars() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_uppercase() && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+            current.extend(c.to_lowercase());
+        } else {
+            current.extend(c.to_lowercase());
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Apply a serde `rename_all` rule to a name
+fn apply_rule(words: Vec<String>, rule: &str, original: &str) -> String {
+    match rule {
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+            .collect(),
+        "PascalCase" => words.iter().map(|word| capitalize(word)).collect(),
+        _ => original.to_string(),
+    }
+}
+
+/// The JSON name of a field or enum variant under a serde `rename_all` rule
+fn rename(name: &str, rule: Option<&str>) -> String {
+    match rule {
+        // serde changes the case of these without splitting the name into words
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") => name.to_uppercase(),
+        Some(rule) => apply_rule(words(name), rule, name),
+        None => name.to_string(),
+    }
+}